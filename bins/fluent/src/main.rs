@@ -1,22 +1,36 @@
 #![allow(missing_docs, dead_code)]
 
+use alloy_primitives::{Address, B256};
 use clap::{Args, Parser};
+use fluentbase_genesis::{dev_accounts, dev_genesis, DEV_ACCOUNT_BALANCE, DEV_MNEMONIC};
 use fluentbase_node::{
-    chainspec::FluentChainSpecParser,
-    consensus::FluentConsensus,
+    chainspec::{fluent_local_chain_spec, FluentChainSpecParser, FLUENT_LOCALNET_CHAIN_ID},
+    consensus::{consensus_engine, FluentConsensus, ValidatorKey},
+    dev::{DevClock, DevHandle, DevPayloadAttributesBuilder, DevStateOverrides, DEV_ACCOUNT_COUNT},
     evm::{FluentEvmConfig, FluentExecutorBuilder, FluentNode},
-    launcher::{launch_consensus_node, launch_consensus_validator},
-    payload::FluentPayloadAttributesBuilder,
+    launcher::{
+        launch_consensus_node, launch_consensus_validator, launch_dev_miner, BlockAuthority,
+    },
+    payload::{
+        FluentPayloadAttributesBuilder, FluentPayloadBuilderBuilder, FluentPayloadBuilderConfig,
+        PayloadOrdering,
+    },
+    rpc::{
+        AnvilApiServer, DevRpc, EvmApiServer, Fluent, FluentApiServer, FluentDebug,
+        FluentDebugApiServer,
+    },
     trusted_peers::{resolve_default_consensus_url, resolve_default_trusted_peers},
 };
+use fluentbase_runtime::{RuntimeConfig, CACHED_MODULES_SIZE_LIMIT};
 use humantime::parse_duration;
 use reth_chainspec::ChainSpec;
-use reth_cli_commands::download::DownloadDefaults;
+use reth_cli_commands::{download::DownloadDefaults, node::NodeCommand};
 use reth_ethereum_cli::{Cli, Commands};
-use reth_node_builder::{DebugNodeLauncherFuture, Node};
+use reth_node_builder::{components::BasicPayloadServiceBuilder, DebugNodeLauncherFuture, Node};
 use reth_node_core::version::{default_reth_version_metadata, try_init_version_metadata};
 use reth_node_ethereum::EthereumAddOns;
-use std::{borrow::Cow, sync::Arc, time::Duration};
+use std::{borrow::Cow, path::PathBuf, sync::Arc, time::Duration};
+use tempfile::TempDir;
 use tracing::info;
 
 #[global_allocator]
@@ -79,8 +93,132 @@ pub struct FluentNodeArgs {
     )]
     pub validator_block_time: Duration,
 
+    /// Hex-encoded secret key sealing the blocks of this validator.
+    ///
+    /// Required with `--validator` on networks whose genesis has a validator set.
+    #[arg(long = "validator.key-file", value_name = "PATH")]
+    pub validator_key_file: Option<PathBuf>,
+
     #[arg(long = "sequencer-url")]
     pub sequencer_url: Option<String>,
+
+    /// Don't propose blocks while the transaction pool has no pending transactions.
+    #[arg(long = "validator.skip-empty-blocks")]
+    pub validator_skip_empty_blocks: bool,
+
+    /// Order in which pending transactions are packed into blocks: effective-tip or fifo.
+    #[arg(long = "builder.ordering", value_name = "ORDERING", default_value_t)]
+    pub builder_ordering: PayloadOrdering,
+
+    /// Most Wasm compilation fuel the deployments packed into one block may charge, unlimited by
+    /// default. Deployments that don't fit wait for a later block.
+    #[arg(long = "builder.max-wasm-compilation-fuel", value_name = "FUEL")]
+    pub builder_max_wasm_compilation_fuel: Option<u64>,
+
+    /// RPC endpoints of other validators to import blocks from, on networks with a validator set.
+    #[arg(long = "consensus.peers", value_name = "URL", value_delimiter = ',')]
+    pub consensus_peers: Vec<String>,
+
+    /// Memory budget of the in-memory compiled module cache, in MiB. Node-local.
+    #[arg(
+        long = "runtime.module-cache-size",
+        value_name = "MIB",
+        default_value_t = (CACHED_MODULES_SIZE_LIMIT / (1024 * 1024)) as u64,
+    )]
+    pub runtime_module_cache_size_mib: u64,
+
+    /// Directory of the persistent compiled module cache. Node-local, disabled by default.
+    #[arg(long = "runtime.module-cache-dir", value_name = "PATH")]
+    pub runtime_module_cache_dir: Option<PathBuf>,
+
+    /// Per-transaction in-flight memory limit of the runtime, in MiB.
+    ///
+    /// Consensus-critical: only accepted on the local dev chain.
+    #[arg(long = "runtime.max-in-flight-memory", value_name = "MIB")]
+    pub runtime_max_in_flight_memory_mib: Option<u64>,
+
+    /// Collect `_debug_event` records emitted by contracts and expose them through `fluentTracer`.
+    ///
+    /// Dev-only: only accepted on the local dev chain.
+    #[arg(long = "runtime.debug-events")]
+    pub runtime_debug_events: bool,
+
+    /// Number of accounts pre-funded with 10 000 ETH in `--dev` mode.
+    #[arg(long = "dev.accounts", value_name = "COUNT", default_value_t = DEV_ACCOUNT_COUNT)]
+    pub dev_accounts: u32,
+
+    /// Mnemonic the pre-funded accounts of `--dev` mode are derived from.
+    #[arg(long = "dev.accounts-mnemonic", value_name = "PHRASE", default_value = DEV_MNEMONIC)]
+    pub dev_accounts_mnemonic: String,
+}
+
+impl FluentNodeArgs {
+    /// Builds the runtime config and checks it against the consensus-safety rules of `chain_id`.
+    pub fn runtime_config(&self, chain_id: u64) -> eyre::Result<RuntimeConfig> {
+        const MIB: u64 = 1024 * 1024;
+        let mut config = RuntimeConfig {
+            cached_modules_size_limit: usize::try_from(
+                self.runtime_module_cache_size_mib.saturating_mul(MIB),
+            )?,
+            module_cache_dir: self.runtime_module_cache_dir.clone(),
+            debug_events: self.runtime_debug_events,
+            ..Default::default()
+        };
+        if let Some(max_in_flight_memory_mib) = self.runtime_max_in_flight_memory_mib {
+            config.max_in_flight_memory_bytes = max_in_flight_memory_mib.saturating_mul(MIB);
+        }
+        config.validate(chain_id == FLUENT_LOCALNET_CHAIN_ID)?;
+        Ok(config)
+    }
+
+    pub fn payload_builder_config(&self) -> FluentPayloadBuilderConfig {
+        FluentPayloadBuilderConfig {
+            ordering: self.builder_ordering,
+            max_wasm_compilation_fuel: self.builder_max_wasm_compilation_fuel,
+        }
+    }
+}
+
+/// A `--dev` node, kept until the node exits.
+struct DevMode {
+    accounts: Vec<(Address, B256)>,
+    block_time: Option<Duration>,
+    /// Deleted with the chain on exit.
+    _datadir: TempDir,
+}
+
+impl DevMode {
+    /// Points the node at a fresh datadir and a local chain with pre-funded accounts.
+    fn prepare(
+        node: &mut NodeCommand<FluentChainSpecParser, FluentNodeArgs>,
+    ) -> eyre::Result<Self> {
+        if node.chain.chain.id() != FLUENT_LOCALNET_CHAIN_ID {
+            eyre::bail!("--dev only runs the local chain");
+        }
+        if node.ext.validator {
+            eyre::bail!("--dev mines its own blocks and can't be combined with --validator");
+        }
+        // Blocks are mined by the Fluent dev miner, reth's local miner would race it.
+        node.dev.dev = false;
+
+        let accounts = dev_accounts(&node.ext.dev_accounts_mnemonic, node.ext.dev_accounts)?;
+        node.chain = fluent_local_chain_spec(dev_genesis(
+            accounts.iter().map(|(address, _)| *address),
+            DEV_ACCOUNT_BALANCE,
+        ));
+        let datadir = tempfile::Builder::new().prefix("fluent-dev-").tempdir()?;
+        node.datadir.datadir = datadir.path().to_path_buf().into();
+        node.network.discovery.disable_discovery = true;
+        node.rpc.http = true;
+        // `evm_revert` moves the head back to an ancestor.
+        node.engine.allow_unwind_canonical_header = true;
+
+        Ok(Self {
+            accounts,
+            block_time: node.dev.block_time,
+            _datadir: datadir,
+        })
+    }
 }
 
 fn init_downloads_defaults() {
@@ -114,17 +252,35 @@ fn main() {
 
     let mut consensus_url: Option<String> = None;
     let mut block_producer: Option<Duration> = None;
+    let mut validator_key: Option<ValidatorKey> = None;
+    let mut consensus_peers: Vec<String> = vec![];
+    let mut runtime_config = RuntimeConfig::default();
+    let mut payload_builder_config = FluentPayloadBuilderConfig::default();
+    let mut skip_empty_blocks = false;
+    let mut dev_mode: Option<DevMode> = None;
 
     let mut cli = Cli::<FluentChainSpecParser, FluentNodeArgs>::parse();
 
     // Adjust several params for node execution
     if let Commands::Node(node) = &mut cli.command {
+        if node.dev.dev {
+            dev_mode = match DevMode::prepare(node) {
+                Ok(dev_mode) => Some(dev_mode),
+                Err(err) => {
+                    eprintln!("Error: {err:?}");
+                    std::process::exit(1);
+                }
+            };
+        }
+
         // Merge default public trusted peers
         let new_trusted_peers = resolve_default_trusted_peers(node.chain.chain);
         node.network.trusted_peers.extend(new_trusted_peers);
 
         // If consensus URL is not specified, resolve default
-        if let Some(sequencer_url) = &node.ext.sequencer_url {
+        if dev_mode.is_some() {
+            consensus_url = None;
+        } else if let Some(sequencer_url) = &node.ext.sequencer_url {
             consensus_url = Some(sequencer_url.clone());
         } else if let Some(debug_consensus_url) = &node.debug.rpc_consensus_url {
             consensus_url = Some(debug_consensus_url.clone());
@@ -136,8 +292,39 @@ fn main() {
         if node.ext.validator {
             block_producer = Some(node.ext.validator_block_time);
         }
+        if let Some(path) = &node.ext.validator_key_file {
+            validator_key = match ValidatorKey::from_file(path) {
+                Ok(key) => Some(key),
+                Err(err) => {
+                    eprintln!("Error: failed to read the validator key: {err:?}");
+                    std::process::exit(1);
+                }
+            };
+        }
+        consensus_peers = node.ext.consensus_peers.clone();
+        payload_builder_config = node.ext.payload_builder_config();
+        skip_empty_blocks = node.ext.validator_skip_empty_blocks;
+
+        runtime_config = match node.ext.runtime_config(node.chain.chain.id()) {
+            Ok(runtime_config) => runtime_config,
+            Err(err) => {
+                eprintln!("Error: {err:?}");
+                std::process::exit(1);
+            }
+        };
+    }
+
+    // Every command executing blocks (node, import, re-execute, stage run) shares the process-wide
+    // runtime executors, so the config goes in before any of them starts.
+    if let Err(err) = runtime_config.clone().install() {
+        eprintln!("Error: {err}");
+        std::process::exit(1);
     }
 
+    let dev_clock = Arc::new(DevClock::default());
+    let dev_state_overrides = Arc::new(DevStateOverrides::default());
+    let (dev_handle, dev_commands) = DevHandle::new(dev_clock.clone());
+
     let components = |spec: Arc<ChainSpec>| {
         (
             FluentEvmConfig::new_with_default_factory(spec.clone()),
@@ -148,25 +335,85 @@ fn main() {
     if let Err(err) = cli.run_with_components::<FluentNode>(components, async move |builder, _| {
         info!(target: "reth::cli", "Launching node");
 
+        let authority = match (consensus_engine(&*builder.config().chain)?, validator_key) {
+            (Some(engine), Some(key)) => Some(BlockAuthority { engine, key }),
+            (Some(_), None) if block_producer.is_some() => {
+                eyre::bail!(
+                    "--validator requires --validator.key-file on networks with a validator set"
+                )
+            }
+            (None, Some(_)) => {
+                eyre::bail!("--validator.key-file requires a validator set in the genesis")
+            }
+            _ => None,
+        };
+
+        let mut executor_builder = FluentExecutorBuilder::new(runtime_config);
+        if dev_mode.is_some() {
+            executor_builder =
+                executor_builder.with_dev_state_overrides(dev_state_overrides.clone());
+        }
+        let dev_rpc = dev_mode.as_ref().map(|_| DevRpc::new(dev_handle));
+
         let components_builder = FluentNode::default()
             .components_builder()
-            .executor(FluentExecutorBuilder::default());
+            .executor(executor_builder)
+            .payload(BasicPayloadServiceBuilder::new(
+                FluentPayloadBuilderBuilder::new(payload_builder_config),
+            ));
         let add_ons = EthereumAddOns::default();
 
         let handle: DebugNodeLauncherFuture<_, _, _> = builder
             .with_types::<FluentNode>()
             .with_components(components_builder)
             .with_add_ons(add_ons)
+            .extend_rpc_modules(|ctx| {
+                let debug =
+                    FluentDebug::new(ctx.registry.eth_api().clone(), ctx.registry.debug_api());
+                ctx.modules.replace_configured(debug.into_rpc())?;
+                let fluent = Fluent::new(ctx.registry.eth_api().clone());
+                ctx.modules.merge_configured(fluent.into_rpc())?;
+                if let Some(dev_rpc) = dev_rpc {
+                    ctx.modules
+                        .merge_configured(EvmApiServer::into_rpc(dev_rpc.clone()))?;
+                    ctx.modules
+                        .merge_configured(AnvilApiServer::into_rpc(dev_rpc))?;
+                }
+                Ok(())
+            })
             .launch_with_debug_capabilities();
 
         let handle = handle.await?;
 
-        if let Some(block_time) = block_producer {
-            launch_consensus_validator(&handle, block_time, FluentPayloadAttributesBuilder {})
-                .await?;
-        } else if let Some(consensus_url) = consensus_url {
+        if let Some(dev_mode) = &dev_mode {
+            launch_dev_miner(
+                &handle,
+                dev_mode.block_time,
+                DevPayloadAttributesBuilder::new(dev_clock.clone()),
+                dev_clock,
+                dev_state_overrides,
+                dev_commands,
+            )
+            .await?;
+            for (index, (address, secret)) in dev_mode.accounts.iter().enumerate() {
+                info!(target: "reth::cli", index, %address, private_key = %secret, "Dev account");
+            }
+        } else if let Some(block_time) = block_producer {
+            launch_consensus_validator(
+                &handle,
+                block_time,
+                FluentPayloadAttributesBuilder {},
+                authority,
+                skip_empty_blocks,
+            )
+            .await?;
+        } else if let Some(consensus_url) = consensus_url.filter(|_| consensus_peers.is_empty()) {
             launch_consensus_node(&handle, consensus_url).await?;
         }
+        // Validators and their followers import each other's blocks.
+        for peer in consensus_peers {
+            launch_consensus_node(&handle, peer).await?;
+        }
         handle.node_exit_future.await
     }) {
         eprintln!("Error: {err:?}");
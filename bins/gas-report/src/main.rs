@@ -0,0 +1,95 @@
+//! Aggregates gas records written by `EvmTestingContext` test runs.
+//!
+//! ```text
+//! FLUENT_GAS_REPORT_DIR=target/gas cargo nextest run --workspace
+//! gas-report target/gas --baseline .gas-report.json
+//! ```
+//!
+//! Prints the report and exits with an error if any function got more expensive than the
+//! baseline allows. `--update-baseline` rewrites the baseline with the current report instead.
+use clap::{Parser, ValueEnum};
+use eyre::{bail, WrapErr};
+use fluentbase_testing::gas_report::GasReport;
+use std::{fs, path::PathBuf};
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Format {
+    Table,
+    Json,
+}
+
+#[derive(Debug, Parser)]
+#[command(about = "Aggregate EvmTestingContext gas records and compare them with a baseline")]
+struct Args {
+    /// Directory the tests wrote records to (`FLUENT_GAS_REPORT_DIR`).
+    records_dir: PathBuf,
+    /// Output format of the report.
+    #[arg(long, value_enum, default_value = "table")]
+    format: Format,
+    /// Write the report to this file instead of stdout.
+    #[arg(long)]
+    out: Option<PathBuf>,
+    /// JSON report to compare against.
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+    /// Allowed growth of mean and max gas over the baseline, in percent.
+    #[arg(long, default_value_t = 0)]
+    tolerance: u64,
+    /// Overwrite the baseline with the current report instead of comparing.
+    #[arg(long, requires = "baseline")]
+    update_baseline: bool,
+}
+
+fn main() -> eyre::Result<()> {
+    let args = Args::parse();
+    let report = GasReport::from_records_dir(&args.records_dir)
+        .wrap_err_with(|| format!("failed to read records in {}", args.records_dir.display()))?;
+
+    let rendered = match args.format {
+        Format::Table => report.to_table(),
+        Format::Json => report.to_json(),
+    };
+    match &args.out {
+        Some(out) => fs::write(out, rendered)?,
+        None => print!("{rendered}"),
+    }
+
+    let Some(baseline_path) = &args.baseline else {
+        return Ok(());
+    };
+    if args.update_baseline {
+        fs::write(baseline_path, report.to_json())?;
+        return Ok(());
+    }
+    let baseline = fs::read_to_string(baseline_path)
+        .wrap_err_with(|| format!("failed to read {}", baseline_path.display()))?;
+    let baseline = GasReport::from_json(&baseline)?;
+    // Every function would count as new, so the comparison would pass whatever the gas is
+    if baseline.contracts.is_empty() {
+        bail!(
+            "{} has no entries, record it with `make gas-report-update`",
+            baseline_path.display()
+        );
+    }
+
+    let regressions = report.regressions(&baseline, args.tolerance);
+    for regression in &regressions {
+        eprintln!(
+            "gas regression in {}::{}: mean {} -> {}, max {} -> {}",
+            regression.contract,
+            regression.function,
+            regression.baseline.mean,
+            regression.current.mean,
+            regression.baseline.max,
+            regression.current.max,
+        );
+    }
+    if !regressions.is_empty() {
+        bail!(
+            "{} function(s) exceed the baseline by more than {}%",
+            regressions.len(),
+            args.tolerance
+        );
+    }
+    Ok(())
+}
@@ -1,6 +1,10 @@
 mod provenance;
+mod replay;
 
-use crate::provenance::{load_release, ManifestBinding, ReleaseProvenance};
+use crate::{
+    provenance::{load_release, ManifestBinding, ReleaseProvenance},
+    replay::ReplayArgs,
+};
 use anyhow::{anyhow, bail, Context, Result};
 use clap::{Args, Parser, Subcommand};
 use ethers::{
@@ -53,6 +57,9 @@ enum Command {
 
     /// Execute previously planned contracts through upgradeToPlanned(...)
     UpgradePlanned(UpgradePlannedArgs),
+
+    /// Replay historical blocks with candidate code and report every transaction that changes
+    Replay(ReplayArgs),
 }
 
 #[derive(Args, Debug)]
@@ -165,6 +172,7 @@ impl Command {
             Self::DirectUpgrade(args) => &args.common,
             Self::PlanUpgrade(args) => &args.common,
             Self::UpgradePlanned(args) => &args.common,
+            Self::Replay(_) => unreachable!("replay doesn't act on a network"),
         }
     }
 }
@@ -710,6 +718,10 @@ pub static FLUENT_HARDFORKS: LazyLock<ChainHardforks> = LazyLock::new(|| {
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    // Replay reads a local datadir and needs none of the network setup below.
+    if let Command::Replay(args) = &cli.command {
+        return replay::run(args).await;
+    }
     let common = cli.command.common();
 
     // Provenance first: nothing below this point may run against an unauthenticated artifact, and
@@ -816,6 +828,7 @@ async fn main() -> Result<()> {
             )
             .await?;
         }
+        Command::Replay(_) => unreachable!("handled above"),
     }
 
     Ok(())
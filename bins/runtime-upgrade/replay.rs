@@ -0,0 +1,577 @@
+//! Regression check of a runtime upgrade against historical blocks, without a devnet.
+//!
+//! `runtime-upgrade replay` re-executes a range of blocks from a reth datadir twice: once with the
+//! code in the datadir (the baseline) and once with candidate rWasm code replacing one or more
+//! system contracts. Every transaction whose status, gas used, return data, logs or state changes
+//! differs between the two runs is reported.
+//!
+//! Each block is replayed on top of the canonical state of its parent, so a difference never
+//! carries over into the following blocks, and every reported transaction diverged on its own.
+
+use crate::{contracts_to_upgrade, load_release_modules, provenance, report_provenance};
+use alloy_evm::block::BlockExecutor;
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Args;
+use fluentbase_node::{
+    chainspec::FluentChainSpecParser,
+    evm::{FluentEvmConfig, FluentNode},
+};
+use fluentbase_sdk::{Address, Bytes, Log, B256, U256};
+use reth_chainspec::{ChainSpec, EthChainSpec};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_ethereum_primitives::{Block, Receipt};
+use reth_evm::ConfigureEvm;
+use reth_primitives_traits::RecoveredBlock;
+use reth_provider::{BlockReader, ReceiptProvider, StateProviderFactory, TransactionVariant};
+use reth_revm::{
+    context::result::{ExecutionResult, HaltReason},
+    database::StateProviderDatabase,
+    db::{CacheDB, State},
+    state::{Bytecode, EvmState},
+    Database,
+};
+use rwasm::RwasmModule;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::PathBuf,
+    sync::Arc,
+};
+
+#[derive(Args, Debug)]
+pub struct ReplayArgs {
+    /// Data directory of a synced node. It's opened read-only, so the node may keep running.
+    #[arg(long, value_name = "PATH")]
+    datadir: PathBuf,
+
+    /// Chain of the datadir: fluent-devnet, fluent-testnet, fluent-mainnet or a genesis file.
+    #[arg(
+        long,
+        value_name = "CHAIN",
+        default_value = "fluent-mainnet",
+        value_parser = FluentChainSpecParser::parser()
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// First block to replay.
+    #[arg(long, value_name = "NUMBER")]
+    from_block: u64,
+
+    /// Last block to replay, inclusive.
+    #[arg(long, value_name = "NUMBER")]
+    to_block: u64,
+
+    /// Contract key name (e.g. PRECOMPILE_EVM_RUNTIME) from CONTRACTS_TO_UPGRADE.
+    /// Required with --rwasm. With --genesis, omitting it replaces every contract of the release.
+    #[arg(long, required_unless_present = "genesis")]
+    contract: Option<String>,
+
+    /// Genesis release tag whose contracts are the candidate code, e.g. v0.5.3
+    #[arg(long, conflicts_with = "rwasm", required_unless_present = "rwasm")]
+    genesis: Option<String>,
+
+    /// Release channel of the genesis asset (e.g. `mainnet`). Omit for the default asset.
+    #[arg(long, requires = "genesis")]
+    genesis_channel: Option<String>,
+
+    /// Candidate rWasm bytecode of --contract, e.g. a local build.
+    #[arg(long, value_name = "PATH")]
+    rwasm: Option<PathBuf>,
+
+    /// Write the JSON report to this file instead of stdout.
+    #[arg(long, value_name = "PATH")]
+    report: Option<PathBuf>,
+}
+
+/// What `replay` found, written as JSON.
+#[derive(Debug, Serialize)]
+struct ReplayReport {
+    chain_id: u64,
+    from_block: u64,
+    to_block: u64,
+    overrides: Vec<CodeOverride>,
+    transactions: usize,
+    changed_transactions: Vec<TxDiff>,
+    /// Blocks that the candidate code could not execute at all.
+    failed_blocks: Vec<BlockFailure>,
+}
+
+#[derive(Debug, Serialize)]
+struct CodeOverride {
+    address: Address,
+    rwasm_hash: B256,
+}
+
+#[derive(Debug, Serialize)]
+struct BlockFailure {
+    block_number: u64,
+    error: String,
+}
+
+/// A value that differs between the baseline and the candidate run.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct Changed<T> {
+    baseline: T,
+    candidate: T,
+}
+
+fn changed<T: PartialEq + Clone>(baseline: &T, candidate: &T) -> Option<Changed<T>> {
+    (baseline != candidate).then(|| Changed {
+        baseline: baseline.clone(),
+        candidate: candidate.clone(),
+    })
+}
+
+/// Differences of one transaction; unchanged fields are left out.
+#[derive(Debug, Serialize)]
+struct TxDiff {
+    block_number: u64,
+    tx_index: usize,
+    tx_hash: B256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<Changed<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gas_used: Option<Changed<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<Changed<Bytes>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    logs: Option<Changed<Vec<Log>>>,
+    /// Accounts left in a different state, `null` where a run didn't touch the account.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    state: BTreeMap<Address, Changed<Option<AccountChange>>>,
+}
+
+impl TxDiff {
+    fn new(
+        block_number: u64,
+        tx_index: usize,
+        tx_hash: B256,
+        baseline: &TxOutcome,
+        candidate: &TxOutcome,
+    ) -> Option<Self> {
+        if baseline == candidate {
+            return None;
+        }
+        let addresses: BTreeSet<&Address> = baseline
+            .state
+            .keys()
+            .chain(candidate.state.keys())
+            .collect();
+        let state = addresses
+            .into_iter()
+            .filter_map(|address| {
+                let diff = changed(
+                    &baseline.state.get(address).cloned(),
+                    &candidate.state.get(address).cloned(),
+                )?;
+                Some((*address, diff))
+            })
+            .collect();
+        Some(Self {
+            block_number,
+            tx_index,
+            tx_hash,
+            status: changed(&baseline.status, &candidate.status),
+            gas_used: changed(&baseline.gas_used, &candidate.gas_used),
+            output: changed(&baseline.output, &candidate.output),
+            logs: changed(&baseline.logs, &candidate.logs),
+            state,
+        })
+    }
+}
+
+/// Observable effects of one transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct TxOutcome {
+    status: String,
+    gas_used: u64,
+    output: Bytes,
+    logs: Vec<Log>,
+    state: BTreeMap<Address, AccountChange>,
+}
+
+/// Checks a baseline run against the receipts stored in the datadir.
+///
+/// The baseline runs the code the chain ran, so any difference means the replay doesn't reproduce
+/// the chain (wrong fork schedule, wrong pre-state, ...) and every diff against it is meaningless.
+fn check_baseline(block_number: u64, receipts: &[Receipt], baseline: &[TxOutcome]) -> Result<()> {
+    if receipts.len() != baseline.len() {
+        bail!(
+            "block {}: {} receipts stored but {} transactions replayed",
+            block_number,
+            receipts.len(),
+            baseline.len()
+        );
+    }
+    let mut cumulative_gas_used = 0;
+    for (tx_index, (receipt, outcome)) in receipts.iter().zip(baseline).enumerate() {
+        cumulative_gas_used += outcome.gas_used;
+        if receipt.success != outcome.is_success()
+            || receipt.cumulative_gas_used != cumulative_gas_used
+            || receipt.logs != outcome.logs
+        {
+            bail!(
+                "block {}: transaction {} doesn't match its stored receipt (success {} vs {}, \
+                 cumulative gas {} vs {}, {} vs {} logs)",
+                block_number,
+                tx_index,
+                receipt.success,
+                outcome.is_success(),
+                receipt.cumulative_gas_used,
+                cumulative_gas_used,
+                receipt.logs.len(),
+                outcome.logs.len()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// State of an account touched by a transaction, with the storage slots it wrote.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+struct AccountChange {
+    balance: U256,
+    nonce: u64,
+    /// `None` for the replaced contracts, whose code hash differs by construction.
+    code_hash: Option<B256>,
+    storage: BTreeMap<U256, U256>,
+    selfdestructed: bool,
+}
+
+impl TxOutcome {
+    fn new(
+        result: &ExecutionResult<HaltReason>,
+        state: &EvmState,
+        overrides: &BTreeMap<Address, Bytes>,
+    ) -> Self {
+        let status = match result {
+            ExecutionResult::Success { reason, .. } => format!("success: {reason:?}"),
+            ExecutionResult::Revert { .. } => "revert".to_string(),
+            ExecutionResult::Halt { reason, .. } => format!("halt: {reason:?}"),
+        };
+        let state = state
+            .iter()
+            .filter(|(_, account)| account.is_touched())
+            .map(|(address, account)| {
+                let change = AccountChange {
+                    balance: account.info.balance,
+                    nonce: account.info.nonce,
+                    code_hash: (!overrides.contains_key(address)).then_some(account.info.code_hash),
+                    storage: account
+                        .storage
+                        .iter()
+                        .filter(|(_, slot)| slot.is_changed())
+                        .map(|(key, slot)| (*key, slot.present_value))
+                        .collect(),
+                    selfdestructed: account.is_selfdestructed(),
+                };
+                (*address, change)
+            })
+            .collect();
+        Self {
+            status,
+            gas_used: result.tx_gas_used(),
+            output: result.output().cloned().unwrap_or_default(),
+            logs: result.logs().to_vec(),
+            state,
+        }
+    }
+
+    fn is_success(&self) -> bool {
+        self.status.starts_with("success")
+    }
+}
+
+/// Serialized rWasm modules of the candidate code, by contract address.
+async fn candidate_code(args: &ReplayArgs) -> Result<BTreeMap<Address, Bytes>> {
+    let contracts = contracts_to_upgrade();
+    let contract = args
+        .contract
+        .as_deref()
+        .map(|key| {
+            contracts
+                .get(key)
+                .copied()
+                .ok_or_else(|| anyhow!("Unknown contract: {}", key))
+        })
+        .transpose()?;
+
+    if let Some(path) = &args.rwasm {
+        let contract = contract.ok_or_else(|| anyhow!("--rwasm requires --contract"))?;
+        let code = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+        RwasmModule::new_checked(&code)
+            .with_context(|| format!("malformed rwasm bytecode in {}", path.display()))?;
+        return Ok(BTreeMap::from([(contract, code.into())]));
+    }
+
+    let tag = args
+        .genesis
+        .as_deref()
+        .expect("clap requires --genesis without --rwasm");
+    let release = provenance::load_release(
+        tag,
+        args.genesis_channel.as_deref(),
+        &provenance::cache_dir(),
+    )
+    .await?;
+    report_provenance(&release.provenance);
+    let upgrade_list: Vec<Address> = match contract {
+        Some(contract) => vec![contract],
+        None => contracts
+            .values()
+            .copied()
+            .filter(|address| release.genesis.alloc.contains_key(address))
+            .collect(),
+    };
+    Ok(load_release_modules(&release.genesis, &upgrade_list)?
+        .into_iter()
+        .map(|(address, module)| (address, module.serialize().into()))
+        .collect())
+}
+
+/// State before block `number`, with `overrides` replacing the code of their contracts.
+fn pre_state(
+    factory: &impl StateProviderFactory,
+    number: u64,
+    overrides: &BTreeMap<Address, Bytes>,
+) -> Result<CacheDB<StateProviderDatabase<reth_provider::StateProviderBox>>> {
+    let state = factory
+        .history_by_block_number(number - 1)
+        .with_context(|| format!("state at block {}", number - 1))?;
+    let mut db = CacheDB::new(StateProviderDatabase::new(state));
+    for (address, code) in overrides {
+        let mut info = db.basic(*address)?.unwrap_or_default();
+        let code = Bytecode::new_raw(code.clone());
+        info.code_hash = code.hash_slow();
+        info.code = Some(code);
+        db.insert_account_info(*address, info);
+    }
+    Ok(db)
+}
+
+fn execute_block<DB: alloy_evm::Database>(
+    evm_config: &FluentEvmConfig,
+    db: DB,
+    block: &RecoveredBlock<Block>,
+    overrides: &BTreeMap<Address, Bytes>,
+) -> Result<Vec<TxOutcome>> {
+    let mut state = State::builder()
+        .with_database(db)
+        .with_bundle_update()
+        .build();
+    let mut executor = evm_config.executor_for_block(&mut state, block.sealed_block())?;
+    executor.apply_pre_execution_changes()?;
+    let mut outcomes = Vec::new();
+    for tx in block.transactions_recovered() {
+        let output = executor.execute_transaction_without_commit(tx)?;
+        outcomes.push(TxOutcome::new(
+            &output.result.result,
+            &output.result.state,
+            overrides,
+        ));
+        executor.commit_transaction(output);
+    }
+    Ok(outcomes)
+}
+
+fn replay(args: &ReplayArgs, overrides: &BTreeMap<Address, Bytes>) -> Result<ReplayReport> {
+    let chain_id = args.chain.chain().id();
+    let factory = FluentNode::provider_factory_builder()
+        .open_read_only(args.chain.clone(), args.datadir.as_path())
+        .with_context(|| format!("opening {}", args.datadir.display()))?;
+    let evm_config = FluentEvmConfig::new_with_default_factory(args.chain.clone());
+    let no_overrides = BTreeMap::new();
+
+    let mut report = ReplayReport {
+        chain_id,
+        from_block: args.from_block,
+        to_block: args.to_block,
+        overrides: overrides
+            .iter()
+            .map(|(address, code)| CodeOverride {
+                address: *address,
+                rwasm_hash: fluentbase_sdk::keccak256(code),
+            })
+            .collect(),
+        transactions: 0,
+        changed_transactions: Vec::new(),
+        failed_blocks: Vec::new(),
+    };
+    for number in args.from_block..=args.to_block {
+        let block = factory
+            .recovered_block(number.into(), TransactionVariant::WithHash)?
+            .ok_or_else(|| anyhow!("block {} is missing from the datadir", number))?;
+        // The baseline must reproduce the chain; if it doesn't, the replay itself is broken.
+        let baseline = execute_block(
+            &evm_config,
+            pre_state(&factory, number, &no_overrides)?,
+            &block,
+            overrides,
+        )
+        .with_context(|| format!("replaying block {} with the current code", number))?;
+        let receipts = factory
+            .receipts_by_block(number.into())?
+            .ok_or_else(|| anyhow!("receipts of block {} are missing from the datadir", number))?;
+        check_baseline(number, &receipts, &baseline)?;
+        let candidate = match execute_block(
+            &evm_config,
+            pre_state(&factory, number, overrides)?,
+            &block,
+            overrides,
+        ) {
+            Ok(candidate) => candidate,
+            Err(err) => {
+                report.failed_blocks.push(BlockFailure {
+                    block_number: number,
+                    error: format!("{err:#}"),
+                });
+                continue;
+            }
+        };
+
+        report.transactions += baseline.len();
+        let hashes = block.body().transactions().map(|tx| *tx.hash());
+        for (tx_index, ((baseline, candidate), tx_hash)) in
+            baseline.iter().zip(&candidate).zip(hashes).enumerate()
+        {
+            report
+                .changed_transactions
+                .extend(TxDiff::new(number, tx_index, tx_hash, baseline, candidate));
+        }
+        println!("Block {}: {} transactions replayed", number, baseline.len());
+    }
+    Ok(report)
+}
+
+pub async fn run(args: &ReplayArgs) -> Result<()> {
+    if args.from_block == 0 || args.from_block > args.to_block {
+        bail!("--from-block must be at least 1 and not after --to-block");
+    }
+    let overrides = candidate_code(args).await?;
+    if overrides.is_empty() {
+        bail!("the release has none of the known contracts");
+    }
+    let report = tokio::task::block_in_place(|| replay(args, &overrides))?;
+
+    let json = serde_json::to_string_pretty(&report)?;
+    match &args.report {
+        Some(path) => {
+            fs::write(path, json).with_context(|| format!("writing {}", path.display()))?;
+            println!("Report written to {}", path.display());
+        }
+        None => println!("{json}"),
+    }
+    if !report.changed_transactions.is_empty() || !report.failed_blocks.is_empty() {
+        bail!(
+            "{} of {} transactions changed and {} blocks failed with the candidate code",
+            report.changed_transactions.len(),
+            report.transactions,
+            report.failed_blocks.len()
+        );
+    }
+    println!(
+        "No changes in {} transactions of blocks {}..={}",
+        report.transactions, args.from_block, args.to_block
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: Address = Address::repeat_byte(0x11);
+
+    fn outcome() -> TxOutcome {
+        TxOutcome {
+            status: "success: Stop".to_string(),
+            gas_used: 21_000,
+            output: Bytes::new(),
+            logs: Vec::new(),
+            state: BTreeMap::from([(
+                ACCOUNT,
+                AccountChange {
+                    balance: U256::from(1),
+                    nonce: 1,
+                    code_hash: Some(B256::ZERO),
+                    storage: BTreeMap::new(),
+                    selfdestructed: false,
+                },
+            )]),
+        }
+    }
+
+    fn receipt(cumulative_gas_used: u64) -> Receipt {
+        Receipt {
+            success: true,
+            cumulative_gas_used,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn baseline_matching_the_stored_receipts_passes() {
+        let receipts = [receipt(21_000), receipt(42_000)];
+        assert!(check_baseline(1, &receipts, &[outcome(), outcome()]).is_ok());
+    }
+
+    #[test]
+    fn baseline_diverging_from_the_stored_receipts_fails() {
+        // A gated syscall that traps in the replay but not on chain shows up as a failed status and
+        // a different gas usage.
+        let mut halted = outcome();
+        halted.status = "halt: OutOfGas(Basic)".to_string();
+        assert!(check_baseline(1, &[receipt(21_000)], &[halted]).is_err());
+
+        let mut more_gas = outcome();
+        more_gas.gas_used = 22_000;
+        assert!(check_baseline(1, &[receipt(21_000)], &[more_gas]).is_err());
+
+        let mut logged = outcome();
+        logged.logs.push(Log::empty());
+        assert!(check_baseline(1, &[receipt(21_000)], &[logged]).is_err());
+
+        assert!(check_baseline(1, &[receipt(21_000)], &[]).is_err());
+    }
+
+    #[test]
+    fn identical_outcomes_are_not_reported() {
+        assert!(TxDiff::new(1, 0, B256::ZERO, &outcome(), &outcome()).is_none());
+    }
+
+    #[test]
+    fn only_changed_fields_are_reported() {
+        let mut candidate = outcome();
+        candidate.gas_used = 22_000;
+        candidate
+            .state
+            .get_mut(&ACCOUNT)
+            .unwrap()
+            .storage
+            .insert(U256::ZERO, U256::from(7));
+        let diff = TxDiff::new(1, 0, B256::ZERO, &outcome(), &candidate).unwrap();
+        assert_eq!(
+            diff.gas_used,
+            Some(Changed {
+                baseline: 21_000,
+                candidate: 22_000
+            })
+        );
+        assert!(diff.status.is_none() && diff.output.is_none() && diff.logs.is_none());
+        assert_eq!(diff.state.len(), 1);
+        assert_eq!(
+            diff.state[&ACCOUNT].candidate.as_ref().unwrap().storage[&U256::ZERO],
+            U256::from(7)
+        );
+    }
+
+    #[test]
+    fn accounts_touched_by_one_run_only_are_reported() {
+        let mut candidate = outcome();
+        candidate.state.clear();
+        let diff = TxDiff::new(1, 0, B256::ZERO, &outcome(), &candidate).unwrap();
+        assert!(diff.state[&ACCOUNT].baseline.is_some());
+        assert!(diff.state[&ACCOUNT].candidate.is_none());
+    }
+}
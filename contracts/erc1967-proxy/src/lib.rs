@@ -0,0 +1,161 @@
+#![cfg_attr(target_arch = "wasm32", no_std, no_main)]
+#![cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+
+extern crate alloc;
+
+use fluentbase_sdk::{
+    basic_entrypoint,
+    derive::{constructor, Contract},
+    proxy::Erc1967,
+    Address, Bytes, ExitCode, SharedAPI, SolidityError,
+};
+
+/// ERC-1967 proxy, the counterpart of OpenZeppelin's `ERC1967Proxy`
+///
+/// The proxy has no storage fields and no methods of its own: the implementation address lives at
+/// the ERC-1967 implementation slot and every call is delegated to it. Upgrades are therefore up to
+/// the implementation, typically a `#[uups]` contract.
+#[derive(Contract)]
+struct App<SDK> {
+    sdk: SDK,
+}
+
+#[constructor(mode = "solidity")]
+impl<SDK: SharedAPI> App<SDK> {
+    /// Points the proxy at `implementation` and, unless `data` is empty, delegates `data` to it,
+    /// which is how the proxy state gets initialized
+    pub fn constructor(&mut self, implementation: Address, data: Bytes) {
+        if let Err(err) = Erc1967::upgrade_to_and_call(&mut self.sdk, implementation, &data) {
+            err.revert(&mut self.sdk);
+        }
+    }
+}
+
+impl<SDK: SharedAPI> App<SDK> {
+    /// Forwards the call to the implementation, returning its output or bubbling up its revert
+    pub fn main(&mut self) {
+        let implementation = Erc1967::implementation(&self.sdk);
+        let input = self.sdk.bytes_input();
+        let result = self.sdk.delegate_call(implementation, &input, None);
+        self.sdk.write(&result.data);
+        if !result.status.is_ok() {
+            self.sdk.native_exit(ExitCode::Panic);
+        }
+    }
+}
+
+basic_entrypoint!(App);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluentbase_sdk::{
+        address,
+        proxy::{ProxyError, Upgraded, IMPLEMENTATION_SLOT},
+        ContextReader, ContractContextV1, StorageAPI, B256, U256,
+    };
+    use fluentbase_testing::{TestingContextImpl, TestingContract};
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    const PROXY: Address = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    const IMPLEMENTATION: Address = address!("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+    const DEPLOYER: Address = address!("1111111111111111111111111111111111111111");
+    const ALICE: Address = address!("2222222222222222222222222222222222222222");
+
+    /// Implementation storing its input at slot 0 and returning the caller, or reverting with
+    /// `0xdead` when the input starts with `0xff`
+    fn implementation() -> TestingContract {
+        TestingContract::new(|mut sdk| {
+            let input = sdk.bytes_input();
+            if input.first() == Some(&0xff) {
+                sdk.write([0xde, 0xad]);
+                sdk.native_exit(ExitCode::Panic);
+            }
+            sdk.write_storage(U256::ZERO, U256::from_be_slice(&input))
+                .unwrap();
+            let caller = sdk.context().contract_caller();
+            sdk.write(caller.as_slice());
+        })
+    }
+
+    fn context() -> TestingContextImpl {
+        TestingContextImpl::default()
+            .with_contract(IMPLEMENTATION, implementation())
+            .with_contract_context(ContractContextV1 {
+                address: PROXY,
+                bytecode_address: PROXY,
+                caller: DEPLOYER,
+                ..Default::default()
+            })
+    }
+
+    /// Deploys the proxy, returning the constructor revert data on failure
+    fn deploy(
+        sdk: &TestingContextImpl,
+        implementation: Address,
+        data: &[u8],
+    ) -> Result<(), Vec<u8>> {
+        let input = ConstructorCall::new((implementation, Bytes::copy_from_slice(data)))
+            .encode()
+            .to_vec();
+        let sdk = sdk.clone().with_input(input);
+        let result = catch_unwind(AssertUnwindSafe(|| App::new(sdk.clone()).deploy()));
+        let output = sdk.take_output();
+        result.map_err(|_| output)
+    }
+
+    fn call(sdk: &TestingContextImpl, input: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+        let sdk = sdk.clone().with_caller(ALICE).with_input(input.to_vec());
+        let result = catch_unwind(AssertUnwindSafe(|| App::new(sdk.clone()).main()));
+        let output = sdk.take_output();
+        result.map(|_| output.clone()).map_err(|_| output)
+    }
+
+    #[test]
+    fn test_constructor_sets_and_initializes_implementation() {
+        let sdk = context();
+        deploy(&sdk, IMPLEMENTATION, &[0x2a]).unwrap();
+
+        assert_eq!(Erc1967::implementation(&sdk), IMPLEMENTATION);
+        assert_eq!(
+            sdk.storage(&IMPLEMENTATION_SLOT).unwrap(),
+            U256::from_be_slice(IMPLEMENTATION.as_slice())
+        );
+        // The initialization call ran on the proxy storage
+        assert_eq!(sdk.storage(&U256::ZERO).unwrap(), U256::from(0x2a));
+
+        let logs = sdk.take_logs();
+        let (_, topics) = &logs[0];
+        assert_eq!(topics[0], B256::new(Upgraded::SELECTOR));
+        assert_eq!(topics[1], IMPLEMENTATION.into_word());
+    }
+
+    #[test]
+    fn test_constructor_rejects_accounts_without_code() {
+        let sdk = context();
+        let revert = deploy(&sdk, ALICE, &[]).unwrap_err();
+        assert_eq!(
+            ProxyError::decode(&revert),
+            Some(ProxyError::ERC1967InvalidImplementation {
+                implementation: ALICE
+            })
+        );
+
+        let revert = deploy(&sdk, IMPLEMENTATION, &[0xff]).unwrap_err();
+        assert_eq!(revert, [0xde, 0xad]);
+    }
+
+    #[test]
+    fn test_calls_are_delegated_to_the_implementation() {
+        let sdk = context();
+        deploy(&sdk, IMPLEMENTATION, &[]).unwrap();
+
+        // The implementation sees the original caller and writes to the proxy storage
+        let output = call(&sdk, &[0x07]).unwrap();
+        assert_eq!(output, ALICE.as_slice());
+        assert_eq!(sdk.storage(&U256::ZERO).unwrap(), U256::from(7));
+
+        let revert = call(&sdk, &[0xff, 0x01]).unwrap_err();
+        assert_eq!(revert, [0xde, 0xad]);
+    }
+}
@@ -1,9 +1,288 @@
 #![cfg_attr(target_arch = "wasm32", no_std, no_main)]
+//! OAuth2 / OpenID Connect verifier: checks a compact JWS (an ID token) against an issuer key kept
+//! in the precompile's storage and returns the verified claims.
+//!
+//! The JWKS is managed by governance (`setRsaKey`, `setEcKey`, `removeKey`); the storage layout and
+//! the calldata types are shared with the host through `fluentbase_sdk::oauth2`.
 
-use fluentbase_sdk::{system_entrypoint, ExitCode, SystemAPI};
+extern crate alloc;
+extern crate fluentbase_sdk;
 
-pub fn main_entry<SDK: SystemAPI>(_sdk: &mut SDK) -> Result<(), ExitCode> {
-    Err(ExitCode::UnreachableCodeReached)
+#[cfg(test)]
+mod tests;
+mod verify;
+
+use crate::verify::{validate_claims, verify_signature};
+use fluentbase_sdk::{
+    derive::Event, evm::write_evm_panic_message, hex, oauth2::*, system_entrypoint, Address,
+    ContextReader, ExitCode, StorageUtils, SystemAPI, DEFAULT_OAUTH2_JWKS_AUTH, U256,
+};
+
+/// Base verification cost, in EVM gas units: calldata decoding, base64url/JSON parsing, the
+/// SHA-256 of the signing input and claim checks. The signature check itself is charged on top
+/// with the gas reported by the `modexp`/`p256verify` precompile.
+const OAUTH2_VERIFY_GAS: u64 = 20_000;
+
+/// Owner lookup cost, in EVM gas units.
+const OAUTH2_OWNER_GAS: u64 = 3_000;
+
+/// JWKS management cost, in EVM gas units. Key records live in preloaded slots, so storage
+/// writes are not charged by the host and the flat fee covers them.
+const OAUTH2_ADMIN_GAS: u64 = 50_000;
+
+// A `verify` call carries the token (bounded by `OAUTH2_MAX_TOKEN_LEN`) plus three short strings
+// (issuer, audience and nonce) and their ABI heads. 16 KiB leaves room for long issuer URLs and
+// nonces while rejecting calldata large enough to force expensive decoding.
+const OAUTH2_MAX_INPUT_SIZE: u32 = 16 * 1024;
+
+/// Largest accepted RSA public exponent (exclusive). JWKS keys use `AQAB` (65537) in practice; the
+/// bound keeps the modular exponentiation cheap and predictable.
+const RSA_MAX_EXPONENT: u64 = 1 << 32;
+
+mod events {
+    use super::*;
+    use alloc::string::String;
+
+    #[derive(Debug, Clone, PartialEq, Eq, Event)]
+    pub struct KeyUpdated {
+        #[indexed]
+        pub issuer: String,
+        #[indexed]
+        pub kid: String,
+        pub alg: u8,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Event)]
+    pub struct KeyRemoved {
+        #[indexed]
+        pub issuer: String,
+        #[indexed]
+        pub kid: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Event)]
+    pub struct OwnerChanged {
+        pub new_owner: Address,
+    }
+}
+
+fn oauth2_panic<SDK: SystemAPI>(sdk: &mut SDK, message: &str) -> Result<(), ExitCode> {
+    write_evm_panic_message(message, |slice| sdk.write(slice));
+    Err(ExitCode::Panic)
+}
+
+fn owner<SDK: SystemAPI>(sdk: &SDK) -> Result<Address, ExitCode> {
+    let owner = sdk.storage_address(&OWNER_STORAGE_SLOT)?;
+    Ok(if owner.is_zero() {
+        DEFAULT_OAUTH2_JWKS_AUTH
+    } else {
+        owner
+    })
+}
+
+/// Only owner modifier, the JWKS is governance-managed.
+fn only_owner<SDK: SystemAPI>(sdk: &SDK) -> Result<bool, ExitCode> {
+    if sdk.context().contract_is_static() {
+        return Err(ExitCode::StateChangeDuringStaticCall);
+    }
+    Ok(sdk.context().contract_caller() == owner(sdk)?)
+}
+
+fn load_jwk<SDK: SystemAPI>(
+    sdk: &SDK,
+    issuer: &str,
+    kid: &str,
+) -> Result<Option<JwkRecord>, ExitCode> {
+    let mut words = [U256::ZERO; JWK_RECORD_SLOTS];
+    for (word, slot) in words.iter_mut().zip(jwk_record_slots(issuer, kid)) {
+        *word = sdk.storage(&slot).ok()?;
+    }
+    Ok(JwkRecord::from_words(&words))
+}
+
+fn store_jwk<SDK: SystemAPI>(
+    sdk: &mut SDK,
+    issuer: &str,
+    kid: &str,
+    record: Option<&JwkRecord>,
+) -> Result<(), ExitCode> {
+    // Every slot of the record is written, so replacing a key never leaves stale words behind.
+    let words = record.map_or([U256::ZERO; JWK_RECORD_SLOTS], JwkRecord::to_words);
+    for (word, slot) in words.into_iter().zip(jwk_record_slots(issuer, kid)) {
+        sdk.write_storage(slot, word).ok()?;
+    }
+    Ok(())
+}
+
+fn oauth2_verify_handler<SDK: SystemAPI>(sdk: &mut SDK, input: &[u8]) -> Result<(), ExitCode> {
+    let command = VerifyCommand::try_decode(input)?;
+    let Ok(jws) = parse_compact_jws(command.token.as_bytes()) else {
+        return oauth2_panic(sdk, "oauth2: malformed token");
+    };
+    let Some(record) = load_jwk(sdk, &command.issuer, &jws.header.kid)? else {
+        return oauth2_panic(sdk, "oauth2: unknown key");
+    };
+
+    let gas_limit = sdk.context().contract_gas_limit();
+    let signature = verify_signature(&jws, &record, gas_limit)?;
+    sdk.sync_evm_gas(signature.gas_used)?;
+    if let Err(err) = signature.result {
+        return oauth2_panic(sdk, err);
+    }
+
+    let now = sdk.context().block_timestamp();
+    match validate_claims(&jws.claims, &command, now) {
+        Ok(claims) => {
+            sdk.write(claims.encode_output());
+            Ok(())
+        }
+        Err(err) => oauth2_panic(sdk, err),
+    }
+}
+
+fn oauth2_set_rsa_key_handler<SDK: SystemAPI>(sdk: &mut SDK, input: &[u8]) -> Result<(), ExitCode> {
+    if !only_owner(sdk)? {
+        return oauth2_panic(sdk, "oauth2: incorrect caller");
+    }
+    let SetRsaKeyCommand {
+        issuer,
+        kid,
+        modulus,
+        exponent,
+    } = SetRsaKeyCommand::try_decode(input)?;
+    if issuer.is_empty() || kid.is_empty() {
+        return oauth2_panic(sdk, "oauth2: empty issuer or kid");
+    }
+    // The JWK `n` parameter is an unsigned big-endian integer without leading zeros.
+    if modulus.len() < JWK_RSA_MIN_MODULUS_LEN
+        || modulus.len() > JWK_RSA_MAX_MODULUS_LEN
+        || modulus[0] == 0
+        || modulus[modulus.len() - 1] & 1 == 0
+    {
+        return oauth2_panic(sdk, "oauth2: invalid rsa modulus");
+    }
+    if exponent < U256::from(3) || exponent >= U256::from(RSA_MAX_EXPONENT) || !exponent.bit(0) {
+        return oauth2_panic(sdk, "oauth2: invalid rsa exponent");
+    }
+    let record = JwkRecord::Rs256 { modulus, exponent };
+    store_jwk(sdk, &issuer, &kid, Some(&record))?;
+    events::KeyUpdated {
+        issuer,
+        kid,
+        alg: JWK_ALG_RS256,
+    }
+    .emit(sdk)
+}
+
+fn oauth2_set_ec_key_handler<SDK: SystemAPI>(sdk: &mut SDK, input: &[u8]) -> Result<(), ExitCode> {
+    if !only_owner(sdk)? {
+        return oauth2_panic(sdk, "oauth2: incorrect caller");
+    }
+    let SetEcKeyCommand { issuer, kid, x, y } = SetEcKeyCommand::try_decode(input)?;
+    if issuer.is_empty() || kid.is_empty() {
+        return oauth2_panic(sdk, "oauth2: empty issuer or kid");
+    }
+    if !is_p256_point(x, y) {
+        return oauth2_panic(sdk, "oauth2: invalid ec key");
+    }
+    let record = JwkRecord::Es256 { x, y };
+    store_jwk(sdk, &issuer, &kid, Some(&record))?;
+    events::KeyUpdated {
+        issuer,
+        kid,
+        alg: JWK_ALG_ES256,
+    }
+    .emit(sdk)
+}
+
+fn oauth2_remove_key_handler<SDK: SystemAPI>(sdk: &mut SDK, input: &[u8]) -> Result<(), ExitCode> {
+    if !only_owner(sdk)? {
+        return oauth2_panic(sdk, "oauth2: incorrect caller");
+    }
+    let RemoveKeyCommand { issuer, kid } = RemoveKeyCommand::try_decode(input)?;
+    if load_jwk(sdk, &issuer, &kid)?.is_none() {
+        return oauth2_panic(sdk, "oauth2: unknown key");
+    }
+    store_jwk(sdk, &issuer, &kid, None)?;
+    events::KeyRemoved { issuer, kid }.emit(sdk)
+}
+
+fn oauth2_change_owner_handler<SDK: SystemAPI>(
+    sdk: &mut SDK,
+    input: &[u8],
+) -> Result<(), ExitCode> {
+    if !only_owner(sdk)? {
+        return oauth2_panic(sdk, "oauth2: incorrect caller");
+    }
+    let ChangeOwnerCommand { new_owner } = ChangeOwnerCommand::try_decode(input)?;
+    // An empty owner slot means the genesis bootstrap authority, so storing zero would hand
+    // control back to the launch key instead of clearing it.
+    if new_owner.is_zero() {
+        return oauth2_panic(sdk, "oauth2: can't set owner to zero address");
+    }
+    sdk.write_storage_address(OWNER_STORAGE_SLOT, new_owner)?;
+    events::OwnerChanged { new_owner }.emit(sdk)
+}
+
+fn oauth2_owner_handler<SDK: SystemAPI>(sdk: &mut SDK, _input: &[u8]) -> Result<(), ExitCode> {
+    let owner = owner(sdk)?;
+    sdk.write(owner.into_word());
+    Ok(())
+}
+
+/// Checks that `(x, y)` is an affine point on P-256: `y^2 = x^3 - 3x + b (mod p)`.
+fn is_p256_point(x: U256, y: U256) -> bool {
+    const P: U256 = U256::from_be_bytes(hex!(
+        "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"
+    ));
+    const B: U256 = U256::from_be_bytes(hex!(
+        "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b"
+    ));
+    if x >= P || y >= P {
+        return false;
+    }
+    let lhs = y.mul_mod(y, P);
+    let x3 = x.mul_mod(x, P).mul_mod(x, P);
+    let three_x = x.mul_mod(U256::from(3), P);
+    let rhs = x3.add_mod(P - three_x, P).add_mod(B, P);
+    lhs == rhs
+}
+
+pub fn main_entry<SDK: SystemAPI>(sdk: &mut SDK) -> Result<(), ExitCode> {
+    let input_size = sdk.input_size();
+    if input_size < SIG_LEN_BYTES as u32 || input_size > OAUTH2_MAX_INPUT_SIZE {
+        return Err(ExitCode::MalformedBuiltinParams);
+    }
+    let input = sdk.bytes_input();
+    let (sig, input) = input.split_at(SIG_LEN_BYTES);
+    let sig = u32::from_be_bytes(sig.try_into().unwrap());
+    match sig {
+        SIG_OAUTH2_VERIFY => {
+            sdk.sync_evm_gas(OAUTH2_VERIFY_GAS)?;
+            oauth2_verify_handler(sdk, input)
+        }
+        SIG_OAUTH2_OWNER => {
+            sdk.sync_evm_gas(OAUTH2_OWNER_GAS)?;
+            oauth2_owner_handler(sdk, input)
+        }
+        SIG_OAUTH2_SET_RSA_KEY => {
+            sdk.sync_evm_gas(OAUTH2_ADMIN_GAS)?;
+            oauth2_set_rsa_key_handler(sdk, input)
+        }
+        SIG_OAUTH2_SET_EC_KEY => {
+            sdk.sync_evm_gas(OAUTH2_ADMIN_GAS)?;
+            oauth2_set_ec_key_handler(sdk, input)
+        }
+        SIG_OAUTH2_REMOVE_KEY => {
+            sdk.sync_evm_gas(OAUTH2_ADMIN_GAS)?;
+            oauth2_remove_key_handler(sdk, input)
+        }
+        SIG_OAUTH2_CHANGE_OWNER => {
+            sdk.sync_evm_gas(OAUTH2_ADMIN_GAS)?;
+            oauth2_change_owner_handler(sdk, input)
+        }
+        _ => Err(ExitCode::MalformedBuiltinParams),
+    }
 }
 
 system_entrypoint!(main_entry);
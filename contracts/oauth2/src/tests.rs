@@ -0,0 +1,303 @@
+use super::*;
+use alloc::{format, string::String, vec::Vec};
+use fluentbase_sdk::{
+    codec::SolidityABI, Bytes, ContractContextV1, SharedContextInputV1, FUEL_DENOM_RATE,
+    PRECOMPILE_OAUTH2_VERIFIER,
+};
+use fluentbase_testing::TestingContextImpl;
+use p256::{
+    ecdsa::{signature::SignerMut, SigningKey, VerifyingKey},
+    elliptic_curve::rand_core::OsRng,
+};
+
+const ISSUER: &str = "https://accounts.example.com";
+const AUDIENCE: &str = "fluent-client";
+const NONCE: &str = "n-0S6_WzA2Mj";
+/// Issued at 1700000000 and expires one hour later, like the RS256 fixture.
+const NOW: u64 = 1_700_000_600;
+const GAS_LIMIT: u64 = 10_000_000;
+
+/// RS256 fixture: a 2048-bit key (e = 65537) and an ID token signed with it, kid `rsa-key-1`.
+const RS256_TOKEN: &str = include_str!("../testdata/rs256.jwt");
+const RS256_MODULUS: &str = include_str!("../testdata/rs256-modulus.hex");
+
+fn context() -> TestingContextImpl {
+    let mut shared_ctx = SharedContextInputV1::default();
+    shared_ctx.block.timestamp = NOW;
+    shared_ctx.contract = ContractContextV1 {
+        address: PRECOMPILE_OAUTH2_VERIFIER,
+        bytecode_address: PRECOMPILE_OAUTH2_VERIFIER,
+        caller: DEFAULT_OAUTH2_JWKS_AUTH,
+        gas_limit: GAS_LIMIT,
+        ..Default::default()
+    };
+    TestingContextImpl::default()
+        .with_shared_context_input(shared_ctx)
+        .with_gas_limit(GAS_LIMIT)
+}
+
+fn call<C: OAuth2Command>(
+    sdk: &TestingContextImpl,
+    caller: Address,
+    command: &C,
+) -> Result<Vec<u8>, ExitCode> {
+    let mut input = Vec::new();
+    command.encode_for_send(&mut input);
+    let mut sdk = sdk.clone().with_input(input).with_caller(caller);
+    let result = main_entry(&mut sdk);
+    let output = sdk.take_output();
+    result.map(|_| output)
+}
+
+fn expect_panic(result: Result<Vec<u8>, ExitCode>, sdk: &TestingContextImpl, message: &str) {
+    assert_eq!(result, Err(ExitCode::Panic));
+    let output = sdk.take_output();
+    let (decoded,) = SolidityABI::<(String,)>::decode(&&output[4..], 0).unwrap();
+    assert_eq!(decoded, message);
+}
+
+fn verify_command(token: String) -> VerifyCommand {
+    VerifyCommand {
+        token,
+        issuer: ISSUER.into(),
+        audience: AUDIENCE.into(),
+        nonce: NONCE.into(),
+    }
+}
+
+fn set_rs256_key(sdk: &TestingContextImpl) {
+    let modulus: Bytes = hex::decode(RS256_MODULUS.trim()).unwrap().into();
+    let command = SetRsaKeyCommand {
+        issuer: ISSUER.into(),
+        kid: "rsa-key-1".into(),
+        modulus,
+        exponent: U256::from(65537),
+    };
+    call(sdk, DEFAULT_OAUTH2_JWKS_AUTH, &command).unwrap();
+}
+
+/// Registers a fresh P-256 key under `kid` and returns its signing half.
+fn set_es256_key(sdk: &TestingContextImpl, kid: &str) -> SigningKey {
+    let signing_key = SigningKey::random(&mut OsRng);
+    let public_key = VerifyingKey::from(&signing_key).to_encoded_point(false);
+    let command = SetEcKeyCommand {
+        issuer: ISSUER.into(),
+        kid: kid.into(),
+        x: U256::from_be_slice(public_key.x().unwrap()),
+        y: U256::from_be_slice(public_key.y().unwrap()),
+    };
+    call(sdk, DEFAULT_OAUTH2_JWKS_AUTH, &command).unwrap();
+    signing_key
+}
+
+fn es256_token(signing_key: &mut SigningKey, kid: &str, payload: &str) -> String {
+    let header = format!("{{\"alg\":\"ES256\",\"kid\":\"{kid}\",\"typ\":\"JWT\"}}");
+    let signing_input = format!(
+        "{}.{}",
+        base64url_encode(header.as_bytes()),
+        base64url_encode(payload.as_bytes())
+    );
+    let signature: p256::ecdsa::Signature = signing_key.sign(signing_input.as_bytes());
+    format!(
+        "{signing_input}.{}",
+        base64url_encode(&signature.to_bytes())
+    )
+}
+
+fn payload(exp: u64) -> String {
+    format!(
+        "{{\"iss\":\"{ISSUER}\",\"sub\":\"user-1\",\"aud\":[\"other\",\"{AUDIENCE}\"],\
+         \"nonce\":\"{NONCE}\",\"email\":\"bob@example.com\",\"iat\":1700000000,\"exp\":{exp}}}"
+    )
+}
+
+#[test]
+fn test_rs256_token_is_verified() {
+    let sdk = context();
+    set_rs256_key(&sdk);
+
+    let fuel_before = sdk.consumed_fuel();
+    let output = call(
+        &sdk,
+        Address::ZERO,
+        &verify_command(RS256_TOKEN.trim().into()),
+    )
+    .unwrap();
+    let claims = VerifiedClaims::decode_output(&output).unwrap();
+    assert_eq!(
+        claims,
+        VerifiedClaims {
+            issuer: ISSUER.into(),
+            subject: "1234567890".into(),
+            audience: AUDIENCE.into(),
+            nonce: NONCE.into(),
+            email: "alice@example.com".into(),
+            issued_at: 1_700_000_000,
+            expires_at: 1_700_003_600,
+        }
+    );
+    // The base fee plus the modexp gas for a 2048-bit modulus and a 17-bit exponent.
+    let gas_used = (sdk.consumed_fuel() - fuel_before) / FUEL_DENOM_RATE;
+    assert!(gas_used > OAUTH2_VERIFY_GAS, "{gas_used}");
+}
+
+#[test]
+fn test_es256_token_is_verified() {
+    let sdk = context();
+    let mut signing_key = set_es256_key(&sdk, "ec-key-1");
+    let token = es256_token(&mut signing_key, "ec-key-1", &payload(NOW + 60));
+
+    let output = call(&sdk, Address::ZERO, &verify_command(token)).unwrap();
+    let claims = VerifiedClaims::decode_output(&output).unwrap();
+    assert_eq!(claims.subject, "user-1");
+    assert_eq!(claims.audience, AUDIENCE);
+    assert_eq!(claims.expires_at, NOW + 60);
+    // No `email_verified`, so the address is not vouched for.
+    assert_eq!(claims.email, "");
+}
+
+#[test]
+fn test_claims_are_checked_against_command_and_block_time() {
+    let sdk = context();
+    let mut signing_key = set_es256_key(&sdk, "ec-key-1");
+    let mut token = |payload: &str| es256_token(&mut signing_key, "ec-key-1", payload);
+
+    let expired = token(&payload(NOW));
+    let result = call(&sdk, Address::ZERO, &verify_command(expired));
+    expect_panic(result, &sdk, "oauth2: token expired");
+
+    let not_yet_valid = token(&payload(NOW + 60).replace("\"iat\"", "\"nbf\":1700000601,\"iat\""));
+    let result = call(&sdk, Address::ZERO, &verify_command(not_yet_valid));
+    expect_panic(result, &sdk, "oauth2: token not yet valid");
+
+    let valid = token(&payload(NOW + 60));
+    let wrong_audience = VerifyCommand {
+        audience: "someone-else".into(),
+        ..verify_command(valid.clone())
+    };
+    let result = call(&sdk, Address::ZERO, &wrong_audience);
+    expect_panic(result, &sdk, "oauth2: audience mismatch");
+
+    let wrong_nonce = VerifyCommand {
+        nonce: "replayed".into(),
+        ..verify_command(valid.clone())
+    };
+    let result = call(&sdk, Address::ZERO, &wrong_nonce);
+    expect_panic(result, &sdk, "oauth2: nonce mismatch");
+
+    // An empty nonce skips the check.
+    let no_nonce = VerifyCommand {
+        nonce: "".into(),
+        ..verify_command(valid)
+    };
+    call(&sdk, Address::ZERO, &no_nonce).unwrap();
+
+    let string_exp = token(&payload(NOW + 60).replace(&format!("{}", NOW + 60), "\"later\""));
+    let result = call(&sdk, Address::ZERO, &verify_command(string_exp));
+    expect_panic(result, &sdk, "oauth2: invalid claim type");
+}
+
+#[test]
+fn test_tampered_or_unknown_tokens_are_rejected() {
+    let sdk = context();
+    set_rs256_key(&sdk);
+    let mut signing_key = set_es256_key(&sdk, "ec-key-1");
+
+    // Swap the payload of the RS256 fixture for a different (still well-formed) claim set.
+    let mut segments = RS256_TOKEN.trim().split('.');
+    let (header, _, signature) = (
+        segments.next().unwrap(),
+        segments.next().unwrap(),
+        segments.next().unwrap(),
+    );
+    let forged = format!(
+        "{header}.{}.{signature}",
+        base64url_encode(payload(NOW + 60).as_bytes())
+    );
+    let result = call(&sdk, Address::ZERO, &verify_command(forged));
+    expect_panic(result, &sdk, "oauth2: invalid signature");
+
+    let unknown_kid = es256_token(&mut signing_key, "ec-key-2", &payload(NOW + 60));
+    let result = call(&sdk, Address::ZERO, &verify_command(unknown_kid));
+    expect_panic(result, &sdk, "oauth2: unknown key");
+
+    // An ES256 token naming the RSA key must not be checked against it.
+    let wrong_alg = es256_token(&mut signing_key, "rsa-key-1", &payload(NOW + 60));
+    let result = call(&sdk, Address::ZERO, &verify_command(wrong_alg));
+    expect_panic(result, &sdk, "oauth2: algorithm mismatch");
+
+    let result = call(&sdk, Address::ZERO, &verify_command("a.b".into()));
+    expect_panic(result, &sdk, "oauth2: malformed token");
+}
+
+#[test]
+fn test_only_owner_manages_jwks() {
+    let sdk = context();
+    const NEW_OWNER: Address = Address::with_last_byte(7);
+    let remove = RemoveKeyCommand {
+        issuer: ISSUER.into(),
+        kid: "ec-key-1".into(),
+    };
+
+    set_es256_key(&sdk, "ec-key-1");
+    let result = call(&sdk, NEW_OWNER, &remove);
+    expect_panic(result, &sdk, "oauth2: incorrect caller");
+
+    let change_owner = ChangeOwnerCommand {
+        new_owner: NEW_OWNER,
+    };
+    call(&sdk, DEFAULT_OAUTH2_JWKS_AUTH, &change_owner).unwrap();
+    let result = call(&sdk, DEFAULT_OAUTH2_JWKS_AUTH, &remove);
+    expect_panic(result, &sdk, "oauth2: incorrect caller");
+
+    call(&sdk, NEW_OWNER, &remove).unwrap();
+    let result = call(&sdk, NEW_OWNER, &remove);
+    expect_panic(result, &sdk, "oauth2: unknown key");
+
+    let logs = sdk.take_logs();
+    assert_eq!(logs.len(), 3);
+    assert_eq!(logs[2].1[0].0, events::KeyRemoved::SELECTOR);
+}
+
+#[test]
+fn test_invalid_keys_are_rejected() {
+    let sdk = context();
+
+    let off_curve = SetEcKeyCommand {
+        issuer: ISSUER.into(),
+        kid: "ec-key-1".into(),
+        x: U256::from(1),
+        y: U256::from(1),
+    };
+    let result = call(&sdk, DEFAULT_OAUTH2_JWKS_AUTH, &off_curve);
+    expect_panic(result, &sdk, "oauth2: invalid ec key");
+
+    let modulus: Vec<u8> = hex::decode(RS256_MODULUS.trim()).unwrap();
+    let short_modulus = SetRsaKeyCommand {
+        issuer: ISSUER.into(),
+        kid: "rsa-key-1".into(),
+        modulus: modulus[..128].to_vec().into(),
+        exponent: U256::from(65537),
+    };
+    let result = call(&sdk, DEFAULT_OAUTH2_JWKS_AUTH, &short_modulus);
+    expect_panic(result, &sdk, "oauth2: invalid rsa modulus");
+
+    let even_exponent = SetRsaKeyCommand {
+        modulus: modulus.into(),
+        exponent: U256::from(65536),
+        ..short_modulus
+    };
+    let result = call(&sdk, DEFAULT_OAUTH2_JWKS_AUTH, &even_exponent);
+    expect_panic(result, &sdk, "oauth2: invalid rsa exponent");
+}
+
+#[test]
+fn test_admin_calls_are_rejected_in_static_context() {
+    let sdk = context();
+    sdk.context_mut().is_static = true;
+    let change_owner = ChangeOwnerCommand {
+        new_owner: Address::with_last_byte(7),
+    };
+    let result = call(&sdk, DEFAULT_OAUTH2_JWKS_AUTH, &change_owner);
+    assert_eq!(result, Err(ExitCode::StateChangeDuringStaticCall));
+}
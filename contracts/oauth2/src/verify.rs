@@ -0,0 +1,213 @@
+use alloc::{string::String, vec::Vec};
+use fluentbase_sdk::{
+    crypto::crypto_sha256,
+    hex,
+    oauth2::{
+        CompactJws, JsonObject, JsonValue, JwkRecord, JwsAlgorithm, VerifiedClaims, VerifyCommand,
+    },
+    ExitCode, U256,
+};
+use revm_precompile::PrecompileHalt;
+
+/// DER prefix of the PKCS#1 `DigestInfo` for SHA-256 (RFC 8017 section 9.2, note 1).
+const SHA256_DIGEST_INFO_PREFIX: [u8; 19] = hex!("3031300d060960864801650304020105000420");
+
+/// Outcome of a signature check together with the precompile gas it consumed.
+pub(crate) struct SignatureCheck {
+    pub(crate) gas_used: u64,
+    pub(crate) result: Result<(), &'static str>,
+}
+
+impl SignatureCheck {
+    fn new(gas_used: u64, is_valid: bool) -> Self {
+        Self {
+            gas_used,
+            result: if is_valid {
+                Ok(())
+            } else {
+                Err("oauth2: invalid signature")
+            },
+        }
+    }
+
+    fn rejected(err: &'static str) -> Self {
+        Self {
+            gas_used: 0,
+            result: Err(err),
+        }
+    }
+}
+
+/// Verifies the JWS signature with the registered key.
+///
+/// The header `alg` must match the key type, so a token can't pick a weaker interpretation of the
+/// registered key.
+pub(crate) fn verify_signature(
+    jws: &CompactJws,
+    record: &JwkRecord,
+    gas_limit: u64,
+) -> Result<SignatureCheck, ExitCode> {
+    let hash = crypto_sha256(jws.signing_input).0;
+    match (jws.header.alg, record) {
+        (JwsAlgorithm::Rs256, JwkRecord::Rs256 { modulus, exponent }) => {
+            verify_rs256(&hash, &jws.signature, modulus, *exponent, gas_limit)
+        }
+        (JwsAlgorithm::Es256, JwkRecord::Es256 { x, y }) => {
+            verify_es256(&hash, &jws.signature, *x, *y, gas_limit)
+        }
+        _ => Ok(SignatureCheck::rejected("oauth2: algorithm mismatch")),
+    }
+}
+
+/// RSASSA-PKCS1-v1_5 verification (RFC 8017 section 8.2.2) on top of the `modexp` precompile.
+fn verify_rs256(
+    hash: &[u8; 32],
+    signature: &[u8],
+    modulus: &[u8],
+    exponent: U256,
+    gas_limit: u64,
+) -> Result<SignatureCheck, ExitCode> {
+    // The signature must be exactly as long as the modulus and smaller than it.
+    if signature.len() != modulus.len() || signature >= modulus {
+        return Ok(SignatureCheck::rejected("oauth2: invalid signature"));
+    }
+
+    // EIP-198 input: `len(base) || len(exp) || len(mod) || base || exp || mod`.
+    let mut input = Vec::with_capacity(3 * 32 + 2 * modulus.len() + 32);
+    input.extend_from_slice(&U256::from(signature.len()).to_be_bytes::<32>());
+    input.extend_from_slice(&U256::from(32).to_be_bytes::<32>());
+    input.extend_from_slice(&U256::from(modulus.len()).to_be_bytes::<32>());
+    input.extend_from_slice(signature);
+    input.extend_from_slice(&exponent.to_be_bytes::<32>());
+    input.extend_from_slice(modulus);
+
+    let result =
+        revm_precompile::modexp::osaka_run(&input, gas_limit).map_err(|err| match err {
+            PrecompileHalt::OutOfGas => ExitCode::OutOfFuel,
+            _ => ExitCode::PrecompileError,
+        })?;
+
+    // EM = 0x00 || 0x01 || PS (0xff..) || 0x00 || DigestInfo || H
+    let encoded = result.bytes.as_ref();
+    let padding_len = modulus.len() - 3 - SHA256_DIGEST_INFO_PREFIX.len() - hash.len();
+    let is_valid = encoded.len() == modulus.len()
+        && encoded[0] == 0x00
+        && encoded[1] == 0x01
+        && encoded[2..2 + padding_len].iter().all(|byte| *byte == 0xff)
+        && encoded[2 + padding_len] == 0x00
+        && encoded[3 + padding_len..3 + padding_len + SHA256_DIGEST_INFO_PREFIX.len()]
+            == SHA256_DIGEST_INFO_PREFIX
+        && encoded[modulus.len() - hash.len()..] == hash[..];
+    Ok(SignatureCheck::new(result.gas_used, is_valid))
+}
+
+/// ECDSA P-256 verification on top of the `p256verify` precompile. JWS carries the signature as
+/// the fixed-size `r || s` concatenation (RFC 7518 section 3.4), not DER.
+fn verify_es256(
+    hash: &[u8; 32],
+    signature: &[u8],
+    x: U256,
+    y: U256,
+    gas_limit: u64,
+) -> Result<SignatureCheck, ExitCode> {
+    if signature.len() != 64 {
+        return Ok(SignatureCheck::rejected("oauth2: invalid signature"));
+    }
+
+    let mut input = Vec::with_capacity(160);
+    input.extend_from_slice(hash);
+    input.extend_from_slice(signature);
+    input.extend_from_slice(&x.to_be_bytes::<32>());
+    input.extend_from_slice(&y.to_be_bytes::<32>());
+
+    let result =
+        revm_precompile::secp256r1::p256_verify(&input, gas_limit).map_err(|err| match err {
+            PrecompileHalt::OutOfGas => ExitCode::OutOfFuel,
+            _ => ExitCode::PrecompileError,
+        })?;
+    let is_valid = result.bytes.last() == Some(&1);
+    Ok(SignatureCheck::new(result.gas_used, is_valid))
+}
+
+fn string_claim(claims: &JsonObject, name: &str) -> Result<Option<String>, &'static str> {
+    match claims.get(name) {
+        None => Ok(None),
+        Some(JsonValue::Str(value)) => Ok(Some(value.clone())),
+        Some(_) => Err("oauth2: invalid claim type"),
+    }
+}
+
+fn numeric_date_claim(claims: &JsonObject, name: &str) -> Result<Option<u64>, &'static str> {
+    match claims.get(name) {
+        None => Ok(None),
+        Some(JsonValue::Integer(value)) => Ok(Some(*value)),
+        Some(_) => Err("oauth2: invalid claim type"),
+    }
+}
+
+/// Validates the claim set of a token whose signature has already been verified.
+///
+/// `now` is the block timestamp. `email` is only returned when the issuer also asserts
+/// `email_verified: true`, so callers can bind accounts to it.
+pub(crate) fn validate_claims(
+    claims: &JsonObject,
+    command: &VerifyCommand,
+    now: u64,
+) -> Result<VerifiedClaims, &'static str> {
+    if string_claim(claims, "iss")?.as_deref() != Some(command.issuer.as_str()) {
+        return Err("oauth2: issuer mismatch");
+    }
+
+    let audience_matches = match claims.get("aud") {
+        Some(JsonValue::Str(audience)) => *audience == command.audience,
+        Some(JsonValue::StrArray(audiences)) => audiences.contains(&command.audience),
+        _ => false,
+    };
+    if command.audience.is_empty() || !audience_matches {
+        return Err("oauth2: audience mismatch");
+    }
+    // OIDC Core 3.1.3.7: if the authorized party is present, it must be this client.
+    if let Some(azp) = string_claim(claims, "azp")? {
+        if azp != command.audience {
+            return Err("oauth2: audience mismatch");
+        }
+    }
+
+    let Some(expires_at) = numeric_date_claim(claims, "exp")? else {
+        return Err("oauth2: missing exp claim");
+    };
+    if now >= expires_at {
+        return Err("oauth2: token expired");
+    }
+    if let Some(not_before) = numeric_date_claim(claims, "nbf")? {
+        if now < not_before {
+            return Err("oauth2: token not yet valid");
+        }
+    }
+    let issued_at = numeric_date_claim(claims, "iat")?.unwrap_or_default();
+
+    let subject = match string_claim(claims, "sub")? {
+        Some(subject) if !subject.is_empty() => subject,
+        _ => return Err("oauth2: missing sub claim"),
+    };
+
+    let nonce = string_claim(claims, "nonce")?.unwrap_or_default();
+    if !command.nonce.is_empty() && nonce != command.nonce {
+        return Err("oauth2: nonce mismatch");
+    }
+
+    let email = match (string_claim(claims, "email")?, claims.get("email_verified")) {
+        (Some(email), Some(JsonValue::Bool(true))) => email,
+        _ => String::new(),
+    };
+
+    Ok(VerifiedClaims {
+        issuer: command.issuer.clone(),
+        subject,
+        audience: command.audience.clone(),
+        nonce,
+        email,
+        issued_at,
+        expires_at,
+    })
+}
@@ -4,12 +4,9 @@ extern crate fluentbase_sdk;
 
 use fluentbase_sdk::{
     default_compilation_config, rwasm_core::RwasmModule, system_entrypoint, ExitCode, SystemAPI,
-    FUEL_DENOM_RATE, RWASM_MAX_CODE_SIZE,
+    RWASM_MAX_CODE_SIZE, WASM_COMPILATION_OVERHEAD_FUEL_PER_BYTE,
 };
 
-/// An average overhead per each Wasm byte in fuel cost (~50 gas per byte).
-const WASM_COMPILATION_OVERHEAD_FUEL_PER_BYTE: u32 = (50 * FUEL_DENOM_RATE) as u32;
-
 pub fn deploy_entry<SDK: SystemAPI>(sdk: &mut SDK) -> Result<(), ExitCode> {
     // Wasm compilation fuel cost is ~50k fuel per one byte (~2500gas/b)
     let fuel_cost = sdk
@@ -21,6 +21,7 @@ pub struct BuildResult {
     pub solidity_path: Option<PathBuf>,
     pub metadata_path: Option<PathBuf>,
     pub foundry_metadata_path: Option<PathBuf>,
+    pub storage_layout_path: Option<PathBuf>,
 }
 
 /// Executes the build process with Docker/local compilation and generates artifacts.
@@ -182,6 +183,7 @@ pub fn execute_build(args: &BuildArgs, contract_dir: Option<PathBuf>) -> Result<
             &docker_image,
             &mount_dir,
             &rust_toolchain,
+            &dependency_sources(&metadata, package),
             &mut result,
         )?;
     }
@@ -189,6 +191,43 @@ pub fn execute_build(args: &BuildArgs, contract_dir: Option<PathBuf>) -> Result<
     Ok(result)
 }
 
+/// Root source files of the direct dependencies of `package` that storage components may come
+/// from
+fn dependency_sources(metadata: &Metadata, package: &Package) -> Vec<PathBuf> {
+    use cargo_metadata::{DependencyKind, TargetKind};
+
+    let Some(node) = metadata
+        .resolve
+        .as_ref()
+        .and_then(|resolve| resolve.nodes.iter().find(|node| node.id == package.id))
+    else {
+        return Vec::new();
+    };
+
+    node.deps
+        .iter()
+        .filter(|dep| {
+            dep.dep_kinds
+                .iter()
+                .any(|info| info.kind == DependencyKind::Normal)
+        })
+        .map(|dep| &metadata[&dep.pkg])
+        // Only crates built on the SDK can declare storage components
+        .filter(|dependency| {
+            dependency.name == "fluentbase-sdk"
+                || dependency
+                    .dependencies
+                    .iter()
+                    .any(|d| d.name == "fluentbase-sdk")
+        })
+        .flat_map(|dependency| &dependency.targets)
+        .filter(|target| {
+            target.kind.contains(&TargetKind::Lib) || target.kind.contains(&TargetKind::RLib)
+        })
+        .map(|target| target.src_path.clone().into_std_path_buf())
+        .collect()
+}
+
 fn build_wasm(
     args: &BuildArgs,
     contract_dir: &Path,
@@ -401,6 +440,7 @@ fn generate_artifacts(
     docker_image: &Option<VerifiedImage>,
     mount_dir: &Path,
     rust_toolchain: &Option<String>,
+    dependency_sources: &[PathBuf],
     result: &mut BuildResult,
 ) -> Result<()> {
     // Helper to write JSON files
@@ -411,10 +451,12 @@ fn generate_artifacts(
     }
 
     // Pre-generate ABI if needed by any artifact
-    let needs_abi = args
-        .generate
-        .iter()
-        .any(|a| matches!(a, Artifact::Abi | Artifact::Solidity | Artifact::Metadata));
+    let needs_abi = args.generate.iter().any(|a| {
+        matches!(
+            a,
+            Artifact::Abi | Artifact::Solidity | Artifact::Metadata | Artifact::Foundry
+        )
+    });
 
     let abi = if needs_abi {
         Some(generators::solidity::generate_abi(contract_dir)?)
@@ -426,17 +468,20 @@ fn generate_artifacts(
     let mut artifacts = args.generate.clone();
     artifacts.sort_by_key(|a| {
         match a {
-            Artifact::Rwasm => 0,    // Can be generated independently
-            Artifact::Wat => 1,      // Can be generated independently
-            Artifact::Abi => 2,      // Depends on pre-generated ABI
-            Artifact::Solidity => 3, // Depends on ABI
-            Artifact::Metadata => 4, // Depends on everything else
-            Artifact::Foundry => 5,  // Depends on everything else
+            Artifact::Rwasm => 0,         // Can be generated independently
+            Artifact::Wat => 1,           // Can be generated independently
+            Artifact::Abi => 2,           // Depends on pre-generated ABI
+            Artifact::Solidity => 3,      // Depends on ABI
+            Artifact::Metadata => 4,      // Depends on everything else
+            Artifact::Foundry => 5,       // Depends on everything else
+            Artifact::StorageLayout => 6, // Can be generated independently
         }
     });
 
     // Cache wasm data to avoid re-reading
     let mut wasm_data: Option<Vec<u8>> = None;
+    // rWasm module, compiled once and shared by every artifact that embeds it
+    let mut rwasm_data: Option<Vec<u8>> = None;
 
     for artifact in &artifacts {
         match artifact {
@@ -455,14 +500,12 @@ fn generate_artifacts(
             // We don't need to run wasm -> rwasm inside docker since
             // it's a pure Rust library function without platform dependencies
             Artifact::Rwasm => {
-                let data = wasm_data
+                let wasm = wasm_data
                     .get_or_insert_with(|| fs::read(wasm_path).expect("Failed to read WASM file"));
-
-                let rwasm_result = fluentbase_sdk::compile_wasm_to_rwasm(data)
-                    .map_err(|e| anyhow::anyhow!("rWASM compilation failed: {:?}", e))?;
+                let rwasm = compile_rwasm(wasm, &mut rwasm_data)?;
 
                 let rwasm_path = output_dir.join("lib.rwasm");
-                fs::write(&rwasm_path, rwasm_result.rwasm_module.serialize())?;
+                fs::write(&rwasm_path, rwasm)?;
                 result.rwasm_path = Some(rwasm_path);
             }
 
@@ -485,8 +528,6 @@ fn generate_artifacts(
                 let wasm = wasm_data
                     .get_or_insert_with(|| fs::read(wasm_path).expect("Failed to read WASM file"));
 
-                let rwasm_data = result.rwasm_path.as_ref().map(fs::read).transpose()?;
-
                 let metadata = generators::metadata::generate(
                     contract_dir,
                     args,
@@ -506,13 +547,9 @@ fn generate_artifacts(
                 let wasm = wasm_data
                     .get_or_insert_with(|| fs::read(wasm_path).expect("Failed to read WASM file"));
 
-                let rwasm_data_path = result
-                    .rwasm_path
-                    .as_ref()
-                    .expect("rwasm is required for Foundry artifact - generate rwasm first");
-
-                let rwasm_data =
-                    fs::read(rwasm_data_path).expect("rwasm should be generated at this point");
+                // `deployedBytecode` is the code the network stores once the WASM init code has
+                // run, so it is compiled here even when `lib.rwasm` itself was not requested
+                let rwasm = compile_rwasm(wasm, &mut rwasm_data)?;
 
                 // Create minimal build metadata for Foundry artifact
                 // (if full metadata is needed, we'd need to generate it first)
@@ -520,17 +557,23 @@ fn generate_artifacts(
                     contract_dir,
                     args,
                     wasm,
-                    Some(&rwasm_data),
+                    Some(rwasm),
                     docker_image.as_ref(),
                     rust_toolchain.as_deref(),
                 )?;
 
+                // Selectors come from the router itself, so custom `#[function_id(...)]` pins
+                // are reported as the contract dispatches them
+                let method_identifiers =
+                    generators::solidity::generate_method_identifiers(contract_dir)?;
+
                 let interface_path = format!("{package_name}.wasm/interface.sol");
                 let foundry_artifact = generators::foundry::generate_artifact(
                     package_name,
                     &serde_json::to_value(abi)?,
+                    method_identifiers,
                     wasm,
-                    &rwasm_data,
+                    rwasm,
                     &build_metadata,
                     &interface_path,
                 )?;
@@ -539,12 +582,35 @@ fn generate_artifacts(
                 write_json(&foundry_path, &foundry_artifact)?;
                 result.foundry_metadata_path = Some(foundry_path);
             }
+
+            Artifact::StorageLayout => {
+                let layout = generators::storage_layout::generate_storage_layout(
+                    contract_dir,
+                    dependency_sources,
+                )?;
+                let layout_path = output_dir.join("storage-layout.json");
+                write_json(&layout_path, &layout)?;
+                result.storage_layout_path = Some(layout_path);
+            }
         }
     }
 
     Ok(())
 }
 
+/// Compiles the WASM binary into the rWasm module a deployment of it produces
+///
+/// Uses the same compilation config as the on-chain WASM runtime, so the bytes match what
+/// `eth_getCode` returns for the deployed contract.
+fn compile_rwasm<'a>(wasm: &[u8], rwasm_data: &'a mut Option<Vec<u8>>) -> Result<&'a [u8]> {
+    if rwasm_data.is_none() {
+        let rwasm_result = fluentbase_sdk::compile_wasm_to_rwasm(wasm)
+            .map_err(|e| anyhow::anyhow!("rWASM compilation failed: {:?}", e))?;
+        *rwasm_data = Some(rwasm_result.rwasm_module.serialize());
+    }
+    Ok(rwasm_data.as_deref().expect("rwasm is compiled above"))
+}
+
 fn cargo_rerun_if_changed(metadata: &Metadata, contract_dir: &Path) {
     // Watch source files
     for path in &["src", "lib.rs", "Cargo.toml", "build.rs"] {
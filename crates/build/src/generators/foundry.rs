@@ -24,6 +24,8 @@ pub struct FoundryArtifact {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bytecode {
     pub object: String,
+    /// Always empty: rWasm carries no mapping back to the Rust sources, so there is nothing to
+    /// report in solc's `s:l:f:j` format. Source maps are out of scope for Rust contracts.
     #[serde(rename = "sourceMap")]
     pub source_map: String,
     #[serde(rename = "linkReferences")]
@@ -101,7 +103,10 @@ pub struct SourceInfo {
 /// # Arguments
 /// * `contract_name` - Clean contract name (e.g., "PowerCalculator")
 /// * `abi` - Already generated ABI as JSON value
-/// * `wasm_bytecode` - Compiled WASM bytecode
+/// * `method_identifiers` - Selectors the router dispatches on, keyed by signature (see
+///   [`generate_method_identifiers`](crate::generators::solidity::generate_method_identifiers))
+/// * `wasm_bytecode` - Compiled WASM bytecode, deployed as init code
+/// * `rwasm` - rWasm module the network stores for the contract once the init code has run
 /// * `build_metadata` - Build metadata from metadata generator
 /// * `interface_path` - Relative path to generated Solidity interface
 ///
@@ -110,6 +115,7 @@ pub struct SourceInfo {
 pub fn generate_artifact(
     contract_name: &str,
     abi: &serde_json::Value,
+    method_identifiers: BTreeMap<String, String>,
     wasm_bytecode: &[u8],
     rwasm: &[u8],
     build_metadata: &BuildMetadata,
@@ -119,9 +125,6 @@ pub fn generate_artifact(
     let bytecode_hex = format!("0x{}", hex::encode(wasm_bytecode));
     let deployed_bytecode_hex = format!("0x{}", hex::encode(rwasm));
 
-    // Create method identifiers from ABI
-    let method_identifiers = create_method_identifiers(abi)?;
-
     // Create Foundry metadata
     let foundry_metadata =
         create_foundry_metadata(contract_name, abi, build_metadata, interface_path)?;
@@ -137,11 +140,13 @@ pub fn generate_artifact(
         link_references: BTreeMap::new(),
         immutable_references: None,
     };
+    // solc always emits `immutableReferences` for runtime code; tooling that diffs artifacts
+    // expects the field even though Rust contracts have no immutables
     let deployed_bytecode = Bytecode {
         object: deployed_bytecode_hex.clone(),
         source_map: String::new(),
         link_references: BTreeMap::new(),
-        immutable_references: None,
+        immutable_references: Some(BTreeMap::new()),
     };
 
     Ok(FoundryArtifact {
@@ -155,12 +160,6 @@ pub fn generate_artifact(
     })
 }
 
-/// Create method identifiers from ABI
-fn create_method_identifiers(_abi: &serde_json::Value) -> Result<BTreeMap<String, String>> {
-    // TODO: Implement proper method identifier calculation
-    Ok(BTreeMap::new())
-}
-
 /// Create Foundry metadata structure
 fn create_foundry_metadata(
     contract_name: &str,
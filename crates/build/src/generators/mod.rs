@@ -1,3 +1,4 @@
 pub mod foundry;
 pub mod metadata;
 pub mod solidity;
+pub mod storage_layout;
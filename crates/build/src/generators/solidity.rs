@@ -2,20 +2,29 @@
 
 use anyhow::{anyhow, Context, Result};
 use convert_case::{Case, Casing};
+use fluentbase_sdk::ComponentAbi;
 use fluentbase_sdk_derive_core::{
     abi::{
+        components::{Imports, SdkComponent},
+        declarations::ContractDeclarations,
+        event::EventABI,
         function::FunctionABI,
+        sol_error::ErrorABI,
         structs::{StructRegistry, StructResolver},
     },
     attr::StateMutabilityExt,
     constructor::{process_constructor_with_structs, Constructor},
     method::ParsedMethod,
     router::{process_router_with_structs, Router},
+    uups::{add_upgrade_methods, is_uups_attr},
 };
 use proc_macro2::TokenStream as TokenStream2;
 use quote::ToTokens;
 use serde_json::Value;
-use std::{collections::HashSet, path::Path};
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::{Path, PathBuf},
+};
 use syn::{parse_file, visit::Visit, Attribute, ImplItemFn, ItemImpl};
 
 /// Solidity ABI represented as JSON values
@@ -27,32 +36,69 @@ pub type Abi = Vec<Value>;
 /// * `contract_dir` - Path to the contract directory (contains src/ with lib.rs or main.rs)
 ///
 /// # Returns
-/// * `Result<Abi>` - JSON array with function definitions or empty array if no functions found
+/// * `Result<Abi>` - JSON array with constructor, function, event and error definitions, or an
+///   empty array if the contract declares none
 pub fn generate_abi(contract_dir: &Path) -> Result<Abi> {
-    // Find the main source file
-    let src_dir = contract_dir.join("src");
-    let main_file = if src_dir.join("lib.rs").exists() {
-        src_dir.join("lib.rs")
-    } else if src_dir.join("main.rs").exists() {
-        src_dir.join("main.rs")
-    } else {
-        return Err(anyhow::anyhow!(
-            "No lib.rs or main.rs found in {}",
-            src_dir.display()
-        ));
-    };
-
-    // Parse all Codec structs reachable from the crate root, so that struct parameters are
-    // expanded into their components before any selector is calculated - exactly as the
-    // #[router] macro does when it compiles the dispatch table
-    let structs = StructRegistry::parse_crate(&main_file)?;
-    let resolver = StructResolver::registry(structs);
+    let main_file = find_main_file(contract_dir)?;
+    let resolver = parse_structs(&main_file)?;
 
     // Parse contract methods (routers and constructors) from the main file
     let methods = parse_contract_methods(&main_file, &resolver)?;
 
-    // Generate ABI from contract methods
-    generate_abi_from_methods(&methods, &resolver)
+    // Events and custom errors never pass through the router, so they are collected from every
+    // module of the crate instead, along with those of the SDK components the contract uses
+    let mut declarations = ContractDeclarations::parse_crate(&main_file, &resolver)?;
+    let mut components = SdkComponent::embedded_in_crate(&main_file)?;
+    if let Some(router) = methods.routers.first() {
+        for component in router.sdk_components(&methods.imports)? {
+            if !components.contains(&component) {
+                components.push(component);
+            }
+        }
+    }
+    for component in components {
+        declarations.extend(component_declarations(component)?);
+    }
+
+    // Generate ABI from contract methods, followed by events and errors
+    let mut abi = generate_abi_from_methods(&methods, &resolver)?;
+    abi.extend(generate_abi_from_declarations(&declarations)?);
+    Ok(abi)
+}
+
+/// Parse contract directory and collect the selector of every router method
+///
+/// # Arguments
+/// * `contract_dir` - Path to the contract directory (contains src/ with lib.rs or main.rs)
+///
+/// # Returns
+/// * `Result<BTreeMap<String, String>>` - Canonical signature of each published function mapped to
+///   the selector the router dispatches it on, as lowercase hex without the `0x` prefix (the shape
+///   solc and Foundry use for `methodIdentifiers`). Selectors pinned with `#[function_id(...)]`
+///   are reported as pinned rather than recomputed from the signature.
+pub fn generate_method_identifiers(contract_dir: &Path) -> Result<BTreeMap<String, String>> {
+    let main_file = find_main_file(contract_dir)?;
+    let resolver = parse_structs(&main_file)?;
+    let methods = parse_contract_methods(&main_file, &resolver)?;
+
+    let mut method_identifiers = BTreeMap::new();
+    if let Some(router) = methods.routers.first() {
+        for method in router.available_methods() {
+            // The published entry is canonical, while a pinned signature is kept as written
+            let signature = match method.function_abi() {
+                Some(abi) => abi.signature().map_err(|error| {
+                    anyhow!(
+                        "Failed to derive the signature of `{}`: {error}",
+                        method.parsed_signature().rust_name()
+                    )
+                })?,
+                None => method.signature().to_string(),
+            };
+            method_identifiers.insert(signature, hex_selector(method.function_id()));
+        }
+    }
+
+    Ok(method_identifiers)
 }
 
 /// Generate Solidity interface from ABI
@@ -69,24 +115,42 @@ pub fn generate_interface(contract_name: &str, abi: &Abi) -> Result<String> {
     // Header
     interface.push_str("// SPDX-License-Identifier: MIT\n");
     interface.push_str("// Auto-generated from Rust source\n");
-    interface.push_str("pragma solidity ^0.8.0;\n\n");
-    interface.push_str(&format!(
-        "interface I{} {{\n",
-        contract_name.to_case(Case::Pascal)
-    ));
 
-    // Extract and add struct definitions
+    // Extract struct definitions and enum declarations
     let mut seen_structs = HashSet::new();
     let mut struct_definitions = Vec::new();
+    let mut seen_enums = HashSet::new();
+    let mut enum_declarations = Vec::new();
 
     // Collect structs from both constructor and functions
     for entry in abi {
         if let Some(inputs) = entry.get("inputs").and_then(Value::as_array) {
             collect_structs(inputs, &mut seen_structs, &mut struct_definitions);
+            collect_enums(inputs, &mut seen_enums, &mut enum_declarations);
         }
         if let Some(outputs) = entry.get("outputs").and_then(Value::as_array) {
             collect_structs(outputs, &mut seen_structs, &mut struct_definitions);
+            collect_enums(outputs, &mut seen_enums, &mut enum_declarations);
+        }
+    }
+
+    // Enums are declared as user-defined value types, which need 0.8.8
+    if enum_declarations.is_empty() {
+        interface.push_str("pragma solidity ^0.8.0;\n\n");
+    } else {
+        interface.push_str("pragma solidity ^0.8.8;\n\n");
+    }
+    interface.push_str(&format!(
+        "interface I{} {{\n",
+        contract_name.to_case(Case::Pascal)
+    ));
+
+    if !enum_declarations.is_empty() {
+        for enum_declaration in &enum_declarations {
+            interface.push_str(enum_declaration);
+            interface.push('\n');
         }
+        interface.push('\n');
     }
 
     // Add structs to interface
@@ -97,8 +161,19 @@ pub fn generate_interface(contract_name: &str, abi: &Abi) -> Result<String> {
         }
     }
 
+    // Events and errors are declared before functions, as solc lays out interfaces
+    for event in abi.iter().filter(|e| e["type"] == "event") {
+        interface.push_str("    ");
+        interface.push_str(&format_event(event));
+        interface.push('\n');
+    }
+    for error in abi.iter().filter(|e| e["type"] == "error") {
+        interface.push_str("    ");
+        interface.push_str(&format_error(error));
+        interface.push('\n');
+    }
+
     // Note: Constructors are not included in interfaces
-    // Add only functions to the interface
     for func in abi.iter().filter(|e| e["type"] == "function") {
         interface.push_str("    ");
         interface.push_str(&format_function(func)?);
@@ -111,10 +186,39 @@ pub fn generate_interface(contract_name: &str, abi: &Abi) -> Result<String> {
 
 // Internal types and functions
 
+/// Finds the crate root of the contract: `src/lib.rs`, or `src/main.rs` for binaries, or a flat
+/// `lib.rs` next to `Cargo.toml` as used by the examples
+pub(crate) fn find_main_file(contract_dir: &Path) -> Result<PathBuf> {
+    let src_dir = contract_dir.join("src");
+    if src_dir.join("lib.rs").exists() {
+        Ok(src_dir.join("lib.rs"))
+    } else if src_dir.join("main.rs").exists() {
+        Ok(src_dir.join("main.rs"))
+    } else if contract_dir.join("lib.rs").exists() {
+        Ok(contract_dir.join("lib.rs"))
+    } else {
+        Err(anyhow!(
+            "No lib.rs or main.rs found in {}",
+            src_dir.display()
+        ))
+    }
+}
+
+/// Parses all Codec structs reachable from the crate root
+///
+/// Struct parameters are expanded into their components before any selector is calculated -
+/// exactly as the #[router] macro does when it compiles the dispatch table.
+fn parse_structs(main_file: &Path) -> Result<StructResolver> {
+    let structs = StructRegistry::parse_crate(main_file)?;
+    Ok(StructResolver::registry(structs))
+}
+
 /// Container for all contract elements found during parsing
 struct ContractMethods {
     constructor: Option<Constructor>,
     routers: Vec<Router>,
+    /// `use` declarations of the file, which the router's types are resolved through
+    imports: Imports,
 }
 
 /// Parses a Rust file and extracts all contract elements (routers and constructors)
@@ -139,9 +243,40 @@ fn parse_contract_methods(path: &Path, resolver: &StructResolver) -> Result<Cont
     Ok(ContractMethods {
         constructor: finder.constructor,
         routers: finder.routers,
+        imports: Imports::from_items(&ast.items),
     })
 }
 
+/// Events and errors of an SDK component, as the SDK itself publishes them
+fn component_declarations(component: SdkComponent) -> Result<ContractDeclarations> {
+    use fluentbase_sdk::{
+        access::{AccessControl, Ownable, Ownable2Step},
+        security::ReentrancyGuard,
+    };
+
+    match component {
+        SdkComponent::Ownable => declarations_of::<Ownable>(),
+        SdkComponent::Ownable2Step => declarations_of::<Ownable2Step>(),
+        SdkComponent::AccessControl => declarations_of::<AccessControl>(),
+        SdkComponent::ReentrancyGuard => declarations_of::<ReentrancyGuard>(),
+    }
+}
+
+fn declarations_of<C: ComponentAbi>() -> Result<ContractDeclarations> {
+    let mut declarations = ContractDeclarations::default();
+    for event in C::EVENTS {
+        let event = serde_json::from_str::<EventABI>(event)
+            .with_context(|| format!("Invalid ABI entry of an SDK event: {event}"))?;
+        declarations.add_event(event);
+    }
+    for error in C::ERRORS.iter().copied().flatten() {
+        let error = serde_json::from_str::<ErrorABI>(error)
+            .with_context(|| format!("Invalid ABI entry of an SDK error: {error}"))?;
+        declarations.add_error(error);
+    }
+    Ok(declarations)
+}
+
 /// Generates ABI from parsed contract methods
 ///
 /// Every entry comes from the same resolved representation the router selector was calculated
@@ -191,6 +326,28 @@ fn generate_abi_from_methods(methods: &ContractMethods, resolver: &StructResolve
     Ok(entries)
 }
 
+/// Generates ABI entries for the events and custom errors declared in the crate
+fn generate_abi_from_declarations(declarations: &ContractDeclarations) -> Result<Abi> {
+    let events = declarations.events.iter().map(|event| {
+        event.to_json_value().with_context(|| {
+            format!(
+                "Failed to serialize the ABI entry of event `{}`",
+                event.name
+            )
+        })
+    });
+    let errors = declarations.errors.iter().map(|error| {
+        error.to_json_value().with_context(|| {
+            format!(
+                "Failed to serialize the ABI entry of error `{}`",
+                error.name
+            )
+        })
+    });
+
+    events.chain(errors).collect()
+}
+
 /// Serializes a constructor into its ABI entry
 ///
 /// Constructors are called by the deployer without a selector, so there is nothing to cross-check
@@ -296,7 +453,14 @@ impl<'ast> Visit<'ast> for ContractMethodFinder<'_> {
         // Look for router or constructor attributes
         for attr in &node.attrs {
             if is_router_attribute(attr) {
-                self.process_router_impl(attr, node);
+                // `#[uups]` adds its methods before the router expands, so the ABI lists them too
+                if node.attrs.iter().any(is_uups_attr) {
+                    let mut impl_block = node.clone();
+                    add_upgrade_methods(&mut impl_block);
+                    self.process_router_impl(attr, &impl_block);
+                } else {
+                    self.process_router_impl(attr, node);
+                }
                 break; // Found router attribute - no need to check other attributes
             } else if is_constructor_attribute(attr) {
                 self.process_constructor_impl(attr, node);
@@ -375,6 +539,60 @@ fn format_function(func: &Value) -> Result<String> {
     ))
 }
 
+fn format_event(event: &Value) -> String {
+    let name = event["name"].as_str().unwrap_or_default();
+    let empty_vec = Vec::new();
+    let inputs = event["inputs"].as_array().unwrap_or(&empty_vec);
+
+    // Event parameters have no data location; `indexed` goes between the type and the name
+    let params = inputs
+        .iter()
+        .map(|p| {
+            let indexed = if p["indexed"].as_bool().unwrap_or(false) {
+                " indexed"
+            } else {
+                ""
+            };
+            format_declaration_parameter(p, indexed)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let anonymous = if event["anonymous"].as_bool().unwrap_or(false) {
+        " anonymous"
+    } else {
+        ""
+    };
+
+    format!("event {name}({params}){anonymous};")
+}
+
+fn format_error(error: &Value) -> String {
+    let name = error["name"].as_str().unwrap_or_default();
+    let empty_vec = Vec::new();
+    let inputs = error["inputs"].as_array().unwrap_or(&empty_vec);
+
+    let params = inputs
+        .iter()
+        .map(|p| format_declaration_parameter(p, ""))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("error {name}({params});")
+}
+
+/// Formats an event or error parameter, which unlike a function parameter takes no data location
+fn format_declaration_parameter(param: &Value, modifier: &str) -> String {
+    let name = param["name"].as_str().unwrap_or("");
+    let ty = format_sol_type(param);
+
+    if name.is_empty() {
+        format!("{ty}{modifier}")
+    } else {
+        format!("{ty}{modifier} {name}")
+    }
+}
+
 fn format_parameter(param: &Value, param_kind: ParameterKind) -> String {
     let name = param["name"].as_str().unwrap_or("");
     let internal_type = param.get("internalType").and_then(Value::as_str);
@@ -408,6 +626,15 @@ fn format_parameter(param: &Value, param_kind: ParameterKind) -> String {
 fn format_sol_type(param: &Value) -> String {
     let param_type = param["type"].as_str().unwrap_or("unknown");
 
+    // Enums are referred to by name, with all array suffixes preserved
+    if let Some(enum_name) = param
+        .get("internalType")
+        .and_then(Value::as_str)
+        .and_then(|internal_type| internal_type.strip_prefix("enum "))
+    {
+        return enum_name.to_string();
+    }
+
     // Handle all tuple types (including multidimensional arrays)
     if let Some(array_suffix) = param_type.strip_prefix("tuple") {
         // Check if it's a named struct
@@ -557,6 +784,29 @@ fn collect_structs(params: &[Value], seen: &mut HashSet<String>, structs: &mut V
     }
 }
 
+/// Declares every enum the parameters refer to, including through struct fields
+///
+/// The ABI only records an enum's name, not its members, so it is declared as a `uint8`
+/// user-defined value type: it has the same ABI encoding and keeps the name in signatures.
+fn collect_enums(params: &[Value], seen: &mut HashSet<String>, enums: &mut Vec<String>) {
+    for param in params {
+        if let Some(enum_name) = param
+            .get("internalType")
+            .and_then(Value::as_str)
+            .and_then(|internal_type| internal_type.strip_prefix("enum "))
+        {
+            let enum_name = strip_array_suffixes(enum_name);
+            if seen.insert(enum_name.to_string()) {
+                enums.push(format!("    type {enum_name} is uint8;"));
+            }
+        }
+
+        if let Some(components) = param.get("components").and_then(Value::as_array) {
+            collect_enums(components, seen, enums);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -623,6 +873,39 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_generate_interface_with_enums() {
+        let abi = vec![serde_json::json!({
+            "name": "setStatus",
+            "type": "function",
+            "inputs": [
+                {"name": "status", "type": "uint8", "internalType": "enum Status"},
+                {"name": "history", "type": "uint8[]", "internalType": "enum Status[]"},
+                {
+                    "name": "order",
+                    "type": "tuple",
+                    "internalType": "struct Order",
+                    "components": [
+                        {"name": "id", "type": "uint256", "internalType": "uint256"},
+                        {"name": "side", "type": "uint8", "internalType": "enum Side"}
+                    ]
+                }
+            ],
+            "outputs": [{"name": "", "type": "uint8", "internalType": "enum Status"}],
+            "stateMutability": "nonpayable"
+        })];
+
+        let interface = generate_interface("Exchange", &abi).unwrap();
+
+        assert!(interface.contains("pragma solidity ^0.8.8;"));
+        assert!(interface.contains("    type Status is uint8;\n    type Side is uint8;\n"));
+        assert!(interface.contains("        Side side;"));
+        assert!(interface.contains(
+            "function setStatus(Status status, Status[] calldata history, Order calldata order) \
+             external returns (Status);"
+        ));
+    }
+
     #[test]
     fn test_generate_interface_with_constructor() {
         let abi = vec![
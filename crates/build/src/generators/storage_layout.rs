@@ -0,0 +1,857 @@
+//! Storage layout export in solc's `storageLayout` format, and upgrade checks between two layouts
+//!
+//! The sources are only read to find the field types and the `#[slot(...)]` expressions of the
+//! contract. Auto-layout fields are placed with `fluentbase_sdk::storage::StorageCursor`, the same
+//! packing rules the code generated by `#[derive(Contract)]` and `#[derive(Storage)]` runs, and
+//! upgrades are checked with `fluentbase_sdk::storage::check_storage_upgrade`, the check contracts
+//! run on their `storage_layout()`. Offsets follow solc's convention and count bytes from the least
+//! significant end of the slot, so tools written for Solidity contracts (verifiers, upgrade
+//! reviews) can read the output as is.
+
+use crate::generators::solidity::find_main_file;
+use anyhow::{anyhow, bail, Context, Result};
+use fluentbase_sdk::{
+    keccak256,
+    storage::{check_storage_upgrade, StorageCursor, StorageField},
+    U256,
+};
+use fluentbase_sdk_derive_core::abi::structs::{has_derive, walk_crate};
+use proc_macro2::TokenTree;
+use quote::ToTokens;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+use syn::{
+    Expr, ExprLit, ExprPath, Field, GenericArgument, Item, ItemStruct, Lit, LitStr, PathArguments,
+    PathSegment, Type,
+};
+
+/// Deepest chain of constants a slot expression may go through
+const MAX_CONSTANT_DEPTH: usize = 16;
+
+/// Storage layout of a contract, as solc reports it in `storageLayout`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageLayout {
+    /// Every field of the contract, in declaration order
+    pub storage: Vec<StorageEntry>,
+    /// Types referenced by the fields, keyed by type identifier
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub types: BTreeMap<String, StorageType>,
+}
+
+/// A field of the contract, or a member of a struct type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageEntry {
+    /// Index of the declaration, in place of the solc AST node id
+    pub ast_id: u64,
+    /// `<source file>:<contract name>`
+    pub contract: String,
+    pub label: String,
+    /// Offset in bytes from the least significant end of the slot
+    pub offset: u8,
+    /// Slot in decimal, relative to the start of the struct for struct members
+    pub slot: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// Description of a type referenced from [`StorageLayout::storage`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageType {
+    /// `inplace`, `mapping`, `dynamic_array` or `bytes`
+    pub encoding: String,
+    pub label: String,
+    pub number_of_bytes: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<StorageEntry>>,
+}
+
+/// Parse contract directory and generate its storage layout
+///
+/// # Arguments
+/// * `contract_dir` - Path to the contract directory (contains src/ with lib.rs or main.rs, or a
+///   flat lib.rs)
+/// * `dependencies` - Root source files of the crates storage components may come from, searched
+///   in order after the contract crate itself
+///
+/// # Returns
+/// * `Result<StorageLayout>` - Layout of the `#[derive(Contract)]` struct passed to the entrypoint
+///   macro (or the only one in the crate), or an empty layout if the crate declares none
+pub fn generate_storage_layout(
+    contract_dir: &Path,
+    dependencies: &[PathBuf],
+) -> Result<StorageLayout> {
+    let main_file = find_main_file(contract_dir)?;
+
+    let mut crates = vec![SourceCrate::parse(&main_file)?];
+    for dependency in dependencies {
+        // Dependencies are only searched for components, so one we cannot parse is not an error
+        // until a field actually needs something from it
+        match SourceCrate::parse(dependency) {
+            Ok(source) => crates.push(source),
+            Err(error) => tracing::debug!("skipping {}: {error:#}", dependency.display()),
+        }
+    }
+
+    let Some(contract) = crates[0].contract()? else {
+        return Ok(StorageLayout::default());
+    };
+
+    let source = main_file.strip_prefix(contract_dir).unwrap_or(&main_file);
+    let mut layouter = Layouter {
+        crates: &crates,
+        contract: format!("{}:{}", source.display(), contract.ident),
+        types: BTreeMap::new(),
+        expanding: Vec::new(),
+    };
+    let placement = layouter.lay_out(contract, 0)?;
+
+    let mut layout = StorageLayout {
+        storage: placement
+            .entries
+            .into_iter()
+            .map(|(entry, _)| entry)
+            .collect(),
+        types: layouter.types,
+    };
+    number_declarations(&mut layout);
+    Ok(layout)
+}
+
+/// Reads a layout written by [`generate_storage_layout`] or by solc
+///
+/// Compiler artifacts embedding the layout under `storageLayout` (Foundry, Hardhat) are accepted
+/// as well.
+pub fn read_storage_layout(path: &Path) -> Result<StorageLayout> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    let mut value: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse JSON: {}", path.display()))?;
+    if let Some(layout) = value.get_mut("storageLayout") {
+        value = layout.take();
+    }
+    serde_json::from_value(value)
+        .with_context(|| format!("Invalid storage layout: {}", path.display()))
+}
+
+/// A field of a layout with its position resolved, the members of struct fields expanded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutField {
+    /// Dotted path from the contract, e.g. `config.owner`
+    pub label: String,
+    /// Solidity type name
+    pub ty: String,
+    pub slot: U256,
+    /// Offset in bytes from the least significant end of the slot
+    pub offset: u8,
+    pub bytes: usize,
+}
+
+impl LayoutField {
+    /// The field as the SDK's upgrade check sees it
+    pub fn as_storage_field(&self) -> StorageField<'_> {
+        let slots = if self.bytes < 32 {
+            0
+        } else {
+            self.bytes.div_ceil(32)
+        };
+        StorageField {
+            label: Cow::Borrowed(&self.label),
+            ty: &self.ty,
+            slot: self.slot,
+            offset: mirror_offset(self.offset, self.bytes, slots),
+            bytes: self.bytes,
+            slots,
+            explicit: false,
+        }
+    }
+}
+
+/// Every field of a layout, struct members included, in declaration order
+pub fn layout_fields(layout: &StorageLayout) -> Result<Vec<LayoutField>> {
+    let mut fields = Vec::new();
+    flatten_entries(layout, &layout.storage, U256::ZERO, "", &mut fields)?;
+    Ok(fields)
+}
+
+/// Checks that a contract deployed with the `old` layout keeps reading its data once upgraded to
+/// the `new` one
+///
+/// Struct fields are compared member by member with `check_storage_upgrade`, so the rules are the
+/// ones contracts apply to their own `storage_layout()`: existing fields keep their name, type and
+/// position, and new fields don't overlap them.
+pub fn check_storage_layouts(old: &StorageLayout, new: &StorageLayout) -> Result<()> {
+    let old = layout_fields(old).context("Invalid old storage layout")?;
+    let new = layout_fields(new).context("Invalid new storage layout")?;
+    let old: Vec<_> = old.iter().map(LayoutField::as_storage_field).collect();
+    let new: Vec<_> = new.iter().map(LayoutField::as_storage_field).collect();
+    check_storage_upgrade(&old, &new)
+        .map_err(|error| anyhow!("unsafe storage layout change: {error}"))
+}
+
+// Internal types and functions
+
+/// Declarations of one crate the layout may draw from
+#[derive(Default)]
+struct SourceCrate {
+    /// `#[derive(Contract)]` structs
+    contracts: Vec<ItemStruct>,
+    /// Struct passed to `basic_entrypoint!` or `entrypoint_with_storage!`
+    entrypoint: Option<String>,
+    /// `#[derive(Storage)]` structs by name
+    components: HashMap<String, Vec<ItemStruct>>,
+    /// Type aliases by name
+    aliases: HashMap<String, Vec<Type>>,
+    /// Constant initializers by name
+    constants: HashMap<String, Vec<Expr>>,
+}
+
+impl SourceCrate {
+    /// Collects the declarations of every module of the crate rooted at `entry_file`
+    fn parse(entry_file: &Path) -> Result<Self> {
+        let mut source = Self::default();
+        walk_crate(entry_file, &mut |_, item| match item {
+            Item::Struct(item_struct) if has_derive(&item_struct.attrs, "Contract") => {
+                source.contracts.push(item_struct.clone());
+            }
+            Item::Struct(item_struct) if has_derive(&item_struct.attrs, "Storage") => {
+                source
+                    .components
+                    .entry(item_struct.ident.to_string())
+                    .or_default()
+                    .push(item_struct.clone());
+            }
+            Item::Type(item_type) => {
+                source
+                    .aliases
+                    .entry(item_type.ident.to_string())
+                    .or_default()
+                    .push((*item_type.ty).clone());
+            }
+            Item::Const(item_const) => {
+                source
+                    .constants
+                    .entry(item_const.ident.to_string())
+                    .or_default()
+                    .push((*item_const.expr).clone());
+            }
+            Item::Macro(item_macro) => {
+                let name = item_macro
+                    .mac
+                    .path
+                    .segments
+                    .last()
+                    .map(|s| s.ident.to_string());
+                if matches!(
+                    name.as_deref(),
+                    Some("basic_entrypoint" | "entrypoint_with_storage")
+                ) {
+                    source.entrypoint =
+                        item_macro
+                            .mac
+                            .tokens
+                            .clone()
+                            .into_iter()
+                            .find_map(|token| match token {
+                                TokenTree::Ident(ident) => Some(ident.to_string()),
+                                _ => None,
+                            });
+                }
+            }
+            _ => {}
+        })
+        .with_context(|| format!("Failed to parse crate {}", entry_file.display()))?;
+        Ok(source)
+    }
+
+    /// The contract whose storage is exported
+    fn contract(&self) -> Result<Option<&ItemStruct>> {
+        if let Some(name) = &self.entrypoint {
+            if let Some(contract) = self.contracts.iter().find(|c| c.ident == name) {
+                return Ok(Some(contract));
+            }
+        }
+        match self.contracts.as_slice() {
+            [] => Ok(None),
+            [contract] => Ok(Some(contract)),
+            contracts => {
+                let names: Vec<_> = contracts.iter().map(|c| c.ident.to_string()).collect();
+                bail!(
+                    "found several #[derive(Contract)] structs ({}) and no entrypoint macro \
+                     naming one of them",
+                    names.join(", ")
+                )
+            }
+        }
+    }
+}
+
+/// Where the fields of a struct end up
+#[derive(Default)]
+struct Placement {
+    /// Entries in declaration order, flagged when pinned with `#[slot(...)]`
+    entries: Vec<(StorageEntry, bool)>,
+    /// Bytes used by auto-layout fields, `StorageLayout::BYTES`
+    bytes: usize,
+    /// Slots used by auto-layout fields, `StorageLayout::SLOTS`
+    slots: usize,
+}
+
+/// Storage type of a field, as far as the auto-layout is concerned
+struct FieldType {
+    id: String,
+    bytes: usize,
+    slots: usize,
+    /// Members pinned with `#[slot(...)]` somewhere inside the type, labelled relative to it
+    pinned: Vec<StorageEntry>,
+}
+
+/// Lays out a contract, registering every type it references
+struct Layouter<'a> {
+    /// The contract crate first, then its dependencies
+    crates: &'a [SourceCrate],
+    contract: String,
+    types: BTreeMap<String, StorageType>,
+    /// Structs being laid out, to reject recursive ones
+    expanding: Vec<String>,
+}
+
+impl Layouter<'_> {
+    /// Places the fields of `item`, mirroring the code generated by the storage derives
+    fn lay_out(&mut self, item: &ItemStruct, krate: usize) -> Result<Placement> {
+        let mut placement = Placement::default();
+        let mut cursor = StorageCursor::new(0);
+
+        for field in &item.fields {
+            let Some(ident) = &field.ident else {
+                bail!("`{}` must have named fields", item.ident);
+            };
+            if ident == "sdk" {
+                continue;
+            }
+            let label = ident.to_string();
+            let field_type = self
+                .field_type(&field.ty, krate)
+                .with_context(|| format!("in field `{label}` of `{}`", item.ident))?;
+
+            let (slot, offset, pinned) = if let Some(slot) = slot_attribute(field)? {
+                let slot = self
+                    .evaluate(&slot, krate, 0)
+                    .with_context(|| format!("in the slot of `{}::{label}`", item.ident))?;
+                (slot, 0, true)
+            } else {
+                placement.bytes += field_type.bytes;
+                let (slot, offset) = cursor.place(field_type.bytes, field_type.slots);
+                // The cursor counts offsets as `StorageDescriptor` does, the artifact as solc does
+                let offset = mirror_offset(offset, field_type.bytes, field_type.slots);
+                (U256::from(slot), offset, false)
+            };
+
+            // Components made only of pinned fields take no room of their own
+            if field_type.bytes > 0 || field_type.slots > 0 {
+                let entry = self.entry(label.clone(), slot, offset, field_type.id);
+                placement.entries.push((entry, pinned));
+            }
+            for mut entry in field_type.pinned {
+                entry.label = format!("{label}.{}", entry.label);
+                placement.entries.push((entry, true));
+            }
+        }
+
+        placement.slots = cursor.slots();
+        Ok(placement)
+    }
+
+    fn entry(&self, label: String, slot: U256, offset: u8, ty: String) -> StorageEntry {
+        StorageEntry {
+            ast_id: 0,
+            contract: self.contract.clone(),
+            label,
+            offset,
+            slot: slot.to_string(),
+            ty,
+        }
+    }
+
+    fn field_type(&mut self, ty: &Type, krate: usize) -> Result<FieldType> {
+        let segment = last_segment(ty)
+            .ok_or_else(|| anyhow!("unsupported storage type `{}`", ty.to_token_stream()))?;
+        let name = segment.ident.to_string();
+        let args = generic_args(segment);
+
+        match (name.as_str(), args.as_slice()) {
+            ("StoragePrimitive", [GenericArgument::Type(value)]) => {
+                let (label, bytes) = elementary_type(value).ok_or_else(|| {
+                    anyhow!("unsupported primitive type `{}`", value.to_token_stream())
+                })?;
+                Ok(self.elementary(&label, bytes))
+            }
+            ("StorageMap", [GenericArgument::Type(key), GenericArgument::Type(value)]) => {
+                let key = self.map_key(key)?;
+                let value = self.field_type(value, krate)?;
+                let label = format!("mapping({} => {})", self.label(&key), self.label(&value.id));
+                let id = self.register(
+                    format!("t_mapping({key},{})", value.id),
+                    StorageType {
+                        key: Some(key),
+                        value: Some(value.id),
+                        ..storage_type("mapping", label, 32)
+                    },
+                );
+                Ok(single_slot(id))
+            }
+            ("StorageVec", [GenericArgument::Type(element)]) => {
+                let element = self.field_type(element, krate)?;
+                let label = format!("{}[]", self.label(&element.id));
+                let id = self.register(
+                    format!("t_array({})dyn_storage", element.id),
+                    StorageType {
+                        base: Some(element.id),
+                        ..storage_type("dynamic_array", label, 32)
+                    },
+                );
+                Ok(single_slot(id))
+            }
+            ("StorageArray", [GenericArgument::Type(element), length]) => {
+                let length = self.array_length(length, krate)?;
+                let element = self.field_type(element, krate)?;
+                let (bytes, slots) = if element.slots == 0 {
+                    let bytes = element.bytes * length;
+                    (bytes, bytes.div_ceil(32))
+                } else {
+                    (element.slots * length * 32, element.slots * length)
+                };
+                let label = format!("{}[{length}]", self.label(&element.id));
+                let id = self.register(
+                    format!("t_array({}){length}_storage", element.id),
+                    StorageType {
+                        base: Some(element.id),
+                        ..storage_type("inplace", label, slots * 32)
+                    },
+                );
+                Ok(FieldType {
+                    id,
+                    bytes,
+                    slots,
+                    pinned: Vec::new(),
+                })
+            }
+            ("StorageBytes", []) => {
+                let id = self.register(
+                    "t_bytes_storage".into(),
+                    storage_type("bytes", "bytes".into(), 32),
+                );
+                Ok(single_slot(id))
+            }
+            ("StorageString", []) => {
+                let id = self.register(
+                    "t_string_storage".into(),
+                    storage_type("bytes", "string".into(), 32),
+                );
+                Ok(single_slot(id))
+            }
+            (name, []) => {
+                if let Some((label, bytes)) = storage_alias(name) {
+                    return Ok(self.elementary(&label, bytes));
+                }
+                if let Some((alias, krate)) = self.find(name, krate, |c| &c.aliases)? {
+                    return self.field_type(&alias, krate);
+                }
+                self.struct_type(name, krate)
+            }
+            _ => bail!("unsupported storage type `{}`", ty.to_token_stream()),
+        }
+    }
+
+    /// Lays out a `#[derive(Storage)]` struct as the type of a field
+    fn struct_type(&mut self, name: &str, krate: usize) -> Result<FieldType> {
+        let Some((item, krate)) = self.find(name, krate, |c| &c.components)? else {
+            bail!(
+                "`{name}` is neither a storage type nor a #[derive(Storage)] struct of the \
+                 contract or its dependencies"
+            );
+        };
+        if self.expanding.iter().any(|expanding| expanding == name) {
+            bail!("storage struct `{name}` contains itself");
+        }
+
+        self.expanding.push(name.to_string());
+        let placement = self.lay_out(&item, krate);
+        self.expanding.pop();
+        let placement = placement?;
+
+        let (pinned, members): (Vec<_>, Vec<_>) = placement
+            .entries
+            .into_iter()
+            .partition(|(_, pinned)| *pinned);
+        let id = format!("t_struct({name})_storage");
+        if placement.slots > 0 {
+            let members = members.into_iter().map(|(entry, _)| entry).collect();
+            self.register(
+                id.clone(),
+                StorageType {
+                    members: Some(members),
+                    ..storage_type("inplace", format!("struct {name}"), placement.slots * 32)
+                },
+            );
+        }
+        Ok(FieldType {
+            id,
+            bytes: placement.bytes,
+            slots: placement.slots,
+            pinned: pinned.into_iter().map(|(entry, _)| entry).collect(),
+        })
+    }
+
+    fn elementary(&mut self, label: &str, bytes: usize) -> FieldType {
+        let id = self.register(
+            format!("t_{label}"),
+            storage_type("inplace", label.to_string(), bytes),
+        );
+        if bytes == 32 {
+            single_slot(id)
+        } else {
+            FieldType {
+                id,
+                bytes,
+                slots: 0,
+                pinned: Vec::new(),
+            }
+        }
+    }
+
+    /// Registers the type of a mapping key, which is hashed rather than stored
+    fn map_key(&mut self, ty: &Type) -> Result<String> {
+        let ty = match ty {
+            Type::Reference(reference) => &*reference.elem,
+            ty => ty,
+        };
+        let name = last_segment(ty).map(|segment| segment.ident.to_string());
+        let (id, label) = match (ty, name.as_deref()) {
+            (Type::Slice(_), _) | (_, Some("Vec" | "Bytes")) => ("t_bytes_memory_ptr", "bytes"),
+            (_, Some("String" | "str")) => ("t_string_memory_ptr", "string"),
+            _ => {
+                let (label, bytes) = elementary_type(ty)
+                    .ok_or_else(|| anyhow!("unsupported mapping key `{}`", ty.to_token_stream()))?;
+                return Ok(self.elementary(&label, bytes).id);
+            }
+        };
+        Ok(self.register(id.into(), storage_type("bytes", label.into(), 32)))
+    }
+
+    fn array_length(&self, length: &GenericArgument, krate: usize) -> Result<usize> {
+        let length = match length {
+            GenericArgument::Const(expr) => expr.clone(),
+            GenericArgument::Type(Type::Path(type_path)) => Expr::Path(ExprPath {
+                attrs: Vec::new(),
+                qself: None,
+                path: type_path.path.clone(),
+            }),
+            _ => bail!("unsupported array length `{}`", length.to_token_stream()),
+        };
+        let value = self.evaluate(&length, krate, 0)?;
+        usize::try_from(value).map_err(|_| anyhow!("array length {value} is too large"))
+    }
+
+    fn register(&mut self, id: String, ty: StorageType) -> String {
+        self.types.entry(id.clone()).or_insert(ty);
+        id
+    }
+
+    fn label(&self, id: &str) -> String {
+        self.types[id].label.clone()
+    }
+
+    /// Finds a declaration by name, first in `krate`, then in the contract crate and its
+    /// dependencies, in order
+    fn find<T: Clone>(
+        &self,
+        name: &str,
+        krate: usize,
+        declarations: impl Fn(&SourceCrate) -> &HashMap<String, Vec<T>>,
+    ) -> Result<Option<(T, usize)>> {
+        let order = std::iter::once(krate).chain((0..self.crates.len()).filter(|&i| i != krate));
+        for index in order {
+            match declarations(&self.crates[index])
+                .get(name)
+                .map(Vec::as_slice)
+            {
+                None | Some([]) => continue,
+                Some([declaration]) => return Ok(Some((declaration.clone(), index))),
+                Some(_) => bail!("`{name}` is declared more than once in the same crate"),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Evaluates a slot expression of the forms `#[slot(...)]` accepts in practice
+    fn evaluate(&self, expr: &Expr, krate: usize, depth: usize) -> Result<U256> {
+        match expr {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(literal),
+                ..
+            }) => literal
+                .base10_digits()
+                .parse()
+                .map_err(|_| anyhow!("invalid slot literal `{literal}`")),
+            Expr::Paren(paren) => self.evaluate(&paren.expr, krate, depth),
+            Expr::Group(group) => self.evaluate(&group.expr, krate, depth),
+            Expr::Macro(expr_macro) => {
+                let name = expr_macro
+                    .mac
+                    .path
+                    .segments
+                    .last()
+                    .map(|s| s.ident.to_string());
+                let id = || expr_macro.mac.parse_body::<LitStr>().map(|id| id.value());
+                match name.as_deref() {
+                    Some("erc7201_slot") => Ok(erc7201_slot(&id()?)),
+                    Some("eip1967_slot") => Ok(eip1967_slot(&id()?)),
+                    _ => bail!(unsupported_slot(expr)),
+                }
+            }
+            // `U256::from(5)` and the like
+            Expr::Call(call) if call.args.len() == 1 => match &*call.func {
+                Expr::Path(path)
+                    if path.path.segments.last().is_some_and(|s| s.ident == "from") =>
+                {
+                    self.evaluate(&call.args[0], krate, depth)
+                }
+                _ => bail!(unsupported_slot(expr)),
+            },
+            Expr::Path(path) => {
+                let Some(name) = path.path.segments.last().map(|s| s.ident.to_string()) else {
+                    bail!(unsupported_slot(expr));
+                };
+                if depth == MAX_CONSTANT_DEPTH {
+                    bail!("constant `{name}` is defined in terms of itself");
+                }
+                let Some((value, krate)) = self.find(&name, krate, |c| &c.constants)? else {
+                    bail!("constant `{name}` not found in the contract or its dependencies");
+                };
+                self.evaluate(&value, krate, depth + 1)
+                    .with_context(|| format!("in constant `{name}`"))
+            }
+            _ => bail!(unsupported_slot(expr)),
+        }
+    }
+}
+
+fn unsupported_slot(expr: &Expr) -> String {
+    format!(
+        "unsupported slot expression `{}`, use an integer literal, erc7201_slot!, eip1967_slot! \
+         or a constant defined with one of them",
+        expr.to_token_stream()
+    )
+}
+
+/// `keccak256(keccak256(id) - 1) & ~0xff`, as `erc7201_slot!` computes it
+fn erc7201_slot(id: &str) -> U256 {
+    let namespace = eip1967_slot(id);
+    let slot = U256::from_be_bytes(keccak256(namespace.to_be_bytes::<32>()).0);
+    slot & !U256::from(0xff)
+}
+
+/// `keccak256(id) - 1`, as `eip1967_slot!` computes it
+fn eip1967_slot(id: &str) -> U256 {
+    U256::from_be_bytes(keccak256(id).0).wrapping_sub(U256::from(1))
+}
+
+fn storage_type(encoding: &str, label: String, bytes: usize) -> StorageType {
+    StorageType {
+        encoding: encoding.into(),
+        label,
+        number_of_bytes: bytes.to_string(),
+        key: None,
+        value: None,
+        base: None,
+        members: None,
+    }
+}
+
+/// A type taking exactly one slot, like a mapping or the head of a dynamic array
+fn single_slot(id: String) -> FieldType {
+    FieldType {
+        id,
+        bytes: 32,
+        slots: 1,
+        pinned: Vec::new(),
+    }
+}
+
+/// Extracts the expression of a `#[slot(...)]` attribute
+fn slot_attribute(field: &Field) -> Result<Option<Expr>> {
+    let Some(attr) = field.attrs.iter().find(|attr| attr.path().is_ident("slot")) else {
+        return Ok(None);
+    };
+    attr.parse_args()
+        .map(Some)
+        .map_err(|error| anyhow!("invalid #[slot(...)] attribute: {error}"))
+}
+
+fn last_segment(ty: &Type) -> Option<&PathSegment> {
+    match ty {
+        Type::Path(type_path) => type_path.path.segments.last(),
+        Type::Group(group) => last_segment(&group.elem),
+        Type::Paren(paren) => last_segment(&paren.elem),
+        _ => None,
+    }
+}
+
+fn generic_args(segment: &PathSegment) -> Vec<&GenericArgument> {
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Solidity name and size of a value stored by `StoragePrimitive`
+fn elementary_type(ty: &Type) -> Option<(String, usize)> {
+    let segment = last_segment(ty)?;
+    let name = segment.ident.to_string();
+    let bits = || match generic_args(segment).first() {
+        Some(GenericArgument::Const(Expr::Lit(ExprLit {
+            lit: Lit::Int(literal),
+            ..
+        }))) => literal.base10_parse::<usize>().ok(),
+        _ => None,
+    };
+
+    match name.as_str() {
+        "bool" => Some(("bool".into(), 1)),
+        "Address" => Some(("address".into(), 20)),
+        "U256" => Some(("uint256".into(), 32)),
+        "I256" => Some(("int256".into(), 32)),
+        "B256" => Some(("bytes32".into(), 32)),
+        "Uint" => bits().map(|bits| (format!("uint{bits}"), bits / 8)),
+        "Signed" => bits().map(|bits| (format!("int{bits}"), bits / 8)),
+        "FixedBytes" => bits().map(|bytes| (format!("bytes{bytes}"), bytes)),
+        _ => {
+            let (sign, bits) = name.split_at(1);
+            let bits: usize = bits.parse().ok()?;
+            match sign {
+                "u" => Some((format!("uint{bits}"), bits / 8)),
+                "i" => Some((format!("int{bits}"), bits / 8)),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Solidity name and size of the `StoragePrimitive` aliases, e.g. `StorageU64`
+fn storage_alias(name: &str) -> Option<(String, usize)> {
+    match name {
+        "StorageBool" => return Some(("bool".into(), 1)),
+        "StorageAddress" => return Some(("address".into(), 20)),
+        "StorageB256" => return Some(("bytes32".into(), 32)),
+        _ => {}
+    }
+    // Longer prefixes first, `StorageU` would also match `StorageUint8`
+    [
+        ("StorageUint", "uint", 8),
+        ("StorageInt", "int", 8),
+        ("StorageU", "uint", 8),
+        ("StorageI", "int", 8),
+        ("StorageBytes", "bytes", 1),
+    ]
+    .into_iter()
+    .find_map(|(prefix, solidity, unit)| {
+        let size: usize = name.strip_prefix(prefix)?.parse().ok()?;
+        Some((format!("{solidity}{size}"), size / unit))
+    })
+}
+
+/// Numbers fields and struct members in the order they appear in the output
+fn number_declarations(layout: &mut StorageLayout) {
+    let members = layout
+        .types
+        .values_mut()
+        .filter_map(|ty| ty.members.as_mut());
+    let entries = layout.storage.iter_mut().chain(members.flatten());
+    for (index, entry) in entries.enumerate() {
+        entry.ast_id = index as u64;
+    }
+}
+
+fn null_as_default<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Converts a solc offset, counted from the least significant end of the slot, to the offset
+/// `StorageDescriptor` counts from the most significant end, and back
+///
+/// Only packed fields have an offset, the others start at the beginning of their slot either way.
+fn mirror_offset(offset: u8, bytes: usize, slots: usize) -> u8 {
+    if slots == 0 {
+        (32 - offset as usize - bytes) as u8
+    } else {
+        0
+    }
+}
+
+fn flatten_entries(
+    layout: &StorageLayout,
+    entries: &[StorageEntry],
+    base: U256,
+    prefix: &str,
+    fields: &mut Vec<LayoutField>,
+) -> Result<()> {
+    for entry in entries {
+        let label = if prefix.is_empty() {
+            entry.label.clone()
+        } else {
+            format!("{prefix}.{}", entry.label)
+        };
+        let slot: U256 = entry
+            .slot
+            .parse()
+            .map_err(|_| anyhow!("invalid slot `{}` of `{label}`", entry.slot))?;
+        let slot = base
+            .checked_add(slot)
+            .ok_or_else(|| anyhow!("slot of `{label}` overflows"))?;
+        let ty = layout
+            .types
+            .get(&entry.ty)
+            .ok_or_else(|| anyhow!("type `{}` of `{label}` is not defined", entry.ty))?;
+
+        match &ty.members {
+            Some(members) if ty.encoding == "inplace" => {
+                flatten_entries(layout, members, slot, &label, fields)?;
+            }
+            _ => {
+                let bytes: usize = ty.number_of_bytes.parse().map_err(|_| {
+                    anyhow!(
+                        "invalid numberOfBytes `{}` of `{label}`",
+                        ty.number_of_bytes
+                    )
+                })?;
+                if bytes < 32 && entry.offset as usize + bytes > 32 {
+                    bail!("`{label}` runs past the end of its slot");
+                }
+                fields.push(LayoutField {
+                    label,
+                    ty: ty.label.clone(),
+                    slot,
+                    offset: entry.offset,
+                    bytes,
+                });
+            }
+        }
+    }
+    Ok(())
+}
@@ -60,6 +60,8 @@ pub enum Artifact {
     Metadata,
     /// Foundry metadata,
     Foundry,
+    /// Storage layout JSON, in solc's `storageLayout` format
+    StorageLayout,
 }
 
 /// Build configuration for Fluent smart contracts
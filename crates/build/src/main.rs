@@ -1,8 +1,77 @@
-use clap::Parser;
-use fluentbase_build::{execute_build, BuildArgs};
+use clap::{Parser, Subcommand};
+use fluentbase_build::{
+    execute_build,
+    storage_layout::{check_storage_layouts, read_storage_layout},
+    BuildArgs,
+};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    build: BuildArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check that an upgrade keeps the storage layout of the deployed contract
+    ///
+    /// Both files hold a layout written by `--generate storage-layout` or by solc, so a Rust
+    /// contract can also be checked against the Solidity contract it replaces.
+    StorageLayoutDiff {
+        /// Layout of the deployed contract
+        old: PathBuf,
+        /// Layout of the upgrade
+        new: PathBuf,
+    },
+}
 
 fn main() -> anyhow::Result<()> {
-    let args = BuildArgs::parse();
-    execute_build(&args, None)?;
+    let cli = Cli::parse();
+    match cli.command {
+        None => {
+            execute_build(&cli.build, None)?;
+        }
+        Some(Command::StorageLayoutDiff { old, new }) => {
+            check_storage_layouts(&read_storage_layout(&old)?, &read_storage_layout(&new)?)?;
+            println!("Storage layouts are compatible");
+        }
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+    use std::path::Path;
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn build_arguments_need_no_subcommand() {
+        let cli = Cli::try_parse_from(["fluentbase-build", "--docker", "-g", "abi"]).unwrap();
+        assert!(cli.command.is_none());
+        assert!(cli.build.docker);
+
+        let cli = Cli::try_parse_from([
+            "fluentbase-build",
+            "storage-layout-diff",
+            "a.json",
+            "b.json",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::StorageLayoutDiff { old, new })
+                if old == Path::new("a.json") && new == Path::new("b.json")
+        ));
+    }
+}
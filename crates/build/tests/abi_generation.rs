@@ -1,7 +1,9 @@
 // tests/abi_generation.rs
 // ABI generation tests with struct support using insta snapshots
 
-use fluentbase_build::solidity::{generate_abi, generate_interface, Abi};
+use fluentbase_build::solidity::{
+    generate_abi, generate_interface, generate_method_identifiers, Abi,
+};
 use fluentbase_sdk_derive_core::{
     abi::structs::{StructRegistry, StructResolver},
     router::process_router_with_structs,
@@ -443,6 +445,62 @@ fn module_qualified_struct_selectors_agree_with_the_router() {
     );
 }
 
+/// `#[abi_name]` renames the published function, and the selector follows it
+#[test]
+fn abi_name_renames_the_published_function() {
+    let (_temp, project) = duplicate_names_project(&["a", "b"]);
+    fs::write(
+        project.join("src").join("lib.rs"),
+        DUPLICATE_NAMES_ROOT.replace(
+            "    pub fn set_a(",
+            "    #[abi_name(\"renameMe\")]\n    pub fn set_a(",
+        ),
+    )
+    .expect("rewrite lib.rs");
+
+    let abi = generate_abi(&project).expect("generate ABI");
+    assert_eq!(
+        published_signature(&abi, "renameMe"),
+        "renameMe((uint256,bool))"
+    );
+    assert_eq!(
+        router_method(&project, "set_a"),
+        (
+            "renameMe((uint256,bool))".to_string(),
+            "0x410cd56e".to_string()
+        )
+    );
+}
+
+/// Foundry's `methodIdentifiers` report the selectors the router dispatches on, pinned ones included
+#[test]
+fn method_identifiers_match_the_router() {
+    let (_temp, project) = duplicate_names_project(&["a", "b"]);
+    fs::write(
+        project.join("src").join("lib.rs"),
+        DUPLICATE_NAMES_ROOT.replace(
+            "    pub fn set_a(",
+            "    #[function_id(\"renameMe((uint256,bool))\")]\n    #[abi_name(\"renameMe\")]\n    pub fn set_a(",
+        ),
+    )
+    .expect("rewrite lib.rs");
+
+    let method_identifiers = generate_method_identifiers(&project).expect("method identifiers");
+    assert_eq!(
+        method_identifiers.into_iter().collect::<Vec<_>>(),
+        vec![
+            (
+                "renameMe((uint256,bool))".to_string(),
+                "410cd56e".to_string()
+            ),
+            (
+                "setB((address,uint256,string))".to_string(),
+                "dc78fda8".to_string()
+            ),
+        ]
+    );
+}
+
 /// A custom selector that no longer matches the published ABI stops the build
 #[test]
 fn selector_that_diverges_from_the_abi_fails_the_build() {
@@ -489,3 +547,57 @@ fn trait_impl_constructor() {
         assert_json_snapshot!(abi);
     });
 }
+
+#[test]
+fn events_and_errors_abi() {
+    let (_temp, project) = fixture_to_project("events_and_errors");
+    let abi = generate_abi(&project).expect("generate ABI");
+
+    // Use insta with settings to make snapshots more stable
+    let mut settings = Settings::clone_current();
+    settings.set_sort_maps(true); // Sort JSON keys for the stable output
+    settings.bind(|| {
+        assert_json_snapshot!(abi);
+    });
+}
+
+#[test]
+fn custom_errors_abi() {
+    let (_temp, project) = fixture_to_project("custom_errors");
+    let abi = generate_abi(&project).expect("generate ABI");
+
+    let mut settings = Settings::clone_current();
+    settings.set_sort_maps(true);
+    settings.bind(|| {
+        assert_json_snapshot!(abi);
+    });
+}
+
+#[test]
+fn enums_abi() {
+    let (_temp, project) = fixture_to_project("enums");
+    let abi = generate_abi(&project).expect("generate ABI");
+
+    let mut settings = Settings::clone_current();
+    settings.set_sort_maps(true);
+    settings.bind(|| {
+        assert_json_snapshot!(abi);
+    });
+}
+
+/// The access components and their modifiers live in `fluentbase-sdk`, so their events and errors
+/// reach the artifacts through the fields and the router, from the ABI entries the SDK publishes
+#[test]
+fn access_control_example_abi() {
+    let project = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/access-control");
+    let abi = generate_abi(&project).expect("generate ABI");
+
+    let mut settings = Settings::clone_current();
+    settings.set_sort_maps(true);
+    settings.bind(|| {
+        assert_json_snapshot!("access_control_example_abi", abi);
+    });
+
+    let interface = generate_interface("AccessControlToken", &abi).expect("generate interface");
+    insta::assert_snapshot!("access_control_example_interface", interface);
+}
@@ -0,0 +1,42 @@
+// Test case: Router methods returning `Result` with `#[derive(SolidityError)]` errors
+#![allow(dead_code)]
+extern crate alloc;
+extern crate fluentbase_sdk;
+
+use fluentbase_sdk::{
+    derive::{router, Contract, SolidityError},
+    Address, ContextReader, SharedAPI, U256,
+};
+
+#[derive(Debug, Clone, PartialEq, SolidityError)]
+pub enum VaultError {
+    Paused,
+    InsufficientBalance { available: U256, required: U256 },
+    Unauthorized(Address),
+}
+
+#[derive(Contract)]
+struct Vault<SDK> {
+    sdk: SDK,
+}
+
+#[router(mode = "solidity")]
+impl<SDK: SharedAPI> Vault<SDK> {
+    pub fn withdraw(&mut self, amount: U256) -> Result<U256, VaultError> {
+        if amount.is_zero() {
+            return Err(VaultError::InsufficientBalance {
+                available: U256::ZERO,
+                required: amount,
+            });
+        }
+        Ok(amount)
+    }
+
+    pub fn pause(&mut self) -> Result<(), VaultError> {
+        Err(VaultError::Unauthorized(
+            self.sdk.context().contract_caller(),
+        ))
+    }
+}
+
+basic_entrypoint!(Vault);
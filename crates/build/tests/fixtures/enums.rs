@@ -0,0 +1,57 @@
+// Test case: C-like enums and a tagged union as router parameters
+#![allow(dead_code)]
+extern crate alloc;
+extern crate fluentbase_sdk;
+
+use fluentbase_sdk::{
+    derive::{router, Codec, Contract},
+    Address, SharedAPI, U256,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Codec)]
+pub enum Side {
+    #[default]
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Codec)]
+pub enum Status {
+    #[default]
+    Open,
+    Filled,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Codec)]
+pub struct Order {
+    pub id: U256,
+    pub side: Side,
+}
+
+#[derive(Debug, Clone, Codec)]
+pub enum Instruction {
+    Stop,
+    Place { order: Order, price: U256 },
+    Cancel(U256),
+}
+
+#[derive(Contract)]
+struct Exchange<SDK> {
+    sdk: SDK,
+}
+
+#[router(mode = "fluent")]
+impl<SDK: SharedAPI> Exchange<SDK> {
+    pub fn place(&mut self, order: Order, history: Vec<Status>) -> Status {
+        let _ = (order, history);
+        Status::Open
+    }
+
+    pub fn execute(&mut self, instruction: Instruction, caller: Address) -> bool {
+        let _ = (instruction, caller);
+        true
+    }
+}
+
+basic_entrypoint!(Exchange);
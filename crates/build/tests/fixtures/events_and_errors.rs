@@ -0,0 +1,62 @@
+// Test case: Events and custom errors published next to the router functions
+#![allow(dead_code)]
+extern crate alloc;
+extern crate fluentbase_sdk;
+
+use fluentbase_sdk::{
+    derive::{derive_evm_error, router, Codec, Contract, Event},
+    Address, EvmExitCode, SharedAPI, B256, U256,
+};
+
+pub const ERR_UNAUTHORIZED: EvmExitCode = derive_evm_error!("Unauthorized(address)");
+
+#[derive(Debug, Clone, Codec)]
+pub struct Order {
+    pub id: U256,
+    pub maker: Address,
+}
+
+#[derive(Event)]
+pub struct OrderPlaced {
+    #[indexed]
+    pub maker: Address,
+    pub order: Order,
+}
+
+mod events {
+    use super::*;
+
+    pub const ERR_INSUFFICIENT_BALANCE: EvmExitCode =
+        derive_evm_error!("InsufficientBalance(uint256,uint256)");
+
+    #[derive(Event)]
+    #[anonymous]
+    pub struct Heartbeat {
+        #[indexed]
+        pub sender: Address,
+        #[indexed]
+        pub topic: B256,
+    }
+}
+
+#[derive(Contract)]
+struct OrderBook<SDK> {
+    sdk: SDK,
+}
+
+#[router(mode = "solidity")]
+impl<SDK: SharedAPI> OrderBook<SDK> {
+    pub fn place_order(&mut self, order: Order) -> bool {
+        if order.maker != self.sdk.context().contract_caller() {
+            return false;
+        }
+        let _ = OrderPlaced {
+            maker: order.maker,
+            order,
+        }
+        .emit(&mut self.sdk);
+        true
+    }
+}
+
+basic_entrypoint!(OrderBook);
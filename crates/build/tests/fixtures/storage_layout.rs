@@ -0,0 +1,65 @@
+#![cfg_attr(target_arch = "wasm32", no_std)]
+extern crate fluentbase_sdk;
+
+use fluentbase_sdk::{
+    basic_entrypoint,
+    derive::{erc7201_slot, router, Contract, Storage},
+    storage::{
+        StorageAddress, StorageArray, StorageBool, StorageMap, StorageString, StorageU128,
+        StorageU16, StorageU256, StorageU64, StorageU8, StorageVec,
+    },
+    Address, SharedAPI, U256,
+};
+
+/// `erc7201:example.storage.Pausable`
+pub const PAUSABLE_STORAGE_SLOT: U256 = erc7201_slot!("example.storage.Pausable");
+
+/// `erc7201:example.storage.Config`
+pub const CONFIG_STORAGE_SLOT: U256 = erc7201_slot!("example.storage.Config");
+
+/// Component living in its own namespace, wherever it is declared
+#[derive(Storage)]
+pub struct Pausable {
+    #[slot(PAUSABLE_STORAGE_SLOT)]
+    paused: StorageBool,
+}
+
+#[derive(Storage)]
+pub struct Position {
+    amount: StorageU128,
+    opened_at: StorageU64,
+    active: StorageBool,
+}
+
+#[derive(Storage)]
+pub struct Config {
+    limit: StorageU256,
+    guardian: StorageAddress,
+}
+
+#[derive(Contract)]
+pub struct Vault<SDK> {
+    sdk: SDK,
+    pausable: Pausable,
+    owner: StorageAddress,
+    fee_bps: StorageU16,
+    initialized: StorageBool,
+    total: StorageU256,
+    balances: StorageMap<Address, StorageU256>,
+    positions: StorageMap<Address, Position>,
+    history: StorageVec<StorageU64>,
+    tiers: StorageArray<StorageU64, 3>,
+    name: StorageString,
+    #[slot(CONFIG_STORAGE_SLOT)]
+    config: Config,
+    version: StorageU8,
+}
+
+#[router(mode = "solidity")]
+impl<SDK: SharedAPI> Vault<SDK> {
+    pub fn total(&self) -> U256 {
+        self.total_accessor().get(&self.sdk)
+    }
+}
+
+basic_entrypoint!(Vault);
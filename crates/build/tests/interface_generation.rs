@@ -604,3 +604,123 @@ fn interface_with_constructor_args() {
     let interface = generate_interface("AllTypesContract", &abi).unwrap();
     assert_snapshot!(interface);
 }
+
+#[test]
+fn interface_with_events_and_errors() {
+    let abi = vec![
+        json!({
+          "inputs": [],
+          "name": "ping",
+          "outputs": [],
+          "stateMutability": "payable",
+          "type": "function"
+        }),
+        json!({
+          "anonymous": false,
+          "inputs": [
+            {
+              "indexed": true,
+              "internalType": "address",
+              "name": "maker",
+              "type": "address"
+            },
+            {
+              "components": [
+                {
+                  "internalType": "uint256",
+                  "name": "id",
+                  "type": "uint256"
+                },
+                {
+                  "internalType": "string",
+                  "name": "note",
+                  "type": "string"
+                }
+              ],
+              "indexed": false,
+              "internalType": "struct Order",
+              "name": "order",
+              "type": "tuple"
+            }
+          ],
+          "name": "OrderPlaced",
+          "type": "event"
+        }),
+        json!({
+          "anonymous": true,
+          "inputs": [
+            {
+              "indexed": true,
+              "internalType": "bytes32",
+              "name": "topic",
+              "type": "bytes32"
+            }
+          ],
+          "name": "Heartbeat",
+          "type": "event"
+        }),
+        json!({
+          "inputs": [
+            {
+              "internalType": "address",
+              "name": "",
+              "type": "address"
+            },
+            {
+              "internalType": "uint256[]",
+              "name": "",
+              "type": "uint256[]"
+            }
+          ],
+          "name": "Unauthorized",
+          "type": "error"
+        }),
+        json!({
+          "inputs": [],
+          "name": "EnforcedPause",
+          "type": "error"
+        }),
+    ];
+
+    let interface = generate_interface("OrderBook", &abi).unwrap();
+    assert_snapshot!(interface);
+}
+
+#[test]
+fn interface_with_enums() {
+    // C-like enums are `uint8` in the ABI, identified by their `enum` internal type
+    let abi = vec![
+        json!({
+            "name": "place",
+            "type": "function",
+            "inputs": [
+                {
+                    "name": "order",
+                    "type": "tuple",
+                    "internalType": "struct Order",
+                    "components": [
+                        {"name": "id", "type": "uint256", "internalType": "uint256"},
+                        {"name": "side", "type": "uint8", "internalType": "enum Side"}
+                    ]
+                },
+                {"name": "history", "type": "uint8[]", "internalType": "enum Status[]"}
+            ],
+            "outputs": [
+                {"name": "_0", "type": "uint8", "internalType": "enum Status"}
+            ],
+            "stateMutability": "nonpayable"
+        }),
+        json!({
+            "anonymous": false,
+            "inputs": [
+                {"indexed": true, "internalType": "uint256", "name": "id", "type": "uint256"},
+                {"indexed": false, "internalType": "enum Status", "name": "status", "type": "uint8"}
+            ],
+            "name": "StatusChanged",
+            "type": "event"
+        }),
+    ];
+
+    let interface = generate_interface("Exchange", &abi).unwrap();
+    assert_snapshot!(interface);
+}
@@ -0,0 +1,343 @@
+// tests/storage_layout.rs
+// Storage layout export and upgrade checks
+
+use fluentbase_build::storage_layout::{
+    check_storage_layouts, generate_storage_layout, layout_fields, read_storage_layout,
+    StorageLayout,
+};
+use fluentbase_sdk::{storage::StorageCursor, U256};
+use insta::{assert_json_snapshot, Settings};
+use std::{fs, path::Path};
+use tempfile::TempDir;
+
+/// `erc7201_slot!("openzeppelin.storage.Ownable")`
+const OWNABLE_STORAGE_SLOT: &str =
+    "65173360639460082030725920392146925864023520599682862633725751242436743107328";
+
+/// Writes `content` as the `src/lib.rs` of a crate in `dir`
+fn write_crate(dir: &Path, content: &str) {
+    let src_dir = dir.join("src");
+    fs::create_dir_all(&src_dir).expect("create src dir");
+    fs::write(src_dir.join("lib.rs"), content).expect("write lib.rs");
+}
+
+/// Helper to create the project from fixture
+fn fixture_to_project(fixture_name: &str) -> (TempDir, std::path::PathBuf) {
+    let content = fs::read_to_string(format!("tests/fixtures/{fixture_name}.rs"))
+        .expect("fixture should exist");
+
+    let temp_dir = TempDir::new().expect("create temp dir");
+    let project_path = temp_dir.path().to_path_buf();
+    write_crate(&project_path, &content);
+
+    (temp_dir, project_path)
+}
+
+/// Layout of a contract declaring `fields`, next to a `Config` struct and its ERC-7201 slot
+fn layout_of(fields: &str) -> StorageLayout {
+    let content = format!(
+        r#"
+use fluentbase_sdk::{{derive::{{erc7201_slot, Contract, Storage}}, storage::*, U256}};
+
+pub const CONFIG_STORAGE_SLOT: U256 = erc7201_slot!("example.storage.Config");
+
+#[derive(Storage)]
+pub struct Config {{
+    limit: StorageU256,
+    guardian: StorageAddress,
+}}
+
+#[derive(Contract)]
+pub struct Counter<SDK> {{
+    sdk: SDK,
+{fields}
+}}
+"#
+    );
+    let temp_dir = TempDir::new().expect("create temp dir");
+    write_crate(temp_dir.path(), &content);
+    generate_storage_layout(temp_dir.path(), &[]).expect("generate storage layout")
+}
+
+/// Outcome of upgrading a contract declaring `old_fields` to one declaring `new_fields`
+fn check(old_fields: &str, new_fields: &str) -> Result<(), String> {
+    check_storage_layouts(&layout_of(old_fields), &layout_of(new_fields))
+        .map_err(|error| error.to_string())
+}
+
+#[test]
+fn vault_storage_layout() {
+    let (_temp, project) = fixture_to_project("storage_layout");
+    let layout = generate_storage_layout(&project, &[]).expect("generate storage layout");
+
+    let mut settings = Settings::clone_current();
+    settings.set_sort_maps(true);
+    settings.bind(|| {
+        assert_json_snapshot!(layout);
+    });
+}
+
+#[test]
+fn components_resolve_from_dependencies() {
+    let temp_dir = TempDir::new().expect("create temp dir");
+    let dependency = temp_dir.path().join("access");
+    write_crate(
+        &dependency,
+        r#"
+mod ownable {
+    use fluentbase_sdk::{derive::{erc7201_slot, Storage}, storage::StorageAddress, U256};
+
+    pub const OWNABLE_STORAGE_SLOT: U256 = erc7201_slot!("openzeppelin.storage.Ownable");
+    pub const SLOT: U256 = OWNABLE_STORAGE_SLOT;
+
+    #[derive(Storage)]
+    pub struct Ownable {
+        #[slot(SLOT)]
+        owner: StorageAddress,
+    }
+}
+pub use ownable::*;
+"#,
+    );
+    let project = temp_dir.path().join("contract");
+    write_crate(
+        &project,
+        r#"
+use access::Ownable;
+use fluentbase_sdk::{basic_entrypoint, derive::Contract, storage::StorageU64};
+
+#[derive(Contract)]
+pub struct App<SDK> {
+    sdk: SDK,
+    ownable: Ownable,
+    nonce: StorageU64,
+}
+
+#[derive(Contract)]
+pub struct Unused<SDK> {
+    sdk: SDK,
+}
+
+basic_entrypoint!(App);
+"#,
+    );
+
+    // Components of dependencies are only found when the dependency is passed in
+    let error = generate_storage_layout(&project, &[]).unwrap_err();
+    assert!(format!("{error:#}").contains("`Ownable`"), "{error:#}");
+
+    let layout = generate_storage_layout(&project, &[dependency.join("src/lib.rs")])
+        .expect("generate storage layout");
+    let fields: Vec<_> = layout
+        .storage
+        .iter()
+        .map(|entry| (entry.label.as_str(), entry.slot.as_str(), entry.ty.as_str()))
+        .collect();
+    assert_eq!(
+        fields,
+        vec![
+            ("ownable.owner", OWNABLE_STORAGE_SLOT, "t_address"),
+            ("nonce", "0", "t_uint64"),
+        ]
+    );
+    assert_eq!(layout.storage[0].contract, "src/lib.rs:App");
+}
+
+#[test]
+fn flat_crates_are_found() {
+    // The examples keep `lib.rs` next to `Cargo.toml` instead of under `src/`
+    let temp_dir = TempDir::new().expect("create temp dir");
+    fs::write(
+        temp_dir.path().join("lib.rs"),
+        r#"
+use fluentbase_sdk::{derive::Contract, storage::StorageU64};
+
+#[derive(Contract)]
+pub struct App<SDK> {
+    sdk: SDK,
+    nonce: StorageU64,
+}
+"#,
+    )
+    .expect("write lib.rs");
+
+    let layout = generate_storage_layout(temp_dir.path(), &[]).expect("generate storage layout");
+    assert_eq!(layout.storage.len(), 1);
+    assert_eq!(layout.storage[0].label, "nonce");
+    assert_eq!(layout.storage[0].contract, "lib.rs:App");
+}
+
+#[test]
+fn several_contracts_need_an_entrypoint() {
+    let temp_dir = TempDir::new().expect("create temp dir");
+    write_crate(
+        temp_dir.path(),
+        r#"
+#[derive(Contract)]
+pub struct A<SDK> { sdk: SDK }
+
+#[derive(Contract)]
+pub struct B<SDK> { sdk: SDK }
+"#,
+    );
+    let error = generate_storage_layout(temp_dir.path(), &[]).unwrap_err();
+    assert!(error.to_string().contains("(A, B)"), "{error}");
+}
+
+#[test]
+fn appending_fields_is_compatible() {
+    let result = check(
+        "count: StorageU256,",
+        "count: StorageU256,
+         owner: StorageAddress,
+         #[slot(CONFIG_STORAGE_SLOT)]
+         config: Config,",
+    );
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn reordered_fields_are_reported() {
+    let result = check(
+        "count: StorageU256,
+         owner: StorageAddress,",
+        "owner: StorageAddress,
+         count: StorageU256,",
+    );
+    assert_eq!(
+        result,
+        Err(
+            "unsafe storage layout change: field `count` moved from slot 0 offset 0 to slot 1 \
+             offset 0"
+                .into()
+        )
+    );
+}
+
+#[test]
+fn type_changes_are_reported() {
+    let result = check(
+        "started_at: StorageU64,
+         ended_at: StorageU64,",
+        "started_at: StorageU128,
+         ended_at: StorageU64,",
+    );
+    assert_eq!(
+        result,
+        Err(
+            "unsafe storage layout change: field `started_at` changed type from `uint64` to \
+             `uint128`"
+                .into()
+        )
+    );
+}
+
+#[test]
+fn removed_struct_members_are_reported() {
+    let result = check(
+        "count: StorageU256,
+         #[slot(CONFIG_STORAGE_SLOT)]
+         config: Config,",
+        "count: StorageU256,",
+    );
+    assert_eq!(
+        result,
+        Err("unsafe storage layout change: field `config.limit` was removed".into())
+    );
+}
+
+#[test]
+fn collisions_with_erc7201_slots_are_reported() {
+    let result = check(
+        "count: StorageU256,
+         #[slot(CONFIG_STORAGE_SLOT)]
+         config: Config,",
+        "count: StorageU256,
+         #[slot(CONFIG_STORAGE_SLOT)]
+         config: Config,
+         #[slot(CONFIG_STORAGE_SLOT)]
+         paused: StorageBool,",
+    );
+    assert_eq!(
+        result,
+        Err(
+            "unsafe storage layout change: new field `paused` overlaps the storage of \
+             `config.limit`"
+                .into()
+        )
+    );
+}
+
+#[test]
+fn fields_are_checked_as_the_sdk_places_them() {
+    let layout = layout_of(
+        "started_at: StorageU64,
+         owner: StorageAddress,
+         count: StorageU256,",
+    );
+    // The artifact counts offsets from the least significant end of the slot, like solc
+    assert_eq!(layout.storage[1].offset, 8);
+
+    // The checker sees the fields where the contract's own `storage_layout()` puts them
+    let fields = layout_fields(&layout).expect("layout fields");
+    let mut cursor = StorageCursor::new(0);
+    for field in &fields {
+        let field = field.as_storage_field();
+        let (slot, offset) = cursor.place(field.bytes, field.slots);
+        assert_eq!(
+            (field.slot, field.offset),
+            (U256::from(slot), offset),
+            "{}",
+            field.label
+        );
+    }
+    assert_eq!(cursor.slots(), 2);
+}
+
+#[test]
+fn solc_layouts_can_be_read() {
+    let temp_dir = TempDir::new().expect("create temp dir");
+
+    // solc reports `types: null` for contracts without storage
+    let empty = temp_dir.path().join("empty.json");
+    fs::write(&empty, r#"{"storage": [], "types": null}"#).unwrap();
+    assert_eq!(
+        read_storage_layout(&empty).unwrap(),
+        StorageLayout::default()
+    );
+
+    // Foundry artifacts embed the layout under `storageLayout`
+    let artifact = temp_dir.path().join("Counter.json");
+    fs::write(
+        &artifact,
+        r#"{
+            "abi": [],
+            "storageLayout": {
+                "storage": [
+                    {
+                        "astId": 3,
+                        "contract": "src/Counter.sol:Counter",
+                        "label": "number",
+                        "offset": 0,
+                        "slot": "0",
+                        "type": "t_uint256"
+                    }
+                ],
+                "types": {
+                    "t_uint256": {
+                        "encoding": "inplace",
+                        "label": "uint256",
+                        "numberOfBytes": "32"
+                    }
+                }
+            }
+        }"#,
+    )
+    .unwrap();
+    let solidity = read_storage_layout(&artifact).unwrap();
+    assert_eq!(solidity.storage[0].label, "number");
+
+    // A Rust port of the contract keeping `number` in place is a compatible upgrade
+    let rust = layout_of("number: StorageU256,\nstep: StorageU64,");
+    check_storage_layouts(&solidity, &rust).unwrap();
+}
@@ -0,0 +1,413 @@
+//! `Codec` for enums
+//!
+//! A C-like enum (no variant carries data) is encoded as its `u8` tag in every mode, which is how
+//! Solidity encodes an `enum`: a `uint8` word in SolidityABI, a single byte in SolidityPackedABI.
+//!
+//! A data-carrying enum has no Solidity counterpart, so it is only encodable with FluentABI, as a
+//! tagged union: the tag word is followed by the fields of the active variant, laid out like the
+//! fields of a struct. The header is sized for the largest variant, so every value of the enum has
+//! the same header size and smaller variants leave the rest of it zeroed.
+use crate::{CodecStruct, FieldInfo};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse_quote, DataEnum, DeriveInput, Expr, ExprLit, Fields, Ident, Lit, WhereClause,
+    WherePredicate,
+};
+
+/// Shape of an enum variant, which decides how it is matched and constructed
+enum VariantStyle {
+    Unit,
+    Named,
+    Unnamed,
+}
+
+/// Holds information about an enum variant
+struct VariantInfo {
+    ident: Ident,
+    tag: u8,
+    style: VariantStyle,
+    /// Fields in declaration order; unnamed fields are bound as `field_0`, `field_1`, ...
+    fields: Vec<FieldInfo>,
+}
+
+impl VariantInfo {
+    /// Pattern matching this variant and binding its fields, also valid as a constructor
+    fn pattern(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        let bindings = self.fields.iter().map(|field| &field.ident);
+        match self.style {
+            VariantStyle::Unit => quote! { Self::#ident },
+            VariantStyle::Named => quote! { Self::#ident { #(#bindings),* } },
+            VariantStyle::Unnamed => quote! { Self::#ident ( #(#bindings),* ) },
+        }
+    }
+}
+
+/// Represents an enum for which we are deriving Codec
+pub(crate) struct CodecEnum {
+    enum_name: Ident,
+    generics: syn::Generics,
+    variants: Vec<VariantInfo>,
+}
+
+impl CodecEnum {
+    /// Parse the DeriveInput to extract enum information, assigning every variant its tag
+    pub(crate) fn parse(ast: &DeriveInput, data_enum: &DataEnum) -> Self {
+        if data_enum.variants.is_empty() {
+            panic!("`Codec` can't be derived for an enum without variants");
+        }
+
+        let mut variants: Vec<VariantInfo> = Vec::new();
+        let mut next_tag = 0u16;
+        for variant in &data_enum.variants {
+            // Tags follow Rust discriminants: explicit integer literals, otherwise one past the
+            // previous variant
+            let tag = match &variant.discriminant {
+                Some((
+                    _,
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Int(lit), ..
+                    }),
+                )) => lit.base10_parse::<u16>().unwrap_or(u16::MAX),
+                Some(_) => panic!(
+                    "`Codec` enum discriminants must be integer literals, found one on `{}`",
+                    variant.ident
+                ),
+                None => next_tag,
+            };
+            let tag = u8::try_from(tag).unwrap_or_else(|_| {
+                panic!(
+                    "`Codec` enum tags must fit in a `u8`, but `{}::{}` is out of range",
+                    ast.ident, variant.ident
+                )
+            });
+            if let Some(existing) = variants.iter().find(|existing| existing.tag == tag) {
+                panic!(
+                    "`{}::{}` and `{}::{}` share the tag {tag}",
+                    ast.ident, existing.ident, ast.ident, variant.ident
+                );
+            }
+            next_tag = tag as u16 + 1;
+
+            let (style, fields) = match &variant.fields {
+                Fields::Unit => (VariantStyle::Unit, Vec::new()),
+                Fields::Named(named) => (
+                    VariantStyle::Named,
+                    named
+                        .named
+                        .iter()
+                        .map(|field| FieldInfo {
+                            ident: field.ident.clone().unwrap(),
+                            ty: field.ty.clone(),
+                        })
+                        .collect(),
+                ),
+                Fields::Unnamed(unnamed) => (
+                    VariantStyle::Unnamed,
+                    unnamed
+                        .unnamed
+                        .iter()
+                        .enumerate()
+                        .map(|(i, field)| FieldInfo {
+                            ident: format_ident!("field_{}", i),
+                            ty: field.ty.clone(),
+                        })
+                        .collect(),
+                ),
+            };
+
+            variants.push(VariantInfo {
+                ident: variant.ident.clone(),
+                tag,
+                style,
+                fields,
+            });
+        }
+
+        CodecEnum {
+            enum_name: ast.ident.clone(),
+            generics: ast.generics.clone(),
+            variants,
+        }
+    }
+
+    /// Whether no variant carries data, making the enum a plain `uint8`
+    fn is_c_like(&self) -> bool {
+        self.variants
+            .iter()
+            .all(|variant| variant.fields.is_empty())
+    }
+
+    fn fields(&self) -> impl Iterator<Item = &FieldInfo> {
+        self.variants.iter().flat_map(|variant| &variant.fields)
+    }
+
+    /// Add where clause predicates for the Encoder trait bound on each variant field
+    fn add_encoder_bounds(
+        &self,
+        generics: &syn::Generics,
+        sol_mode: bool,
+        is_static: bool,
+    ) -> WhereClause {
+        let crate_path = CodecStruct::get_crate_path();
+
+        let encoder_bounds: Vec<WherePredicate> = self
+            .fields()
+            .map(|field| {
+                let ty = &field.ty;
+                parse_quote!(#ty: #crate_path::Encoder<B, ALIGN, {#sol_mode}, {#is_static}>)
+            })
+            .collect();
+
+        let mut where_clause = generics
+            .where_clause
+            .clone()
+            .unwrap_or_else(|| parse_quote!(where));
+        where_clause.predicates.extend(encoder_bounds);
+        where_clause
+    }
+
+    /// Generate the body of a C-like enum impl: the tag is the whole encoding
+    fn generate_c_like_items(&self, sol_mode: bool, is_static: bool) -> TokenStream2 {
+        let crate_path = CodecStruct::get_crate_path();
+        let enum_name = self.enum_name.to_string();
+        let tag_encoder =
+            quote! { <u8 as #crate_path::Encoder<B, ALIGN, {#sol_mode}, {#is_static}>> };
+
+        let patterns = self
+            .variants
+            .iter()
+            .map(VariantInfo::pattern)
+            .collect::<Vec<_>>();
+        let tags = self
+            .variants
+            .iter()
+            .map(|variant| variant.tag)
+            .collect::<Vec<_>>();
+
+        quote! {
+            const HEADER_SIZE: usize = #tag_encoder::HEADER_SIZE;
+            const IS_DYNAMIC: bool = false;
+
+            fn encode(&self, buf: &mut #crate_path::bytes::BytesMut, offset: usize) -> Result<(), #crate_path::CodecError> {
+                let tag: u8 = match self {
+                    #( #patterns => #tags, )*
+                };
+                #tag_encoder::encode(&tag, buf, offset)
+            }
+
+            fn decode(buf: &impl #crate_path::bytes::Buf, offset: usize) -> Result<Self, #crate_path::CodecError> {
+                match #tag_encoder::decode(buf, offset)? {
+                    #( #tags => Ok(#patterns), )*
+                    tag => Err(#crate_path::invalid_enum_tag(#enum_name, tag)),
+                }
+            }
+
+            fn partial_decode(buffer: &impl #crate_path::bytes::Buf, offset: usize) -> Result<(usize, usize), #crate_path::CodecError> {
+                #tag_encoder::partial_decode(buffer, offset)
+            }
+        }
+    }
+
+    /// Generate the body of a tagged union impl, only ever instantiated for FluentABI
+    fn generate_tagged_union_items(&self, is_static: bool) -> TokenStream2 {
+        let crate_path = CodecStruct::get_crate_path();
+        let enum_name = self.enum_name.to_string();
+        let encoder = |ty: &dyn ToTokens| {
+            quote! { <#ty as #crate_path::Encoder<B, ALIGN, {false}, {#is_static}>> }
+        };
+        let tag_encoder = encoder(&quote! { u8 });
+        let field_size = |field: &FieldInfo| {
+            let field_encoder = encoder(&field.ty);
+            quote! { #crate_path::align_up::<ALIGN>(#field_encoder::HEADER_SIZE) }
+        };
+
+        // Payload size of every variant, the header reserves room for the largest one
+        let variant_sizes = self.variants.iter().map(|variant| {
+            let sizes = variant.fields.iter().map(field_size);
+            quote! {
+                let size = 0 #( + #sizes )*;
+                if size > max { max = size; }
+            }
+        });
+        let is_dynamic = self.fields().map(|field| {
+            let field_encoder = encoder(&field.ty);
+            quote! { #field_encoder::IS_DYNAMIC }
+        });
+
+        let encode_arms = self.variants.iter().map(|variant| {
+            let pattern = variant.pattern();
+            let tag = variant.tag;
+            let fields = variant.fields.iter().map(|field| {
+                let ident = &field.ident;
+                let field_encoder = encoder(&field.ty);
+                let size = field_size(field);
+                quote! {
+                    #field_encoder::encode(#ident, buf, current_offset)?;
+                    current_offset += #size;
+                }
+            });
+            quote! {
+                #pattern => {
+                    #tag_encoder::encode(&#tag, buf, aligned_offset)?;
+                    #(#fields)*
+                }
+            }
+        });
+
+        let decode_arms = self.variants.iter().map(|variant| {
+            let pattern = variant.pattern();
+            let tag = variant.tag;
+            let fields = variant.fields.iter().map(|field| {
+                let ident = &field.ident;
+                let field_encoder = encoder(&field.ty);
+                let size = field_size(field);
+                quote! {
+                    let #ident = #field_encoder::decode(buf, current_offset)?;
+                    current_offset += #size;
+                }
+            });
+            quote! {
+                #tag => {
+                    #(#fields)*
+                    Ok(#pattern)
+                }
+            }
+        });
+
+        quote! {
+            const HEADER_SIZE: usize = #crate_path::align_up::<ALIGN>(#tag_encoder::HEADER_SIZE) + {
+                let mut max = 0;
+                #(#variant_sizes)*
+                max
+            };
+            const IS_DYNAMIC: bool = false #( || #is_dynamic )*;
+
+            fn encode(&self, buf: &mut #crate_path::bytes::BytesMut, offset: usize) -> Result<(), #crate_path::CodecError> {
+                let aligned_offset = #crate_path::align_up::<ALIGN>(offset);
+                let header_size = <Self as #crate_path::Encoder<B, ALIGN, {false}, {#is_static}>>::HEADER_SIZE;
+
+                if buf.len() < aligned_offset + header_size {
+                    buf.resize(aligned_offset + header_size, 0);
+                }
+
+                let mut current_offset = aligned_offset + #crate_path::align_up::<ALIGN>(#tag_encoder::HEADER_SIZE);
+                match self {
+                    #(#encode_arms)*
+                }
+                Ok(())
+            }
+
+            fn decode(buf: &impl #crate_path::bytes::Buf, offset: usize) -> Result<Self, #crate_path::CodecError> {
+                let aligned_offset = #crate_path::align_up::<ALIGN>(offset);
+                let mut current_offset = aligned_offset + #crate_path::align_up::<ALIGN>(#tag_encoder::HEADER_SIZE);
+
+                match #tag_encoder::decode(buf, aligned_offset)? {
+                    #(#decode_arms)*
+                    tag => Err(#crate_path::invalid_enum_tag(#enum_name, tag)),
+                }
+            }
+
+            fn partial_decode(_buffer: &impl #crate_path::bytes::Buf, offset: usize) -> Result<(usize, usize), #crate_path::CodecError> {
+                Ok((#crate_path::align_up::<ALIGN>(offset), <Self as #crate_path::Encoder<B, ALIGN, {false}, {#is_static}>>::HEADER_SIZE))
+            }
+        }
+    }
+
+    /// Generate the `SolidityEventTopic` implementation
+    ///
+    /// A C-like enum is a value type whose topic is its `uint8` word. A tagged union has no
+    /// Solidity encoding, so the impl only exists to let structs holding one derive `Codec`, and
+    /// fails if such a struct is ever emitted as an indexed event parameter.
+    fn generate_event_topic_impl(&self) -> TokenStream2 {
+        let enum_name = &self.enum_name;
+        let crate_path = CodecStruct::get_crate_path();
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        let (is_reference_type, body) = if self.is_c_like() {
+            let patterns = self.variants.iter().map(VariantInfo::pattern);
+            let tags = self.variants.iter().map(|variant| variant.tag);
+            let body = quote! {
+                let tag: u8 = match self {
+                    #( #patterns => #tags, )*
+                };
+                #crate_path::SolidityEventTopic::encode_topic_preimage(&tag, out)
+            };
+            (false, body)
+        } else {
+            let msg = format!("`{enum_name}` is a tagged union and has no Solidity encoding");
+            let body = quote! {
+                let _ = out;
+                Err(#crate_path::CodecError::Encoding(
+                    #crate_path::EncodingError::InvalidInputData(#msg.into()),
+                ))
+            };
+            (true, body)
+        };
+
+        quote! {
+            impl #impl_generics #crate_path::SolidityEventTopic for #enum_name #ty_generics
+                #where_clause
+            {
+                const IS_REFERENCE_TYPE: bool = #is_reference_type;
+
+                fn encode_topic_preimage(
+                    &self,
+                    out: &mut #crate_path::bytes::BytesMut,
+                ) -> Result<(), #crate_path::CodecError> {
+                    #body
+                }
+            }
+        }
+    }
+
+    /// Generate the complete trait implementation for a specific mode and static/dynamic setting
+    fn generate_impl(&self, sol_mode: bool, is_static: bool) -> TokenStream2 {
+        let enum_name = &self.enum_name;
+        let crate_path = CodecStruct::get_crate_path();
+
+        let generics = CodecStruct::prepare_generics(&self.generics);
+        let where_clause = self.add_encoder_bounds(&generics, sol_mode, is_static);
+        let (impl_generics, _, _) = generics.split_for_impl();
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+
+        let items = if self.is_c_like() {
+            self.generate_c_like_items(sol_mode, is_static)
+        } else {
+            self.generate_tagged_union_items(is_static)
+        };
+
+        quote! {
+            impl #impl_generics #crate_path::Encoder<B, ALIGN, {#sol_mode}, {#is_static}>
+                for #enum_name #ty_generics
+                #where_clause
+            {
+                #items
+            }
+        }
+    }
+}
+
+impl ToTokens for CodecEnum {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let wasm_impl_static = self.generate_impl(false, true);
+        let wasm_impl_dynamic = self.generate_impl(false, false);
+        let event_topic_impl = self.generate_event_topic_impl();
+        tokens.extend(quote! {
+            #wasm_impl_static
+            #wasm_impl_dynamic
+            #event_topic_impl
+        });
+
+        // Solidity has no tagged unions, so only C-like enums get the Solidity modes
+        if self.is_c_like() {
+            let sol_impl_static = self.generate_impl(true, true);
+            let sol_impl_dynamic = self.generate_impl(true, false);
+            tokens.extend(quote! {
+                #sol_impl_static
+                #sol_impl_dynamic
+            });
+        }
+    }
+}
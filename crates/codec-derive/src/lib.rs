@@ -1,4 +1,7 @@
-//! Procedural macros for deriving `fluentbase_codec::Codec` on Rust structs.
+//! Procedural macros for deriving `fluentbase_codec::Codec` on Rust structs and enums.
+mod enums;
+
+use enums::CodecEnum;
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, ToTokens};
@@ -25,7 +28,7 @@ impl CodecStruct {
     fn parse(ast: &DeriveInput) -> Self {
         let data_struct = match &ast.data {
             Data::Struct(s) => s,
-            _ => panic!("`Codec` can only be derived for structs"),
+            _ => panic!("`Codec` can only be derived for structs and enums"),
         };
 
         let named_fields = match &data_struct.fields {
@@ -65,7 +68,7 @@ impl CodecStruct {
     }
 
     /// Prepare generics by adding necessary type and const parameters
-    fn prepare_generics(&self, original_generics: &syn::Generics) -> syn::Generics {
+    fn prepare_generics(original_generics: &syn::Generics) -> syn::Generics {
         let mut generics = original_generics.clone();
         let crate_path = Self::get_crate_path();
 
@@ -408,7 +411,7 @@ impl CodecStruct {
         let struct_name = &self.struct_name;
         let crate_path = Self::get_crate_path();
 
-        let generics = self.prepare_generics(&self.generics);
+        let generics = Self::prepare_generics(&self.generics);
         let where_clause = self.add_encoder_bounds(&generics, sol_mode, is_static);
         let (impl_generics, ty_generics, _) = generics.split_for_impl();
 
@@ -468,10 +471,20 @@ impl ToTokens for CodecStruct {
     }
 }
 
-/// Derive macro for implementing Codec trait for structs
+/// Derive macro for implementing Codec trait for structs and enums
+///
+/// C-like enums are encoded as a `uint8` tag in every mode; enums with data-carrying variants are
+/// tagged unions and only support FluentABI.
 #[proc_macro_derive(Codec, attributes(codec))]
 pub fn codec_macro_derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
+    if let Data::Enum(data_enum) = &ast.data {
+        let codec_enum = CodecEnum::parse(&ast, data_enum);
+        return quote! {
+            #codec_enum
+        }
+        .into();
+    }
     let codec_struct = CodecStruct::parse(&ast);
     quote! {
         #codec_struct
@@ -488,8 +501,16 @@ mod tests {
 
     fn get_generated_code(input: TokenStream) -> String {
         let ast = syn::parse2::<DeriveInput>(input).unwrap();
-        let codec_struct = CodecStruct::parse(&ast);
-        let tokens = quote! { #codec_struct };
+        let tokens = match &ast.data {
+            Data::Enum(data_enum) => {
+                let codec_enum = CodecEnum::parse(&ast, data_enum);
+                quote! { #codec_enum }
+            }
+            _ => {
+                let codec_struct = CodecStruct::parse(&ast);
+                quote! { #codec_struct }
+            }
+        };
         prettyplease::unparse(&syn::parse2::<syn::File>(tokens).unwrap())
     }
 
@@ -544,4 +565,33 @@ mod tests {
 
         assert_snapshot!("empty_struct", get_generated_code(input));
     }
+
+    #[test]
+    fn test_c_like_enum() {
+        let input = parse_quote! {
+            #[derive(Codec, Default, Debug, PartialEq)]
+            enum Status {
+                #[default]
+                Pending,
+                Active = 3,
+                Closed,
+            }
+        };
+
+        assert_snapshot!("c_like_enum", get_generated_code(input));
+    }
+
+    #[test]
+    fn test_tagged_union_enum() {
+        let input = parse_quote! {
+            #[derive(Codec, Debug, PartialEq)]
+            enum Action {
+                Noop,
+                Transfer { to: Address, amount: U256 },
+                Call(Address, Bytes),
+            }
+        };
+
+        assert_snapshot!("tagged_union_enum", get_generated_code(input));
+    }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    alloc::string::ToString,
+    alloc::{format, string::ToString},
     error::{CodecError, DecodingError},
     func::FunctionArgs,
 };
@@ -302,6 +302,17 @@ pub fn checked_decode_slice_from<'a>(
     })
 }
 
+/// Error returned when a decoded enum tag matches none of the enum's variants.
+///
+/// This is public for code emitted by `fluentbase-codec-derive`; it is not part of the stable
+/// high-level codec API.
+#[doc(hidden)]
+pub fn invalid_enum_tag(enum_name: &'static str, tag: u8) -> CodecError {
+    CodecError::Decoding(DecodingError::InvalidData(format!(
+        "invalid `{enum_name}` variant tag {tag}"
+    )))
+}
+
 /// Checks that a collection's fixed-size element headers fit in its encoded body before any
 /// collection allocation is attempted.
 pub(crate) fn validate_collection_body(
@@ -0,0 +1,178 @@
+use super::*;
+use byteorder::LE;
+
+#[derive(Codec, Default, Debug, Clone, Copy, PartialEq)]
+enum Status {
+    #[default]
+    Pending,
+    Active,
+    Closed,
+}
+
+#[test]
+fn test_c_like_enum_solidity() {
+    sol!(
+        contract OrderContract {
+            enum Status {
+                Pending,
+                Active,
+                Closed
+            }
+
+            struct Order {
+                uint64 id;
+                Status status;
+                Status[] history;
+            }
+        }
+    );
+
+    #[derive(Codec, Default, Debug, PartialEq)]
+    struct Order {
+        id: u64,
+        status: Status,
+        history: Vec<Status>,
+    }
+
+    let order = Order {
+        id: 7,
+        status: Status::Closed,
+        history: vec![Status::Pending, Status::Active],
+    };
+    let order_sol = OrderContract::Order {
+        id: 7,
+        status: OrderContract::Status::Closed,
+        history: vec![
+            OrderContract::Status::Pending,
+            OrderContract::Status::Active,
+        ],
+    };
+
+    let mut buf = BytesMut::new();
+    SolidityABI::encode(&order, &mut buf, 0).unwrap();
+    let encoded = buf.freeze();
+    assert_eq!(hex::encode(order_sol.abi_encode()), hex::encode(&encoded));
+
+    let decoded = SolidityABI::<Order>::decode(&encoded, 0).unwrap();
+    assert_eq!(decoded, order);
+}
+
+#[test]
+fn test_c_like_enum_explicit_tags() {
+    #[derive(Codec, Default, Debug, PartialEq)]
+    enum Level {
+        #[default]
+        Low = 1,
+        High = 10,
+        Max,
+    }
+
+    for (level, tag) in [(Level::Low, 1u8), (Level::High, 10), (Level::Max, 11)] {
+        let mut buf = BytesMut::new();
+        SolidityPackedABI::encode(&level, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), vec![tag]);
+
+        let mut buf = BytesMut::new();
+        CompactABI::encode(&level, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), vec![tag, 0, 0, 0]);
+        assert_eq!(
+            CompactABI::<Level>::decode(&buf.freeze(), 0).unwrap(),
+            level
+        );
+    }
+}
+
+#[test]
+fn test_c_like_enum_invalid_tag() {
+    let mut encoded = [0u8; 32];
+    encoded[31] = 3;
+    assert!(SolidityABI::<Status>::decode(&&encoded[..], 0).is_err());
+
+    encoded[31] = 2;
+    assert_eq!(
+        SolidityABI::<Status>::decode(&&encoded[..], 0).unwrap(),
+        Status::Closed
+    );
+}
+
+#[test]
+fn test_tagged_union_wasm() {
+    #[derive(Codec, Default, Debug, PartialEq)]
+    enum Shape {
+        #[default]
+        Empty,
+        Circle {
+            radius: u32,
+        },
+        Rect(u16, u64),
+    }
+
+    // The header fits the tag and the largest variant, `Rect`: 4 + 4 + 8 bytes
+    assert_eq!(<Shape as Encoder<LE, 4, false, true>>::HEADER_SIZE, 16);
+    const { assert!(!<Shape as Encoder<LE, 4, false, true>>::IS_DYNAMIC) };
+
+    let cases = [
+        (Shape::Empty, "00000000000000000000000000000000"),
+        (
+            Shape::Circle { radius: 5 },
+            "01000000050000000000000000000000",
+        ),
+        (Shape::Rect(7, 9), "02000000070000000900000000000000"),
+    ];
+    for (shape, expected) in cases {
+        let mut buf = BytesMut::new();
+        CompactABI::encode(&shape, &mut buf, 0).unwrap();
+        let encoded = buf.freeze();
+        assert_eq!(hex::encode(&encoded), expected);
+        assert_eq!(CompactABI::<Shape>::decode(&encoded, 0).unwrap(), shape);
+    }
+
+    let shapes = vec![Shape::Rect(1, 2), Shape::Empty, Shape::Circle { radius: 3 }];
+    let mut buf = BytesMut::new();
+    CompactABI::encode(&shapes, &mut buf, 0).unwrap();
+    let decoded = CompactABI::<Vec<Shape>>::decode(&buf.freeze(), 0).unwrap();
+    assert_eq!(decoded, shapes);
+
+    let mut encoded = [0u8; 16];
+    encoded[0] = 3;
+    assert!(CompactABI::<Shape>::decode(&&encoded[..], 0).is_err());
+}
+
+#[test]
+fn test_tagged_union_dynamic_wasm() {
+    #[derive(Codec, Debug, PartialEq)]
+    enum Action {
+        Noop,
+        Transfer { to: Address, amount: U256 },
+        Call(Address, Bytes),
+    }
+
+    #[derive(Codec, Debug, PartialEq)]
+    struct Envelope {
+        nonce: u32,
+        action: Action,
+        memo: Bytes,
+    }
+
+    const { assert!(<Action as Encoder<LE, 4, false, false>>::IS_DYNAMIC) };
+
+    for action in [
+        Action::Noop,
+        Action::Transfer {
+            to: Address::repeat_byte(0x11),
+            amount: U256::from(1_000_000u64),
+        },
+        Action::Call(Address::repeat_byte(0x22), Bytes::from(vec![1, 2, 3, 4, 5])),
+    ] {
+        let envelope = Envelope {
+            nonce: 42,
+            action,
+            memo: Bytes::from_static(b"memo"),
+        };
+
+        let mut buf = BytesMut::new();
+        CompactABI::encode(&envelope, &mut buf, 0).unwrap();
+        let decoded = CompactABI::<Envelope>::decode(&buf.freeze(), 0).unwrap();
+        assert_eq!(decoded, envelope);
+    }
+}
@@ -8,6 +8,7 @@ use fluentbase_codec::{
     Codec,
 };
 
+mod enums;
 mod func;
 mod structs;
 mod tuples;
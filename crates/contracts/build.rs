@@ -1,7 +1,7 @@
 use cargo_metadata::{CrateType, MetadataCommand, Package, PackageId, TargetKind};
 use fluentbase_build::{
-    docker, BuildArgs, DEFAULT_DOCKER_IMAGE, DEFAULT_DOCKER_TAG, ENV_ALLOW_UNVERIFIED_IMAGE,
-    ENV_DOCKER_DIGEST,
+    docker, solidity, BuildArgs, DEFAULT_DOCKER_IMAGE, DEFAULT_DOCKER_TAG,
+    ENV_ALLOW_UNVERIFIED_IMAGE, ENV_DOCKER_DIGEST,
 };
 use std::{
     collections::HashSet,
@@ -244,6 +244,21 @@ fn run_cargo_build(
     }
 }
 
+/// Writes the Solidity ABI of `package` the way `fluentbase build` writes `abi.json`.
+///
+/// Packages the generator can't parse get an empty ABI, so gas reports fall back to selectors.
+fn write_abi(package: &Package, abi_path: &Path) {
+    let package_dir = package.manifest_path.parent().unwrap().as_std_path();
+    let abi = solidity::generate_abi(package_dir).unwrap_or_else(|err| {
+        println!(
+            "cargo:warning=failed to generate the ABI of {}: {err:#}",
+            package.name
+        );
+        vec![]
+    });
+    fs::write(abi_path, serde_json::to_string_pretty(&abi).unwrap()).unwrap();
+}
+
 fn main() {
     // Make sure we rerun the build if the feature has changed
     println!("cargo:rerun-if-env-changed=CARGO_FEATURE_STD");
@@ -306,7 +321,10 @@ fn main() {
         .join("wasm32-unknown-unknown")
         .join(if is_debug_profile { "debug" } else { "release" });
 
-    let mut paths: Vec<(String, PathBuf)> = Vec::new();
+    let abi_dir = PathBuf::from(env::var("OUT_DIR").unwrap()).join("abi");
+    fs::create_dir_all(&abi_dir).unwrap();
+
+    let mut paths: Vec<(String, PathBuf, PathBuf)> = Vec::new();
 
     for package in &packages_resolver.packages {
         if !packages_resolver.workspace_members.contains(&package.id) {
@@ -327,7 +345,9 @@ fn main() {
                 let mut path = artifacts_dir.clone();
                 path.push(&target.name);
                 path.set_extension("wasm");
-                paths.push((target.name.clone(), path));
+                let abi_path = abi_dir.join(format!("{}.json", target.name));
+                write_abi(package, &abi_path);
+                paths.push((target.name.clone(), path, abi_path));
             }
         }
     }
@@ -338,17 +358,23 @@ fn main() {
         "pub struct BuildOutput {".to_string(),
         "    pub name: &'static str,".to_string(),
         "    pub wasm_bytecode: &'static [u8],".to_string(),
+        "    /// Solidity JSON ABI, as written to `abi.json` by `fluentbase build`.".to_string(),
+        "    pub abi: &'static str,".to_string(),
         "}".to_string(),
     ];
-    for (name, path) in paths {
+    let mut constant_names = vec![];
+    for (name, path, abi_path) in paths {
         let constant_name = name.to_uppercase().replace('-', "_");
         let path = path.to_str().unwrap();
+        let abi_path = abi_path.to_str().unwrap();
         code.push(format!(
             "pub const {constant_name}: BuildOutput = BuildOutput {{"
         ));
         code.push(format!("    name: \"{name}\","));
         code.push(format!("    wasm_bytecode: include_bytes!(\"{path}\"),"));
+        code.push(format!("    abi: include_str!(\"{abi_path}\"),"));
         code.push("};".to_string());
+        constant_names.push(constant_name);
     }
     let permissive_evm_package = packages_resolver
         .packages
@@ -369,7 +395,14 @@ fn main() {
         "    wasm_bytecode: include_bytes!(\"{}\"),",
         permissive_evm_path.to_str().unwrap()
     ));
+    code.push("    abi: FLUENTBASE_CONTRACTS_EVM.abi,".to_string());
     code.push("};".to_string());
+    constant_names.push("FLUENTBASE_CONTRACTS_EVM_PERMISSIVE".to_string());
+    code.push("pub const BUILD_OUTPUTS: &[BuildOutput] = &[".to_string());
+    for constant_name in constant_names {
+        code.push(format!("    {constant_name},"));
+    }
+    code.push("];".to_string());
     let code = code.join("\n");
     let build_output_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("build_output.rs");
 
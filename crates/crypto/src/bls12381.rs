@@ -7,6 +7,29 @@ use fluentbase_types::CryptoAPI;
 /// The number of limbs in `Bls12381AffinePoint`.
 pub const N: usize = 24;
 
+/// Size of a field element in big-endian encoding.
+pub const FP_SIZE: usize = 48;
+/// Size of an uncompressed G1 point, big-endian `x || y`.
+pub const G1_UNCOMPRESSED_SIZE: usize = 2 * FP_SIZE;
+/// Size of a scalar in big-endian encoding.
+pub const SCALAR_SIZE: usize = 32;
+
+/// The base field modulus, big-endian.
+const FP_MODULUS: [u8; FP_SIZE] = [
+    0x1a, 0x01, 0x11, 0xea, 0x39, 0x7f, 0xe6, 0x9a, 0x4b, 0x1b, 0xa7, 0xb6, 0x43, 0x4b, 0xac, 0xd7,
+    0x64, 0x77, 0x4b, 0x84, 0xf3, 0x85, 0x12, 0xbf, 0x67, 0x30, 0xd2, 0xa0, 0xf6, 0xb0, 0xf6, 0x24,
+    0x1e, 0xab, 0xff, 0xfe, 0xb1, 0x53, 0xff, 0xff, 0xb9, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xaa, 0xab,
+];
+/// The order of G1, big-endian.
+const G1_ORDER: [u8; SCALAR_SIZE] = [
+    0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48, 0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8, 0x05,
+    0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01,
+];
+
+const COMPRESSION_FLAG: u8 = 0b_1000_0000;
+const INFINITY_FLAG: u8 = 0b_0100_0000;
+const SIGN_FLAG: u8 = 0b_0010_0000;
+
 /// A point on the BLS12-381 curve.
 #[derive(Copy, Clone)]
 #[repr(align(4))]
@@ -65,7 +88,7 @@ impl AffinePoint<N> for Bls12381Point {
     fn add_assign(&mut self, other: &Self) {
         let a: [u8; 96] = bytemuck::cast(*self.limbs_mut());
         let b: [u8; 96] = bytemuck::cast(*other.limbs_ref());
-        CryptoRuntime::bls12381_add(a, b);
+        *self.limbs_mut() = bytemuck::cast(CryptoRuntime::bls12381_add(a, b));
     }
 
     fn complete_add_assign(&mut self, other: &Self) {
@@ -73,18 +96,267 @@ impl AffinePoint<N> for Bls12381Point {
     }
 
     fn double(&mut self) {
-        let _a = self.limbs_mut();
-        unimplemented!("where is bls12_381_double?")
+        if self.is_infinity() {
+            return;
+        }
+        let a: [u8; 96] = bytemuck::cast(*self.limbs_ref());
+        *self.limbs_mut() = bytemuck::cast(CryptoRuntime::bls12381_double(a));
+    }
+}
+
+impl Bls12381Point {
+    /// Decodes a point from big-endian `x || y`, all zeros being the point at infinity.
+    ///
+    /// Returns `None` unless both coordinates are reduced field elements and the point is on the
+    /// curve and in G1. The subgroup check costs a full scalar multiplication, points known to be
+    /// valid can be decoded with [`Self::from_uncompressed_unchecked`] instead.
+    pub fn from_uncompressed(bytes: &[u8; G1_UNCOMPRESSED_SIZE]) -> Option<Self> {
+        let point = Self::from_uncompressed_unchecked(bytes);
+        if point.is_infinity() {
+            return Some(point);
+        }
+        let (x, y) = bytes.split_at(FP_SIZE);
+        if x >= FP_MODULUS.as_slice() || y >= FP_MODULUS.as_slice() {
+            return None;
+        }
+        (point.is_on_curve() && point.is_in_g1()).then_some(point)
+    }
+
+    /// Decodes a point from big-endian `x || y` without validating it, all zeros being the point
+    /// at infinity.
+    ///
+    /// Unsafe for untrusted input: the point is not checked to be on the curve or in G1, and
+    /// [`Self::mul_scalar`] and [`Self::msm`] return meaningless results for such points. Only
+    /// pass points that come from a trusted source or were validated before.
+    pub fn from_uncompressed_unchecked(bytes: &[u8; G1_UNCOMPRESSED_SIZE]) -> Self {
+        if bytes.iter().all(|byte| *byte == 0) {
+            return Self::infinity();
+        }
+        let (mut x, mut y) = ([0u8; FP_SIZE], [0u8; FP_SIZE]);
+        x.copy_from_slice(&bytes[..FP_SIZE]);
+        y.copy_from_slice(&bytes[FP_SIZE..]);
+        x.reverse();
+        y.reverse();
+        <Self as AffinePoint<N>>::from(&x, &y)
+    }
+
+    /// Checks `y^2 = x^3 + 4` for a point that is not at infinity.
+    fn is_on_curve(&self) -> bool {
+        let bytes: [u8; G1_UNCOMPRESSED_SIZE] = bytemuck::cast(*self.limbs_ref());
+        let x: [u8; FP_SIZE] = bytes[..FP_SIZE].try_into().unwrap();
+        let y: [u8; FP_SIZE] = bytes[FP_SIZE..].try_into().unwrap();
+        let mut b = [0u8; FP_SIZE];
+        b[0] = 4;
+        let x3 =
+            CryptoRuntime::tower_fp1_bls12381_mul(CryptoRuntime::tower_fp1_bls12381_mul(x, x), x);
+        CryptoRuntime::tower_fp1_bls12381_mul(y, y) == CryptoRuntime::tower_fp1_bls12381_add(x3, b)
+    }
+
+    /// Checks that the order of a point on the curve divides the order of G1.
+    fn is_in_g1(&self) -> bool {
+        self.mul_scalar(&G1_ORDER).is_infinity()
+    }
+
+    /// Encodes the point as big-endian `x || y`, all zeros for the point at infinity.
+    pub fn to_uncompressed(&self) -> [u8; G1_UNCOMPRESSED_SIZE] {
+        let mut result = [0u8; G1_UNCOMPRESSED_SIZE];
+        if self.is_infinity() {
+            return result;
+        }
+        let bytes: [u8; G1_UNCOMPRESSED_SIZE] = bytemuck::cast(*self.limbs_ref());
+        let (x, y) = result.split_at_mut(FP_SIZE);
+        x.copy_from_slice(&bytes[..FP_SIZE]);
+        y.copy_from_slice(&bytes[FP_SIZE..]);
+        x.reverse();
+        y.reverse();
+        result
+    }
+
+    /// Multiplies the point by a big-endian scalar.
+    ///
+    /// The scalar doesn't have to be reduced, any 256-bit value is accepted.
+    pub fn mul_scalar(&self, scalar: &[u8; SCALAR_SIZE]) -> Self {
+        Self::msm(&[(*self, *scalar)])
+    }
+
+    /// Computes `sum(scalar_i * point_i)` for big-endian scalars.
+    ///
+    /// All terms share one double-and-add pass over the scalar bits, so the number of doublings
+    /// doesn't grow with the number of terms. Points at infinity and zero scalars are allowed.
+    pub fn msm(terms: &[(Self, [u8; SCALAR_SIZE])]) -> Self {
+        let bit_is_set = |scalar: &[u8; SCALAR_SIZE], bit: usize| {
+            (scalar[SCALAR_SIZE - 1 - bit / 8] >> (bit % 8)) & 1 == 1
+        };
+        let mut result = Self::infinity();
+        for bit in (0..SCALAR_SIZE * 8).rev() {
+            result.double();
+            for (point, scalar) in terms {
+                if bit_is_set(scalar, bit) {
+                    result.weierstrass_add_assign(point);
+                }
+            }
+        }
+        result
     }
 }
 
 /// Decompresses a compressed public key using bls12381_decompress precompile.
+///
+/// The input uses the ZCash encoding: the top three bits of the first byte are the compression,
+/// infinity and sign flags. Returns the uncompressed big-endian `x || y` encoding, all zeros for
+/// the point at infinity, and `Err` if the flags are malformed, `x` is not a reduced field element
+/// or the point is not in G1. An `x` that is not on the curve makes the decompression syscall
+/// fail, aborting the execution.
 #[allow(clippy::result_unit_err)]
-pub fn decompress_pubkey(_compressed_key: &[u8; 48]) -> Result<[u8; 96], ()> {
-    // let mut decompressed_key = [0u8; 96];
-    // decompressed_key[..48].copy_from_slice(compressed_key);
-    // let _sign_bit = ((decompressed_key[0] & 0b_0010_0000) >> 5) == 1;
-    // decompressed_key[0] &= 0b_0001_1111;
-    // Ok(decompressed_key)
-    unimplemented!("where is bls12_381_decompress?");
+pub fn decompress_pubkey(compressed_key: &[u8; FP_SIZE]) -> Result<[u8; G1_UNCOMPRESSED_SIZE], ()> {
+    let flags = compressed_key[0];
+    if flags & COMPRESSION_FLAG == 0 {
+        return Err(());
+    }
+    let mut x = *compressed_key;
+    x[0] &= !(COMPRESSION_FLAG | INFINITY_FLAG | SIGN_FLAG);
+    if flags & INFINITY_FLAG != 0 {
+        // The point at infinity has neither the sign bit nor any other bit set.
+        if flags & SIGN_FLAG != 0 || x.iter().any(|byte| *byte != 0) {
+            return Err(());
+        }
+        return Ok([0u8; G1_UNCOMPRESSED_SIZE]);
+    }
+    if x >= FP_MODULUS {
+        return Err(());
+    }
+    let sign = (flags & SIGN_FLAG != 0) as u32;
+    let uncompressed = CryptoRuntime::bls12381_decompress(x, sign);
+    Bls12381Point::from_uncompressed(&uncompressed).ok_or(())?;
+    Ok(uncompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// EIP-2537 encodes field elements as 64 bytes with 16 leading zero bytes.
+    fn decode_eip2537_point(bytes: &[u8]) -> Bls12381Point {
+        let mut uncompressed = [0u8; G1_UNCOMPRESSED_SIZE];
+        uncompressed[..FP_SIZE].copy_from_slice(&bytes[16..64]);
+        uncompressed[FP_SIZE..].copy_from_slice(&bytes[80..128]);
+        Bls12381Point::from_uncompressed(&uncompressed).expect("valid point")
+    }
+
+    fn encode_eip2537_point(point: &Bls12381Point) -> Vec<u8> {
+        let uncompressed = point.to_uncompressed();
+        let mut result = vec![0u8; 128];
+        result[16..64].copy_from_slice(&uncompressed[..FP_SIZE]);
+        result[80..128].copy_from_slice(&uncompressed[FP_SIZE..]);
+        result
+    }
+
+    #[test]
+    fn msm_matches_precompile_test_vectors() {
+        let testcases: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../contracts/bls12381/testcases/msm_G1_bls.json"
+        ))
+        .unwrap();
+        for testcase in testcases.as_array().unwrap() {
+            let input = hex::decode(testcase["Input"].as_str().unwrap()).unwrap();
+            let terms = input
+                .chunks_exact(160)
+                .map(|chunk| {
+                    let point = decode_eip2537_point(&chunk[..128]);
+                    (point, chunk[128..].try_into().unwrap())
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                hex::encode(encode_eip2537_point(&Bls12381Point::msm(&terms))),
+                testcase["Expected"].as_str().unwrap(),
+                "{}",
+                testcase["Name"]
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_points_are_rejected() {
+        let testcases: serde_json::Value = serde_json::from_str(include_str!(
+            "../../../contracts/bls12381/testcases/fail-msm_G1_bls.json"
+        ))
+        .unwrap();
+        for name in [
+            "bls_g1msm_invalid_field_element",
+            "bls_g1msm_point_not_on_curve",
+            "bls_g1msm_g1_not_in_correct_subgroup",
+            "bls_g1msm_point_in_correct_subgroup_invalid_curve",
+        ] {
+            let testcase = testcases
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|testcase| testcase["Name"] == name)
+                .unwrap();
+            // The first term holds the invalid point
+            let input = hex::decode(testcase["Input"].as_str().unwrap()).unwrap();
+            let mut uncompressed = [0u8; G1_UNCOMPRESSED_SIZE];
+            uncompressed[..FP_SIZE].copy_from_slice(&input[16..64]);
+            uncompressed[FP_SIZE..].copy_from_slice(&input[80..128]);
+            assert!(
+                Bls12381Point::from_uncompressed(&uncompressed).is_none(),
+                "{name}"
+            );
+        }
+        assert!(
+            Bls12381Point::from_uncompressed(&Bls12381Point::GENERATOR_T.to_uncompressed())
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn double_matches_add_and_mul() {
+        let generator = Bls12381Point::GENERATOR_T;
+        let mut doubled = generator;
+        doubled.double();
+        let mut added = generator;
+        added.complete_add_assign(&generator);
+        let mut two = [0u8; SCALAR_SIZE];
+        two[SCALAR_SIZE - 1] = 2;
+        assert_eq!(doubled.to_uncompressed(), added.to_uncompressed());
+        assert_eq!(
+            doubled.to_uncompressed(),
+            generator.mul_scalar(&two).to_uncompressed()
+        );
+        let mut infinity = Bls12381Point::infinity();
+        infinity.double();
+        assert!(infinity.is_infinity());
+    }
+
+    #[test]
+    fn decompress_pubkey_restores_generator() {
+        let compressed: [u8; FP_SIZE] = hex::decode(
+            "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb",
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+        assert_eq!(
+            decompress_pubkey(&compressed).unwrap(),
+            Bls12381Point::GENERATOR_T.to_uncompressed()
+        );
+
+        let generator = Bls12381Point::GENERATOR_T.to_uncompressed();
+        let mut negated = compressed;
+        negated[0] |= SIGN_FLAG;
+        let negated = decompress_pubkey(&negated).unwrap();
+        assert_eq!(negated[..FP_SIZE], generator[..FP_SIZE]);
+        assert_ne!(negated[FP_SIZE..], generator[FP_SIZE..]);
+
+        let mut infinity = [0u8; FP_SIZE];
+        infinity[0] = COMPRESSION_FLAG | INFINITY_FLAG;
+        assert_eq!(
+            decompress_pubkey(&infinity),
+            Ok([0u8; G1_UNCOMPRESSED_SIZE])
+        );
+        assert_eq!(decompress_pubkey(&[0u8; FP_SIZE]), Err(()));
+        infinity[FP_SIZE - 1] = 1;
+        assert_eq!(decompress_pubkey(&infinity), Err(()));
+    }
 }
@@ -1,4 +1,6 @@
-use crate::utils::AffinePoint;
+use crate::{field25519::FieldElement, scalar25519, utils::AffinePoint, CryptoRuntime};
+use fluentbase_types::CryptoAPI;
+use sha2::{Digest, Sha512};
 
 /// The number of limbs in [Ed25519AffinePoint].
 pub const N: usize = 16;
@@ -9,11 +11,12 @@ pub const N: usize = 16;
 pub struct Ed25519AffinePoint(pub [u32; N]);
 
 impl AffinePoint<N> for Ed25519AffinePoint {
-    /// The generator/base point for the Ed25519 curve. Reference: <https://datatracker.ietf.org/doc/html/rfc7748#section-4.1>
+    /// The base point `B` of RFC 8032, `y = 4/5` with positive `x`, as `x || y` little-endian
+    /// words. Reference: <https://datatracker.ietf.org/doc/html/rfc8032#section-5.1>
     const GENERATOR: [u32; N] = [
-        216936062, 3086116296, 2351951131, 1681893421, 3444223839, 2756123356, 3800373269,
-        3284567716, 2518301344, 752319464, 3983256831, 1952656717, 3669724772, 3793645816,
-        3665724614, 2969860233,
+        2401621274, 3377868128, 2502272946, 1764542304, 4258716764, 3232031281, 3446559742,
+        560543443, 1717986904, 1717986918, 1717986918, 1717986918, 1717986918, 1717986918,
+        1717986918, 1717986918,
     ];
 
     #[allow(deprecated)]
@@ -35,10 +38,11 @@ impl AffinePoint<N> for Ed25519AffinePoint {
         &mut self.0
     }
 
+    /// Edwards addition is complete, so this handles the identity and equal points too.
     fn add_assign(&mut self, other: &Self) {
-        let _a = self.limbs_mut();
-        let _b = other.limbs_ref();
-        unimplemented!()
+        let a: [u8; 64] = bytemuck::cast(self.0);
+        let b: [u8; 64] = bytemuck::cast(other.0);
+        self.0 = bytemuck::cast(CryptoRuntime::ed25519_add(a, b));
     }
 
     fn is_identity(&self) -> bool {
@@ -47,8 +51,8 @@ impl AffinePoint<N> for Ed25519AffinePoint {
 
     /// In Edwards curves, doubling is the same as adding a point to itself.
     fn double(&mut self) {
-        let _a = self.limbs_mut();
-        unimplemented!()
+        let a = *self;
+        self.add_assign(&a);
     }
 }
 
@@ -58,4 +62,202 @@ impl Ed25519AffinePoint {
     pub fn identity() -> Self {
         Self(Self::IDENTITY)
     }
+
+    pub(crate) fn from_coordinates(x: &FieldElement, y: &FieldElement) -> Self {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&x.to_bytes());
+        bytes[32..].copy_from_slice(&y.to_bytes());
+        Self(bytemuck::cast(bytes))
+    }
+
+    pub(crate) fn coordinates(&self) -> (FieldElement, FieldElement) {
+        let bytes: [u8; 64] = bytemuck::cast(self.0);
+        let (x, y) = bytes.split_at(32);
+        (
+            FieldElement::from_bytes(x.try_into().unwrap()),
+            FieldElement::from_bytes(y.try_into().unwrap()),
+        )
+    }
+
+    /// Decodes a point from its RFC 8032 encoding: `y` in little-endian with the sign of `x` in
+    /// the top bit.
+    ///
+    /// Returns `None` for non-canonical `y` and for encodings that are not on the curve. Unlike
+    /// the `ED25519_DECOMPRESS` syscall, which aborts the execution on such input, this is safe
+    /// to call on untrusted data. The point is not checked to be in the prime-order subgroup.
+    pub fn from_compressed(bytes: &[u8; 32]) -> Option<Self> {
+        let sign = bytes[31] >> 7 == 1;
+        let mut y_bytes = *bytes;
+        y_bytes[31] &= 0x7f;
+        if !FieldElement::is_canonical(&y_bytes) {
+            return None;
+        }
+        let y = FieldElement::from_bytes(&y_bytes);
+        // x^2 = (y^2 - 1) / (d * y^2 + 1)
+        let yy = y.square();
+        let u = yy.sub(&FieldElement::ONE);
+        let v = FieldElement::D.mul(&yy).add(&FieldElement::ONE);
+        let (is_square, mut x) = FieldElement::sqrt_ratio_m1(&u, &v);
+        if !is_square || (sign && x.is_zero()) {
+            return None;
+        }
+        if x.is_negative() != sign {
+            x = x.neg();
+        }
+        Some(Self::from_coordinates(&x, &y))
+    }
+
+    /// Encodes the point as `y` in little-endian with the sign of `x` in the top bit.
+    pub fn to_compressed(&self) -> [u8; 32] {
+        let (x, y) = self.coordinates();
+        let mut result = y.to_bytes();
+        result[31] |= (x.is_negative() as u8) << 7;
+        result
+    }
+
+    /// Returns `-self`.
+    pub fn neg(&self) -> Self {
+        let (x, y) = self.coordinates();
+        Self::from_coordinates(&x.neg(), &y)
+    }
+
+    /// Multiplies the point by a little-endian scalar, which doesn't have to be reduced.
+    pub fn mul_scalar(&self, scalar: &[u8; 32]) -> Self {
+        Self::msm(&[(*self, *scalar)])
+    }
+
+    /// Computes `sum(scalar_i * point_i)` for little-endian scalars.
+    ///
+    /// All terms share one double-and-add pass over the scalar bits.
+    pub fn msm(terms: &[(Self, [u8; 32])]) -> Self {
+        let highest_bit = terms
+            .iter()
+            .filter_map(|(_, scalar)| {
+                let byte = scalar.iter().rposition(|byte| *byte != 0)?;
+                Some(byte * 8 + 7 - scalar[byte].leading_zeros() as usize)
+            })
+            .max();
+        let mut result = Self::identity();
+        let Some(highest_bit) = highest_bit else {
+            return result;
+        };
+        for bit in (0..=highest_bit).rev() {
+            result.double();
+            for (point, scalar) in terms {
+                if (scalar[bit / 8] >> (bit % 8)) & 1 == 1 {
+                    result.add_assign(point);
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Verifies an Ed25519 signature over `message` as specified in RFC 8032.
+///
+/// Uses the cofactorless equation `[S]B == R + [k]A`, the same check as `ed25519-dalek`'s
+/// `verify`. Malformed public keys and signatures, including a non-reduced `S`, are rejected.
+pub fn ed25519_verify(public_key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    let Some(a) = Ed25519AffinePoint::from_compressed(public_key) else {
+        return false;
+    };
+    let (r, s) = signature.split_at(32);
+    let s: [u8; 32] = s.try_into().unwrap();
+    if !scalar25519::is_canonical(&s) {
+        return false;
+    }
+    let digest: [u8; 64] = Sha512::new()
+        .chain_update(r)
+        .chain_update(public_key)
+        .chain_update(message)
+        .finalize()
+        .into();
+    let k = scalar25519::reduce_wide(&digest);
+    // R' = [S]B - [k]A must encode to the same bytes as R.
+    let expected_r = Ed25519AffinePoint::msm(&[(Ed25519AffinePoint::GENERATOR_T, s), (a.neg(), k)]);
+    expected_r.to_compressed() == r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// `(public key, message, signature)` from RFC 8032, section 7.1: TEST 1, 2, 3 and SHA(abc).
+    const RFC8032_VECTORS: [(&str, &str, &str); 4] = [
+        (
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            "",
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        ),
+        (
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            "72",
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        ),
+        (
+            "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+            "af82",
+            "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+        ),
+        (
+            "ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf",
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+            "dc2a4459e7369633a52b1bf277839a00201009a3efbf3ecb69bea2186c26b58909351fc9ac90b3ecfdfbc7c66431e0303dca179c138ac17ad9bef1177331a704",
+        ),
+    ];
+
+    fn decode_vector(vector: &(&str, &str, &str)) -> ([u8; 32], Vec<u8>, [u8; 64]) {
+        (
+            hex::decode(vector.0).unwrap().try_into().unwrap(),
+            hex::decode(vector.1).unwrap(),
+            hex::decode(vector.2).unwrap().try_into().unwrap(),
+        )
+    }
+
+    #[test]
+    fn verifies_rfc8032_signatures() {
+        for vector in &RFC8032_VECTORS {
+            let (public_key, message, signature) = decode_vector(vector);
+            assert!(ed25519_verify(&public_key, &message, &signature));
+        }
+    }
+
+    #[test]
+    fn rejects_tampered_signatures() {
+        let (public_key, message, signature) = decode_vector(&RFC8032_VECTORS[1]);
+
+        assert!(!ed25519_verify(&public_key, b"s", &signature));
+        let mut tampered_r = signature;
+        tampered_r[0] ^= 1;
+        assert!(!ed25519_verify(&public_key, &message, &tampered_r));
+        let mut tampered_s = signature;
+        tampered_s[40] ^= 1;
+        assert!(!ed25519_verify(&public_key, &message, &tampered_s));
+        // S + L encodes the same scalar but is not reduced.
+        let mut unreduced_s = signature;
+        unreduced_s[63] |= 0xf0;
+        assert!(!ed25519_verify(&public_key, &message, &unreduced_s));
+        // y = 2 is not on the curve, this must not abort like the decompress syscall.
+        let mut invalid_key = [0u8; 32];
+        invalid_key[0] = 2;
+        assert!(!ed25519_verify(&invalid_key, &message, &signature));
+    }
+
+    #[test]
+    fn compression_roundtrips() {
+        assert_eq!(
+            Ed25519AffinePoint::GENERATOR_T.to_compressed(),
+            <[u8; 32]>::try_from(
+                hex::decode("5866666666666666666666666666666666666666666666666666666666666666")
+                    .unwrap()
+            )
+            .unwrap()
+        );
+        for vector in &RFC8032_VECTORS {
+            let (public_key, ..) = decode_vector(vector);
+            let point = Ed25519AffinePoint::from_compressed(&public_key).unwrap();
+            assert_eq!(point.to_compressed(), public_key);
+        }
+    }
 }
@@ -0,0 +1,233 @@
+//! Arithmetic over GF(2^255 - 19) for encoding and decoding Curve25519 points.
+//!
+//! Point arithmetic goes through the edwards syscalls, the field is only needed where the
+//! syscalls can't help: validating and decompressing untrusted encodings, and the Ristretto255
+//! encoding. Elements are kept in five 51-bit limbs, products use `u128`.
+
+const MASK: u64 = (1 << 51) - 1;
+
+/// An element of GF(2^255 - 19), not necessarily fully reduced.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct FieldElement([u64; 5]);
+
+impl FieldElement {
+    pub(crate) const ZERO: Self = Self([0, 0, 0, 0, 0]);
+    pub(crate) const ONE: Self = Self([1, 0, 0, 0, 0]);
+    /// Edwards `d = -121665/121666`.
+    pub(crate) const D: Self = Self([
+        929955233495203,
+        466365720129213,
+        1662059464998953,
+        2033849074728123,
+        1442794654840575,
+    ]);
+    /// `sqrt(-1)`.
+    pub(crate) const SQRT_M1: Self = Self([
+        1718705420411056,
+        234908883556509,
+        2233514472574048,
+        2117202627021982,
+        765476049583133,
+    ]);
+    /// `1/sqrt(a - d)` with `a = -1`.
+    pub(crate) const INVSQRT_A_MINUS_D: Self = Self([
+        278908739862762,
+        821645201101625,
+        8113234426968,
+        1777959178193151,
+        2118520810568447,
+    ]);
+
+    /// Decodes 32 little-endian bytes, ignoring the top bit. Values above `p` are accepted and
+    /// reduced, use [`FieldElement::is_canonical`] to reject them.
+    pub(crate) fn from_bytes(bytes: &[u8; 32]) -> Self {
+        let load = |offset: usize| {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&bytes[offset..offset + 8]);
+            u64::from_le_bytes(word)
+        };
+        Self([
+            load(0) & MASK,
+            (load(6) >> 3) & MASK,
+            (load(12) >> 6) & MASK,
+            (load(19) >> 1) & MASK,
+            (load(24) >> 12) & MASK,
+        ])
+    }
+
+    /// Encodes the fully reduced value as 32 little-endian bytes.
+    pub(crate) fn to_bytes(self) -> [u8; 32] {
+        let mut limbs = Self::weak_reduce(self.0);
+        // `limbs` is below 2p now, subtract p once if the value is at least p.
+        let mut q = (limbs[0] + 19) >> 51;
+        for limb in &limbs[1..] {
+            q = (limb + q) >> 51;
+        }
+        limbs[0] += 19 * q;
+        for i in 0..4 {
+            limbs[i + 1] += limbs[i] >> 51;
+            limbs[i] &= MASK;
+        }
+        limbs[4] &= MASK;
+
+        let mut result = [0u8; 32];
+        let mut accumulator = 0u128;
+        let mut bits = 0;
+        let mut offset = 0;
+        for limb in limbs {
+            accumulator |= (limb as u128) << bits;
+            bits += 51;
+            while bits >= 8 && offset < 32 {
+                result[offset] = accumulator as u8;
+                accumulator >>= 8;
+                bits -= 8;
+                offset += 1;
+            }
+        }
+        if offset < 32 {
+            result[offset] = accumulator as u8;
+        }
+        result
+    }
+
+    /// Whether the encoding is below `p` with the top bit clear.
+    pub(crate) fn is_canonical(bytes: &[u8; 32]) -> bool {
+        Self::from_bytes(bytes).to_bytes() == *bytes
+    }
+
+    fn weak_reduce(mut limbs: [u64; 5]) -> [u64; 5] {
+        let carry = limbs[4] >> 51;
+        limbs[4] &= MASK;
+        limbs[0] += carry * 19;
+        for i in 0..4 {
+            limbs[i + 1] += limbs[i] >> 51;
+            limbs[i] &= MASK;
+        }
+        let carry = limbs[4] >> 51;
+        limbs[4] &= MASK;
+        limbs[0] += carry * 19;
+        limbs
+    }
+
+    pub(crate) fn add(&self, other: &Self) -> Self {
+        let mut limbs = self.0;
+        for (limb, other) in limbs.iter_mut().zip(other.0) {
+            *limb += other;
+        }
+        Self(Self::weak_reduce(limbs))
+    }
+
+    pub(crate) fn sub(&self, other: &Self) -> Self {
+        // Add 16p first so that the limbs never underflow.
+        const SIXTEEN_P: [u64; 5] = [
+            36028797018963664,
+            36028797018963952,
+            36028797018963952,
+            36028797018963952,
+            36028797018963952,
+        ];
+        let a = Self::weak_reduce(self.0);
+        let b = Self::weak_reduce(other.0);
+        let mut limbs = [0u64; 5];
+        for i in 0..5 {
+            limbs[i] = a[i] + SIXTEEN_P[i] - b[i];
+        }
+        Self(Self::weak_reduce(limbs))
+    }
+
+    pub(crate) fn neg(&self) -> Self {
+        Self::ZERO.sub(self)
+    }
+
+    pub(crate) fn mul(&self, other: &Self) -> Self {
+        let a = Self::weak_reduce(self.0).map(|limb| limb as u128);
+        let b = Self::weak_reduce(other.0).map(|limb| limb as u128);
+        // Limbs that wrap past 2^255 are multiplied by 19.
+        let b19 = b.map(|limb| limb * 19);
+        let wide = [
+            a[0] * b[0] + a[1] * b19[4] + a[2] * b19[3] + a[3] * b19[2] + a[4] * b19[1],
+            a[0] * b[1] + a[1] * b[0] + a[2] * b19[4] + a[3] * b19[3] + a[4] * b19[2],
+            a[0] * b[2] + a[1] * b[1] + a[2] * b[0] + a[3] * b19[4] + a[4] * b19[3],
+            a[0] * b[3] + a[1] * b[2] + a[2] * b[1] + a[3] * b[0] + a[4] * b19[4],
+            a[0] * b[4] + a[1] * b[3] + a[2] * b[2] + a[3] * b[1] + a[4] * b[0],
+        ];
+        let mut limbs = [0u64; 5];
+        let mut carry = 0u128;
+        for i in 0..5 {
+            let value = wide[i] + carry;
+            limbs[i] = value as u64 & MASK;
+            carry = value >> 51;
+        }
+        limbs[0] += carry as u64 * 19;
+        Self(Self::weak_reduce(limbs))
+    }
+
+    pub(crate) fn square(&self) -> Self {
+        self.mul(self)
+    }
+
+    fn pow2k(&self, k: u32) -> Self {
+        let mut result = *self;
+        for _ in 0..k {
+            result = result.square();
+        }
+        result
+    }
+
+    /// Computes `self^((p - 5) / 8) = self^(2^252 - 3)`.
+    fn pow_p58(&self) -> Self {
+        let t0 = self.square();
+        let t2 = self.mul(&t0.pow2k(2));
+        let t3 = t0.mul(&t2);
+        let t5 = t2.mul(&t3.square());
+        let t7 = t5.pow2k(5).mul(&t5);
+        let t9 = t7.pow2k(10).mul(&t7);
+        let t11 = t9.pow2k(20).mul(&t9);
+        let t13 = t11.pow2k(10).mul(&t7);
+        let t15 = t13.pow2k(50).mul(&t13);
+        let t17 = t15.pow2k(100).mul(&t15);
+        let t19 = t17.pow2k(50).mul(&t13);
+        self.mul(&t19.pow2k(2))
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.to_bytes() == [0u8; 32]
+    }
+
+    /// Whether the reduced value is odd, the "negative" half of the field.
+    pub(crate) fn is_negative(&self) -> bool {
+        self.to_bytes()[0] & 1 == 1
+    }
+
+    pub(crate) fn equals(&self, other: &Self) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+
+    /// Returns the non-negative one of `self` and `-self`.
+    pub(crate) fn abs(&self) -> Self {
+        if self.is_negative() {
+            self.neg()
+        } else {
+            *self
+        }
+    }
+
+    /// Computes the non-negative `sqrt(u/v)` if it exists, and `sqrt(i*u/v)` otherwise.
+    ///
+    /// Returns whether `u/v` is a square, `v == 0` counts as not square unless `u == 0` too.
+    pub(crate) fn sqrt_ratio_m1(u: &Self, v: &Self) -> (bool, Self) {
+        let v3 = v.square().mul(v);
+        let v7 = v3.square().mul(v);
+        let mut r = u.mul(&v3).mul(&u.mul(&v7).pow_p58());
+        let check = v.mul(&r.square());
+
+        let u_neg = u.neg();
+        let correct_sign_sqrt = check.equals(u);
+        let flipped_sign_sqrt = check.equals(&u_neg);
+        let flipped_sign_sqrt_i = check.equals(&u_neg.mul(&Self::SQRT_M1));
+        if flipped_sign_sqrt || flipped_sign_sqrt_i {
+            r = r.mul(&Self::SQRT_M1);
+        }
+        (correct_sign_sqrt || flipped_sign_sqrt, r.abs())
+    }
+}
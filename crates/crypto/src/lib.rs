@@ -9,7 +9,11 @@ pub mod bls12381;
 pub mod bn254;
 pub mod ecdsa;
 pub mod ed25519;
-mod ristretto255;
+mod field25519;
+mod poseidon;
+pub use poseidon::*;
+pub mod ristretto255;
+mod scalar25519;
 pub mod secp256k1;
 pub mod secp256r1;
 mod sha256;
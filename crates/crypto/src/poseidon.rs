@@ -0,0 +1,37 @@
+use crate::CryptoRuntime;
+use alloc::vec::Vec;
+use fluentbase_types::{CryptoAPI, B256, POSEIDON_MAX_INPUTS};
+
+/// Poseidon hash of big-endian BN254 scalar field elements, compatible with circomlib's
+/// `Poseidon(n)`.
+///
+/// Returns `None` for no inputs, more than [`POSEIDON_MAX_INPUTS`] inputs, or an input that isn't
+/// below the field modulus.
+pub fn crypto_poseidon(inputs: &[B256]) -> Option<B256> {
+    if inputs.is_empty() || inputs.len() > POSEIDON_MAX_INPUTS {
+        return None;
+    }
+    let data = inputs.iter().flat_map(|input| input.0).collect::<Vec<u8>>();
+    CryptoRuntime::poseidon_bn254(&data).map(B256::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluentbase_types::{b256, U256};
+
+    #[test]
+    fn matches_circomlib_vectors() {
+        let one = B256::from(U256::from(1));
+        let two = B256::from(U256::from(2));
+        assert_eq!(
+            crypto_poseidon(&[one, two]),
+            Some(b256!(
+                "115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"
+            ))
+        );
+        assert_eq!(crypto_poseidon(&[]), None);
+        assert_eq!(crypto_poseidon(&[one; POSEIDON_MAX_INPUTS + 1]), None);
+        assert_eq!(crypto_poseidon(&[B256::repeat_byte(0xff)]), None);
+    }
+}
@@ -1 +1,181 @@
-// TODO(dmitry123): Implement ristretto255 wrapper over ed25519
+//! Ristretto255 prime-order group (RFC 9496) on top of the Ed25519 curve.
+//!
+//! Elements are represented by an Edwards point from their coset, group operations use the
+//! edwards syscalls, and only encoding, decoding and equality run in software.
+use crate::{ed25519::Ed25519AffinePoint, field25519::FieldElement, utils::AffinePoint};
+
+/// An element of the Ristretto255 group.
+#[derive(Copy, Clone)]
+pub struct RistrettoPoint(pub Ed25519AffinePoint);
+
+impl RistrettoPoint {
+    /// The identity element.
+    pub fn identity() -> Self {
+        Self(Ed25519AffinePoint::identity())
+    }
+
+    /// The canonical generator, the image of the Ed25519 base point.
+    pub fn generator() -> Self {
+        Self(Ed25519AffinePoint::GENERATOR_T)
+    }
+
+    /// Decodes a canonical 32-byte encoding, returning `None` for invalid or non-canonical input.
+    pub fn decompress(bytes: &[u8; 32]) -> Option<Self> {
+        let s = FieldElement::from_bytes(bytes);
+        if !FieldElement::is_canonical(bytes) || s.is_negative() {
+            return None;
+        }
+        let ss = s.square();
+        let u1 = FieldElement::ONE.sub(&ss);
+        let u2 = FieldElement::ONE.add(&ss);
+        let u2_sqr = u2.square();
+        let v = FieldElement::D.mul(&u1.square()).neg().sub(&u2_sqr);
+        let (was_square, invsqrt) =
+            FieldElement::sqrt_ratio_m1(&FieldElement::ONE, &v.mul(&u2_sqr));
+        let den_x = invsqrt.mul(&u2);
+        let den_y = invsqrt.mul(&den_x).mul(&v);
+        let x = s.add(&s).mul(&den_x).abs();
+        let y = u1.mul(&den_y);
+        if !was_square || x.mul(&y).is_negative() || y.is_zero() {
+            return None;
+        }
+        Some(Self(Ed25519AffinePoint::from_coordinates(&x, &y)))
+    }
+
+    /// Returns the canonical 32-byte encoding.
+    pub fn compress(&self) -> [u8; 32] {
+        let (x0, y0) = self.0.coordinates();
+        // Affine coordinates, so `z = 1` and `t = x * y`.
+        let z0 = FieldElement::ONE;
+        let t0 = x0.mul(&y0);
+        let u1 = z0.add(&y0).mul(&z0.sub(&y0));
+        let u2 = x0.mul(&y0);
+        let (_, invsqrt) = FieldElement::sqrt_ratio_m1(&FieldElement::ONE, &u1.mul(&u2.square()));
+        let den1 = invsqrt.mul(&u1);
+        let den2 = invsqrt.mul(&u2);
+        let z_inv = den1.mul(&den2).mul(&t0);
+
+        let rotate = t0.mul(&z_inv).is_negative();
+        let (x, mut y, den_inv) = if rotate {
+            (
+                y0.mul(&FieldElement::SQRT_M1),
+                x0.mul(&FieldElement::SQRT_M1),
+                den1.mul(&FieldElement::INVSQRT_A_MINUS_D),
+            )
+        } else {
+            (x0, y0, den2)
+        };
+        if x.mul(&z_inv).is_negative() {
+            y = y.neg();
+        }
+        den_inv.mul(&z0.sub(&y)).abs().to_bytes()
+    }
+
+    /// Returns `self + other`.
+    pub fn add(&self, other: &Self) -> Self {
+        let mut result = self.0;
+        result.add_assign(&other.0);
+        Self(result)
+    }
+
+    /// Returns `self - other`.
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.neg())
+    }
+
+    /// Returns `-self`.
+    pub fn neg(&self) -> Self {
+        Self(self.0.neg())
+    }
+
+    /// Multiplies the element by a little-endian scalar, which doesn't have to be reduced.
+    pub fn mul_scalar(&self, scalar: &[u8; 32]) -> Self {
+        Self(self.0.mul_scalar(scalar))
+    }
+
+    /// Computes `sum(scalar_i * point_i)` for little-endian scalars.
+    pub fn msm(terms: &[(Self, [u8; 32])]) -> Self {
+        let terms = terms
+            .iter()
+            .map(|(point, scalar)| (point.0, *scalar))
+            .collect::<alloc::vec::Vec<_>>();
+        Self(Ed25519AffinePoint::msm(&terms))
+    }
+}
+
+impl PartialEq for RistrettoPoint {
+    /// Two Edwards points represent the same element iff `x1 * y2 == y1 * x2` or
+    /// `y1 * y2 == x1 * x2`.
+    fn eq(&self, other: &Self) -> bool {
+        let (x1, y1) = self.0.coordinates();
+        let (x2, y2) = other.0.coordinates();
+        x1.mul(&y2).equals(&y1.mul(&x2)) || y1.mul(&y2).equals(&x1.mul(&x2))
+    }
+}
+
+impl Eq for RistrettoPoint {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(encoding: &str) -> [u8; 32] {
+        hex::decode(encoding).unwrap().try_into().unwrap()
+    }
+
+    /// Encodings of `0..8` times the generator, RFC 9496 appendix A.1.
+    const MULTIPLES_OF_GENERATOR: [&str; 9] = [
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "e2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76",
+        "6a493210f7499cd17fecb510ae0cea23a110e8d5b901f8acadd3095c73a3b919",
+        "94741f5d5d52755ece4f23f044ee27d5d1ea1e2bd196b462166b16152a9d0259",
+        "da80862773358b466ffadfe0b3293ab3d9fd53c5ea6c955358f568322daf6a57",
+        "e882b131016b52c1d3337080187cf768423efccbb517bb495ab812c4160ff44e",
+        "f64746d3c92b13050ed8d80236a7f0007c3b3f962f5ba793d19a601ebb1df403",
+        "44f53520926ec81fbd5a387845beb7df85a96a24ece18738bdcfa6a7822a176d",
+        "903293d8f2287ebe10e2374dc1a53e0bc887e592699f02d077d5263cdd55601c",
+    ];
+
+    #[test]
+    fn encodes_multiples_of_generator() {
+        let mut point = RistrettoPoint::identity();
+        for (i, encoding) in MULTIPLES_OF_GENERATOR.iter().enumerate() {
+            assert_eq!(point.compress(), decode(encoding), "{i} * G");
+            let decoded = RistrettoPoint::decompress(&decode(encoding)).unwrap();
+            assert!(decoded == point);
+            assert_eq!(decoded.compress(), decode(encoding));
+
+            let mut scalar = [0u8; 32];
+            scalar[0] = i as u8;
+            assert!(RistrettoPoint::generator().mul_scalar(&scalar) == point);
+            point = point.add(&RistrettoPoint::generator());
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_encodings() {
+        for encoding in [
+            // Non-canonical field elements.
+            "00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+            // Negative field element.
+            "0100000000000000000000000000000000000000000000000000000000000000",
+            // Non-square x^2.
+            "26948d35ca62e643e26a83177332e6b6afeb9d08e4268b650f1f5bbd8d81d371",
+        ] {
+            assert!(RistrettoPoint::decompress(&decode(encoding)).is_none());
+        }
+    }
+
+    #[test]
+    fn msm_matches_scalar_multiplication() {
+        let generator = RistrettoPoint::generator();
+        let (mut three, mut five, mut eight) = ([0u8; 32], [0u8; 32], [0u8; 32]);
+        three[0] = 3;
+        five[0] = 5;
+        eight[0] = 8;
+        let sum = RistrettoPoint::msm(&[(generator, three), (generator, five)]);
+        assert!(sum == generator.mul_scalar(&eight));
+        assert!(sum.sub(&generator.mul_scalar(&eight)) == RistrettoPoint::identity());
+    }
+}
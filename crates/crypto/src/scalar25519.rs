@@ -0,0 +1,59 @@
+//! Scalars modulo the order `L = 2^252 + 27742317777372353535851937790883648493` of the
+//! Curve25519 prime-order subgroup, in 32-byte little-endian encoding.
+
+const L: [u64; 4] = [
+    0x5812631a5cf5d3ed,
+    0x14def9dea2f79cd6,
+    0,
+    0x1000000000000000,
+];
+
+fn to_words(bytes: &[u8; 32]) -> [u64; 4] {
+    let mut words = [0u64; 4];
+    for (word, chunk) in words.iter_mut().zip(bytes.chunks_exact(8)) {
+        *word = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    words
+}
+
+fn is_below_l(words: &[u64; 4]) -> bool {
+    for i in (0..4).rev() {
+        if words[i] != L[i] {
+            return words[i] < L[i];
+        }
+    }
+    false
+}
+
+/// Whether the scalar is fully reduced, as RFC 8032 requires for the `S` half of a signature.
+pub(crate) fn is_canonical(scalar: &[u8; 32]) -> bool {
+    is_below_l(&to_words(scalar))
+}
+
+/// Reduces a 512-bit little-endian value, such as a SHA-512 digest, modulo `L`.
+pub(crate) fn reduce_wide(bytes: &[u8; 64]) -> [u8; 32] {
+    // Bitwise long division, the remainder stays below 2L < 2^254 and never overflows.
+    let mut remainder = [0u64; 4];
+    for bit in (0..512).rev() {
+        let mut carry = ((bytes[bit / 8] >> (bit % 8)) & 1) as u64;
+        for word in remainder.iter_mut() {
+            let next_carry = *word >> 63;
+            *word = (*word << 1) | carry;
+            carry = next_carry;
+        }
+        if !is_below_l(&remainder) {
+            let mut borrow = false;
+            for (word, l) in remainder.iter_mut().zip(L) {
+                let (value, borrow1) = word.overflowing_sub(l);
+                let (value, borrow2) = value.overflowing_sub(borrow as u64);
+                *word = value;
+                borrow = borrow1 || borrow2;
+            }
+        }
+    }
+    let mut result = [0u8; 32];
+    for (chunk, word) in result.chunks_exact_mut(8).zip(remainder) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    result
+}
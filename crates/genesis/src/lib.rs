@@ -1,14 +1,44 @@
 //! Genesis helpers and embedded build outputs for Fluentbase system contracts.
 pub use alloy_genesis::Genesis;
-use fluentbase_sdk::{Address, Bytes, GenesisContract, B256};
+use alloy_signer_local::{coins_bip39::English, LocalSignerError, MnemonicBuilder};
+use fluentbase_sdk::{Address, Bytes, GenesisContract, B256, U256};
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 
+/// Mnemonic of the accounts pre-funded on dev chains, the one anvil and hardhat use too.
+pub const DEV_MNEMONIC: &str = "test test test test test test test test test test test junk";
+
+/// Balance of every pre-funded dev account, 10 000 ETH.
+pub const DEV_ACCOUNT_BALANCE: U256 = U256::from_limbs([0x19e0c9bab2400000, 0x21e, 0, 0]);
+
 pub fn local_genesis_from_file() -> Genesis {
     let json_file = include_str!("../genesis-devnet.json");
     serde_json::from_str::<Genesis>(json_file).expect("failed to parse genesis json file")
 }
 
+/// Derives the address and secret key of the first `count` accounts of `mnemonic`, along the
+/// default Ethereum path `m/44'/60'/0'/0/{index}`.
+pub fn dev_accounts(mnemonic: &str, count: u32) -> Result<Vec<(Address, B256)>, LocalSignerError> {
+    (0..count)
+        .map(|index| {
+            let signer = MnemonicBuilder::<English>::default()
+                .phrase(mnemonic)
+                .index(index)?
+                .build()?;
+            Ok((signer.address(), signer.to_bytes()))
+        })
+        .collect()
+}
+
+/// Local genesis with every address of `accounts` holding `balance`.
+pub fn dev_genesis(accounts: impl IntoIterator<Item = Address>, balance: U256) -> Genesis {
+    let mut genesis = local_genesis_from_file();
+    for address in accounts {
+        genesis.alloc.entry(address).or_default().balance = balance;
+    }
+    genesis
+}
+
 include!(concat!(env!("OUT_DIR"), "/build_output.rs"));
 
 lazy_static! {
@@ -29,8 +59,10 @@ lazy_static! {
 
 #[cfg(test)]
 mod tests {
-    use crate::local_genesis_from_file;
-    use fluentbase_revm::revm::primitives::address;
+    use crate::{
+        dev_accounts, dev_genesis, local_genesis_from_file, DEV_ACCOUNT_BALANCE, DEV_MNEMONIC,
+    };
+    use fluentbase_revm::revm::primitives::{address, b256};
     use fluentbase_sdk::{Address, U256};
 
     #[test]
@@ -47,4 +79,35 @@ mod tests {
             U256::from(9_000000000000000000u128),
         );
     }
+
+    #[test]
+    fn test_dev_accounts_match_the_well_known_ones() {
+        assert_eq!(
+            DEV_ACCOUNT_BALANCE,
+            U256::from(10_000u128 * 1_000000000000000000u128)
+        );
+        let accounts = dev_accounts(DEV_MNEMONIC, 2).unwrap();
+        assert_eq!(
+            accounts[0],
+            (
+                address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"),
+                b256!("0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"),
+            )
+        );
+        assert_eq!(
+            accounts[1].0,
+            address!("0x70997970C51812dc3A010C7d01b50e0d17dc79C8")
+        );
+
+        let genesis = dev_genesis(
+            accounts.iter().map(|(address, _)| *address),
+            DEV_ACCOUNT_BALANCE,
+        );
+        let local = local_genesis_from_file();
+        assert_eq!(genesis.alloc.len(), local.alloc.len() + 2);
+        assert_eq!(
+            genesis.alloc.get(&accounts[1].0).unwrap().balance,
+            DEV_ACCOUNT_BALANCE
+        );
+    }
 }
@@ -1,6 +1,6 @@
-use crate::utils::download_and_cache_genesis_verified;
+use crate::{consensus::RoundRobinConfig, utils::download_and_cache_genesis_verified};
 use alloy_primitives::{b256, hex};
-use fluentbase_genesis::local_genesis_from_file;
+use fluentbase_genesis::{local_genesis_from_file, Genesis};
 use fluentbase_release_verify::ReleaseAsset;
 use reth_chainspec::{
     make_genesis_header, BaseFeeParams, BaseFeeParamsKind, Chain, ChainHardforks, ChainSpec,
@@ -12,6 +12,11 @@ use reth_revm::primitives::U256;
 use std::sync::{Arc, LazyLock};
 use tracing::warn;
 
+pub use fluentbase_types::{
+    FLUENT_DEVNET_CHAIN_ID, FLUENT_LOCALNET_CHAIN_ID, FLUENT_MAINNET_CHAIN_ID,
+    FLUENT_TESTNET_CHAIN_ID,
+};
+
 // Genesis assets for the built-in networks.
 //
 // Each entry names a GitHub release asset and pins the SHA-256 of the exact `.json.gz` published
@@ -59,14 +64,12 @@ pub(crate) fn built_in_genesis_assets() -> Vec<(&'static str, ReleaseAsset)> {
     ]
 }
 
-pub const FLUENT_LOCALNET_CHAIN_ID: u64 = 1337;
-pub const FLUENT_DEVNET_CHAIN_ID: u64 = 0x5201;
-pub const FLUENT_TESTNET_CHAIN_ID: u64 = 0x5202;
-pub const FLUENT_MAINNET_CHAIN_ID: u64 = 25363;
-
 /// Local Node (1337)
-pub static FLUENT_LOCAL: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
-    let genesis = local_genesis_from_file();
+pub static FLUENT_LOCAL: LazyLock<Arc<ChainSpec>> =
+    LazyLock::new(|| fluent_local_chain_spec(local_genesis_from_file()));
+
+/// Local chain spec over a custom genesis, e.g. one with pre-funded dev accounts.
+pub fn fluent_local_chain_spec(genesis: Genesis) -> Arc<ChainSpec> {
     let hardforks = DEV_HARDFORKS.clone();
     ChainSpec {
         chain: Chain::from(FLUENT_LOCALNET_CHAIN_ID),
@@ -79,7 +82,7 @@ pub static FLUENT_LOCAL: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
         ..Default::default()
     }
     .into()
-});
+}
 
 /// Fluent Devnet
 pub static FLUENT_DEVNET: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
@@ -224,6 +227,11 @@ pub(crate) fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::
         "fluent-devnet" => FLUENT_DEVNET.clone(),
         "fluent-testnet" => FLUENT_TESTNET.clone(),
         "fluent-mainnet" => FLUENT_MAINNET.clone(),
-        _ => Arc::new(parse_genesis(s)?.into()),
+        _ => {
+            let genesis = parse_genesis(s)?;
+            // Reject a malformed validator set here rather than when consensus is built.
+            RoundRobinConfig::from_genesis(&genesis)?;
+            Arc::new(genesis.into())
+        }
     })
 }
@@ -1,4 +1,12 @@
+mod engine;
+pub use engine::*;
+mod round_robin;
+pub use round_robin::*;
+mod seal;
+pub use seal::*;
+
 use crate::types::FLUENT_MAXIMUM_EXTRA_DATA_SIZE;
+use alloy_consensus::Header;
 use alloy_evm::block::BlockExecutionResult;
 use fluentbase_types::PRECOMPILE_FEE_MANAGER;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
@@ -8,11 +16,17 @@ use reth_ethereum_primitives::EthPrimitives;
 use reth_node_api::FullNodeTypes;
 use reth_node_builder::{components::ConsensusBuilder, BuilderContext};
 use reth_node_types::NodeTypes;
-use reth_primitives_traits::{
-    Block, BlockHeader, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader,
-};
+use reth_primitives_traits::{Block, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader};
 use std::{fmt::Debug, sync::Arc};
 
+/// Returns the [`ConsensusEngine`] of the network, `None` if its genesis has no validator set.
+pub fn consensus_engine<ChainSpec: EthChainSpec>(
+    chain_spec: &ChainSpec,
+) -> eyre::Result<Option<Arc<dyn ConsensusEngine>>> {
+    Ok(RoundRobinConfig::from_genesis(chain_spec.genesis())?
+        .map(|config| Arc::new(RoundRobin::new(chain_spec.chain_id(), config)) as _))
+}
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct FluentConsensusBuilder {}
 
@@ -32,27 +46,46 @@ where
 #[derive(Debug, Clone)]
 pub struct FluentConsensus<ChainSpec> {
     inner: EthBeaconConsensus<ChainSpec>,
+    /// Seal rules of networks with a validator set, `None` for single-sequencer networks.
+    engine: Option<Arc<dyn ConsensusEngine>>,
 }
 
 impl<ChainSpec: EthChainSpec + EthereumHardforks> FluentConsensus<ChainSpec> {
     /// Create a new instance of [`EthBeaconConsensus`]
-    pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
+    ///
+    /// Networks whose genesis has a [`RoundRobinConfig`] get the [`RoundRobin`] engine. The chain
+    /// spec parser rejects malformed configs, so this only panics for hand-built chain specs.
+    pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
+        let engine = consensus_engine(&*chain_spec)
+            .expect("malformed fluent consensus config in the genesis");
         Self {
             inner: EthBeaconConsensus::new(chain_spec)
                 .with_max_extra_data_size(FLUENT_MAXIMUM_EXTRA_DATA_SIZE),
+            engine,
         }
     }
 
+    /// Replaces the seal rules, e.g. with a custom [`ConsensusEngine`].
+    pub fn with_engine(mut self, engine: Option<Arc<dyn ConsensusEngine>>) -> Self {
+        self.engine = engine;
+        self
+    }
+
     /// Returns the chain spec associated with this consensus engine.
     pub const fn chain_spec(&self) -> &Arc<ChainSpec> {
         self.inner.chain_spec()
     }
+
+    /// Returns the seal rules of the network, if it has a validator set.
+    pub fn engine(&self) -> Option<&Arc<dyn ConsensusEngine>> {
+        self.engine.as_ref()
+    }
 }
 
 impl<ChainSpec, N> FullConsensus<N> for FluentConsensus<ChainSpec>
 where
-    ChainSpec: Send + Sync + EthChainSpec<Header = N::BlockHeader> + EthereumHardforks + Debug,
-    N: NodePrimitives,
+    ChainSpec: Send + Sync + EthChainSpec<Header = Header> + EthereumHardforks + Debug,
+    N: NodePrimitives<BlockHeader = Header>,
 {
     fn validate_block_post_execution(
         &self,
@@ -71,8 +104,8 @@ where
 
 impl<B, ChainSpec> Consensus<B> for FluentConsensus<ChainSpec>
 where
-    B: Block,
-    ChainSpec: EthChainSpec<Header = B::Header> + EthereumHardforks + Debug + Send + Sync,
+    B: Block<Header = Header>,
+    ChainSpec: EthChainSpec<Header = Header> + EthereumHardforks + Debug + Send + Sync,
 {
     fn validate_body_against_header(
         &self,
@@ -100,28 +133,35 @@ where
     }
 }
 
-impl<H, ChainSpec> HeaderValidator<H> for FluentConsensus<ChainSpec>
+impl<ChainSpec> HeaderValidator<Header> for FluentConsensus<ChainSpec>
 where
-    H: BlockHeader,
-    ChainSpec: EthChainSpec<Header = H> + EthereumHardforks + Debug + Send + Sync,
+    ChainSpec: EthChainSpec<Header = Header> + EthereumHardforks + Debug + Send + Sync,
 {
-    fn validate_header(&self, header: &SealedHeader<H>) -> Result<(), ConsensusError> {
+    fn validate_header(&self, header: &SealedHeader<Header>) -> Result<(), ConsensusError> {
         self.inner.validate_header(header)?;
 
         // Make sure a header has correct coinbase, all fees must be accumulated
         // inside fee manager smart contract
-        if header.header().beneficiary() != PRECOMPILE_FEE_MANAGER {
+        if header.header().beneficiary != PRECOMPILE_FEE_MANAGER {
             return Err(ConsensusError::msg("malformed beneficiary".to_owned()));
         }
 
+        if let Some(engine) = &self.engine {
+            engine.validate_seal(header.header())?;
+        }
+
         Ok(())
     }
 
     fn validate_header_against_parent(
         &self,
-        header: &SealedHeader<H>,
-        parent: &SealedHeader<H>,
+        header: &SealedHeader<Header>,
+        parent: &SealedHeader<Header>,
     ) -> Result<(), ConsensusError> {
-        self.inner.validate_header_against_parent(header, parent)
+        self.inner.validate_header_against_parent(header, parent)?;
+        if let Some(engine) = &self.engine {
+            engine.validate_seal_against_parent(header.header(), parent.header())?;
+        }
+        Ok(())
     }
 }
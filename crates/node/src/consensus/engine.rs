@@ -0,0 +1,31 @@
+use crate::consensus::ValidatorKey;
+use alloy_consensus::Header;
+use alloy_primitives::Address;
+use reth_consensus::ConsensusError;
+use std::{fmt::Debug, time::Duration};
+
+/// Block authorship rules of a network with a validator set.
+///
+/// [`FluentConsensus`](crate::consensus::FluentConsensus) checks imported headers against it, and
+/// the launcher asks it when the local validator may propose and how to seal what it built.
+/// [`RoundRobin`](crate::consensus::RoundRobin) is the first implementation.
+pub trait ConsensusEngine: Debug + Send + Sync {
+    /// Checks the seal of `header` on its own, e.g. that it's signed by a known validator.
+    fn validate_seal(&self, header: &Header) -> Result<(), ConsensusError>;
+
+    /// Checks the seal of `header` against its parent, e.g. that the signer owned the slot.
+    fn validate_seal_against_parent(
+        &self,
+        header: &Header,
+        parent: &Header,
+    ) -> Result<(), ConsensusError>;
+
+    /// Returns `true` if `validator` may propose a block at `timestamp` on top of `parent`.
+    fn is_proposer(&self, validator: Address, parent: &Header, timestamp: u64) -> bool;
+
+    /// Seals a block built by the local validator.
+    fn seal(&self, key: &ValidatorKey, header: Header) -> Header;
+
+    /// How often a validator checks whether it's its turn to propose.
+    fn poll_interval(&self) -> Duration;
+}
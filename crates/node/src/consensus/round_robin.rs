@@ -0,0 +1,319 @@
+//! Round-robin block production by a static validator set.
+//!
+//! The set lives in the genesis under [`FLUENT_CONSENSUS_GENESIS_KEY`]. Block `n` belongs to
+//! validator `n % len`. Once `block_time` has passed since the parent, that validator has
+//! `slot_timeout` seconds to produce the block, after which the slot moves on to the next
+//! validator, and so on. The slot a header claims is derived from its timestamp, so every node
+//! agrees on who was allowed to seal it and a single crashed validator only delays the chain by
+//! one timeout per round.
+//!
+//! There is no finality: when a late block and its replacement race each other, nodes follow
+//! whichever they imported last, and the next block settles it.
+use crate::consensus::{recover_seal_signer, ConsensusEngine, ValidatorKey};
+use alloy_consensus::Header;
+use alloy_genesis::Genesis;
+use alloy_primitives::Address;
+use reth_consensus::ConsensusError;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, time::Duration};
+
+/// Genesis `config` field holding the [`RoundRobinConfig`] of a network.
+pub const FLUENT_CONSENSUS_GENESIS_KEY: &str = "fluentConsensus";
+
+/// How far ahead of the local clock a header timestamp may be.
+pub const ALLOWED_FUTURE_BLOCK_TIME_SECONDS: u64 = 15;
+
+/// Validator set and timing of a round-robin network.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundRobinConfig {
+    /// Validators in proposing order.
+    pub validators: Vec<Address>,
+    /// Seconds between a block and the earliest timestamp of its child.
+    pub block_time: u64,
+    /// Seconds a proposer has to fill its slot before the next validator takes over.
+    pub slot_timeout: u64,
+}
+
+impl RoundRobinConfig {
+    /// Reads the config from the genesis, `None` for networks without a validator set.
+    pub fn from_genesis(genesis: &Genesis) -> eyre::Result<Option<Self>> {
+        let Some(config) = genesis
+            .config
+            .extra_fields
+            .get_deserialized::<Self>(FLUENT_CONSENSUS_GENESIS_KEY)
+        else {
+            return Ok(None);
+        };
+        let config = config?;
+        config.validate()?;
+        Ok(Some(config))
+    }
+
+    pub fn validate(&self) -> eyre::Result<()> {
+        if self.validators.is_empty() {
+            eyre::bail!("{FLUENT_CONSENSUS_GENESIS_KEY}: the validator set is empty");
+        }
+        if self.validators.iter().collect::<HashSet<_>>().len() != self.validators.len() {
+            eyre::bail!("{FLUENT_CONSENSUS_GENESIS_KEY}: the validator set has duplicates");
+        }
+        if self.block_time == 0 || self.slot_timeout == 0 {
+            eyre::bail!(
+                "{FLUENT_CONSENSUS_GENESIS_KEY}: blockTime and slotTimeout must be positive"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// [`ConsensusEngine`] rotating block production through a static validator set.
+#[derive(Clone, Debug)]
+pub struct RoundRobin {
+    chain_id: u64,
+    config: RoundRobinConfig,
+}
+
+impl RoundRobin {
+    /// Expects a config that passed [`RoundRobinConfig::validate`].
+    pub fn new(chain_id: u64, config: RoundRobinConfig) -> Self {
+        Self { chain_id, config }
+    }
+
+    pub fn config(&self) -> &RoundRobinConfig {
+        &self.config
+    }
+
+    /// Returns the validator owning the slot of block `number` at `timestamp`, or `None` if
+    /// `timestamp` is earlier than `block_time` after the parent.
+    pub fn proposer(&self, number: u64, parent_timestamp: u64, timestamp: u64) -> Option<Address> {
+        let delay = timestamp
+            .checked_sub(parent_timestamp)?
+            .checked_sub(self.config.block_time)?;
+        let missed_slots = delay / self.config.slot_timeout;
+        let len = self.config.validators.len() as u64;
+        let index = (number % len + missed_slots % len) % len;
+        Some(self.config.validators[index as usize])
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+impl ConsensusEngine for RoundRobin {
+    fn validate_seal(&self, header: &Header) -> Result<(), ConsensusError> {
+        if header.timestamp > unix_timestamp().saturating_add(ALLOWED_FUTURE_BLOCK_TIME_SECONDS) {
+            return Err(ConsensusError::msg(
+                "block timestamp is in the future".to_owned(),
+            ));
+        }
+        let signer = recover_seal_signer(self.chain_id, header)?;
+        if !self.config.validators.contains(&signer) {
+            return Err(ConsensusError::msg(format!(
+                "block sealed by {signer}, which is not a validator"
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_seal_against_parent(
+        &self,
+        header: &Header,
+        parent: &Header,
+    ) -> Result<(), ConsensusError> {
+        let signer = recover_seal_signer(self.chain_id, header)?;
+        match self.proposer(header.number, parent.timestamp, header.timestamp) {
+            Some(proposer) if proposer == signer => Ok(()),
+            Some(proposer) => Err(ConsensusError::msg(format!(
+                "block sealed by {signer}, but the slot belongs to {proposer}"
+            ))),
+            None => Err(ConsensusError::msg(
+                "block sealed before its slot opened".to_owned(),
+            )),
+        }
+    }
+
+    fn is_proposer(&self, validator: Address, parent: &Header, timestamp: u64) -> bool {
+        self.proposer(parent.number + 1, parent.timestamp, timestamp) == Some(validator)
+    }
+
+    fn seal(&self, key: &ValidatorKey, header: Header) -> Header {
+        key.seal(self.chain_id, header)
+    }
+
+    fn poll_interval(&self) -> Duration {
+        // Timestamps have a resolution of a second, polling faster keeps slots from being missed
+        // by a tick without producing more blocks.
+        Duration::from_millis(250)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    const CHAIN_ID: u64 = 1337;
+
+    fn key(byte: u8) -> ValidatorKey {
+        ValidatorKey::from_secret(&B256::repeat_byte(byte)).unwrap()
+    }
+
+    fn engine(keys: &[ValidatorKey]) -> RoundRobin {
+        RoundRobin::new(
+            CHAIN_ID,
+            RoundRobinConfig {
+                validators: keys.iter().map(ValidatorKey::address).collect(),
+                block_time: 1,
+                slot_timeout: 2,
+            },
+        )
+    }
+
+    fn child(parent: &Header, timestamp: u64) -> Header {
+        Header {
+            number: parent.number + 1,
+            timestamp,
+            parent_hash: parent.hash_slow(),
+            ..Default::default()
+        }
+    }
+
+    fn genesis_with(config: serde_json::Value) -> Genesis {
+        let mut genesis = Genesis::default();
+        genesis
+            .config
+            .extra_fields
+            .insert(FLUENT_CONSENSUS_GENESIS_KEY.to_string(), config);
+        genesis
+    }
+
+    #[test]
+    fn config_is_read_from_the_genesis() {
+        let genesis = genesis_with(serde_json::json!({
+            "validators": ["0x0000000000000000000000000000000000000001"],
+            "blockTime": 1,
+            "slotTimeout": 3,
+        }));
+        let config = RoundRobinConfig::from_genesis(&genesis).unwrap().unwrap();
+        assert_eq!(config.validators, vec![Address::with_last_byte(1)]);
+        assert_eq!(config.slot_timeout, 3);
+
+        assert_eq!(
+            RoundRobinConfig::from_genesis(&Genesis::default()).unwrap(),
+            None
+        );
+        let empty = genesis_with(serde_json::json!({
+            "validators": [],
+            "blockTime": 1,
+            "slotTimeout": 3,
+        }));
+        assert!(RoundRobinConfig::from_genesis(&empty).is_err());
+    }
+
+    #[test]
+    fn slots_rotate_and_time_out() {
+        let keys = [key(1), key(2), key(3)];
+        let engine = engine(&keys);
+        // Block 4 belongs to validator 1, it may not be produced before block_time has passed.
+        assert_eq!(engine.proposer(4, 100, 100), None);
+        assert_eq!(engine.proposer(4, 100, 101), Some(keys[1].address()));
+        assert_eq!(engine.proposer(4, 100, 102), Some(keys[1].address()));
+        // Each missed slot hands the block to the next validator, wrapping around the set.
+        assert_eq!(engine.proposer(4, 100, 103), Some(keys[2].address()));
+        assert_eq!(engine.proposer(4, 100, 105), Some(keys[0].address()));
+        assert_eq!(engine.proposer(4, 100, 107), Some(keys[1].address()));
+    }
+
+    #[test]
+    fn seals_are_checked_against_the_slot() {
+        let keys = [key(1), key(2), key(3)];
+        let engine = engine(&keys);
+        let parent = Header {
+            number: 3,
+            timestamp: 100,
+            ..Default::default()
+        };
+
+        let on_time = keys[1].seal(CHAIN_ID, child(&parent, 101));
+        assert!(engine.validate_seal(&on_time).is_ok());
+        assert!(engine
+            .validate_seal_against_parent(&on_time, &parent)
+            .is_ok());
+
+        // The next validator may only take over after the timeout.
+        let early_takeover = keys[2].seal(CHAIN_ID, child(&parent, 102));
+        assert!(engine.validate_seal(&early_takeover).is_ok());
+        assert!(engine
+            .validate_seal_against_parent(&early_takeover, &parent)
+            .is_err());
+        let takeover = keys[2].seal(CHAIN_ID, child(&parent, 103));
+        assert!(engine
+            .validate_seal_against_parent(&takeover, &parent)
+            .is_ok());
+
+        let early = keys[1].seal(CHAIN_ID, child(&parent, 100));
+        assert!(engine
+            .validate_seal_against_parent(&early, &parent)
+            .is_err());
+
+        let outsider = key(9).seal(CHAIN_ID, child(&parent, 101));
+        assert!(engine.validate_seal(&outsider).is_err());
+
+        let future = keys[1].seal(
+            CHAIN_ID,
+            child(
+                &parent,
+                unix_timestamp() + ALLOWED_FUTURE_BLOCK_TIME_SECONDS + 10,
+            ),
+        );
+        assert!(engine.validate_seal(&future).is_err());
+    }
+
+    /// Simulates several validators against a shared clock with one of them down and checks that
+    /// the seal rules keep the chain growing with blocks every other node accepts.
+    ///
+    /// Only the engine is exercised here. No nodes are launched, so the producer in
+    /// `launcher.rs`, the `--consensus.peers` import loop and following the canonical head are
+    /// not covered by any test yet.
+    #[test]
+    fn seal_rules_survive_a_crashed_validator() {
+        let keys = [key(1), key(2), key(3), key(4)];
+        let engine = engine(&keys);
+        let crashed = keys[2].address();
+        let genesis = Header::default();
+        let mut chain = vec![genesis];
+
+        let mut now = 0;
+        while chain.len() <= 40 {
+            now += 1;
+            let parent = chain.last().unwrap();
+            let proposer = keys.iter().find(|key| {
+                key.address() != crashed && engine.is_proposer(key.address(), parent, now)
+            });
+            let Some(proposer) = proposer else {
+                continue;
+            };
+            let header = engine.seal(proposer, child(parent, now));
+            engine.validate_seal(&header).unwrap();
+            engine
+                .validate_seal_against_parent(&header, parent)
+                .unwrap();
+            chain.push(header);
+        }
+
+        let signers: Vec<_> = chain[1..]
+            .iter()
+            .map(|header| recover_seal_signer(CHAIN_ID, header).unwrap())
+            .collect();
+        assert!(!signers.contains(&crashed));
+        for key in keys.iter().filter(|key| key.address() != crashed) {
+            assert!(signers.contains(&key.address()));
+        }
+        // Three healthy slots of one second and one timed-out slot of three seconds per round.
+        assert_eq!(now, 10 * 6);
+    }
+}
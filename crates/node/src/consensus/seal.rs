@@ -0,0 +1,155 @@
+//! `extra_data` seals of blocks produced by a validator set.
+//!
+//! On networks with a validator set the whole `extra_data` of a header is the proposer's
+//! secp256k1 signature `r || s || v` over [`seal_hash`], which is why the extra data limit is
+//! [`FLUENT_MAXIMUM_EXTRA_DATA_SIZE`]. The seal commits to the chain id so a validator key shared
+//! by two networks can't have its blocks replayed across them.
+use crate::types::FLUENT_MAXIMUM_EXTRA_DATA_SIZE;
+use alloy_consensus::Header;
+use alloy_primitives::{keccak256, Address, Bytes, B256};
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use reth_consensus::ConsensusError;
+use std::{fmt, path::Path, str::FromStr};
+
+/// Length of a seal, a recoverable secp256k1 signature.
+pub const SEAL_LENGTH: usize = 65;
+
+const _: () = assert!(SEAL_LENGTH == FLUENT_MAXIMUM_EXTRA_DATA_SIZE);
+
+/// Hash signed by the proposer: the chain id followed by the hash of the header without a seal.
+pub fn seal_hash(chain_id: u64, header: &Header) -> B256 {
+    let mut unsealed = header.clone();
+    unsealed.extra_data = Bytes::new();
+    let mut buf = [0u8; 40];
+    buf[..8].copy_from_slice(&chain_id.to_be_bytes());
+    buf[8..].copy_from_slice(unsealed.hash_slow().as_slice());
+    keccak256(buf)
+}
+
+/// Recovers the validator that sealed `header`.
+///
+/// High-`s` signatures are rejected, so every sealed header has exactly one valid encoding.
+pub fn recover_seal_signer(chain_id: u64, header: &Header) -> Result<Address, ConsensusError> {
+    let seal: &[u8; SEAL_LENGTH] = header
+        .extra_data
+        .as_ref()
+        .try_into()
+        .map_err(|_| ConsensusError::msg("missing block seal".to_owned()))?;
+    let signature = Signature::from_slice(&seal[..64])
+        .map_err(|_| ConsensusError::msg("malformed block seal".to_owned()))?;
+    if signature.normalize_s().is_some() {
+        return Err(ConsensusError::msg("malformed block seal".to_owned()));
+    }
+    let recovery_id = RecoveryId::from_byte(seal[64])
+        .ok_or_else(|| ConsensusError::msg("malformed block seal".to_owned()))?;
+    let verifying_key = VerifyingKey::recover_from_prehash(
+        seal_hash(chain_id, header).as_slice(),
+        &signature,
+        recovery_id,
+    )
+    .map_err(|_| ConsensusError::msg("invalid block seal".to_owned()))?;
+    Ok(public_key_to_address(&verifying_key))
+}
+
+fn public_key_to_address(key: &VerifyingKey) -> Address {
+    let point = key.to_encoded_point(false);
+    Address::from_slice(&keccak256(&point.as_bytes()[1..])[12..])
+}
+
+/// Signing key of a validator.
+#[derive(Clone)]
+pub struct ValidatorKey {
+    key: SigningKey,
+    address: Address,
+}
+
+impl ValidatorKey {
+    pub fn from_secret(secret: &B256) -> eyre::Result<Self> {
+        let key = SigningKey::from_slice(secret.as_slice())
+            .map_err(|_| eyre::eyre!("invalid validator key"))?;
+        let address = public_key_to_address(key.verifying_key());
+        Ok(Self { key, address })
+    }
+
+    /// Reads a hex-encoded secret key, the format written by most key tools.
+    pub fn from_file(path: &Path) -> eyre::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        contents.trim().parse()
+    }
+
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Returns `header` with its `extra_data` replaced by this validator's seal.
+    pub fn seal(&self, chain_id: u64, mut header: Header) -> Header {
+        let hash = seal_hash(chain_id, &header);
+        let (signature, recovery_id) = self
+            .key
+            .sign_prehash_recoverable(hash.as_slice())
+            .expect("signing a 32-byte prehash can't fail");
+        let mut seal = [0u8; SEAL_LENGTH];
+        seal[..64].copy_from_slice(&signature.to_bytes());
+        seal[64] = recovery_id.to_byte();
+        header.extra_data = Bytes::copy_from_slice(&seal);
+        header
+    }
+}
+
+impl FromStr for ValidatorKey {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_secret(&s.parse::<B256>()?)
+    }
+}
+
+impl fmt::Debug for ValidatorKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidatorKey")
+            .field("address", &self.address)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> ValidatorKey {
+        ValidatorKey::from_secret(&B256::repeat_byte(byte)).unwrap()
+    }
+
+    #[test]
+    fn seal_recovers_to_the_signer() {
+        let header = Header {
+            number: 7,
+            timestamp: 100,
+            ..Default::default()
+        };
+        let sealed = key(1).seal(1337, header.clone());
+        assert_eq!(sealed.extra_data.len(), SEAL_LENGTH);
+        assert_eq!(seal_hash(1337, &sealed), seal_hash(1337, &header));
+        assert_eq!(
+            recover_seal_signer(1337, &sealed).unwrap(),
+            key(1).address()
+        );
+        // A different chain or a modified header recovers to some unrelated address.
+        assert_ne!(recover_seal_signer(1, &sealed).ok(), Some(key(1).address()));
+        let mut tampered = sealed.clone();
+        tampered.timestamp += 1;
+        assert_ne!(
+            recover_seal_signer(1337, &tampered).ok(),
+            Some(key(1).address())
+        );
+    }
+
+    #[test]
+    fn unsealed_headers_are_rejected() {
+        let header = Header {
+            extra_data: Bytes::from_static(b"fluent/v1.4.0/linux"),
+            ..Default::default()
+        };
+        assert!(recover_seal_signer(1337, &header).is_err());
+    }
+}
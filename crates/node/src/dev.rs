@@ -0,0 +1,354 @@
+//! `--dev` mode: a throwaway local chain with pre-funded accounts that is mined on demand.
+//!
+//! The dev miner in [`launcher`](crate::launcher) mines a block as soon as a transaction is
+//! pending and on every [`DevCommand`] the `evm` and `anvil` RPC namespaces send it. Block
+//! timestamps come from a [`DevClock`] that `evm_increaseTime` moves forward, and
+//! `evm_snapshot`/`evm_revert` move the head back to an earlier block.
+//!
+//! State can't be written to the database directly without breaking the state root, so
+//! `anvil_setCode` and `anvil_setStorageAt` mine a block that starts by applying the change, see
+//! [`DevStateOverrides`].
+use crate::payload::fluent_payload_attributes;
+use alloy_consensus::Header;
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_rpc_types_engine::PayloadAttributes as EthPayloadAttributes;
+use eyre::eyre;
+use fluentbase_evm::EthereumMetadata;
+use fluentbase_revm::revm::{
+    bytecode::{rwasm::RWASM_MAGIC_BYTES, Bytecode},
+    state::{Account, EvmState, EvmStorageSlot},
+    Database, DatabaseCommit,
+};
+use fluentbase_sdk::compile_rwasm_maybe_system;
+use fluentbase_types::{PRECOMPILE_EVM_RUNTIME, WASM_MAGIC_BYTES};
+use reth_payload_primitives::PayloadAttributesBuilder;
+use reth_primitives_traits::SealedHeader;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc, Mutex,
+    },
+};
+use tokio::sync::{mpsc, oneshot};
+
+/// Number of accounts pre-funded by default.
+pub const DEV_ACCOUNT_COUNT: u32 = 10;
+
+fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Clock of a dev chain: the wall clock shifted by `evm_increaseTime` and `evm_mine`.
+#[derive(Debug, Default)]
+pub struct DevClock {
+    offset: AtomicI64,
+}
+
+impl DevClock {
+    pub fn now(&self) -> u64 {
+        unix_timestamp().saturating_add_signed(self.offset())
+    }
+
+    /// Seconds the clock is ahead of the wall clock, negative when behind.
+    pub fn offset(&self) -> i64 {
+        self.offset.load(Ordering::Relaxed)
+    }
+
+    pub fn set_offset(&self, offset: i64) {
+        self.offset.store(offset, Ordering::Relaxed);
+    }
+
+    /// Moves the clock forward by `seconds` and returns the new offset.
+    pub fn increase(&self, seconds: u64) -> i64 {
+        let seconds = i64::try_from(seconds).unwrap_or(i64::MAX);
+        let previous = self
+            .offset
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |offset| {
+                Some(offset.saturating_add(seconds))
+            })
+            .expect("the update never fails");
+        previous.saturating_add(seconds)
+    }
+
+    /// Sets the clock to `timestamp`.
+    pub fn set_time(&self, timestamp: u64) {
+        let offset = i128::from(timestamp) - i128::from(unix_timestamp());
+        self.set_offset(offset.clamp(i64::MIN.into(), i64::MAX.into()) as i64);
+    }
+}
+
+/// Attributes of dev blocks, timestamped by a [`DevClock`].
+#[derive(Clone, Debug, Default)]
+pub struct DevPayloadAttributesBuilder {
+    clock: Arc<DevClock>,
+}
+
+impl DevPayloadAttributesBuilder {
+    pub fn new(clock: Arc<DevClock>) -> Self {
+        Self { clock }
+    }
+}
+
+impl PayloadAttributesBuilder<EthPayloadAttributes, Header> for DevPayloadAttributesBuilder {
+    fn build(&self, parent: &SealedHeader<Header>) -> EthPayloadAttributes {
+        fluent_payload_attributes(parent, self.clock.now())
+    }
+}
+
+/// Turns code passed to `anvil_setCode` into the bytecode stored at `address`.
+///
+/// rWasm is stored as is and Wasm is compiled like a deployment to `address` would be, without
+/// running its constructor. Anything else is EVM runtime code, stored in an account owned by the
+/// EVM runtime.
+pub fn dev_account_code(address: &Address, code: Bytes) -> eyre::Result<Bytecode> {
+    if code.is_empty() {
+        return Ok(Bytecode::new());
+    }
+    if code.starts_with(&RWASM_MAGIC_BYTES) {
+        return Ok(Bytecode::new_raw(code));
+    }
+    if code.starts_with(&WASM_MAGIC_BYTES) {
+        let result = compile_rwasm_maybe_system(address, &code)
+            .map_err(|err| eyre!("failed to compile the wasm binary: {err:?}"))?;
+        return Ok(Bytecode::new_raw(result.rwasm_module.serialize().into()));
+    }
+    let metadata = EthereumMetadata::new_analyzed(code).write_to_bytes();
+    Ok(Bytecode::new_ownable_account(
+        PRECOMPILE_EVM_RUNTIME,
+        metadata,
+    ))
+}
+
+/// Code and storage forced into an account.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountOverride {
+    pub code: Option<Bytecode>,
+    pub storage: HashMap<U256, U256>,
+}
+
+/// Account overrides applied at the start of the block built on top of a given parent.
+///
+/// Keyed by the parent hash, so the payload builder and the engine, which executes the block
+/// again when it's imported, apply the same changes.
+#[derive(Debug, Default)]
+pub struct DevStateOverrides {
+    by_parent: Mutex<HashMap<B256, HashMap<Address, AccountOverride>>>,
+}
+
+impl DevStateOverrides {
+    pub fn insert(&self, parent: B256, address: Address, account: AccountOverride) {
+        let mut by_parent = self.by_parent.lock().unwrap();
+        let pending = by_parent
+            .entry(parent)
+            .or_default()
+            .entry(address)
+            .or_default();
+        if account.code.is_some() {
+            pending.code = account.code;
+        }
+        pending.storage.extend(account.storage);
+    }
+
+    pub fn remove(&self, parent: B256) {
+        self.by_parent.lock().unwrap().remove(&parent);
+    }
+
+    /// Commits the overrides of the child of `parent` to `db`, if there are any.
+    pub fn apply<DB: Database + DatabaseCommit>(
+        &self,
+        parent: B256,
+        db: &mut DB,
+    ) -> Result<(), DB::Error> {
+        let Some(accounts) = self.by_parent.lock().unwrap().get(&parent).cloned() else {
+            return Ok(());
+        };
+        let mut changes = EvmState::default();
+        for (address, account_override) in accounts {
+            let mut account = Account::from(db.basic(address)?.unwrap_or_default());
+            if let Some(code) = account_override.code {
+                account.info.code_hash = code.hash_slow();
+                account.info.code = Some(code);
+            }
+            for (slot, value) in account_override.storage {
+                let original = db.storage(address, slot)?;
+                account
+                    .storage
+                    .insert(slot, EvmStorageSlot::new_changed(original, value, 0));
+            }
+            account.mark_touch();
+            changes.insert(address, account);
+        }
+        db.commit(changes);
+        Ok(())
+    }
+}
+
+/// Request of the dev RPC namespaces to the dev miner.
+#[derive(Debug)]
+pub enum DevCommand {
+    /// Mines a block, empty if nothing is pending.
+    Mine {
+        reply: oneshot::Sender<eyre::Result<()>>,
+    },
+    /// Remembers the current head and clock, replying with the snapshot id.
+    Snapshot { reply: oneshot::Sender<U256> },
+    /// Goes back to a snapshot and forgets it and every later one. Replies `false` for unknown
+    /// ids.
+    Revert {
+        id: U256,
+        reply: oneshot::Sender<eyre::Result<bool>>,
+    },
+    /// Mines a block that starts by applying `account` to `address`.
+    Override {
+        address: Address,
+        account: AccountOverride,
+        reply: oneshot::Sender<eyre::Result<()>>,
+    },
+}
+
+/// Handle of the RPC to the dev miner and its clock.
+#[derive(Clone, Debug)]
+pub struct DevHandle {
+    clock: Arc<DevClock>,
+    commands: mpsc::UnboundedSender<DevCommand>,
+}
+
+impl DevHandle {
+    /// Returns the handle and the receiver to pass to the dev miner.
+    pub fn new(clock: Arc<DevClock>) -> (Self, mpsc::UnboundedReceiver<DevCommand>) {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        (Self { clock, commands }, receiver)
+    }
+
+    pub fn clock(&self) -> &DevClock {
+        &self.clock
+    }
+
+    async fn request<R>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<R>) -> DevCommand,
+    ) -> eyre::Result<R> {
+        let (reply, response) = oneshot::channel();
+        self.commands
+            .send(command(reply))
+            .map_err(|_| eyre!("the dev miner isn't running"))?;
+        response
+            .await
+            .map_err(|_| eyre!("the dev miner isn't running"))
+    }
+
+    pub async fn mine(&self) -> eyre::Result<()> {
+        self.request(|reply| DevCommand::Mine { reply }).await?
+    }
+
+    pub async fn snapshot(&self) -> eyre::Result<U256> {
+        self.request(|reply| DevCommand::Snapshot { reply }).await
+    }
+
+    pub async fn revert(&self, id: U256) -> eyre::Result<bool> {
+        self.request(|reply| DevCommand::Revert { id, reply })
+            .await?
+    }
+
+    pub async fn override_account(
+        &self,
+        address: Address,
+        account: AccountOverride,
+    ) -> eyre::Result<()> {
+        self.request(|reply| DevCommand::Override {
+            address,
+            account,
+            reply,
+        })
+        .await?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluentbase_revm::revm::{database::InMemoryDB, state::AccountInfo};
+
+    #[test]
+    fn clock_moves_forward_and_back() {
+        let clock = DevClock::default();
+        assert_eq!(clock.increase(60), 60);
+        assert_eq!(clock.increase(40), 100);
+        assert!(clock.now() >= unix_timestamp() + 100);
+
+        clock.set_time(1_000);
+        assert!(clock.offset() < 0);
+        assert!((1_000..1_010).contains(&clock.now()));
+        clock.set_offset(0);
+        assert!(clock.now() >= unix_timestamp());
+    }
+
+    #[test]
+    fn evm_code_is_owned_by_the_evm_runtime() {
+        let code = dev_account_code(&Address::ZERO, Bytes::from_static(&[0x60, 0x00])).unwrap();
+        match code {
+            Bytecode::OwnableAccount(account) => {
+                assert_eq!(account.owner_address, PRECOMPILE_EVM_RUNTIME)
+            }
+            _ => panic!("expected an ownable account"),
+        }
+        assert!(dev_account_code(&Address::ZERO, Bytes::new())
+            .unwrap()
+            .is_empty());
+        // A truncated Wasm binary doesn't compile.
+        assert!(
+            dev_account_code(&Address::ZERO, Bytes::copy_from_slice(&WASM_MAGIC_BYTES)).is_err()
+        );
+    }
+
+    #[test]
+    fn overrides_apply_to_the_child_of_their_parent_only() {
+        let address = Address::with_last_byte(1);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            address,
+            AccountInfo {
+                balance: U256::from(7),
+                ..Default::default()
+            },
+        );
+        db.insert_account_storage(address, U256::from(1), U256::from(10))
+            .unwrap();
+
+        let overrides = DevStateOverrides::default();
+        let parent = B256::repeat_byte(1);
+        overrides.insert(
+            parent,
+            address,
+            AccountOverride {
+                code: Some(dev_account_code(&address, Bytes::from_static(&[0x00])).unwrap()),
+                storage: HashMap::from([(U256::from(1), U256::from(11))]),
+            },
+        );
+        overrides.insert(
+            parent,
+            address,
+            AccountOverride {
+                code: None,
+                storage: HashMap::from([(U256::from(2), U256::from(12))]),
+            },
+        );
+
+        overrides.apply(B256::repeat_byte(2), &mut db).unwrap();
+        assert_eq!(db.storage(address, U256::from(1)).unwrap(), U256::from(10));
+
+        overrides.apply(parent, &mut db).unwrap();
+        let info = db.basic(address).unwrap().unwrap();
+        assert_eq!(info.balance, U256::from(7));
+        assert!(info.code.is_some_and(|code| !code.is_empty()));
+        assert_eq!(db.storage(address, U256::from(1)).unwrap(), U256::from(11));
+        assert_eq!(db.storage(address, U256::from(2)).unwrap(), U256::from(12));
+
+        overrides.remove(parent);
+        assert!(overrides.by_parent.lock().unwrap().is_empty());
+    }
+}
@@ -1,6 +1,10 @@
 //! Ethereum EVM implementation.
 
-use crate::{consensus::FluentConsensusBuilder, payload::FluentPayloadAttributesBuilder};
+use crate::{
+    consensus::FluentConsensusBuilder,
+    dev::DevStateOverrides,
+    payload::{FluentPayloadAttributesBuilder, FluentPayloadBuilderBuilder},
+};
 use alloy_consensus::{Header, TxType};
 use alloy_evm::{
     block::{
@@ -13,7 +17,7 @@ use alloy_evm::{
     precompiles::PrecompilesMap,
     Database, Evm,
 };
-use alloy_primitives::{Address, Bytes};
+use alloy_primitives::{Address, Bytes, B256};
 use alloy_rpc_types_engine::{ExecutionData, PayloadAttributes as EthPayloadAttributes};
 use core::{
     fmt::Debug,
@@ -27,10 +31,11 @@ use fluentbase_revm::{
         inspector::NoOpInspector,
         interpreter::{interpreter::EthInterpreter, InterpreterResult},
         primitives::hardfork::SpecId,
-        Context, ExecuteEvm, InspectEvm, Inspector, SystemCallEvm,
+        Context, DatabaseCommit, ExecuteEvm, InspectEvm, Inspector, SystemCallEvm,
     },
     DefaultRwasm, RwasmBuilder, RwasmEvm, RwasmFrame, RwasmPrecompiles,
 };
+use fluentbase_runtime::RuntimeConfig;
 use reth_chainspec::ChainSpec;
 use reth_ethereum_engine_primitives::{EthBuiltPayload, EthEngineTypes};
 use reth_ethereum_primitives::{EthPrimitives, Receipt, TransactionSigned};
@@ -46,7 +51,7 @@ use reth_node_builder::{
 };
 use reth_node_ethereum::{
     EthereumAddOns, EthereumEngineValidatorBuilder, EthereumEthApiBuilder, EthereumNetworkBuilder,
-    EthereumPayloadBuilder, EthereumPoolBuilder,
+    EthereumPoolBuilder,
 };
 use reth_node_types::NodeTypes;
 use reth_payload_primitives::{PayloadAttributesBuilder, PayloadTypes};
@@ -284,9 +289,28 @@ impl EvmFactory for FluentEvmFactory {
 }
 
 /// Builds a regular ethereum block executor that uses the custom EVM.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct FluentExecutorBuilder;
+pub struct FluentExecutorBuilder {
+    runtime_config: RuntimeConfig,
+    dev_state_overrides: Option<Arc<DevStateOverrides>>,
+}
+
+impl FluentExecutorBuilder {
+    /// Creates a builder whose executors run with the given runtime config.
+    pub fn new(runtime_config: RuntimeConfig) -> Self {
+        Self {
+            runtime_config,
+            dev_state_overrides: None,
+        }
+    }
+
+    /// Applies the account overrides of a `--dev` node at the start of blocks.
+    pub fn with_dev_state_overrides(mut self, overrides: Arc<DevStateOverrides>) -> Self {
+        self.dev_state_overrides = Some(overrides);
+        self
+    }
+}
 
 impl<Node> ExecutorBuilder<Node> for FluentExecutorBuilder
 where
@@ -295,7 +319,11 @@ where
     type EVM = FluentEvmConfig;
 
     async fn build_evm(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::EVM> {
-        let evm_config = FluentEvmConfig::new(ctx.chain_spec(), FluentEvmFactory::default());
+        // The runtime executors are process-wide, so the config must be in place before the
+        // first block is executed; installing it again with the same value is a no-op.
+        self.runtime_config.clone().install()?;
+        let mut evm_config = FluentEvmConfig::new_with_default_factory(ctx.chain_spec());
+        evm_config.dev_state_overrides = self.dev_state_overrides;
         Ok(evm_config)
     }
 }
@@ -304,13 +332,18 @@ where
 pub struct FluentEvmConfig {
     /// Inner evm config
     pub inner: EthEvmConfig<ChainSpec, FluentEvmFactory>,
+    /// Account overrides of a `--dev` node, `None` everywhere else.
+    pub dev_state_overrides: Option<Arc<DevStateOverrides>>,
 }
 
 impl FluentEvmConfig {
     /// Create a new [`TempoEvmConfig`] with the given chain spec and EVM factory.
     pub fn new(chain_spec: Arc<ChainSpec>, evm_factory: FluentEvmFactory) -> Self {
         let inner = EthEvmConfig::new_with_evm_factory(chain_spec.clone(), evm_factory);
-        Self { inner }
+        Self {
+            inner,
+            dev_state_overrides: None,
+        }
     }
 
     /// Create a new [`TempoEvmConfig`] with the given chain spec and default EVM factory.
@@ -352,6 +385,8 @@ impl BlockExecutorFactory for FluentEvmConfig {
         I: Inspector<<Self::EvmFactory as EvmFactory>::Context<DB>>,
     {
         FluentBlockExecutor {
+            dev_state_overrides: self.dev_state_overrides.as_deref(),
+            parent_hash: ctx.parent_hash,
             inner: EthBlockExecutor::new(
                 evm,
                 ctx,
@@ -435,7 +470,7 @@ impl FluentNode {
     pub fn components<Node>() -> ComponentsBuilder<
         Node,
         EthereumPoolBuilder,
-        BasicPayloadServiceBuilder<EthereumPayloadBuilder>,
+        BasicPayloadServiceBuilder<FluentPayloadBuilderBuilder>,
         EthereumNetworkBuilder,
         FluentExecutorBuilder,
         FluentConsensusBuilder,
@@ -473,7 +508,7 @@ where
     type ComponentsBuilder = ComponentsBuilder<
         N,
         EthereumPoolBuilder,
-        BasicPayloadServiceBuilder<EthereumPayloadBuilder>,
+        BasicPayloadServiceBuilder<FluentPayloadBuilderBuilder>,
         EthereumNetworkBuilder,
         FluentExecutorBuilder,
         FluentConsensusBuilder,
@@ -509,11 +544,13 @@ impl<N: FullNodeComponents<Types = Self>> DebugNode<N> for FluentNode {
 pub struct FluentBlockExecutor<'a, Evm> {
     /// Inner Ethereum execution strategy.
     inner: EthBlockExecutor<'a, Evm, &'a Arc<ChainSpec>, &'a RethReceiptBuilder>,
+    dev_state_overrides: Option<&'a DevStateOverrides>,
+    parent_hash: B256,
 }
 
 impl<'a, E> BlockExecutor for FluentBlockExecutor<'a, E>
 where
-    E: Evm<Tx = TxEnv>,
+    E: Evm<Tx = TxEnv, DB: DatabaseCommit>,
     EthBlockExecutor<'a, E, &'a Arc<ChainSpec>, &'a RethReceiptBuilder>: BlockExecutor<
         Transaction = TransactionSigned,
         Receipt = Receipt,
@@ -532,7 +569,13 @@ where
         // Instead, we can just re-create the store to make sure all data is pruned.
         fluentbase_runtime::runtime::SystemRuntime::reset_cached_runtimes();
         // Invoke parent method
-        self.inner.apply_pre_execution_changes()
+        self.inner.apply_pre_execution_changes()?;
+        if let Some(overrides) = self.dev_state_overrides {
+            overrides
+                .apply(self.parent_hash, self.inner.evm_mut().db_mut())
+                .map_err(BlockExecutionError::other)?;
+        }
+        Ok(())
     }
 
     fn execute_transaction_without_commit(
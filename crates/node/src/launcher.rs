@@ -1,7 +1,17 @@
-//! This is temporary single-node consensus that is used for block production for Fluent,
-//! it will be replaced with DPoS consensus later.
+//! Block production and block following for Fluent nodes.
+//!
+//! Single-sequencer networks have one [`BlockProducer`] that proposes every block while every
+//! other node follows it over RPC. Networks with a validator set run a producer on every
+//! validator, each sealing only the blocks its [`ConsensusEngine`] assigns to it, and import the
+//! blocks of the other validators from their RPC endpoints. This will be replaced with DPoS
+//! consensus later. `--dev` nodes run a producer that mines on demand, see [`crate::dev`].
+use crate::{
+    consensus::{ConsensusEngine, ValidatorKey},
+    dev::{DevClock, DevCommand, DevStateOverrides},
+};
+use alloy_consensus::Header;
 use alloy_network::AnyNetwork;
-use alloy_primitives::B256;
+use alloy_primitives::{TxHash, B256, U256};
 use alloy_rpc_types_engine::ForkchoiceState;
 use eyre::OptionExt;
 use reth_consensus_debug_client::{BlockProvider, RpcBlockProvider};
@@ -11,35 +21,73 @@ use reth_node_builder::{rpc::RethRpcAddOns, DebugNode, NodeHandle};
 use reth_node_types::PayloadAttrTy;
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{
-    BuiltPayload, ExecutionPayload, PayloadAttributesBuilder, PayloadKind, PayloadTypes,
+    BuiltPayload, ExecutionPayload, PayloadAttributes, PayloadAttributesBuilder, PayloadKind,
+    PayloadTypes,
 };
-use reth_primitives_traits::{HeaderTy, NodePrimitives, SealedBlock, SealedHeaderFor};
+use reth_primitives_traits::{Block, HeaderTy, NodePrimitives, SealedBlock, SealedHeaderFor};
 use reth_storage_api::BlockReader;
 use reth_tasks::shutdown::GracefulShutdown;
+use reth_transaction_pool::TransactionPool;
 use std::{sync::Arc, time::Duration};
 use tokio::{sync::mpsc, time::Interval};
 use tracing::{error, info};
 
+/// The local validator of a network with a validator set.
+#[derive(Debug, Clone)]
+pub struct BlockAuthority {
+    pub engine: Arc<dyn ConsensusEngine>,
+    pub key: ValidatorKey,
+}
+
+impl BlockAuthority {
+    fn is_proposer(&self, parent: &Header, timestamp: u64) -> bool {
+        self.engine
+            .is_proposer(self.key.address(), parent, timestamp)
+    }
+
+    fn seal<B: Block<Header = Header>>(&self, block: SealedBlock<B>) -> SealedBlock<B> {
+        let (header, body) = block.into_block().split();
+        SealedBlock::seal_slow(B::new(self.engine.seal(&self.key, header), body))
+    }
+}
+
+/// Starts producing blocks.
+///
+/// Without an `authority` a block is proposed every `block_time`. With one, the producer polls
+/// at the engine's interval and only proposes in the slots the engine assigns to the validator.
+/// With `skip_empty_blocks` no block is proposed while the pool has no pending transactions.
 pub async fn launch_consensus_validator<N, AddOns: RethRpcAddOns<N>, B>(
     handle: &NodeHandle<N, AddOns>,
     block_time: Duration,
     payload_attributes_builder: B,
+    authority: Option<BlockAuthority>,
+    skip_empty_blocks: bool,
 ) -> eyre::Result<()>
 where
     N: FullNodeComponents<Types: DebugNode<N>>,
+    N::Types: reth_node_types::NodeTypes<Primitives: NodePrimitives<BlockHeader = Header>>,
     B: PayloadAttributesBuilder<PayloadAttrTy<N::Types>, reth_node_types::HeaderTy<N::Types>>,
 {
+    let block_time = match &authority {
+        Some(authority) => authority.engine.poll_interval(),
+        None => block_time,
+    };
     let block_time = tokio::time::interval_at(tokio::time::Instant::now() + block_time, block_time);
 
     let blockchain_db = handle.node.provider.clone();
     let beacon_engine_handle = handle.node.add_ons_handle.beacon_engine_handle.clone();
     let payload_builder_handle = handle.node.payload_builder_handle.clone();
 
+    if let Some(authority) = &authority {
+        info!(target: "reth::cli", validator = %authority.key.address(), "Producing blocks as a validator");
+    }
     let block_producer = BlockProducer::new(
         blockchain_db,
         payload_attributes_builder,
         payload_builder_handle,
         beacon_engine_handle,
+        authority,
+        skip_empty_blocks.then(|| handle.node.pool.clone()),
     );
 
     handle
@@ -54,27 +102,83 @@ where
     Ok(())
 }
 
+/// Starts the block producer of a `--dev` node.
+///
+/// A block is mined as soon as a transaction is pending, on every [`DevCommand`], and every
+/// `block_time` if one is set.
+pub async fn launch_dev_miner<N, AddOns: RethRpcAddOns<N>, B>(
+    handle: &NodeHandle<N, AddOns>,
+    block_time: Option<Duration>,
+    payload_attributes_builder: B,
+    clock: Arc<DevClock>,
+    overrides: Arc<DevStateOverrides>,
+    commands: mpsc::UnboundedReceiver<DevCommand>,
+) -> eyre::Result<()>
+where
+    N: FullNodeComponents<Types: DebugNode<N>>,
+    N::Types: reth_node_types::NodeTypes<Primitives: NodePrimitives<BlockHeader = Header>>,
+    B: PayloadAttributesBuilder<PayloadAttrTy<N::Types>, reth_node_types::HeaderTy<N::Types>>,
+{
+    let block_time = block_time.map(|block_time| {
+        tokio::time::interval_at(tokio::time::Instant::now() + block_time, block_time)
+    });
+    let pending = handle.node.pool.pending_transactions_listener();
+    let block_producer = BlockProducer::new(
+        handle.node.provider.clone(),
+        payload_attributes_builder,
+        handle.node.payload_builder_handle.clone(),
+        handle.node.add_ons_handle.beacon_engine_handle.clone(),
+        None,
+        Some(handle.node.pool.clone()),
+    );
+
+    handle
+        .node
+        .task_executor
+        .spawn_critical_with_graceful_shutdown_signal("dev miner", move |shutdown| async move {
+            block_producer
+                .run_dev(pending, commands, block_time, clock, overrides, shutdown)
+                .await;
+        });
+    Ok(())
+}
+
+/// Heads and clock offsets saved by `evm_snapshot`, indexed by snapshot id.
+type DevSnapshots<T> = Vec<(
+    SealedHeaderFor<<<T as PayloadTypes>::BuiltPayload as BuiltPayload>::Primitives>,
+    i64,
+)>;
+
 #[derive(Debug)]
-pub struct BlockProducer<T: PayloadTypes, B> {
+pub struct BlockProducer<T: PayloadTypes, B, P, Pool> {
     to_engine: ConsensusEngineHandle<T>,
     payload_attributes_builder: B,
     payload_builder: PayloadBuilderHandle<T>,
+    provider: P,
     last_header: SealedHeaderFor<<T::BuiltPayload as BuiltPayload>::Primitives>,
     last_block_hash: B256,
+    authority: Option<BlockAuthority>,
+    /// Pool checked for pending transactions before proposing, `None` to propose empty blocks.
+    empty_blocks_pool: Option<Pool>,
 }
 
-impl<T: PayloadTypes, B> BlockProducer<T, B>
+impl<T, B, P, Pool> BlockProducer<T, B, P, Pool>
 where
+    T: PayloadTypes<BuiltPayload: BuiltPayload<Primitives: NodePrimitives<BlockHeader = Header>>>,
     B: PayloadAttributesBuilder<
         T::PayloadAttributes,
         HeaderTy<<T::BuiltPayload as BuiltPayload>::Primitives>,
     >,
+    P: BlockReader<Header = Header>,
+    Pool: TransactionPool,
 {
     fn new(
-        provider: impl BlockReader<Header = HeaderTy<<T::BuiltPayload as BuiltPayload>::Primitives>>,
+        provider: P,
         payload_attributes_builder: B,
         payload_builder: PayloadBuilderHandle<T>,
         to_engine: ConsensusEngineHandle<T>,
+        authority: Option<BlockAuthority>,
+        empty_blocks_pool: Option<Pool>,
     ) -> Self {
         let last_header = provider
             .sealed_header(provider.best_block_number().unwrap())
@@ -85,9 +189,25 @@ where
             to_engine,
             payload_attributes_builder,
             payload_builder,
+            provider,
             last_header,
             last_block_hash,
+            authority,
+            empty_blocks_pool,
+        }
+    }
+
+    /// Moves onto blocks of other validators that the node imported since the last proposal.
+    fn follow_canonical_head(&mut self) -> eyre::Result<()> {
+        let best_block_number = self.provider.best_block_number()?;
+        if best_block_number <= self.last_header.number {
+            return Ok(());
+        }
+        if let Some(header) = self.provider.sealed_header(best_block_number)? {
+            self.last_block_hash = header.hash();
+            self.last_header = header;
         }
+        Ok(())
     }
 
     pub async fn run(mut self, mut block_time: Interval, shutdown: GracefulShutdown) {
@@ -121,12 +241,129 @@ where
         }
     }
 
+    /// Runs the producer of a `--dev` node, see [`launch_dev_miner`].
+    pub async fn run_dev(
+        mut self,
+        mut pending: mpsc::Receiver<TxHash>,
+        mut commands: mpsc::UnboundedReceiver<DevCommand>,
+        mut block_time: Option<Interval>,
+        clock: Arc<DevClock>,
+        overrides: Arc<DevStateOverrides>,
+        shutdown: GracefulShutdown,
+    ) {
+        let mut snapshots = Vec::new();
+        let mut fcu_interval = tokio::time::interval(Duration::from_secs(1));
+        tokio::pin!(shutdown);
+
+        loop {
+            tokio::select! {
+                biased;
+
+                guard = &mut shutdown => {
+                    info!(target: "engine::local", "Shutting down dev miner");
+                    drop(guard);
+                    break;
+                }
+                Some(command) = commands.recv() => {
+                    self.on_dev_command(command, &mut snapshots, &clock, &overrides).await;
+                }
+                Some(_) = pending.recv() => {
+                    // Transactions that arrived together go into one block.
+                    while pending.try_recv().is_ok() {}
+                    if let Err(e) = self.advance_forkchoice_state().await {
+                        error!(target: "engine::local", "Error advancing the chain: {:?}", e);
+                    }
+                }
+                _ = async { block_time.as_mut().expect("checked by the guard").tick().await },
+                    if block_time.is_some() => {
+                    if let Err(e) = self.mine_next_block().await {
+                        error!(target: "engine::local", "Error advancing the chain: {:?}", e);
+                    }
+                }
+                _ = fcu_interval.tick() => {
+                    if let Err(e) = self.update_forkchoice_state().await {
+                        error!(target: "engine::local", "Error updating fork choice: {:?}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    async fn on_dev_command(
+        &mut self,
+        command: DevCommand,
+        snapshots: &mut DevSnapshots<T>,
+        clock: &DevClock,
+        overrides: &DevStateOverrides,
+    ) {
+        match command {
+            DevCommand::Mine { reply } => {
+                let _ = reply.send(self.mine_next_block().await);
+            }
+            DevCommand::Snapshot { reply } => {
+                snapshots.push((self.last_header.clone(), clock.offset()));
+                let _ = reply.send(U256::from(snapshots.len() - 1));
+            }
+            DevCommand::Revert { id, reply } => {
+                let Some(index) = usize::try_from(id)
+                    .ok()
+                    .filter(|index| *index < snapshots.len())
+                else {
+                    let _ = reply.send(Ok(false));
+                    return;
+                };
+                let (header, offset) = snapshots.drain(index..).next().expect("index is in bounds");
+                clock.set_offset(offset);
+                self.last_block_hash = header.hash();
+                self.last_header = header;
+                // Requires the engine to unwind the canonical chain to an ancestor.
+                let _ = reply.send(self.update_forkchoice_state().await.map(|_| true));
+            }
+            DevCommand::Override {
+                address,
+                account,
+                reply,
+            } => {
+                let parent = self.last_block_hash;
+                overrides.insert(parent, address, account);
+                let result = self.mine_next_block().await;
+                overrides.remove(parent);
+                let _ = reply.send(result);
+            }
+        }
+    }
+
     async fn advance_forkchoice_state(&mut self) -> eyre::Result<()> {
+        if self.authority.is_some() {
+            self.follow_canonical_head()?;
+        }
+        if let Some(pool) = &self.empty_blocks_pool {
+            if pool.pool_size().pending == 0 {
+                return Ok(());
+            }
+        }
+        let attributes = self.payload_attributes_builder.build(&self.last_header);
+        if let Some(authority) = &self.authority {
+            // The slot is derived from the timestamp the block will carry.
+            if !authority.is_proposer(self.last_header.header(), attributes.timestamp()) {
+                return Ok(());
+            }
+        }
+        self.mine(attributes).await
+    }
+
+    /// Mines a block on top of the head regardless of the pool and the validator set.
+    async fn mine_next_block(&mut self) -> eyre::Result<()> {
+        let attributes = self.payload_attributes_builder.build(&self.last_header);
+        self.mine(attributes).await
+    }
+
+    async fn mine(&mut self, attributes: T::PayloadAttributes) -> eyre::Result<()> {
         let res = self
             .to_engine
             .fork_choice_updated(
                 ForkchoiceState::same_hash(self.last_block_hash),
-                Some(self.payload_attributes_builder.build(&self.last_header)),
+                Some(attributes),
             )
             .await?;
 
@@ -144,8 +381,12 @@ where
             eyre::bail!("No payload")
         };
 
-        let header = payload.block().sealed_header().clone();
-        let payload = T::block_to_payload(payload.block().clone());
+        let mut block = payload.block().clone();
+        if let Some(authority) = &self.authority {
+            block = authority.seal(block);
+        }
+        let header = block.sealed_header().clone();
+        let payload = T::block_to_payload(block);
         let res = self.to_engine.new_payload(payload).await?;
 
         if !res.is_valid() {
@@ -159,6 +400,9 @@ where
     }
 
     async fn update_forkchoice_state(&mut self) -> eyre::Result<()> {
+        if self.authority.is_some() {
+            self.follow_canonical_head()?;
+        }
         let state = ForkchoiceState::same_hash(self.last_block_hash);
         let res = self.to_engine.fork_choice_updated(state, None).await?;
         if !res.is_valid() {
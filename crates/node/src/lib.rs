@@ -1,8 +1,10 @@
 pub mod chainspec;
 pub mod consensus;
+pub mod dev;
 pub mod evm;
 pub mod launcher;
 pub mod payload;
+pub mod rpc;
 pub mod trusted_peers;
 pub mod types;
 pub mod utils;
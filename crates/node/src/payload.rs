@@ -1,3 +1,8 @@
+mod best;
+pub use best::*;
+mod builder;
+pub use builder::*;
+
 use alloy_consensus::{BlockHeader, Header};
 use alloy_primitives::B256;
 use alloy_rpc_types_engine::PayloadAttributes as EthPayloadAttributes;
@@ -11,18 +16,26 @@ pub struct FluentPayloadAttributesBuilder;
 
 impl PayloadAttributesBuilder<EthPayloadAttributes, Header> for FluentPayloadAttributesBuilder {
     fn build(&self, parent: &SealedHeader<Header>) -> EthPayloadAttributes {
-        let mut timestamp = std::time::SystemTime::now()
+        let timestamp = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_secs();
-        timestamp = std::cmp::max(parent.timestamp().saturating_add(1), timestamp);
-        EthPayloadAttributes {
-            timestamp,
-            prev_randao: B256::random(),
-            suggested_fee_recipient: PRECOMPILE_FEE_MANAGER,
-            withdrawals: Default::default(),
-            parent_beacon_block_root: Some(B256::ZERO),
-            slot_number: None,
-        }
+        fluent_payload_attributes(parent, timestamp)
+    }
+}
+
+/// Attributes of a local block on top of `parent`, at `timestamp` or right after the parent if
+/// that's later.
+pub fn fluent_payload_attributes(
+    parent: &SealedHeader<Header>,
+    timestamp: u64,
+) -> EthPayloadAttributes {
+    EthPayloadAttributes {
+        timestamp: std::cmp::max(parent.timestamp().saturating_add(1), timestamp),
+        prev_randao: B256::random(),
+        suggested_fee_recipient: PRECOMPILE_FEE_MANAGER,
+        withdrawals: Default::default(),
+        parent_beacon_block_root: Some(B256::ZERO),
+        slot_number: None,
     }
 }
@@ -0,0 +1,302 @@
+use alloy_primitives::Address;
+use fluentbase_types::{
+    resolve_precompiled_runtime_from_input, PRECOMPILE_WASM_RUNTIME,
+    WASM_COMPILATION_OVERHEAD_FUEL_PER_BYTE,
+};
+use reth_transaction_pool::{
+    error::{InvalidPoolTransactionError, PoolTransactionError},
+    BestTransactions, PoolTransaction, ValidPoolTransaction,
+};
+use std::{
+    any::Any,
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    fmt,
+    str::FromStr,
+    sync::Arc,
+    time::Instant,
+};
+
+/// Order in which the payload builder packs pending transactions.
+///
+/// Both orders keep the transactions of a sender in nonce order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PayloadOrdering {
+    /// Highest tip per gas above the block's base fee first, which is the pool's own order.
+    #[default]
+    EffectiveTip,
+    /// Earliest submitted first, regardless of the fees paid.
+    Fifo,
+}
+
+impl FromStr for PayloadOrdering {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "effective-tip" => Ok(Self::EffectiveTip),
+            "fifo" => Ok(Self::Fifo),
+            _ => Err(format!(
+                "unknown ordering {s:?}, expected effective-tip or fifo"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for PayloadOrdering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::EffectiveTip => "effective-tip",
+            Self::Fifo => "fifo",
+        })
+    }
+}
+
+/// Fuel the Wasm runtime charges to compile the binary deployed by a transaction, zero for
+/// anything that isn't a Wasm deployment.
+///
+/// Uses the same routing as the executor, so a deployment is counted exactly when it ends up in
+/// `PRECOMPILE_WASM_RUNTIME`. Deployments made from inside contracts aren't visible here.
+pub fn wasm_compilation_fuel(is_create: bool, input: &[u8]) -> u64 {
+    if !is_create || resolve_precompiled_runtime_from_input(input) != PRECOMPILE_WASM_RUNTIME {
+        return 0;
+    }
+    (input.len() as u64).saturating_mul(WASM_COMPILATION_OVERHEAD_FUEL_PER_BYTE as u64)
+}
+
+/// Per-block budget of [`wasm_compilation_fuel`], unlimited when `None`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WasmCompilationBudget(Option<u64>);
+
+impl WasmCompilationBudget {
+    pub fn new(limit: Option<u64>) -> Self {
+        Self(limit)
+    }
+
+    /// Reserves `fuel` from the budget, or returns `false` and leaves it untouched if it doesn't
+    /// fit.
+    pub fn try_consume(&mut self, fuel: u64) -> bool {
+        let Some(left) = &mut self.0 else {
+            return true;
+        };
+        match left.checked_sub(fuel) {
+            Some(rest) => {
+                *left = rest;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// A Wasm deployment that doesn't fit the block's remaining compilation budget.
+#[derive(Debug, thiserror::Error)]
+#[error("wasm compilation fuel {fuel} exceeds the remaining block budget")]
+pub struct WasmCompilationBudgetExceeded {
+    pub fuel: u64,
+}
+
+impl PoolTransactionError for WasmCompilationBudgetExceeded {
+    fn is_bad_transaction(&self) -> bool {
+        // It may fit the next block.
+        false
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Interleaves per-sender nonce sequences by arrival time.
+///
+/// `items` must list each sender's transactions in nonce order, as the pool yields them. The
+/// earliest-submitted transaction whose predecessors are already placed always goes next.
+pub fn order_by_arrival<T>(
+    items: impl IntoIterator<Item = T>,
+    sender: impl Fn(&T) -> Address,
+    arrival: impl Fn(&T) -> Instant,
+) -> VecDeque<T> {
+    let mut senders: Vec<VecDeque<T>> = Vec::new();
+    let mut sender_index = HashMap::new();
+    for item in items {
+        let index = *sender_index.entry(sender(&item)).or_insert_with(|| {
+            senders.push(VecDeque::new());
+            senders.len() - 1
+        });
+        senders[index].push_back(item);
+    }
+    let mut heads: BinaryHeap<_> = senders
+        .iter()
+        .enumerate()
+        .filter_map(|(index, queue)| Some(Reverse((arrival(queue.front()?), index))))
+        .collect();
+    let mut ordered = VecDeque::new();
+    while let Some(Reverse((_, index))) = heads.pop() {
+        let item = senders[index]
+            .pop_front()
+            .expect("heads only track non-empty queues");
+        if let Some(next) = senders[index].front() {
+            heads.push(Reverse((arrival(next), index)));
+        }
+        ordered.push_back(item);
+    }
+    ordered
+}
+
+type BestTransactionsOf<T> = Box<dyn BestTransactions<Item = Arc<ValidPoolTransaction<T>>>>;
+
+/// Pool iterator applying a [`PayloadOrdering`] and a [`WasmCompilationBudget`].
+pub struct FluentBestTransactions<T: PoolTransaction> {
+    inner: BestTransactionsOf<T>,
+    /// Transactions reordered up front, `None` when the pool's order is used as is.
+    reordered: Option<VecDeque<Arc<ValidPoolTransaction<T>>>>,
+    invalid_senders: HashSet<Address>,
+    skip_blobs: bool,
+    wasm_compilation_budget: WasmCompilationBudget,
+}
+
+impl<T: PoolTransaction> FluentBestTransactions<T> {
+    pub fn new(
+        mut inner: BestTransactionsOf<T>,
+        ordering: PayloadOrdering,
+        wasm_compilation_budget: WasmCompilationBudget,
+    ) -> Self {
+        let reordered = match ordering {
+            PayloadOrdering::EffectiveTip => None,
+            PayloadOrdering::Fifo => {
+                // The snapshot is taken once, transactions arriving later wait for the next block.
+                inner.no_updates();
+                let pending: Vec<_> = inner.by_ref().collect();
+                Some(order_by_arrival(
+                    pending,
+                    |tx| tx.sender(),
+                    |tx| tx.timestamp,
+                ))
+            }
+        };
+        Self {
+            inner,
+            reordered,
+            invalid_senders: HashSet::new(),
+            skip_blobs: false,
+            wasm_compilation_budget,
+        }
+    }
+}
+
+impl<T: PoolTransaction> fmt::Debug for FluentBestTransactions<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FluentBestTransactions")
+            .field("reordered", &self.reordered.as_ref().map(VecDeque::len))
+            .field("invalid_senders", &self.invalid_senders)
+            .field("skip_blobs", &self.skip_blobs)
+            .field("wasm_compilation_budget", &self.wasm_compilation_budget)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: PoolTransaction> Iterator for FluentBestTransactions<T> {
+    type Item = Arc<ValidPoolTransaction<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let tx = match &mut self.reordered {
+                Some(reordered) => reordered.pop_front()?,
+                None => self.inner.next()?,
+            };
+            if self.invalid_senders.contains(&tx.sender())
+                || (self.skip_blobs && tx.transaction.is_eip4844())
+            {
+                continue;
+            }
+            let fuel = wasm_compilation_fuel(tx.transaction.is_create(), tx.transaction.input());
+            if !self.wasm_compilation_budget.try_consume(fuel) {
+                let error =
+                    InvalidPoolTransactionError::Other(Box::new(WasmCompilationBudgetExceeded {
+                        fuel,
+                    }));
+                self.mark_invalid(&tx, &error);
+                continue;
+            }
+            return Some(tx);
+        }
+    }
+}
+
+impl<T: PoolTransaction> BestTransactions for FluentBestTransactions<T> {
+    fn mark_invalid(&mut self, transaction: &Self::Item, kind: &InvalidPoolTransactionError) {
+        // Later nonces of the sender can't be included anymore.
+        self.invalid_senders.insert(transaction.sender());
+        self.inner.mark_invalid(transaction, kind);
+    }
+
+    fn no_updates(&mut self) {
+        self.inner.no_updates();
+    }
+
+    fn set_skip_blobs(&mut self, skip_blobs: bool) {
+        self.skip_blobs = skip_blobs;
+        self.inner.set_skip_blobs(skip_blobs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluentbase_types::WASM_MAGIC_BYTES;
+    use std::time::Duration;
+
+    #[test]
+    fn only_wasm_deployments_cost_compilation_fuel() {
+        let mut wasm = WASM_MAGIC_BYTES.to_vec();
+        wasm.extend_from_slice(&[0u8; 96]);
+        assert_eq!(
+            wasm_compilation_fuel(true, &wasm),
+            100 * WASM_COMPILATION_OVERHEAD_FUEL_PER_BYTE as u64
+        );
+        assert_eq!(wasm_compilation_fuel(false, &wasm), 0);
+        assert_eq!(
+            wasm_compilation_fuel(true, &[0x60, 0x80, 0x60, 0x40, 0x52]),
+            0
+        );
+        assert_eq!(wasm_compilation_fuel(true, &WASM_MAGIC_BYTES), 0);
+    }
+
+    #[test]
+    fn budget_rejects_what_does_not_fit() {
+        let mut budget = WasmCompilationBudget::new(Some(100));
+        assert!(budget.try_consume(60));
+        assert!(!budget.try_consume(50));
+        assert!(budget.try_consume(40));
+        assert!(!budget.try_consume(1));
+        assert!(budget.try_consume(0));
+
+        let mut unlimited = WasmCompilationBudget::new(None);
+        assert!(unlimited.try_consume(u64::MAX));
+        assert!(unlimited.try_consume(u64::MAX));
+    }
+
+    #[test]
+    fn arrival_order_keeps_nonce_order() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let (alice, bob) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        // (sender, nonce, arrival), each sender in nonce order. Alice's second transaction
+        // arrived first, it still can't go before her first one.
+        let pending = vec![
+            (alice, 0, at(5)),
+            (alice, 1, at(1)),
+            (bob, 0, at(2)),
+            (bob, 1, at(3)),
+            (alice, 2, at(4)),
+        ];
+        let ordered: Vec<_> = order_by_arrival(pending, |tx| tx.0, |tx| tx.2)
+            .into_iter()
+            .map(|tx| (tx.0, tx.1))
+            .collect();
+        assert_eq!(
+            ordered,
+            vec![(bob, 0), (bob, 1), (alice, 0), (alice, 1), (alice, 2)]
+        );
+    }
+}
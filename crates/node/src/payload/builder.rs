@@ -0,0 +1,153 @@
+use crate::payload::{FluentBestTransactions, PayloadOrdering, WasmCompilationBudget};
+use alloy_rpc_types_engine::PayloadAttributes as EthPayloadAttributes;
+use reth_basic_payload_builder::{BuildArguments, BuildOutcome, PayloadBuilder, PayloadConfig};
+use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
+use reth_ethereum_engine_primitives::EthBuiltPayload;
+use reth_ethereum_payload_builder::{default_ethereum_payload, EthereumBuilderConfig};
+use reth_ethereum_primitives::EthPrimitives;
+use reth_evm::{ConfigureEvm, NextBlockEnvAttributes};
+use reth_node_api::{FullNodeTypes, NodeTypes, PrimitivesTy, TxTy};
+use reth_node_builder::{components::PayloadBuilderBuilder, BuilderContext, PayloadBuilderConfig};
+use reth_payload_primitives::{PayloadBuilderError, PayloadTypes};
+use reth_storage_api::StateProviderFactory;
+use reth_transaction_pool::{BestTransactionsAttributes, PoolTransaction, TransactionPool};
+
+/// Fluent-specific knobs of the payload builder, see `FluentNodeArgs`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FluentPayloadBuilderConfig {
+    pub ordering: PayloadOrdering,
+    /// Most compilation fuel the Wasm deployments of one block may charge, unlimited when `None`.
+    pub max_wasm_compilation_fuel: Option<u64>,
+}
+
+/// Ethereum payload builder that packs transactions per [`FluentPayloadBuilderConfig`].
+#[derive(Clone, Debug)]
+pub struct FluentPayloadBuilder<Pool, Client, EvmConfig> {
+    client: Client,
+    pool: Pool,
+    evm_config: EvmConfig,
+    builder_config: EthereumBuilderConfig,
+    config: FluentPayloadBuilderConfig,
+}
+
+impl<Pool, Client, EvmConfig> FluentPayloadBuilder<Pool, Client, EvmConfig>
+where
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<EthPrimitives>>>,
+{
+    pub const fn new(
+        client: Client,
+        pool: Pool,
+        evm_config: EvmConfig,
+        builder_config: EthereumBuilderConfig,
+        config: FluentPayloadBuilderConfig,
+    ) -> Self {
+        Self {
+            client,
+            pool,
+            evm_config,
+            builder_config,
+            config,
+        }
+    }
+
+    fn best_transactions(
+        &self,
+        attributes: BestTransactionsAttributes,
+    ) -> FluentBestTransactions<Pool::Transaction> {
+        FluentBestTransactions::new(
+            self.pool.best_transactions_with_attributes(attributes),
+            self.config.ordering,
+            WasmCompilationBudget::new(self.config.max_wasm_compilation_fuel),
+        )
+    }
+}
+
+impl<Pool, Client, EvmConfig> PayloadBuilder for FluentPayloadBuilder<Pool, Client, EvmConfig>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
+    Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks> + Clone,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<EthPrimitives>>>,
+{
+    type Attributes = EthPayloadAttributes;
+    type BuiltPayload = EthBuiltPayload;
+
+    fn try_build(
+        &self,
+        args: BuildArguments<EthPayloadAttributes, EthBuiltPayload>,
+    ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
+        default_ethereum_payload(
+            self.evm_config.clone(),
+            self.client.clone(),
+            self.pool.clone(),
+            self.builder_config.clone(),
+            args,
+            |attributes| Box::new(self.best_transactions(attributes)),
+        )
+    }
+
+    fn build_empty_payload(
+        &self,
+        config: PayloadConfig<Self::Attributes>,
+    ) -> Result<EthBuiltPayload, PayloadBuilderError> {
+        let args = BuildArguments::new(Default::default(), config, Default::default(), None);
+        default_ethereum_payload(
+            self.evm_config.clone(),
+            self.client.clone(),
+            self.pool.clone(),
+            self.builder_config.clone(),
+            args,
+            |attributes| Box::new(self.best_transactions(attributes)),
+        )?
+        .into_payload()
+        .ok_or_else(|| PayloadBuilderError::MissingPayload)
+    }
+}
+
+/// Builds [`FluentPayloadBuilder`], configured like reth's Ethereum payload builder.
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct FluentPayloadBuilderBuilder {
+    config: FluentPayloadBuilderConfig,
+}
+
+impl FluentPayloadBuilderBuilder {
+    pub fn new(config: FluentPayloadBuilderConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl<Types, Node, Pool, Evm> PayloadBuilderBuilder<Node, Pool, Evm> for FluentPayloadBuilderBuilder
+where
+    Types: NodeTypes<ChainSpec: EthereumHardforks, Primitives = EthPrimitives>,
+    Node: FullNodeTypes<Types = Types>,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>>
+        + Unpin
+        + 'static,
+    Evm: ConfigureEvm<Primitives = PrimitivesTy<Types>, NextBlockEnvCtx = NextBlockEnvAttributes>
+        + 'static,
+    Types::Payload:
+        PayloadTypes<BuiltPayload = EthBuiltPayload, PayloadAttributes = EthPayloadAttributes>,
+{
+    type PayloadBuilder = FluentPayloadBuilder<Pool, Node::Provider, Evm>;
+
+    async fn build_payload_builder(
+        self,
+        ctx: &BuilderContext<Node>,
+        pool: Pool,
+        evm_config: Evm,
+    ) -> eyre::Result<Self::PayloadBuilder> {
+        let conf = ctx.payload_builder_config();
+        let chain = ctx.chain_spec().chain();
+        let gas_limit = conf.gas_limit_for(chain);
+        Ok(FluentPayloadBuilder::new(
+            ctx.provider().clone(),
+            pool,
+            evm_config,
+            EthereumBuilderConfig::new()
+                .with_gas_limit(gas_limit)
+                .with_max_blobs_per_block(conf.max_blobs_per_block())
+                .with_extra_data(conf.extra_data_bytes()),
+            self.config,
+        ))
+    }
+}
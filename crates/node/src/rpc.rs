@@ -0,0 +1,7 @@
+//! Fluent-specific RPC namespaces and overrides.
+mod debug;
+pub use debug::*;
+mod dev;
+pub use dev::*;
+mod fluent;
+pub use fluent::*;
@@ -0,0 +1,127 @@
+//! Fluent overrides of the `debug` RPC namespace.
+//!
+//! `debug_traceTransaction` and `debug_traceCall` accept `{"tracer": "fluentTracer"}` and return
+//! the rWasm-aware call tree produced by [`FluentTracer`]. Every other tracer is served by reth's
+//! [`DebugApi`] unchanged.
+use alloy_primitives::B256;
+use alloy_rpc_types_eth::{state::EvmOverrides, BlockId, TransactionRequest};
+use alloy_rpc_types_trace::geth::{
+    GethDebugTracerType, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
+};
+use fluentbase_revm::tracer::{FluentCallFrame, FluentTracer, FLUENT_TRACER_NAME};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::ErrorObjectOwned};
+use reth_rpc::DebugApi;
+use reth_rpc_api::DebugApiServer;
+use reth_rpc_eth_api::{
+    helpers::{EthCall, Trace},
+    EthApiTypes, RpcNodeCore,
+};
+use reth_rpc_eth_types::EthApiError;
+
+/// Methods of the `debug` namespace that Fluent serves itself.
+#[rpc(server, namespace = "debug")]
+pub trait FluentDebugApi {
+    /// Traces an executed transaction, see [`FluentDebugApiServer`].
+    #[method(name = "traceTransaction")]
+    async fn trace_transaction(
+        &self,
+        tx_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<GethTrace>;
+
+    /// Traces a call on top of the given block, see [`FluentDebugApiServer`].
+    #[method(name = "traceCall")]
+    async fn trace_call(
+        &self,
+        request: TransactionRequest,
+        block_id: Option<BlockId>,
+        opts: Option<GethDebugTracingCallOptions>,
+    ) -> RpcResult<GethTrace>;
+}
+
+/// `debug` namespace with `fluentTracer` support.
+#[derive(Debug)]
+pub struct FluentDebug<Eth: RpcNodeCore> {
+    eth_api: Eth,
+    inner: DebugApi<Eth>,
+}
+
+impl<Eth: RpcNodeCore> FluentDebug<Eth> {
+    pub fn new(eth_api: Eth, inner: DebugApi<Eth>) -> Self {
+        Self { eth_api, inner }
+    }
+}
+
+fn is_fluent_tracer(opts: &GethDebugTracingOptions) -> bool {
+    matches!(&opts.tracer, Some(GethDebugTracerType::JsTracer(name)) if name == FLUENT_TRACER_NAME)
+}
+
+fn into_geth_trace(frame: Option<FluentCallFrame>) -> GethTrace {
+    GethTrace::JS(serde_json::to_value(frame).unwrap_or_default())
+}
+
+#[jsonrpsee::core::async_trait]
+impl<Eth> FluentDebugApiServer for FluentDebug<Eth>
+where
+    Eth: EthApiTypes + RpcNodeCore + Trace + EthCall + Clone + Send + Sync + 'static,
+    DebugApi<Eth>: DebugApiServer<TransactionRequest>,
+{
+    async fn trace_transaction(
+        &self,
+        tx_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<GethTrace> {
+        let opts = opts.unwrap_or_default();
+        if !is_fluent_tracer(&opts) {
+            return DebugApiServer::debug_trace_transaction(&self.inner, tx_hash, opts).await;
+        }
+        let frame = self
+            .eth_api
+            .spawn_trace_transaction_in_block_with_inspector(
+                tx_hash,
+                FluentTracer::default(),
+                |_tx_info, tracer, _result, _db| Ok(tracer.into_call_frame()),
+            )
+            .await
+            .map_err(Into::<ErrorObjectOwned>::into)?;
+        match frame {
+            Some(frame) => Ok(into_geth_trace(frame)),
+            None => Err(EthApiError::TransactionNotFound.into()),
+        }
+    }
+
+    async fn trace_call(
+        &self,
+        request: TransactionRequest,
+        block_id: Option<BlockId>,
+        opts: Option<GethDebugTracingCallOptions>,
+    ) -> RpcResult<GethTrace> {
+        let opts = opts.unwrap_or_default();
+        if !is_fluent_tracer(&opts.tracing_options) {
+            return DebugApiServer::debug_trace_call(&self.inner, request, block_id, Some(opts))
+                .await;
+        }
+        let GethDebugTracingCallOptions {
+            state_overrides,
+            block_overrides,
+            ..
+        } = opts;
+        let overrides = EvmOverrides::new(state_overrides, block_overrides.map(Box::new));
+        let eth_api = self.eth_api.clone();
+        let frame = self
+            .eth_api
+            .spawn_with_call_at(
+                request,
+                block_id.unwrap_or_default(),
+                overrides,
+                move |db, evm_env, tx_env| {
+                    let mut tracer = FluentTracer::default();
+                    eth_api.inspect(db, evm_env, tx_env, &mut tracer)?;
+                    Ok(tracer.into_call_frame())
+                },
+            )
+            .await
+            .map_err(Into::<ErrorObjectOwned>::into)?;
+        Ok(into_geth_trace(frame))
+    }
+}
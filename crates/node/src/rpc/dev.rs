@@ -0,0 +1,114 @@
+//! The `evm` and `anvil` RPC namespaces of `--dev` nodes.
+//!
+//! Implements the subset of the hardhat and anvil methods that test suites use to control a
+//! local chain, see [`crate::dev`]. `anvil_setCode` and `anvil_setStorageAt` take effect in a
+//! block they mine, and accept EVM, Wasm and rWasm code alike.
+use crate::dev::{dev_account_code, AccountOverride, DevHandle};
+use alloy_primitives::{Address, Bytes, B256, U256, U64};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use std::collections::HashMap;
+
+/// Methods of the `evm` namespace.
+#[rpc(server, namespace = "evm")]
+pub trait EvmApi {
+    /// Mines a block, at `timestamp` if given.
+    #[method(name = "mine")]
+    async fn mine(&self, timestamp: Option<U64>) -> RpcResult<String>;
+
+    /// Moves the clock of the chain forward and returns the total shift in seconds.
+    #[method(name = "increaseTime")]
+    async fn increase_time(&self, seconds: U64) -> RpcResult<i64>;
+
+    /// Snapshots the chain and returns the id to pass to `evm_revert`.
+    #[method(name = "snapshot")]
+    async fn snapshot(&self) -> RpcResult<U256>;
+
+    /// Reverts the chain to a snapshot, returning `false` for unknown ids.
+    #[method(name = "revert")]
+    async fn revert(&self, id: U256) -> RpcResult<bool>;
+}
+
+/// Methods of the `anvil` namespace.
+#[rpc(server, namespace = "anvil")]
+pub trait AnvilApi {
+    /// Replaces the code of `address`.
+    #[method(name = "setCode")]
+    async fn set_code(&self, address: Address, code: Bytes) -> RpcResult<()>;
+
+    /// Writes a storage slot of `address`.
+    #[method(name = "setStorageAt")]
+    async fn set_storage_at(&self, address: Address, slot: U256, value: B256) -> RpcResult<bool>;
+}
+
+/// `evm` and `anvil` namespaces backed by the dev miner.
+#[derive(Clone, Debug)]
+pub struct DevRpc {
+    dev: DevHandle,
+}
+
+impl DevRpc {
+    pub fn new(dev: DevHandle) -> Self {
+        Self { dev }
+    }
+}
+
+#[jsonrpsee::core::async_trait]
+impl EvmApiServer for DevRpc {
+    async fn mine(&self, timestamp: Option<U64>) -> RpcResult<String> {
+        if let Some(timestamp) = timestamp {
+            self.dev.clock().set_time(timestamp.to());
+        }
+        self.dev
+            .mine()
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok("0x0".to_owned())
+    }
+
+    async fn increase_time(&self, seconds: U64) -> RpcResult<i64> {
+        Ok(self.dev.clock().increase(seconds.to()))
+    }
+
+    async fn snapshot(&self) -> RpcResult<U256> {
+        self.dev
+            .snapshot()
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    async fn revert(&self, id: U256) -> RpcResult<bool> {
+        self.dev
+            .revert(id)
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+}
+
+#[jsonrpsee::core::async_trait]
+impl AnvilApiServer for DevRpc {
+    async fn set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        let code = dev_account_code(&address, code)
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))?;
+        let account = AccountOverride {
+            code: Some(code),
+            ..Default::default()
+        };
+        self.dev
+            .override_account(address, account)
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    async fn set_storage_at(&self, address: Address, slot: U256, value: B256) -> RpcResult<bool> {
+        let account = AccountOverride {
+            code: None,
+            storage: HashMap::from([(slot, value.into())]),
+        };
+        self.dev
+            .override_account(address, account)
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(true)
+    }
+}
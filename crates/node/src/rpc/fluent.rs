@@ -0,0 +1,272 @@
+//! The `fluent` RPC namespace for runtime introspection.
+//!
+//! Answers the questions that the `eth` namespace can't express for rWasm accounts: which
+//! delegated runtime owns an account, what compiled module sits behind a code hash, what a
+//! universal token's metadata is, and how much fuel a call burns.
+use alloy_primitives::{Address, Bytes, B256, U256, U64};
+use alloy_rpc_types_eth::{state::StateOverride, BlockId, TransactionRequest};
+use fluentbase_revm::revm::bytecode::Bytecode;
+use fluentbase_runtime::ModuleFactory;
+use fluentbase_sdk::universal_token::{
+    TokenNameOrSymbol, CONTRACT_FROZEN_STORAGE_SLOT, DECIMALS_STORAGE_SLOT, MINTER_STORAGE_SLOT,
+    NAME_STORAGE_SLOT, PAUSER_STORAGE_SLOT, SYMBOL_STORAGE_SLOT, TOTAL_SUPPLY_STORAGE_SLOT,
+    WRAPPED_STORAGE_SLOT,
+};
+use fluentbase_types::{
+    EXECUTE_USING_SYSTEM_RUNTIME_ADDRESSES, FUEL_DENOM_RATE, PRECOMPILE_UNIVERSAL_TOKEN_RUNTIME,
+};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::ErrorObjectOwned};
+use reth_rpc_eth_api::{
+    helpers::{EstimateCall, LoadState},
+    EthApiTypes, FromEthApiError, RpcNodeCore,
+};
+use reth_storage_api::{errors::ProviderResult, StateProvider};
+use serde::{Deserialize, Serialize};
+
+/// Methods of the `fluent` namespace.
+#[rpc(server, namespace = "fluent")]
+pub trait FluentApi {
+    /// Returns the delegated runtime that executes `address`, or `null` for plain accounts.
+    #[method(name = "getRuntimeOwner")]
+    async fn get_runtime_owner(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Option<Address>>;
+
+    /// Returns the serialized rWasm module for `code_hash`, or `null` if it isn't rWasm code.
+    #[method(name = "getRwasmModule")]
+    async fn get_rwasm_module(&self, code_hash: B256) -> RpcResult<Option<Bytes>>;
+
+    /// Returns the runtime owner and metadata of `address`, decoded for universal tokens.
+    #[method(name = "getContractMetadata")]
+    async fn get_contract_metadata(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<FluentContractMetadata>;
+
+    /// Returns the system contracts that execute under the system runtime.
+    #[method(name = "getSystemContracts")]
+    async fn get_system_contracts(&self) -> RpcResult<Vec<Address>>;
+
+    /// Estimates a call like `eth_estimateGas` and reports the matching fuel limit.
+    #[method(name = "estimateFuel")]
+    async fn estimate_fuel(
+        &self,
+        request: TransactionRequest,
+        block_id: Option<BlockId>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<FluentFuelEstimate>;
+}
+
+/// Response of `fluent_getContractMetadata`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FluentContractMetadata {
+    /// Delegated runtime of the account, `None` for plain EVM accounts and EOAs.
+    pub runtime_owner: Option<Address>,
+    /// Raw runtime metadata stored in the ownable account.
+    pub metadata: Option<Bytes>,
+    /// Decoded metadata when the account is a universal token.
+    pub universal_token: Option<UniversalTokenMetadata>,
+}
+
+/// Universal token (UST20) metadata read from the token's storage.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UniversalTokenMetadata {
+    /// `None` when the stored short string isn't valid UTF-8.
+    pub name: Option<String>,
+    /// `None` when the stored short string isn't valid UTF-8.
+    pub symbol: Option<String>,
+    pub decimals: u8,
+    pub total_supply: U256,
+    pub minter: Address,
+    pub pauser: Address,
+    pub paused: bool,
+    pub wrapped: bool,
+}
+
+/// Response of `fluent_estimateFuel`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FluentFuelEstimate {
+    /// Gas limit that `eth_estimateGas` reports for the call.
+    pub gas: U64,
+    /// Fuel available to the rWasm runtime under that gas limit.
+    pub fuel: U64,
+    /// Fuel units per unit of gas.
+    pub fuel_denom_rate: U64,
+}
+
+/// `fluent` namespace backed by the node's `eth` API and the process-wide [`ModuleFactory`].
+#[derive(Debug)]
+pub struct Fluent<Eth> {
+    eth_api: Eth,
+}
+
+impl<Eth> Fluent<Eth> {
+    pub fn new(eth_api: Eth) -> Self {
+        Self { eth_api }
+    }
+}
+
+fn read_universal_token_metadata(
+    state: &dyn StateProvider,
+    address: Address,
+) -> ProviderResult<UniversalTokenMetadata> {
+    let storage = |slot: U256| -> ProviderResult<U256> {
+        Ok(state
+            .storage(address, B256::from(slot))?
+            .unwrap_or_default())
+    };
+    let short_string = |slot: U256| -> ProviderResult<Option<String>> {
+        let word = B256::from(storage(slot)?);
+        Ok(TokenNameOrSymbol::from_word(word)
+            .as_str()
+            .map(ToString::to_string))
+    };
+    Ok(UniversalTokenMetadata {
+        name: short_string(NAME_STORAGE_SLOT)?,
+        symbol: short_string(SYMBOL_STORAGE_SLOT)?,
+        decimals: storage(DECIMALS_STORAGE_SLOT)?.saturating_to(),
+        total_supply: storage(TOTAL_SUPPLY_STORAGE_SLOT)?,
+        minter: Address::from_word(storage(MINTER_STORAGE_SLOT)?.into()),
+        pauser: Address::from_word(storage(PAUSER_STORAGE_SLOT)?.into()),
+        paused: !storage(CONTRACT_FROZEN_STORAGE_SLOT)?.is_zero(),
+        wrapped: !storage(WRAPPED_STORAGE_SLOT)?.is_zero(),
+    })
+}
+
+#[jsonrpsee::core::async_trait]
+impl<Eth> FluentApiServer for Fluent<Eth>
+where
+    Eth: EthApiTypes + RpcNodeCore + LoadState + EstimateCall + Clone + Send + Sync + 'static,
+{
+    async fn get_runtime_owner(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Option<Address>> {
+        let owner = self
+            .eth_api
+            .spawn_blocking_io_fut(async move |this| {
+                let state = this.state_at_block_id_or_latest(block_id).await?;
+                let code = state
+                    .account_code(&address)
+                    .map_err(Eth::Error::from_eth_err)?;
+                Ok(match code.map(|code| code.0) {
+                    Some(Bytecode::OwnableAccount(ownable)) => Some(ownable.owner_address),
+                    _ => None,
+                })
+            })
+            .await
+            .map_err(Into::<ErrorObjectOwned>::into)?;
+        Ok(owner)
+    }
+
+    async fn get_rwasm_module(&self, code_hash: B256) -> RpcResult<Option<Bytes>> {
+        // Modules compiled by this node are served from the cache, anything else is read from
+        // the latest state, where rWasm code is stored already compiled.
+        if let Some(module) = ModuleFactory::new().get_cached_module(&code_hash) {
+            return Ok(Some(module.serialize().into()));
+        }
+        let module = self
+            .eth_api
+            .spawn_blocking_io_fut(async move |this| {
+                let state = this.state_at_block_id_or_latest(None).await?;
+                let code = state
+                    .bytecode_by_hash(&code_hash)
+                    .map_err(Eth::Error::from_eth_err)?;
+                Ok(match code.map(|code| code.0) {
+                    Some(Bytecode::Rwasm(bytecode)) => Some(bytecode.module.serialize().into()),
+                    _ => None,
+                })
+            })
+            .await
+            .map_err(Into::<ErrorObjectOwned>::into)?;
+        Ok(module)
+    }
+
+    async fn get_contract_metadata(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<FluentContractMetadata> {
+        let metadata = self
+            .eth_api
+            .spawn_blocking_io_fut(async move |this| {
+                let state = this.state_at_block_id_or_latest(block_id).await?;
+                let code = state
+                    .account_code(&address)
+                    .map_err(Eth::Error::from_eth_err)?;
+                let Some(Bytecode::OwnableAccount(ownable)) = code.map(|code| code.0) else {
+                    return Ok(FluentContractMetadata::default());
+                };
+                let universal_token = if ownable.owner_address == PRECOMPILE_UNIVERSAL_TOKEN_RUNTIME
+                {
+                    Some(
+                        read_universal_token_metadata(&*state, address)
+                            .map_err(Eth::Error::from_eth_err)?,
+                    )
+                } else {
+                    None
+                };
+                Ok(FluentContractMetadata {
+                    runtime_owner: Some(ownable.owner_address),
+                    metadata: Some(ownable.metadata.clone()),
+                    universal_token,
+                })
+            })
+            .await
+            .map_err(Into::<ErrorObjectOwned>::into)?;
+        Ok(metadata)
+    }
+
+    async fn get_system_contracts(&self) -> RpcResult<Vec<Address>> {
+        Ok(EXECUTE_USING_SYSTEM_RUNTIME_ADDRESSES.to_vec())
+    }
+
+    async fn estimate_fuel(
+        &self,
+        request: TransactionRequest,
+        block_id: Option<BlockId>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<FluentFuelEstimate> {
+        let gas = self
+            .eth_api
+            .estimate_gas_at(request, block_id.unwrap_or_default(), state_override)
+            .await
+            .map_err(Into::<ErrorObjectOwned>::into)?;
+        let gas: u64 = gas.saturating_to();
+        Ok(FluentFuelEstimate {
+            gas: U64::from(gas),
+            // Matches how the executor turns a frame's remaining gas into its fuel limit.
+            fuel: U64::from(gas.saturating_mul(FUEL_DENOM_RATE)),
+            fuel_denom_rate: U64::from(FUEL_DENOM_RATE),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_serializes_with_camel_case_keys() {
+        let metadata = FluentContractMetadata {
+            runtime_owner: Some(PRECOMPILE_UNIVERSAL_TOKEN_RUNTIME),
+            metadata: Some(Bytes::new()),
+            universal_token: Some(UniversalTokenMetadata {
+                name: Some("Token".to_string()),
+                decimals: 18,
+                ..Default::default()
+            }),
+        };
+        let value = serde_json::to_value(&metadata).unwrap();
+        assert!(value.get("runtimeOwner").is_some());
+        assert_eq!(value["universalToken"]["decimals"], 18);
+        assert_eq!(value["universalToken"]["totalSupply"], "0x0");
+    }
+}
@@ -0,0 +1,47 @@
+//! Sink for `_debug_event` records emitted by contracts.
+//!
+//! Records are collected when the installed `RuntimeConfig` enables them, which is only accepted
+//! on development chains, or while [`collect_debug_events`] runs, which is how tests read them.
+//! The executor forwards every record here and to the `fluentTracer` recorder.
+use fluentbase_sdk::{Address, DebugEvent};
+use std::{cell::RefCell, vec::Vec};
+
+/// A debug record together with the account whose frame emitted it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractDebugEvent {
+    pub address: Address,
+    pub event: DebugEvent,
+}
+
+thread_local! {
+    static SINK: RefCell<Option<Vec<ContractDebugEvent>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` and returns every debug record emitted meanwhile on this thread, in emission order.
+pub fn collect_debug_events<R>(f: impl FnOnce() -> R) -> (R, Vec<ContractDebugEvent>) {
+    SINK.with_borrow_mut(|sink| *sink = Some(Vec::new()));
+    let result = f();
+    let events = SINK.with_borrow_mut(Option::take).unwrap_or_default();
+    (result, events)
+}
+
+/// Returns `true` while [`collect_debug_events`] is running on this thread.
+pub(crate) fn is_collecting() -> bool {
+    SINK.with_borrow(Option::is_some)
+}
+
+pub(crate) fn emit(address: Address, events: Vec<DebugEvent>) {
+    if events.is_empty() {
+        return;
+    }
+    crate::tracer::record(|| crate::tracer::RwasmTraceEvent::DebugEvents(events.clone()));
+    SINK.with_borrow_mut(|sink| {
+        if let Some(sink) = sink {
+            sink.extend(
+                events
+                    .into_iter()
+                    .map(|event| ContractDebugEvent { address, event }),
+            );
+        }
+    });
+}
@@ -10,18 +10,20 @@ use fluentbase_evm::types::{exit_code_from_instruction_result, instruction_resul
 use fluentbase_runtime::{
     default_runtime_executor,
     syscall_handler::{syscall_exec_impl, syscall_resume_impl},
-    RuntimeContext, RuntimeExecutor,
+    RuntimeConfig, RuntimeContext, RuntimeExecutor,
 };
 use fluentbase_sdk::{
     bincode, is_delegated_runtime_address, is_execute_using_system_runtime, keccak256,
+    oauth2::oauth2_compute_storage_keys,
     rwasm_core::RwasmModule,
     system::{
         JournalLog, RuntimeExecutionOutcomeV1, RuntimeInterruptionOutcomeV1, RuntimeNewFrameInputV1,
     },
     universal_token::erc20_compute_storage_keys,
-    BlockContextV1, BytecodeOrHash, Bytes, ContractContextV1, ExitCode, SharedContextInput,
-    SharedContextInputV1, SyscallInvocationParams, TxContextV1, FUEL_DENOM_RATE,
-    PRECOMPILE_EIP2935, PRECOMPILE_UNIVERSAL_TOKEN_RUNTIME, STATE_DEPLOY, STATE_MAIN, U256,
+    BlockContextV1, BytecodeOrHash, Bytes, ContractContextV1, ExitCode, RuntimeForkSchedule,
+    SharedContextInput, SharedContextInputV1, SyscallInvocationParams, TxContextV1,
+    FUEL_DENOM_RATE, PRECOMPILE_EIP2935, PRECOMPILE_OAUTH2_VERIFIER,
+    PRECOMPILE_UNIVERSAL_TOKEN_RUNTIME, STATE_DEPLOY, STATE_MAIN, U256,
 };
 use revm::{
     bytecode::{opcode, ownable_account::OwnableAccountBytecode, Bytecode},
@@ -316,6 +318,9 @@ fn execute_rwasm_frame<CTX: ContextTr, INSP: Inspector<CTX>>(
                 eip2935_compute_storage_keys(input.as_ref(), &caller_address, block_number)
                     .unwrap_or_default()
             }
+            PRECOMPILE_OAUTH2_VERIFIER => {
+                oauth2_compute_storage_keys(input.as_ref(), &caller_address).unwrap_or_default()
+            }
             _ => {
                 // TODO(dmitry123): Add support for pre-loading for some storage slots to EVM runtime
                 /*let mut storage_list: Vec<U256> = vec![];
@@ -431,8 +436,18 @@ fn execute_rwasm_frame<CTX: ContextTr, INSP: Inspector<CTX>>(
     // The multiplication can overflow in pathological cases; saturate to u64::MAX.
     let fuel_limit = interpreter.gas.remaining().saturating_mul(FUEL_DENOM_RATE);
 
-    // Execute rwasm entrypoint for this frame.
-    let mut runtime_context = RuntimeContext::default();
+    // Execute rwasm entrypoint for this frame. Gated syscalls are resolved against the block, so
+    // historical blocks replay with the features that were active when they were produced.
+    let forks = RuntimeForkSchedule::for_chain(ctx.cfg().chain_id()).active_at(
+        ctx.block().number().as_limbs()[0],
+        ctx.block().timestamp().as_limbs()[0],
+    );
+    let collect_debug_events = RuntimeConfig::installed().debug_events;
+    #[cfg(feature = "std")]
+    let collect_debug_events = collect_debug_events || crate::debug_events::is_collecting();
+    let mut runtime_context = RuntimeContext::default()
+        .with_forks(forks)
+        .with_debug_events(collect_debug_events);
     let (fuel_consumed, fuel_refunded, exit_code) = syscall_exec_impl(
         &mut runtime_context,
         bytecode_hash,
@@ -444,6 +459,19 @@ fn execute_rwasm_frame<CTX: ContextTr, INSP: Inspector<CTX>>(
     // Convert consumed fuel into gas to charge inside REVM.
     // On some networks we floor vs. ceil; keep the behavior feature-gated.
     let gas_consumed = fuel_consumed.div_ceil(FUEL_DENOM_RATE);
+    #[cfg(feature = "std")]
+    crate::tracer::record(|| crate::tracer::RwasmTraceEvent::Exec {
+        runtime_owner: interpreter.input.account_owner,
+        fuel_consumed,
+        fuel_refunded,
+        gas_charged: gas_consumed,
+        exit_code,
+    });
+    #[cfg(feature = "std")]
+    crate::debug_events::emit(
+        target_address,
+        std::mem::take(&mut runtime_context.execution_result.debug_events),
+    );
 
     // Charge gas. If we cannot, halt with out-of-fuel.
     if !interpreter.gas.record_regular_cost(gas_consumed) {
@@ -568,10 +596,23 @@ pub(crate) fn execute_rwasm_resume<CTX: ContextTr, INSP: Inspector<CTX>>(
         ));
     };
 
+    #[cfg(feature = "std")]
+    crate::debug_events::emit(
+        frame.interpreter.input.target_address(),
+        std::mem::take(&mut runtime_context.execution_result.debug_events),
+    );
     let return_data: Bytes = runtime_context.execution_result.return_data.into();
 
     // Convert consumed fuel into gas for REVM.
     let gas_consumed = fuel_consumed.div_ceil(FUEL_DENOM_RATE);
+    #[cfg(feature = "std")]
+    crate::tracer::record(|| crate::tracer::RwasmTraceEvent::Resume {
+        call_id,
+        fuel_consumed,
+        fuel_refunded,
+        gas_charged: gas_consumed,
+        exit_code,
+    });
 
     // Charge gas for the resumed segment.
     if !frame.interpreter.gas.record_regular_cost(gas_consumed) {
@@ -898,6 +939,26 @@ pub(crate) fn process_exec_result<CTX: ContextTr, INSP: Inspector<CTX>>(
         preloaded_slot_costs,
     };
 
+    #[cfg(feature = "std")]
+    if crate::tracer::is_recording() {
+        let (syscall_id, fuel_limit) = (inputs.code_hash, inputs.fuel_limit);
+        let next_action = execute_rwasm_interruption::<CTX, INSP>(
+            frame,
+            inspector,
+            ctx,
+            inputs,
+            DefaultRuntimeExecutorMemoryReader {},
+        )?;
+        crate::tracer::record(|| crate::tracer::RwasmTraceEvent::Interruption {
+            call_id,
+            syscall_id,
+            fuel_limit,
+            gas_charged: frame.interpreter.gas.total_gas_spent() - gas.total_gas_spent(),
+            outcome: crate::tracer::SyscallOutcomeKind::of(&next_action),
+        });
+        return Ok(next_action);
+    }
+
     execute_rwasm_interruption::<CTX, INSP>(
         frame,
         inspector,
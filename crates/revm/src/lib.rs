@@ -7,6 +7,8 @@ extern crate alloc as std;
 
 mod api;
 mod bridge;
+#[cfg(feature = "std")]
+pub mod debug_events;
 mod eip2935;
 mod evm;
 mod executor;
@@ -18,6 +20,8 @@ mod result;
 mod syscall;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "std")]
+pub mod tracer;
 mod types;
 
 pub use api::*;
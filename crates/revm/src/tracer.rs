@@ -0,0 +1,594 @@
+//! Native `fluentTracer`: a call tracer that understands rWasm frames.
+//!
+//! Geth-style tracers only see the one-opcode EVM steps that [`crate::inspector`] fakes for
+//! syscalls, so a Wasm contract shows up as an opaque call. [`FluentTracer`] instead records what
+//! the rWasm executor actually does inside every frame:
+//! - which runtime owner (EVM, WASM, UST20, ...) executed the frame,
+//! - every interruption, identified by its `SYSCALL_ID_*` and the `exec`/`resume`
+//!   ([`SysFuncIdx::EXEC`]/[`SysFuncIdx::RESUME`]) host calls around it,
+//! - fuel consumed by the runtime next to the gas charged for it and for the syscall itself,
+//! - `_debug_event` records emitted by the frame, when the node collects them (see
+//!   [`crate::debug_events`]).
+//!
+//! The executor can't reach the inspector it runs under with rWasm-specific events (the
+//! `Inspector` trait is shared with upstream revm), so events go to a thread-local recorder that
+//! is only armed while a [`FluentTracer`] is tracing the outermost frame. When no tracer is active
+//! recording costs one thread-local check per exec/resume.
+use crate::NextAction;
+use fluentbase_sdk::{
+    syscall::*, Address, Bytes, DebugEvent, DebugEventFormat, SysFuncIdx, B256,
+    PRECOMPILE_EVM_RUNTIME, PRECOMPILE_SVM_RUNTIME, PRECOMPILE_UNIVERSAL_TOKEN_RUNTIME,
+    PRECOMPILE_WASM_RUNTIME, U256,
+};
+use revm::{
+    context::ContextTr,
+    interpreter::{CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome, CreateScheme},
+    Inspector,
+};
+use std::{cell::RefCell, mem::take, string::String, vec::Vec};
+
+/// Tracer name accepted by `debug_traceTransaction`/`debug_traceCall`.
+pub const FLUENT_TRACER_NAME: &str = "fluentTracer";
+
+/// How the host answered an interruption.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyscallOutcomeKind {
+    /// Answered in place, the runtime resumes right away.
+    Result,
+    /// Opened a child call or create frame, the runtime resumes once it returns.
+    Frame,
+    /// Halted the interrupted frame.
+    Halt,
+}
+
+impl SyscallOutcomeKind {
+    pub(crate) fn of(action: &NextAction) -> Self {
+        match action {
+            NextAction::InterruptionResult => Self::Result,
+            NextAction::NewFrame(_) => Self::Frame,
+            NextAction::Return(_) => Self::Halt,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Result => "result",
+            Self::Frame => "frame",
+            Self::Halt => "halt",
+        }
+    }
+}
+
+/// A single rWasm executor event, recorded in execution order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RwasmTraceEvent {
+    /// The runtime entered a frame through `exec`.
+    Exec {
+        runtime_owner: Option<Address>,
+        fuel_consumed: u64,
+        fuel_refunded: i64,
+        gas_charged: u64,
+        exit_code: i32,
+    },
+    /// The runtime interrupted with a syscall and the host serviced it.
+    Interruption {
+        call_id: u32,
+        syscall_id: B256,
+        fuel_limit: u64,
+        gas_charged: u64,
+        outcome: SyscallOutcomeKind,
+    },
+    /// The runtime continued after an interruption through `resume`.
+    Resume {
+        call_id: u32,
+        fuel_consumed: u64,
+        fuel_refunded: i64,
+        gas_charged: u64,
+        exit_code: i32,
+    },
+    /// The runtime emitted debug records during the last `exec`/`resume`.
+    DebugEvents(Vec<DebugEvent>),
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Vec<RwasmTraceEvent>>> = const { RefCell::new(None) };
+}
+
+/// Returns `true` while a [`FluentTracer`] is collecting events on this thread.
+pub(crate) fn is_recording() -> bool {
+    RECORDER.with_borrow(Option::is_some)
+}
+
+/// Records an event if a [`FluentTracer`] is active; the event is only built in that case.
+pub(crate) fn record(event: impl FnOnce() -> RwasmTraceEvent) {
+    RECORDER.with_borrow_mut(|recorder| {
+        if let Some(events) = recorder {
+            events.push(event());
+        }
+    });
+}
+
+fn start_recording() {
+    RECORDER.with_borrow_mut(|recorder| *recorder = Some(Vec::new()));
+}
+
+fn take_recorded() -> Vec<RwasmTraceEvent> {
+    RECORDER.with_borrow_mut(|recorder| recorder.as_mut().map(take).unwrap_or_default())
+}
+
+fn stop_recording() {
+    RECORDER.with_borrow_mut(|recorder| *recorder = None);
+}
+
+/// Human-readable name of a `SYSCALL_ID_*` interruption.
+pub fn syscall_name(syscall_id: &B256) -> Option<&'static str> {
+    Some(match *syscall_id {
+        SYSCALL_ID_STORAGE_READ => "storage_read",
+        SYSCALL_ID_STORAGE_WRITE => "storage_write",
+        SYSCALL_ID_CALL => "call",
+        SYSCALL_ID_STATIC_CALL => "static_call",
+        SYSCALL_ID_CALL_CODE => "call_code",
+        SYSCALL_ID_DELEGATE_CALL => "delegate_call",
+        SYSCALL_ID_CREATE => "create",
+        SYSCALL_ID_CREATE2 => "create2",
+        SYSCALL_ID_EMIT_LOG => "emit_log",
+        SYSCALL_ID_DESTROY_ACCOUNT => "destroy_account",
+        SYSCALL_ID_BALANCE => "balance",
+        SYSCALL_ID_SELF_BALANCE => "self_balance",
+        SYSCALL_ID_CODE_SIZE => "code_size",
+        SYSCALL_ID_CODE_HASH => "code_hash",
+        SYSCALL_ID_CODE_COPY => "code_copy",
+        SYSCALL_ID_TRANSIENT_READ => "transient_read",
+        SYSCALL_ID_TRANSIENT_WRITE => "transient_write",
+        SYSCALL_ID_BLOCK_HASH => "block_hash",
+        SYSCALL_ID_METADATA_WRITE => "metadata_write",
+        SYSCALL_ID_METADATA_SIZE => "metadata_size",
+        SYSCALL_ID_METADATA_CREATE => "metadata_create",
+        SYSCALL_ID_METADATA_COPY => "metadata_copy",
+        SYSCALL_ID_METADATA_STORAGE_READ => "metadata_storage_read",
+        SYSCALL_ID_METADATA_STORAGE_WRITE => "metadata_storage_write",
+        SYSCALL_ID_METADATA_ACCOUNT_OWNER => "metadata_account_owner",
+        SYSCALL_ID_UPGRADE_EVM_RUNTIME => "upgrade_evm_runtime",
+        SYSCALL_ID_UPGRADE_WASM_RUNTIME => "upgrade_wasm_runtime",
+        _ => return None,
+    })
+}
+
+/// Label of the runtime that executed a frame.
+///
+/// Frames without a delegated owner run their own rWasm module; they are reported as `wasm`
+/// because that is how user Wasm contracts execute after deployment.
+pub fn runtime_owner_label(owner: Option<&Address>) -> &'static str {
+    match owner {
+        None => "wasm",
+        Some(&PRECOMPILE_EVM_RUNTIME) => "evm",
+        Some(&PRECOMPILE_WASM_RUNTIME) => "wasm",
+        Some(&PRECOMPILE_UNIVERSAL_TOKEN_RUNTIME) => "ust20",
+        Some(&PRECOMPILE_SVM_RUNTIME) => "svm",
+        Some(_) => "system",
+    }
+}
+
+/// `exec`/`resume` host call of a [`FluentTraceStep`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct FluentRuntimeSegment {
+    /// Host function that ran the segment, `EXEC` or `RESUME`.
+    pub host_function: String,
+    pub fuel_consumed: u64,
+    pub fuel_refunded: i64,
+    /// Gas charged to the frame for `fuel_consumed`.
+    pub gas_charged: u64,
+    /// Runtime exit code; positive values are the call id of an interruption.
+    pub exit_code: i32,
+}
+
+/// Syscall serviced by the host between two runtime segments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct FluentSyscall {
+    pub call_id: u32,
+    pub syscall_id: B256,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<String>,
+    /// Fuel the runtime made available to the syscall.
+    pub fuel_limit: u64,
+    /// Gas the host charged for the syscall itself, excluding any child frame.
+    pub gas_charged: u64,
+    /// `result` when answered in place, `frame` when it opened a child frame, `halt` otherwise.
+    pub outcome: String,
+}
+
+/// `_debug_event` record of a [`FluentCallFrame`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct FluentDebugEvent {
+    pub channel: u32,
+    /// `bytes` or `json`.
+    pub format: String,
+    /// Raw payload of `bytes` records.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub data: Option<Bytes>,
+    /// Payload of `json` records, parsed when it is valid JSON and kept as a string otherwise.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub json: Option<serde_json::Value>,
+}
+
+impl From<DebugEvent> for FluentDebugEvent {
+    fn from(event: DebugEvent) -> Self {
+        match event.format {
+            DebugEventFormat::Bytes => Self {
+                channel: event.channel,
+                format: "bytes".into(),
+                data: Some(event.data),
+                json: None,
+            },
+            DebugEventFormat::Json => Self {
+                channel: event.channel,
+                format: "json".into(),
+                data: None,
+                json: Some(serde_json::from_slice(&event.data).unwrap_or_else(|_| {
+                    serde_json::Value::String(String::from_utf8_lossy(&event.data).into_owned())
+                })),
+            },
+        }
+    }
+}
+
+/// One step of an rWasm frame, in execution order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum FluentTraceStep {
+    Runtime(FluentRuntimeSegment),
+    Syscall(FluentSyscall),
+}
+
+/// Call frame reported by the `fluentTracer`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct FluentCallFrame {
+    /// `CALL`, `STATICCALL`, `DELEGATECALL`, `CALLCODE`, `CREATE` or `CREATE2`.
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub kind: String,
+    pub from: Address,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub to: Option<Address>,
+    pub value: U256,
+    pub gas: u64,
+    pub gas_used: u64,
+    pub input: Bytes,
+    pub output: Bytes,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub error: Option<String>,
+    /// Runtime that executed the frame; `None` for frames that never entered rWasm.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub runtime_owner: Option<String>,
+    /// Total fuel consumed by all runtime segments of the frame.
+    pub fuel_used: u64,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub steps: Vec<FluentTraceStep>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub debug_events: Vec<FluentDebugEvent>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub calls: Vec<FluentCallFrame>,
+}
+
+impl FluentCallFrame {
+    fn apply(&mut self, event: RwasmTraceEvent) {
+        let step = match event {
+            RwasmTraceEvent::Exec {
+                runtime_owner,
+                fuel_consumed,
+                fuel_refunded,
+                gas_charged,
+                exit_code,
+            } => {
+                self.runtime_owner = Some(runtime_owner_label(runtime_owner.as_ref()).into());
+                self.fuel_used += fuel_consumed;
+                FluentTraceStep::Runtime(FluentRuntimeSegment {
+                    host_function: SysFuncIdx::EXEC.to_string(),
+                    fuel_consumed,
+                    fuel_refunded,
+                    gas_charged,
+                    exit_code,
+                })
+            }
+            RwasmTraceEvent::Resume {
+                call_id: _,
+                fuel_consumed,
+                fuel_refunded,
+                gas_charged,
+                exit_code,
+            } => {
+                self.fuel_used += fuel_consumed;
+                FluentTraceStep::Runtime(FluentRuntimeSegment {
+                    host_function: SysFuncIdx::RESUME.to_string(),
+                    fuel_consumed,
+                    fuel_refunded,
+                    gas_charged,
+                    exit_code,
+                })
+            }
+            RwasmTraceEvent::Interruption {
+                call_id,
+                syscall_id,
+                fuel_limit,
+                gas_charged,
+                outcome,
+            } => FluentTraceStep::Syscall(FluentSyscall {
+                call_id,
+                syscall_id,
+                name: syscall_name(&syscall_id).map(Into::into),
+                fuel_limit,
+                gas_charged,
+                outcome: outcome.as_str().into(),
+            }),
+            RwasmTraceEvent::DebugEvents(events) => {
+                self.debug_events.extend(events.into_iter().map(Into::into));
+                return;
+            }
+        };
+        self.steps.push(step);
+    }
+}
+
+/// Inspector producing a [`FluentCallFrame`] tree for one transaction.
+#[derive(Debug, Default)]
+pub struct FluentTracer {
+    stack: Vec<FluentCallFrame>,
+    root: Option<FluentCallFrame>,
+}
+
+impl FluentTracer {
+    /// Returns the root call frame, or `None` if nothing was traced.
+    pub fn into_call_frame(self) -> Option<FluentCallFrame> {
+        self.root
+    }
+
+    /// Attaches events recorded since the last hook to the frame that was running meanwhile.
+    fn flush_events(&mut self) {
+        let events = take_recorded();
+        if let Some(frame) = self.stack.last_mut() {
+            for event in events {
+                frame.apply(event);
+            }
+        }
+    }
+
+    fn enter(&mut self, frame: FluentCallFrame) {
+        if self.stack.is_empty() {
+            start_recording();
+        } else {
+            self.flush_events();
+        }
+        self.stack.push(frame);
+    }
+
+    fn exit(&mut self, gas_used: u64, output: Bytes, error: Option<String>, to: Option<Address>) {
+        self.flush_events();
+        let Some(mut frame) = self.stack.pop() else {
+            return;
+        };
+        frame.gas_used = gas_used;
+        frame.output = output;
+        frame.error = error;
+        if to.is_some() {
+            frame.to = to;
+        }
+        match self.stack.last_mut() {
+            Some(parent) => parent.calls.push(frame),
+            None => {
+                stop_recording();
+                self.root = Some(frame);
+            }
+        }
+    }
+}
+
+impl Drop for FluentTracer {
+    fn drop(&mut self) {
+        // A transaction aborted mid-frame never reaches the outermost `*_end` hook.
+        if !self.stack.is_empty() {
+            stop_recording();
+        }
+    }
+}
+
+fn call_kind(scheme: CallScheme) -> &'static str {
+    match scheme {
+        CallScheme::Call => "CALL",
+        CallScheme::CallCode => "CALLCODE",
+        CallScheme::DelegateCall => "DELEGATECALL",
+        CallScheme::StaticCall => "STATICCALL",
+    }
+}
+
+fn error_of(result: &revm::interpreter::InterpreterResult) -> Option<String> {
+    if result.result.is_ok() {
+        None
+    } else if result.result.is_revert() {
+        Some("execution reverted".into())
+    } else {
+        Some(format!("{:?}", result.result))
+    }
+}
+
+impl<CTX: ContextTr> Inspector<CTX> for FluentTracer {
+    fn call(&mut self, ctx: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.enter(FluentCallFrame {
+            kind: call_kind(inputs.scheme).into(),
+            from: inputs.caller,
+            to: Some(inputs.target_address),
+            value: inputs.value.get(),
+            gas: inputs.gas_limit,
+            input: inputs.input.bytes(ctx),
+            ..Default::default()
+        });
+        None
+    }
+
+    fn call_end(&mut self, _ctx: &mut CTX, _inputs: &CallInputs, outcome: &mut CallOutcome) {
+        self.exit(
+            outcome.result.gas.spent(),
+            outcome.result.output.clone(),
+            error_of(&outcome.result),
+            None,
+        );
+    }
+
+    fn create(&mut self, _ctx: &mut CTX, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        let kind = match inputs.scheme() {
+            CreateScheme::Create2 { .. } => "CREATE2",
+            _ => "CREATE",
+        };
+        self.enter(FluentCallFrame {
+            kind: kind.into(),
+            from: inputs.caller(),
+            value: inputs.value(),
+            gas: inputs.gas_limit(),
+            input: inputs.init_code().clone(),
+            ..Default::default()
+        });
+        None
+    }
+
+    fn create_end(&mut self, _ctx: &mut CTX, _inputs: &CreateInputs, outcome: &mut CreateOutcome) {
+        self.exit(
+            outcome.result.gas.spent(),
+            outcome.result.output.clone(),
+            error_of(&outcome.result),
+            outcome.address,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(kind: &str) -> FluentCallFrame {
+        FluentCallFrame {
+            kind: kind.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn events_attach_to_the_frame_that_produced_them() {
+        let mut tracer = FluentTracer::default();
+        tracer.enter(frame("CALL"));
+        assert!(is_recording());
+
+        // The parent runs and interrupts with a CALL that opens a child frame.
+        record(|| RwasmTraceEvent::Exec {
+            runtime_owner: Some(PRECOMPILE_EVM_RUNTIME),
+            fuel_consumed: 5_000,
+            fuel_refunded: 0,
+            gas_charged: 5,
+            exit_code: 1,
+        });
+        record(|| RwasmTraceEvent::Interruption {
+            call_id: 1,
+            syscall_id: SYSCALL_ID_CALL,
+            fuel_limit: 90_000,
+            gas_charged: 2_600,
+            outcome: SyscallOutcomeKind::Frame,
+        });
+        tracer.enter(frame("STATICCALL"));
+        record(|| RwasmTraceEvent::Exec {
+            runtime_owner: None,
+            fuel_consumed: 1_000,
+            fuel_refunded: 0,
+            gas_charged: 1,
+            exit_code: 0,
+        });
+        tracer.exit(1, Bytes::new(), None, None);
+
+        // The parent resumes after the child returned.
+        record(|| RwasmTraceEvent::Resume {
+            call_id: 1,
+            fuel_consumed: 2_000,
+            fuel_refunded: 0,
+            gas_charged: 2,
+            exit_code: 0,
+        });
+        tracer.exit(2_608, Bytes::new(), None, None);
+        assert!(!is_recording());
+
+        let root = tracer.into_call_frame().unwrap();
+        assert_eq!(root.runtime_owner.as_deref(), Some("evm"));
+        assert_eq!(root.fuel_used, 7_000);
+        assert_eq!(root.steps.len(), 3);
+        let FluentTraceStep::Syscall(syscall) = &root.steps[1] else {
+            panic!("expected a syscall step");
+        };
+        assert_eq!(syscall.name.as_deref(), Some("call"));
+        assert_eq!(syscall.outcome, "frame");
+        let FluentTraceStep::Runtime(resume) = &root.steps[2] else {
+            panic!("expected a runtime step");
+        };
+        assert_eq!(resume.host_function, "RESUME");
+
+        assert_eq!(root.calls.len(), 1);
+        let child = &root.calls[0];
+        assert_eq!(child.kind, "STATICCALL");
+        assert_eq!(child.runtime_owner.as_deref(), Some("wasm"));
+        assert_eq!(child.fuel_used, 1_000);
+        assert_eq!(child.steps.len(), 1);
+    }
+
+    #[test]
+    fn debug_events_are_attached_to_the_frame() {
+        let mut tracer = FluentTracer::default();
+        tracer.enter(frame("CALL"));
+        record(|| {
+            RwasmTraceEvent::DebugEvents(vec![
+                DebugEvent {
+                    channel: 1,
+                    format: DebugEventFormat::Json,
+                    data: Bytes::from_static(br#"{"step":1}"#),
+                },
+                DebugEvent {
+                    channel: 2,
+                    format: DebugEventFormat::Bytes,
+                    data: Bytes::from_static(&[0xde, 0xad]),
+                },
+            ])
+        });
+        tracer.exit(0, Bytes::new(), None, None);
+
+        let root = tracer.into_call_frame().unwrap();
+        assert!(root.steps.is_empty());
+        assert_eq!(root.debug_events.len(), 2);
+        assert_eq!(root.debug_events[0].format, "json");
+        assert_eq!(
+            root.debug_events[0].json,
+            Some(serde_json::json!({ "step": 1 }))
+        );
+        assert_eq!(
+            root.debug_events[1].data,
+            Some(Bytes::from_static(&[0xde, 0xad]))
+        );
+    }
+
+    #[test]
+    fn nothing_is_recorded_without_an_active_tracer() {
+        assert!(!is_recording());
+        record(|| unreachable!("events must not be built while no tracer is active"));
+        assert!(take_recorded().is_empty());
+    }
+
+    #[test]
+    fn known_syscalls_have_names() {
+        assert_eq!(
+            syscall_name(&SYSCALL_ID_STORAGE_WRITE),
+            Some("storage_write")
+        );
+        assert_eq!(syscall_name(&B256::repeat_byte(0xaa)), None);
+    }
+}
@@ -0,0 +1,204 @@
+//! Per-node configuration of the runtime executor.
+//!
+//! Knobs fall into two classes:
+//! - **node-local** knobs only change how fast a node executes. Any value is safe, and nodes on the
+//!   same chain may disagree freely (`cached_modules_size_limit`, `module_cache_dir`).
+//! - **consensus-critical** knobs change execution results. A transaction that stays under the
+//!   limit on one node and exceeds it on another produces a different receipt and state root, so
+//!   they must match the chain's value on every node (`max_in_flight_memory_bytes`). Overrides are
+//!   only accepted for local development chains, see [`RuntimeConfig::validate`].
+//! - **dev-only** knobs don't change execution results but expose data that production nodes
+//!   must not collect, they are rejected outside of development chains (`debug_events`).
+//!
+//! The runtime fork schedule is consensus-critical too, but it isn't a knob: the executor resolves
+//! it from the chain id of the block, see [`fluentbase_types::RuntimeForkSchedule::for_chain`].
+//!
+//! The import linker is also consensus-critical and is deliberately not configurable.
+//!
+//! The executor and module cache are process-wide, so the configuration is installed once, before
+//! the first execution, with [`RuntimeConfig::install`].
+use crate::module_factory::CACHED_MODULES_SIZE_LIMIT;
+use core::fmt;
+use fluentbase_types::MAX_IN_FLIGHT_MEMORY_BYTES;
+use std::{path::PathBuf, sync::OnceLock};
+
+static INSTALLED_RUNTIME_CONFIG: OnceLock<RuntimeConfig> = OnceLock::new();
+
+/// Tuning of the runtime executor and its compiled module cache.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RuntimeConfig {
+    /// Memory budget of the in-memory compiled module cache, in bytes. Node-local.
+    pub cached_modules_size_limit: usize,
+    /// Directory of the persistent compiled module cache, disabled when `None`. Node-local.
+    pub module_cache_dir: Option<PathBuf>,
+    /// Ceiling on linear memory held by all live frames of one transaction, in bytes.
+    ///
+    /// Consensus-critical: exceeding it fails the call with `ExitCode::OutOfMemory`.
+    pub max_in_flight_memory_bytes: u64,
+    /// Collect `_debug_event` records into execution results. Dev-only.
+    pub debug_events: bool,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        Self {
+            cached_modules_size_limit: CACHED_MODULES_SIZE_LIMIT,
+            module_cache_dir: None,
+            max_in_flight_memory_bytes: MAX_IN_FLIGHT_MEMORY_BYTES,
+            debug_events: false,
+        }
+    }
+}
+
+/// Rejected [`RuntimeConfig`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RuntimeConfigError {
+    /// A size limit is zero and would reject every entry.
+    ZeroLimit(&'static str),
+    /// A consensus-critical knob differs from the chain value outside a development chain.
+    ConsensusCriticalOverride {
+        knob: &'static str,
+        value: u64,
+        expected: u64,
+    },
+    /// A dev-only knob is enabled outside a development chain.
+    DevChainOnly(&'static str),
+    /// A different configuration was installed, or the default was already used by an execution.
+    AlreadyInstalled,
+}
+
+impl fmt::Display for RuntimeConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroLimit(knob) => write!(f, "runtime: `{knob}` must be greater than 0"),
+            Self::ConsensusCriticalOverride {
+                knob,
+                value,
+                expected,
+            } => write!(
+                f,
+                "runtime: `{knob}` is consensus-critical and can only be changed on a development \
+                 chain (got {value}, chain value is {expected})"
+            ),
+            Self::DevChainOnly(knob) => write!(
+                f,
+                "runtime: `{knob}` can only be enabled on a development chain"
+            ),
+            Self::AlreadyInstalled => write!(
+                f,
+                "runtime: a different runtime config is already in use by this process"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for RuntimeConfigError {}
+
+impl RuntimeConfig {
+    /// Checks the config against the consensus-safety rules described in the module docs.
+    ///
+    /// `allow_consensus_overrides` must only be set for chains whose blocks are never validated by
+    /// another node, such as a local dev chain.
+    pub fn validate(&self, allow_consensus_overrides: bool) -> Result<(), RuntimeConfigError> {
+        if self.cached_modules_size_limit == 0 {
+            return Err(RuntimeConfigError::ZeroLimit("cached_modules_size_limit"));
+        }
+        if self.max_in_flight_memory_bytes == 0 {
+            return Err(RuntimeConfigError::ZeroLimit("max_in_flight_memory_bytes"));
+        }
+        if !allow_consensus_overrides
+            && self.max_in_flight_memory_bytes != MAX_IN_FLIGHT_MEMORY_BYTES
+        {
+            return Err(RuntimeConfigError::ConsensusCriticalOverride {
+                knob: "max_in_flight_memory_bytes",
+                value: self.max_in_flight_memory_bytes,
+                expected: MAX_IN_FLIGHT_MEMORY_BYTES,
+            });
+        }
+        if !allow_consensus_overrides && self.debug_events {
+            return Err(RuntimeConfigError::DevChainOnly("debug_events"));
+        }
+        Ok(())
+    }
+
+    /// Makes this config the one used by every runtime executor of the process.
+    ///
+    /// Installing the same config twice is a no-op. Fails if a different config was installed, or
+    /// if an execution already started with the default one: the module cache and executors are
+    /// created lazily on first use and cannot be reconfigured afterward.
+    pub fn install(self) -> Result<(), RuntimeConfigError> {
+        let installed = INSTALLED_RUNTIME_CONFIG.get_or_init(|| self.clone());
+        if *installed != self {
+            return Err(RuntimeConfigError::AlreadyInstalled);
+        }
+        Ok(())
+    }
+
+    /// Returns the installed config, freezing the default one if nothing was installed yet.
+    pub fn installed() -> &'static RuntimeConfig {
+        INSTALLED_RUNTIME_CONFIG.get_or_init(RuntimeConfig::default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_is_valid_everywhere() {
+        assert_eq!(RuntimeConfig::default().validate(false), Ok(()));
+        assert_eq!(RuntimeConfig::default().validate(true), Ok(()));
+    }
+
+    #[test]
+    fn node_local_knobs_can_change_on_any_chain() {
+        let config = RuntimeConfig {
+            cached_modules_size_limit: 64 * 1024 * 1024,
+            module_cache_dir: Some(PathBuf::from("/tmp/fluent-module-cache")),
+            ..Default::default()
+        };
+        assert_eq!(config.validate(false), Ok(()));
+    }
+
+    #[test]
+    fn consensus_critical_knobs_require_dev_chain() {
+        let config = RuntimeConfig {
+            max_in_flight_memory_bytes: MAX_IN_FLIGHT_MEMORY_BYTES / 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.validate(false),
+            Err(RuntimeConfigError::ConsensusCriticalOverride {
+                knob: "max_in_flight_memory_bytes",
+                value: MAX_IN_FLIGHT_MEMORY_BYTES / 2,
+                expected: MAX_IN_FLIGHT_MEMORY_BYTES,
+            })
+        );
+        assert_eq!(config.validate(true), Ok(()));
+    }
+
+    #[test]
+    fn debug_events_require_dev_chain() {
+        let config = RuntimeConfig {
+            debug_events: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.validate(false),
+            Err(RuntimeConfigError::DevChainOnly("debug_events"))
+        );
+        assert_eq!(config.validate(true), Ok(()));
+    }
+
+    #[test]
+    fn zero_limits_are_rejected() {
+        let config = RuntimeConfig {
+            cached_modules_size_limit: 0,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.validate(true),
+            Err(RuntimeConfigError::ZeroLimit("cached_modules_size_limit"))
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{executor::ExecutionResult, syscall_handler::InterruptionHolder};
-use fluentbase_types::{Bytes, ExitCode, CALL_DEPTH_ROOT, STATE_MAIN};
+use fluentbase_types::{ActiveRuntimeForks, Bytes, ExitCode, CALL_DEPTH_ROOT, STATE_MAIN};
 
 /// Per-invocation execution context carried inside the VM store.
 #[derive(Debug, Clone)]
@@ -16,6 +16,10 @@ pub struct RuntimeContext {
     pub execution_result: ExecutionResult,
     /// Deferred invocation metadata used to resume an interrupted call.
     pub resumable_context: Option<InterruptionHolder>,
+    /// Gated runtime features active for the executed block.
+    pub forks: ActiveRuntimeForks,
+    /// Whether `_debug_event` records are collected into the execution result.
+    pub debug_events: bool,
 }
 
 impl Default for RuntimeContext {
@@ -27,6 +31,8 @@ impl Default for RuntimeContext {
             input: Bytes::default(),
             execution_result: ExecutionResult::default(),
             resumable_context: None,
+            forks: ActiveRuntimeForks::default(),
+            debug_events: false,
         }
     }
 }
@@ -56,6 +62,18 @@ impl RuntimeContext {
         self
     }
 
+    /// Sets the runtime features active for this invocation.
+    pub fn with_forks(mut self, forks: ActiveRuntimeForks) -> Self {
+        self.forks = forks;
+        self
+    }
+
+    /// Enables collection of `_debug_event` records for this invocation.
+    pub fn with_debug_events(mut self, debug_events: bool) -> Self {
+        self.debug_events = debug_events;
+        self
+    }
+
     /// Extract serialized resumable context
     pub fn take_resumable_context_serialized(&mut self) -> Result<Option<Vec<u8>>, ExitCode> {
         // Take resumable context from execution context
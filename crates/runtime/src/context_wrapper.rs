@@ -1,6 +1,6 @@
 use crate::{syscall_handler::*, RuntimeContext};
 use alloc::borrow::Cow;
-use fluentbase_types::{BytecodeOrHash, ExitCode, NativeAPI, UnwrapExitCode};
+use fluentbase_types::{BytecodeOrHash, DebugEventFormat, ExitCode, NativeAPI, UnwrapExitCode};
 use std::cell::RefCell;
 
 #[derive(Default)]
@@ -121,4 +121,8 @@ impl NativeAPI for RuntimeContextWrapper {
     fn write_fd(&self, fd: u32, slice: &[u8]) {
         syscall_write_fd_impl(&mut self.ctx.borrow_mut(), fd, slice).unwrap_exit_code();
     }
+
+    fn debug_event(&self, channel: u32, format: DebugEventFormat, data: &[u8]) {
+        syscall_debug_event_impl(&mut self.ctx.borrow_mut(), channel, format as u32, data);
+    }
 }
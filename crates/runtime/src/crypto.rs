@@ -2,8 +2,9 @@ use crate::{syscall_handler::*, RuntimeContextWrapper};
 use fluentbase_types::{
     CryptoAPI, UnwrapExitCode, BLS12381_FP_SIZE, BLS12381_G1_COMPRESSED_SIZE,
     BLS12381_G1_RAW_AFFINE_SIZE, BN254_FP_SIZE, BN254_G1_RAW_AFFINE_SIZE,
-    ED25519_POINT_COMPRESSED_SIZE, ED25519_POINT_DECOMPRESSED_SIZE, SECP256K1_G1_COMPRESSED_SIZE,
-    SECP256K1_G1_RAW_AFFINE_SIZE, SECP256R1_G1_COMPRESSED_SIZE, SECP256R1_G1_RAW_AFFINE_SIZE,
+    ED25519_POINT_COMPRESSED_SIZE, ED25519_POINT_DECOMPRESSED_SIZE, POSEIDON_ENDIANNESS_BIG,
+    POSEIDON_PARAMETERS_BN254_X5, SECP256K1_G1_COMPRESSED_SIZE, SECP256K1_G1_RAW_AFFINE_SIZE,
+    SECP256R1_G1_COMPRESSED_SIZE, SECP256R1_G1_RAW_AFFINE_SIZE,
 };
 
 #[rustfmt::skip]
@@ -20,6 +21,10 @@ impl CryptoAPI for RuntimeContextWrapper {
     fn sha256_compress(state: &mut [u32; 8], w: &[u32; 64]) {
         syscall_hashing_sha256_compress_impl(state, w);
     }
+    #[inline(always)]
+    fn poseidon_bn254(data: &[u8]) -> Option<[u8; 32]> {
+        syscall_hashing_poseidon_impl(POSEIDON_PARAMETERS_BN254_X5, POSEIDON_ENDIANNESS_BIG, data).unwrap_exit_code().map(|hash| hash.0)
+    }
 
     #[inline(always)]
     fn ed25519_decompress(y: [u8; ED25519_POINT_COMPRESSED_SIZE], sign: u32) -> [u8; ED25519_POINT_DECOMPRESSED_SIZE] {
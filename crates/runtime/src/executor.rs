@@ -1,13 +1,14 @@
+#[cfg(feature = "profiler")]
+use crate::profiler;
 use crate::{
     metrics::{self, RuntimeModeLabel, RuntimeTimer},
     module_factory::ModuleFactory,
     runtime::{ContractRuntime, ExecutionMode, SystemRuntime},
-    RuntimeContext,
+    RuntimeConfig, RuntimeContext,
 };
 use fluentbase_types::{
     byteorder::{ByteOrder, LittleEndian},
-    import_linker_v1_preview, Address, BytecodeOrHash, ExitCode, HashMap, B256,
-    MAX_IN_FLIGHT_MEMORY_BYTES,
+    import_linker_v1_preview, Address, BytecodeOrHash, DebugEvent, ExitCode, HashMap, B256,
 };
 use rwasm::{ExecutionEngine, ImportLinker, RwasmModule, StrategyDefinition, TrapCode};
 use std::{cell::RefCell, mem::take, sync::Arc};
@@ -27,6 +28,8 @@ pub struct ExecutionResult {
     pub output: Vec<u8>,
     /// Return data propagated back to the parent on success paths of nested calls.
     pub return_data: Vec<u8>,
+    /// Debug records emitted through `_debug_event`, only collected on development nodes.
+    pub debug_events: Vec<DebugEvent>,
 }
 
 impl ExecutionResult {
@@ -50,6 +53,8 @@ pub struct ExecutionInterruption {
     pub fuel_refunded: i64,
     /// Encoded interruption payload (e.g., delegated call parameters).
     pub return_data: Vec<u8>,
+    /// Debug records emitted before the interruption point.
+    pub debug_events: Vec<DebugEvent>,
 }
 
 /// Result of running or resuming a runtime.
@@ -113,7 +118,7 @@ pub trait RuntimeExecutor {
 pub struct ThreadLocalExecutor;
 
 thread_local! {
-    pub static LOCAL_RUNTIME_EXECUTOR: RefCell<RuntimeFactoryExecutor> = RefCell::new(RuntimeFactoryExecutor::new(import_linker_v1_preview()));
+    pub static LOCAL_RUNTIME_EXECUTOR: RefCell<RuntimeFactoryExecutor> = RefCell::new(RuntimeFactoryExecutor::with_config(import_linker_v1_preview(), RuntimeConfig::installed()));
 }
 
 impl RuntimeExecutor for ThreadLocalExecutor {
@@ -190,19 +195,27 @@ pub struct RuntimeFactoryExecutor {
     pub transaction_call_id_counter: u32,
     /// Ceiling on linear memory held simultaneously by all live frames of one transaction.
     ///
-    /// Defaults to [`MAX_IN_FLIGHT_MEMORY_BYTES`]; overridable so tests can exercise the limit
-    /// without allocating gigabytes.
+    /// Defaults to [`fluentbase_types::MAX_IN_FLIGHT_MEMORY_BYTES`]; overridable so tests can exercise the limit
+    /// without allocating gigabytes. Consensus-critical, see [`RuntimeConfig`].
     pub max_in_flight_memory_bytes: u64,
 }
 
 impl RuntimeFactoryExecutor {
     pub fn new(import_linker: Arc<ImportLinker>) -> Self {
+        Self::with_config(import_linker, &RuntimeConfig::default())
+    }
+
+    /// Creates an executor with the limits of `config`.
+    ///
+    /// The module cache is process-wide and is sized by the installed config only, see
+    /// [`RuntimeConfig::install`].
+    pub fn with_config(import_linker: Arc<ImportLinker>, config: &RuntimeConfig) -> Self {
         Self {
             module_factory: ModuleFactory::new(),
             recoverable_runtimes: HashMap::new(),
             import_linker,
             transaction_call_id_counter: 1,
-            max_in_flight_memory_bytes: MAX_IN_FLIGHT_MEMORY_BYTES,
+            max_in_flight_memory_bytes: config.max_in_flight_memory_bytes,
         }
     }
 
@@ -245,6 +258,7 @@ impl RuntimeFactoryExecutor {
                 fuel_refunded: interruption.fuel_refunded,
                 output: vec![],
                 return_data: vec![],
+                debug_events: interruption.debug_events,
             };
         }
 
@@ -263,6 +277,7 @@ impl RuntimeFactoryExecutor {
             // The output we map into return data
             output: interruption.return_data,
             return_data: vec![],
+            debug_events: interruption.debug_events,
         }
     }
 
@@ -310,6 +325,7 @@ impl RuntimeFactoryExecutor {
                 fuel_consumed,
                 fuel_refunded,
                 mut return_data,
+                debug_events,
                 ..
             } = execution_result;
             // A case for normal interruption (not system runtime interruption), where we should
@@ -329,6 +345,7 @@ impl RuntimeFactoryExecutor {
                         fuel_refunded,
                         output: vec![],
                         return_data: vec![],
+                        debug_events,
                     });
                 }
             }
@@ -336,6 +353,7 @@ impl RuntimeFactoryExecutor {
                 fuel_consumed,
                 fuel_refunded,
                 return_data,
+                debug_events,
             });
         }
 
@@ -358,8 +376,18 @@ impl RuntimeExecutor for RuntimeFactoryExecutor {
             BytecodeOrHash::Hash(_) => None,
         };
 
+        #[cfg(feature = "profiler")]
+        let (profiled_address, profiled_hash) = match &bytecode_or_hash {
+            BytecodeOrHash::Bytecode { address, hash, .. } => (Some(*address), *hash),
+            BytecodeOrHash::Hash(hash) => (None, *hash),
+        };
+        #[cfg(feature = "profiler")]
+        let profiled_state = ctx.state;
+
         // If we have a cached module, then use it, otherwise create a new one and cache
         let module = self.module_factory.get_module_or_init(bytecode_or_hash);
+        #[cfg(feature = "profiler")]
+        let profiled_module = module.clone();
 
         // If there is no cached store, then construct a new one (slow)
         let fuel_limit_value = ctx.fuel_limit;
@@ -397,6 +425,7 @@ impl RuntimeExecutor for RuntimeFactoryExecutor {
                     fuel_refunded: 0,
                     output: vec![],
                     return_data: vec![],
+                    debug_events: vec![],
                 };
                 metrics::record_execution(RuntimeModeLabel::Contract, state, &timer, &result);
                 return result;
@@ -425,12 +454,20 @@ impl RuntimeExecutor for RuntimeFactoryExecutor {
                 fuel_refunded: 0,
                 output: vec![],
                 return_data: vec![],
+                debug_events: vec![],
             };
             metrics::record_execution(mode, state, &timer, &result);
             return result;
         }
 
         // Execute program
+        #[cfg(feature = "profiler")]
+        profiler::enter_frame(
+            || profiler::frame_label(profiled_address.as_ref(), &profiled_hash, profiled_state),
+            profiled_hash,
+            &profiled_module,
+            exec_mode.remaining_fuel(),
+        );
         let result = exec_mode.execute();
         let fuel_consumed = exec_mode
             .remaining_fuel()
@@ -439,7 +476,11 @@ impl RuntimeExecutor for RuntimeFactoryExecutor {
 
         let runtime_result =
             self.handle_execution_result(result, fuel_consumed, exec_mode.context_mut());
+        let remaining_fuel = exec_mode.remaining_fuel();
         let result = self.try_remember_runtime(runtime_result, exec_mode);
+        // Positive exit codes are call ids of interrupted frames that will be resumed later.
+        #[cfg(feature = "profiler")]
+        profiler::leave_frame(remaining_fuel, result.exit_code <= 0);
         metrics::record_execution(mode, state, &timer, &result);
         metrics::set_recoverable_runtimes(self.recoverable_runtimes.len());
         result
@@ -465,6 +506,7 @@ impl RuntimeExecutor for RuntimeFactoryExecutor {
                 fuel_refunded: 0,
                 output: vec![],
                 return_data: vec![],
+                debug_events: vec![],
             };
         };
         metrics::set_recoverable_runtimes(self.recoverable_runtimes.len());
@@ -481,6 +523,8 @@ impl RuntimeExecutor for RuntimeFactoryExecutor {
             }
             runtime.resume(exit_code, fuel_consumed)
         };
+        #[cfg(feature = "profiler")]
+        profiler::resume_frame(fuel_remaining.map(|v| v.saturating_sub(fuel_consumed)));
         let result = resume_inner(&mut runtime);
         // We need to adjust the fuel limit because `fuel_consumed` should not be included into spent.
         if result != Err(TrapCode::OutOfFuel) {
@@ -492,7 +536,11 @@ impl RuntimeExecutor for RuntimeFactoryExecutor {
             .and_then(|remaining_fuel| Some(fuel_remaining? - remaining_fuel));
         let runtime_result =
             self.handle_execution_result(result, fuel_consumed, runtime.context_mut());
+        let remaining_fuel = runtime.remaining_fuel();
         let result = self.try_remember_runtime(runtime_result, runtime);
+        // Positive exit codes are call ids of interrupted frames that will be resumed later.
+        #[cfg(feature = "profiler")]
+        profiler::leave_frame(remaining_fuel, result.exit_code <= 0);
         metrics::record_resume(mode, state, &timer, &result);
         metrics::set_recoverable_runtimes(self.recoverable_runtimes.len());
         result
@@ -500,6 +548,8 @@ impl RuntimeExecutor for RuntimeFactoryExecutor {
 
     fn forget_runtime(&mut self, call_id: u32) {
         if let Some(runtime) = self.recoverable_runtimes.remove(&call_id) {
+            #[cfg(feature = "profiler")]
+            profiler::leave_frame(runtime.remaining_fuel(), true);
             let (mode, state) = runtime_labels(&runtime);
             metrics::record_forget_runtime(mode, state);
         }
@@ -580,6 +630,7 @@ mod tests {
             fuel_consumed: 100,
             fuel_refunded: 0,
             return_data: vec![1, 2, 3],
+            debug_events: vec![],
         });
 
         let engine = ExecutionEngine::acquire_shared();
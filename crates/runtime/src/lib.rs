@@ -6,17 +6,24 @@ extern crate core;
 #[cfg(test)]
 use wat as _;
 
+mod config;
 mod context;
 #[cfg(feature = "std")]
 mod context_wrapper;
 mod crypto;
 mod executor;
 mod metrics;
+mod module_cache;
 mod module_factory;
+#[cfg(feature = "profiler")]
+pub mod profiler;
 pub mod runtime;
 pub mod syscall_handler;
 
+pub use config::{RuntimeConfig, RuntimeConfigError};
 pub use context::*;
 #[cfg(feature = "std")]
 pub use context_wrapper::*;
 pub use executor::{default_runtime_executor, RuntimeExecutor};
+pub use module_cache::{DiskModuleCache, ModuleCacheBackend};
+pub use module_factory::{ModuleFactory, CACHED_MODULES_SIZE_LIMIT};
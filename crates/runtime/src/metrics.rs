@@ -180,6 +180,43 @@ pub fn record_system_runtime_cache_invalidation(
     .increment(1);
 }
 
+/// Compiled module cache tier consulted by a lookup.
+#[derive(Clone, Copy, Debug)]
+pub enum ModuleCacheTier {
+    Memory,
+    Persistent,
+}
+
+impl ModuleCacheTier {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Persistent => "persistent",
+        }
+    }
+}
+
+/// Records whether a compiled module lookup was served by the given cache tier.
+pub fn record_module_cache_lookup(tier: ModuleCacheTier, cache_hit: bool) {
+    #[cfg(feature = "std")]
+    metrics::counter!(
+        "fluentbase_module_cache_lookups_total",
+        "tier" => tier.as_str(),
+        "result" => if cache_hit { "hit" } else { "miss" },
+    )
+    .increment(1);
+}
+
+/// Records a persistent module cache failure; the lookup falls back as if the entry was missing.
+pub fn record_module_cache_error(operation: &'static str) {
+    #[cfg(feature = "std")]
+    metrics::counter!(
+        "fluentbase_module_cache_errors_total",
+        "operation" => operation,
+    )
+    .increment(1);
+}
+
 /// Exposes the active cache-key version for operational dashboards.
 pub fn set_compilation_cache_fingerprint_version() {
     #[cfg(feature = "std")]
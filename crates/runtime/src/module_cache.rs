@@ -0,0 +1,308 @@
+//! Persistent tier for compiled rWasm modules.
+//!
+//! [`ModuleFactory`](crate::ModuleFactory) keeps hot modules in an in-memory LRU; a
+//! [`ModuleCacheBackend`] sits behind it so evicted modules and modules compiled by a previous
+//! process can be restored without the original bytecode. Every entry is keyed by the full
+//! [`CompiledModuleCacheKey`], so a module compiled under a different configuration is never
+//! returned for the same code hash.
+use crate::metrics::record_module_cache_error;
+use fluentbase_types::{keccak256, CompilationConfigFingerprint, CompiledModuleCacheKey, B256};
+use rwasm::RwasmModule;
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Storage for compiled modules that outlives the in-memory cache.
+///
+/// Implementations are best-effort: a failed read is reported as a miss and a failed write is
+/// dropped, the factory always falls back to compiling from bytecode when it has it.
+pub trait ModuleCacheBackend: Send + Sync {
+    /// Loads the module compiled for exactly this key.
+    fn get(&self, key: &CompiledModuleCacheKey) -> Option<RwasmModule>;
+
+    /// Resolves the key a code hash was last compiled under, used for hash-only lookups.
+    fn get_key(&self, code_hash: &B256) -> Option<CompiledModuleCacheKey>;
+
+    /// Persists a freshly compiled module and records its key in the code-hash index.
+    fn insert(&self, key: &CompiledModuleCacheKey, module: &RwasmModule);
+}
+
+/// Magic prefix of a module record, bumped whenever the record layout changes.
+const RECORD_MAGIC: [u8; 4] = *b"FMC1";
+
+/// File-system backed [`ModuleCacheBackend`].
+///
+/// Layout under the cache directory:
+/// - `modules/<code_hash>-<fingerprint_hash>.bin` - one record per compiled module
+/// - `code-hashes/<code_hash>` - stable fingerprint bytes of the latest key for the code hash
+///
+/// A record stores the fingerprint it was compiled under and a checksum of the serialized module;
+/// both are verified on load, so a truncated or foreign file is treated as a miss. Files are
+/// written to a temporary path and renamed into place, so concurrent readers never observe a
+/// partially written entry.
+#[derive(Debug)]
+pub struct DiskModuleCache {
+    dir: PathBuf,
+    tmp_counter: AtomicU64,
+}
+
+impl DiskModuleCache {
+    /// Opens (and creates if needed) a module cache rooted at `dir`.
+    pub fn open(dir: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(dir.join("modules"))?;
+        fs::create_dir_all(dir.join("code-hashes"))?;
+        Ok(Self {
+            dir,
+            tmp_counter: AtomicU64::new(0),
+        })
+    }
+
+    /// Root directory of the cache.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn module_path(&self, key: &CompiledModuleCacheKey) -> PathBuf {
+        self.dir.join("modules").join(format!(
+            "{:x}-{:x}.bin",
+            key.code_hash,
+            key.config_fingerprint.identity_hash()
+        ))
+    }
+
+    fn index_path(&self, code_hash: &B256) -> PathBuf {
+        self.dir.join("code-hashes").join(format!("{code_hash:x}"))
+    }
+
+    fn write_atomic(&self, path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+        let tmp_path = path.with_extension(format!(
+            "tmp-{}-{}",
+            std::process::id(),
+            self.tmp_counter.fetch_add(1, Ordering::Relaxed)
+        ));
+        let result = fs::File::create(&tmp_path)
+            .and_then(|mut file| file.write_all(bytes).and_then(|_| file.sync_all()))
+            .and_then(|_| fs::rename(&tmp_path, path));
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp_path);
+        }
+        result
+    }
+}
+
+fn encode_record(fingerprint: &CompilationConfigFingerprint, module: &[u8]) -> Vec<u8> {
+    let fingerprint = fingerprint.stable_bytes();
+    let mut record =
+        Vec::with_capacity(RECORD_MAGIC.len() + 2 + fingerprint.len() + 32 + module.len());
+    record.extend_from_slice(&RECORD_MAGIC);
+    record.extend_from_slice(&(fingerprint.len() as u16).to_le_bytes());
+    record.extend_from_slice(&fingerprint);
+    record.extend_from_slice(keccak256(module).as_slice());
+    record.extend_from_slice(module);
+    record
+}
+
+/// Returns the serialized module if the record matches `fingerprint` and its checksum holds.
+fn decode_record<'a>(
+    record: &'a [u8],
+    fingerprint: &CompilationConfigFingerprint,
+) -> Option<&'a [u8]> {
+    let record = record.strip_prefix(&RECORD_MAGIC)?;
+    let (fingerprint_len, record) = record.split_first_chunk::<2>()?;
+    let fingerprint_len = u16::from_le_bytes(*fingerprint_len) as usize;
+    if record.len() < fingerprint_len {
+        return None;
+    }
+    let (stored_fingerprint, record) = record.split_at(fingerprint_len);
+    if CompilationConfigFingerprint::from_stable_bytes(stored_fingerprint)? != *fingerprint {
+        return None;
+    }
+    let (checksum, module) = record.split_first_chunk::<32>()?;
+    (keccak256(module).0 == *checksum).then_some(module)
+}
+
+impl ModuleCacheBackend for DiskModuleCache {
+    fn get(&self, key: &CompiledModuleCacheKey) -> Option<RwasmModule> {
+        let record = match fs::read(self.module_path(key)) {
+            Ok(record) => record,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(_) => {
+                record_module_cache_error("read");
+                return None;
+            }
+        };
+        let Some(module) = decode_record(&record, &key.config_fingerprint) else {
+            record_module_cache_error("verify");
+            return None;
+        };
+        match RwasmModule::new_checked(module) {
+            Ok((module, _)) => Some(module),
+            Err(_) => {
+                record_module_cache_error("decode");
+                None
+            }
+        }
+    }
+
+    fn get_key(&self, code_hash: &B256) -> Option<CompiledModuleCacheKey> {
+        let fingerprint = match fs::read(self.index_path(code_hash)) {
+            Ok(fingerprint) => fingerprint,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(_) => {
+                record_module_cache_error("read");
+                return None;
+            }
+        };
+        let Some(fingerprint) = CompilationConfigFingerprint::from_stable_bytes(&fingerprint)
+        else {
+            record_module_cache_error("verify");
+            return None;
+        };
+        Some(CompiledModuleCacheKey::new(*code_hash, fingerprint))
+    }
+
+    fn insert(&self, key: &CompiledModuleCacheKey, module: &RwasmModule) {
+        let module_path = self.module_path(key);
+        if !module_path.exists() {
+            let record = encode_record(&key.config_fingerprint, &module.serialize());
+            if self.write_atomic(&module_path, &record).is_err() {
+                record_module_cache_error("write");
+                return;
+            }
+        }
+        // The index is only written once the module record is in place, so a hash-only lookup
+        // that resolves a key can always load its module.
+        if self.get_key(&key.code_hash) != Some(*key)
+            && self
+                .write_atomic(
+                    &self.index_path(&key.code_hash),
+                    &key.config_fingerprint.stable_bytes(),
+                )
+                .is_err()
+        {
+            record_module_cache_error("write");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluentbase_types::{Address, CompilationBackend};
+    use rwasm::{InstructionSet, RwasmModuleInner};
+
+    fn module(hint: &[u8]) -> RwasmModule {
+        RwasmModuleInner {
+            code_section: InstructionSet::default(),
+            hint_section: hint.to_vec(),
+            data_section: vec![1, 2, 3],
+            elem_section: vec![],
+            source_pc: 0,
+        }
+        .into()
+    }
+
+    fn cache_key(code_hash_byte: u8, address_byte: u8) -> CompiledModuleCacheKey {
+        CompiledModuleCacheKey::new(
+            B256::repeat_byte(code_hash_byte),
+            CompilationConfigFingerprint::from_config(
+                &rwasm::CompilationConfig::default(),
+                CompilationBackend::Rwasm,
+                Address::repeat_byte(address_byte),
+            ),
+        )
+    }
+
+    #[test]
+    fn fingerprint_stable_bytes_roundtrip() {
+        let fingerprint = cache_key(1, 0xaa).config_fingerprint;
+        let bytes = fingerprint.stable_bytes();
+
+        assert_eq!(
+            CompilationConfigFingerprint::from_stable_bytes(&bytes),
+            Some(fingerprint)
+        );
+        assert_eq!(
+            CompilationConfigFingerprint::from_stable_bytes(&bytes[..bytes.len() - 1]),
+            None
+        );
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            CompilationConfigFingerprint::from_stable_bytes(&trailing),
+            None
+        );
+    }
+
+    #[test]
+    fn insert_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskModuleCache::open(dir.path()).unwrap();
+        let key = cache_key(1, 0xaa);
+        let module = module(&[7u8; 64]);
+
+        assert!(cache.get(&key).is_none());
+        assert!(cache.get_key(&key.code_hash).is_none());
+
+        cache.insert(&key, &module);
+
+        assert_eq!(cache.get(&key), Some(module.clone()));
+        assert_eq!(cache.get_key(&key.code_hash), Some(key));
+
+        // A fresh handle over the same directory sees the entry, as a restarted node would.
+        let reopened = DiskModuleCache::open(dir.path()).unwrap();
+        assert_eq!(reopened.get(&key), Some(module));
+    }
+
+    #[test]
+    fn different_fingerprint_is_a_miss() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskModuleCache::open(dir.path()).unwrap();
+        let key = cache_key(1, 0xaa);
+        let other = cache_key(1, 0xbb);
+        assert_ne!(key.config_fingerprint, other.config_fingerprint);
+
+        cache.insert(&key, &module(&[1u8; 16]));
+
+        assert!(cache.get(&other).is_none());
+        // Even a record copied under the other key's file name is rejected by its fingerprint.
+        fs::copy(cache.module_path(&key), cache.module_path(&other)).unwrap();
+        assert!(cache.get(&other).is_none());
+    }
+
+    #[test]
+    fn corrupt_record_is_a_miss() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskModuleCache::open(dir.path()).unwrap();
+        let key = cache_key(2, 0xaa);
+
+        cache.insert(&key, &module(&[3u8; 32]));
+        let path = cache.module_path(&key);
+        let mut record = fs::read(&path).unwrap();
+        *record.last_mut().unwrap() ^= 0xff;
+        fs::write(&path, &record).unwrap();
+        assert!(cache.get(&key).is_none());
+
+        fs::write(&path, &record[..8]).unwrap();
+        assert!(cache.get(&key).is_none());
+    }
+
+    #[test]
+    fn index_follows_latest_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskModuleCache::open(dir.path()).unwrap();
+        let key_a = cache_key(3, 0xaa);
+        let key_b = cache_key(3, 0xbb);
+
+        cache.insert(&key_a, &module(&[1u8; 8]));
+        cache.insert(&key_b, &module(&[2u8; 8]));
+
+        assert_eq!(cache.get_key(&key_a.code_hash), Some(key_b));
+        assert!(cache.get(&key_a).is_some());
+        assert!(cache.get(&key_b).is_some());
+    }
+}
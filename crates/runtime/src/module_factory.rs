@@ -1,3 +1,8 @@
+use crate::{
+    metrics::{record_module_cache_error, record_module_cache_lookup, ModuleCacheTier},
+    module_cache::{DiskModuleCache, ModuleCacheBackend},
+    RuntimeConfig,
+};
 use fluentbase_types::{
     BytecodeOrHash, CompilationBackend, CompilationConfigFingerprint, CompiledModuleCacheKey, B256,
 };
@@ -16,51 +21,124 @@ pub struct ModuleFactory {
 
 impl ModuleFactory {
     /// Creates a factory configured for the v1 preview import surface.
+    ///
+    /// The factory is process-wide and sized from [`RuntimeConfig::installed`] on first use.
     pub fn new() -> Self {
-        static INSTANCE: LazyLock<ModuleFactory> = LazyLock::new(|| ModuleFactory {
-            inner: Arc::new(Mutex::new(ModuleFactoryInner::default())),
+        static INSTANCE: LazyLock<ModuleFactory> = LazyLock::new(|| {
+            let config = RuntimeConfig::installed();
+            let factory = ModuleFactory {
+                inner: Arc::new(Mutex::new(ModuleFactoryInner::new(
+                    config.cached_modules_size_limit,
+                ))),
+            };
+            if let Some(dir) = &config.module_cache_dir {
+                match DiskModuleCache::open(dir) {
+                    Ok(cache) => factory.set_persistent_cache(Arc::new(cache)),
+                    Err(_) => record_module_cache_error("open"),
+                }
+            }
+            factory
         });
         INSTANCE.clone()
     }
 
+    /// Attaches a persistent tier behind the in-memory cache.
+    ///
+    /// Freshly compiled modules are written through to `backend`, and lookups that miss memory
+    /// are served from it, including hash-only lookups after a restart.
+    pub fn set_persistent_cache(&self, backend: Arc<dyn ModuleCacheBackend>) {
+        self.inner.lock().unwrap().persistent_cache = Some(backend);
+    }
+
+    /// Returns the module cached for `code_hash`, if any, without compiling or panicking.
+    ///
+    /// Looks in memory first and then in the persistent tier. Unlike a hash-only
+    /// [`Self::get_module_or_init`] this is safe to call for arbitrary hashes, e.g. from RPC.
+    pub fn get_cached_module(&self, code_hash: &B256) -> Option<RwasmModule> {
+        let backend = {
+            let mut ctx = self.inner.lock().unwrap();
+            if let Some(module_key) = ctx.module_keys_by_code_hash.get(code_hash).copied() {
+                if let Some(module) = ctx.cached_modules.get(&module_key) {
+                    return Some(module.clone());
+                }
+            }
+            ctx.persistent_cache.clone()?
+        };
+        // Disk reads happen without the lock, so a slow lookup never stalls executors
+        let module_key = backend.get_key(code_hash)?;
+        backend.get(&module_key)
+    }
+
     /// Returns a cached module for the given bytecode or compiles and caches it on first use.
+    ///
+    /// The factory lock only guards the in-memory tier: the persistent tier is read and written
+    /// after it is released, so a cache miss never stalls other executors or RPC lookups on disk
+    /// I/O.
     pub fn get_module_or_init(&mut self, bytecode_or_hash: BytecodeOrHash) -> RwasmModule {
-        let mut ctx = self.inner.lock().unwrap();
         let code_hash = bytecode_or_hash.code_hash();
-        let module_key = match &bytecode_or_hash {
-            BytecodeOrHash::Bytecode { address, .. } => CompiledModuleCacheKey::new(
-                code_hash,
-                CompilationConfigFingerprint::from_config(
-                    &fluentbase_sdk_config_for_runtime_cache(*address),
-                    CompilationBackend::Rwasm,
-                    *address,
-                ),
-            ),
+        let mut ctx = self.inner.lock().unwrap();
+        match bytecode_or_hash {
+            BytecodeOrHash::Bytecode {
+                bytecode, address, ..
+            } => {
+                let module_key = CompiledModuleCacheKey::new(
+                    code_hash,
+                    CompilationConfigFingerprint::from_config(
+                        &fluentbase_sdk_config_for_runtime_cache(address),
+                        CompilationBackend::Rwasm,
+                        address,
+                    ),
+                );
+                if let Some(entry) = ctx.cached_modules.get(&module_key) {
+                    record_module_cache_lookup(ModuleCacheTier::Memory, true);
+                    return entry.clone();
+                }
+                record_module_cache_lookup(ModuleCacheTier::Memory, false);
+
+                ctx.module_keys_by_code_hash.insert(code_hash, module_key);
+                ctx.cached_modules.insert(module_key, bytecode.clone());
+                let backend = ctx.persistent_cache.clone();
+                drop(ctx);
+
+                // Writing through syncs the record to disk, which must not happen under the lock
+                if let Some(backend) = backend {
+                    backend.insert(&module_key, &bytecode);
+                }
+                bytecode
+            }
             BytecodeOrHash::Hash(_hash) => {
-                // Hash-only lookups are only valid after an earlier bytecode warmup. Keep this
+                // Hash-only lookups are only valid after an earlier bytecode warmup, either in this
+                // process or one that wrote through to the persistent cache. Keep this
                 // deterministic by resolving through the explicit code-hash index.
-                let Some(module_key) = ctx.module_keys_by_code_hash.get(&code_hash).copied() else {
+                let memory_key = ctx.module_keys_by_code_hash.get(&code_hash).copied();
+                if let Some(module_key) = memory_key {
+                    if let Some(entry) = ctx.cached_modules.get(&module_key) {
+                        record_module_cache_lookup(ModuleCacheTier::Memory, true);
+                        return entry.clone();
+                    }
+                }
+                record_module_cache_lookup(ModuleCacheTier::Memory, false);
+                let backend = ctx.persistent_cache.clone();
+                drop(ctx);
+
+                let module_key = memory_key.or_else(|| backend.as_ref()?.get_key(&code_hash));
+                let persistent_module = module_key
+                    .zip(backend)
+                    .and_then(|(module_key, backend)| backend.get(&module_key));
+                record_module_cache_lookup(
+                    ModuleCacheTier::Persistent,
+                    persistent_module.is_some(),
+                );
+                let (Some(module_key), Some(rwasm_module)) = (module_key, persistent_module) else {
                     panic!("runtime: can't compile just by hash")
                 };
-                module_key
-            }
-        };
 
-        if let Some(entry) = ctx.cached_modules.get(&module_key) {
-            return entry.clone();
-        }
-
-        let rwasm_module = match bytecode_or_hash {
-            BytecodeOrHash::Bytecode { bytecode, .. } => bytecode,
-            BytecodeOrHash::Hash(_hash) => {
-                // TODO(dmitry123): Do we want to have lock here until resources are warmed up?
-                panic!("runtime: can't compile just by hash")
+                let mut ctx = self.inner.lock().unwrap();
+                ctx.module_keys_by_code_hash.insert(code_hash, module_key);
+                ctx.cached_modules.insert(module_key, rwasm_module.clone());
+                rwasm_module
             }
-        };
-
-        ctx.module_keys_by_code_hash.insert(code_hash, module_key);
-        ctx.cached_modules.insert(module_key, rwasm_module.clone());
-        rwasm_module
+        }
     }
 }
 
@@ -68,25 +146,33 @@ struct ModuleFactoryInner {
     pub cached_modules:
         LruMap<CompiledModuleCacheKey, RwasmModule, ModuleMemoryLimiter<RwasmModule>>,
     pub module_keys_by_code_hash: std::collections::HashMap<B256, CompiledModuleCacheKey>,
+    pub persistent_cache: Option<Arc<dyn ModuleCacheBackend>>,
 }
 
-/// Maximum memory for module cache: 1 GB
+/// Default maximum memory for module cache: 1 GB, see [`RuntimeConfig::cached_modules_size_limit`]
 ///
 /// This limits only the estimated size of cached module content,
 /// not the hash table overhead (which is negligible for typical workloads).
 pub const CACHED_MODULES_SIZE_LIMIT: usize = 1024 * 1024 * 1024;
 
-impl Default for ModuleFactoryInner {
-    fn default() -> Self {
+impl ModuleFactoryInner {
+    fn new(cached_modules_size_limit: usize) -> Self {
         Self {
             cached_modules: LruMap::new(ModuleMemoryLimiter::<RwasmModule>::new(
-                CACHED_MODULES_SIZE_LIMIT,
+                cached_modules_size_limit,
             )),
             module_keys_by_code_hash: std::collections::HashMap::new(),
+            persistent_cache: None,
         }
     }
 }
 
+impl Default for ModuleFactoryInner {
+    fn default() -> Self {
+        Self::new(CACHED_MODULES_SIZE_LIMIT)
+    }
+}
+
 fn fluentbase_sdk_config_for_runtime_cache(
     address: fluentbase_types::Address,
 ) -> rwasm::CompilationConfig {
@@ -283,6 +369,7 @@ impl<V: SizeEstimator> Limiter<CompiledModuleCacheKey, V> for ModuleMemoryLimite
 mod tests {
     use super::*;
     use rwasm::{InstructionSet, RwasmModuleInner};
+    use std::sync::mpsc;
 
     // ==================== Test Helpers ====================
 
@@ -563,4 +650,128 @@ mod tests {
         assert!(cache.get(&cache_key(250)).is_some());
         assert!(cache.get(&cache_key(499)).is_some());
     }
+
+    // ==================== Persistent Tier ====================
+
+    fn isolated_factory() -> ModuleFactory {
+        ModuleFactory {
+            inner: Arc::new(Mutex::new(ModuleFactoryInner::default())),
+        }
+    }
+
+    #[test]
+    fn cold_hash_lookup_loads_from_persistent_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let code_hash = key(7);
+        let bytecode = module(100);
+
+        let mut warm = isolated_factory();
+        warm.set_persistent_cache(Arc::new(crate::DiskModuleCache::open(dir.path()).unwrap()));
+        warm.get_module_or_init(BytecodeOrHash::Bytecode {
+            bytecode: bytecode.clone(),
+            hash: code_hash,
+            address: fluentbase_types::Address::repeat_byte(0xaa),
+        });
+
+        // A factory with an empty in-memory cache, as after a restart, resolves the hash on disk.
+        let mut cold = isolated_factory();
+        cold.set_persistent_cache(Arc::new(crate::DiskModuleCache::open(dir.path()).unwrap()));
+        assert_eq!(
+            cold.get_module_or_init(BytecodeOrHash::Hash(code_hash)),
+            bytecode
+        );
+        // Now served from memory.
+        assert_eq!(cold.inner.lock().unwrap().cached_modules.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't compile just by hash")]
+    fn cold_hash_lookup_without_persistent_entry_panics() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut factory = isolated_factory();
+        factory.set_persistent_cache(Arc::new(crate::DiskModuleCache::open(dir.path()).unwrap()));
+        factory.get_module_or_init(BytecodeOrHash::Hash(key(8)));
+    }
+
+    /// Backend whose writes block until released, standing in for a slow fsync
+    struct BlockingBackend {
+        entered: Mutex<mpsc::Sender<()>>,
+        release: Mutex<mpsc::Receiver<()>>,
+    }
+
+    impl ModuleCacheBackend for BlockingBackend {
+        fn get(&self, _key: &CompiledModuleCacheKey) -> Option<RwasmModule> {
+            None
+        }
+
+        fn get_key(&self, _code_hash: &B256) -> Option<CompiledModuleCacheKey> {
+            None
+        }
+
+        fn insert(&self, _key: &CompiledModuleCacheKey, _module: &RwasmModule) {
+            self.entered.lock().unwrap().send(()).unwrap();
+            self.release.lock().unwrap().recv().unwrap();
+        }
+    }
+
+    #[test]
+    fn persistent_write_does_not_hold_the_factory_lock() {
+        let (entered_tx, entered_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel();
+        let factory = isolated_factory();
+        factory.set_persistent_cache(Arc::new(BlockingBackend {
+            entered: Mutex::new(entered_tx),
+            release: Mutex::new(release_rx),
+        }));
+
+        let code_hash = key(11);
+        let bytecode = module(100);
+        let writer = {
+            let mut factory = factory.clone();
+            let bytecode = bytecode.clone();
+            std::thread::spawn(move || {
+                factory.get_module_or_init(BytecodeOrHash::Bytecode {
+                    bytecode,
+                    hash: code_hash,
+                    address: fluentbase_types::Address::repeat_byte(0xcc),
+                })
+            })
+        };
+
+        // While the write is in flight the lock is free and the module is served from memory
+        entered_rx.recv().unwrap();
+        assert!(factory.inner.try_lock().is_ok());
+        assert_eq!(
+            factory.get_cached_module(&code_hash),
+            Some(bytecode.clone())
+        );
+
+        release_tx.send(()).unwrap();
+        assert_eq!(writer.join().unwrap(), bytecode);
+    }
+
+    #[test]
+    fn cached_module_lookup_never_compiles() {
+        let dir = tempfile::tempdir().unwrap();
+        let code_hash = key(9);
+        let bytecode = module(100);
+
+        let mut factory = isolated_factory();
+        assert_eq!(factory.get_cached_module(&code_hash), None);
+        factory.set_persistent_cache(Arc::new(crate::DiskModuleCache::open(dir.path()).unwrap()));
+        factory.get_module_or_init(BytecodeOrHash::Bytecode {
+            bytecode: bytecode.clone(),
+            hash: code_hash,
+            address: fluentbase_types::Address::repeat_byte(0xbb),
+        });
+        assert_eq!(
+            factory.get_cached_module(&code_hash),
+            Some(bytecode.clone())
+        );
+
+        let cold = isolated_factory();
+        cold.set_persistent_cache(Arc::new(crate::DiskModuleCache::open(dir.path()).unwrap()));
+        assert_eq!(cold.get_cached_module(&code_hash), Some(bytecode));
+        assert_eq!(cold.get_cached_module(&key(10)), None);
+    }
 }
@@ -0,0 +1,615 @@
+//! Deterministic fuel profiler for rWasm executions.
+//!
+//! Only built with the `profiler` feature, so the hooks never run on a node. Profiling is scoped
+//! to one thread: [`profile_fuel`] arms a thread-local collector, runs the closure, and returns
+//! every fuel sample recorded by the runtime executor meanwhile.
+//!
+//! Samples are taken at host-call boundaries rather than on a timer, so the same transaction
+//! always produces the same profile:
+//! - fuel burnt by Wasm code between two host calls is attributed to the Wasm function issuing the
+//!   second call, as a child of the executing contract frame,
+//! - fuel charged by a host function is attributed to a child frame named after its [`SysFuncIdx`],
+//! - nested calls appear as child frames of the caller that triggered them.
+//!
+//! Functions are resolved from the program counter of the host call with a [`FunctionMap`] and
+//! named after the Wasm name section when the module carries one. Only the function issuing the
+//! call is known, not its Wasm callers, and fuel burnt after the last host call of a frame stays on
+//! the contract frame. The sum of all samples equals the fuel consumed by the traced frames.
+use fluentbase_types::{Address, SysFuncIdx, B256, STATE_DEPLOY};
+use rwasm::{Opcode, RwasmModule};
+use std::{
+    cell::RefCell, collections::BTreeMap, fmt::Write as _, string::String, sync::Arc, vec::Vec,
+};
+
+/// Fuel consumed per call stack, root frame first.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FuelProfile {
+    /// Call stack to `(sample count, fuel)`, ordered so that output is reproducible.
+    pub stacks: BTreeMap<Vec<String>, (u64, u64)>,
+}
+
+impl FuelProfile {
+    /// Total fuel of all samples.
+    pub fn total_fuel(&self) -> u64 {
+        self.stacks.values().map(|(_, fuel)| fuel).sum()
+    }
+
+    /// Renders the profile in the folded-stack format used by `flamegraph.pl` and `inferno`.
+    pub fn to_folded(&self) -> String {
+        let mut result = String::new();
+        for (stack, (_, fuel)) in &self.stacks {
+            let _ = writeln!(result, "{} {fuel}", stack.join(";"));
+        }
+        result
+    }
+
+    /// Encodes the profile as an uncompressed pprof `Profile` protobuf message.
+    ///
+    /// Every sample carries two values, `samples/count` and `fuel/fuel`.
+    pub fn to_pprof(&self) -> Vec<u8> {
+        let mut strings = PprofStrings::default();
+        let mut functions = BTreeMap::<String, u64>::new();
+        let mut message = Vec::new();
+
+        for (kind, unit) in [("samples", "count"), ("fuel", "fuel")] {
+            let mut value_type = Vec::new();
+            pb_varint_field(&mut value_type, 1, strings.index(kind));
+            pb_varint_field(&mut value_type, 2, strings.index(unit));
+            pb_bytes_field(&mut message, 1, &value_type);
+        }
+
+        for (stack, (count, fuel)) in &self.stacks {
+            let mut location_ids = Vec::new();
+            // pprof lists locations leaf first.
+            for frame in stack.iter().rev() {
+                let next_id = functions.len() as u64 + 1;
+                pb_varint(
+                    &mut location_ids,
+                    *functions.entry(frame.clone()).or_insert(next_id),
+                );
+            }
+            let mut values = Vec::new();
+            pb_varint(&mut values, *count);
+            pb_varint(&mut values, *fuel);
+            let mut sample = Vec::new();
+            pb_bytes_field(&mut sample, 1, &location_ids);
+            pb_bytes_field(&mut sample, 2, &values);
+            pb_bytes_field(&mut message, 2, &sample);
+        }
+
+        // Each frame gets one function and one location sharing the same id.
+        let mut functions = functions.into_iter().collect::<Vec<_>>();
+        functions.sort_by_key(|(_, id)| *id);
+        for (_, id) in &functions {
+            let mut line = Vec::new();
+            pb_varint_field(&mut line, 1, *id);
+            let mut location = Vec::new();
+            pb_varint_field(&mut location, 1, *id);
+            pb_bytes_field(&mut location, 4, &line);
+            pb_bytes_field(&mut message, 4, &location);
+        }
+        for (name, id) in &functions {
+            let name = strings.index(name);
+            let mut function = Vec::new();
+            pb_varint_field(&mut function, 1, *id);
+            pb_varint_field(&mut function, 2, name);
+            pb_varint_field(&mut function, 3, name);
+            pb_bytes_field(&mut message, 5, &function);
+        }
+
+        for string in &strings.table {
+            pb_bytes_field(&mut message, 6, string.as_bytes());
+        }
+        message
+    }
+}
+
+#[derive(Default)]
+struct PprofStrings {
+    table: Vec<String>,
+    index: BTreeMap<String, u64>,
+}
+
+impl PprofStrings {
+    fn index(&mut self, value: &str) -> u64 {
+        if self.table.is_empty() {
+            // pprof requires the empty string at index 0.
+            self.table.push(String::new());
+            self.index.insert(String::new(), 0);
+        }
+        if let Some(index) = self.index.get(value) {
+            return *index;
+        }
+        let index = self.table.len() as u64;
+        self.table.push(value.into());
+        self.index.insert(value.into(), index);
+        index
+    }
+}
+
+fn pb_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+fn pb_varint_field(buffer: &mut Vec<u8>, field: u64, value: u64) {
+    pb_varint(buffer, field << 3);
+    pb_varint(buffer, value);
+}
+
+fn pb_bytes_field(buffer: &mut Vec<u8>, field: u64, value: &[u8]) {
+    pb_varint(buffer, (field << 3) | 2);
+    pb_varint(buffer, value.len() as u64);
+    buffer.extend_from_slice(value);
+}
+
+/// Resolves rWasm program counters to the Wasm functions they were compiled from.
+///
+/// rWasm compiles the defined functions of a module in Wasm order and opens each of them with a
+/// stack height check, so the n-th `StackCheck` of the code section is the entry of the n-th
+/// defined function, whose Wasm index comes after every imported function.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FunctionMap {
+    /// Entry program counter of every defined function, ascending.
+    entries: Vec<u32>,
+    /// Number of imported functions, they come first in the Wasm function index space.
+    imported_functions: u32,
+    /// Function names from the Wasm name section, by Wasm function index.
+    names: BTreeMap<u32, String>,
+}
+
+impl FunctionMap {
+    /// Builds the map of a compiled module, with names from its original Wasm if it is present
+    /// in the hint section.
+    pub fn from_module(module: &RwasmModule) -> Self {
+        let entries = module
+            .code_section
+            .iter()
+            .enumerate()
+            .filter(|(_, opcode)| matches!(opcode, Opcode::StackCheck(_)))
+            .map(|(pc, _)| pc as u32)
+            .collect();
+        let (imported_functions, names) =
+            parse_function_names(&module.hint_section).unwrap_or_default();
+        Self {
+            entries,
+            imported_functions,
+            names,
+        }
+    }
+
+    /// Label of the function containing `pc`, `None` for code before the first function, such
+    /// as the entrypoint router.
+    pub fn function_at(&self, pc: u32) -> Option<String> {
+        let index = self
+            .entries
+            .partition_point(|entry| *entry <= pc)
+            .checked_sub(1)?;
+        let wasm_index = self.imported_functions + index as u32;
+        Some(match self.names.get(&wasm_index) {
+            Some(name) => name.clone(),
+            None => format!("func[{wasm_index}]"),
+        })
+    }
+}
+
+/// Reads the imported function count and the function names subsection of a Wasm binary.
+///
+/// Returns `None` if `wasm` isn't a well-formed module; a module without a name section yields
+/// no names.
+fn parse_function_names(wasm: &[u8]) -> Option<(u32, BTreeMap<u32, String>)> {
+    let mut reader = WasmReader(wasm.strip_prefix(b"\0asm\x01\0\0\0")?);
+    let mut imported_functions = 0;
+    let mut names = BTreeMap::new();
+    while !reader.0.is_empty() {
+        let id = reader.byte()?;
+        let mut section = WasmReader(reader.bytes()?);
+        match id {
+            // Import section
+            2 => {
+                for _ in 0..section.leb()? {
+                    section.bytes()?;
+                    section.bytes()?;
+                    match section.byte()? {
+                        0 => {
+                            section.leb()?;
+                            imported_functions += 1;
+                        }
+                        1 => {
+                            section.byte()?;
+                            section.limits()?;
+                        }
+                        2 => section.limits()?,
+                        3 => {
+                            section.byte()?;
+                            section.byte()?;
+                        }
+                        4 => {
+                            section.byte()?;
+                            section.leb()?;
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+            // Custom section
+            0 if section.bytes()? == b"name" => {
+                while !section.0.is_empty() {
+                    let subsection_id = section.byte()?;
+                    let mut subsection = WasmReader(section.bytes()?);
+                    // Only the function names subsection is used.
+                    if subsection_id != 1 {
+                        continue;
+                    }
+                    for _ in 0..subsection.leb()? {
+                        let index = subsection.leb()?;
+                        let name = String::from_utf8_lossy(subsection.bytes()?).into_owned();
+                        names.insert(index, name);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Some((imported_functions, names))
+}
+
+struct WasmReader<'a>(&'a [u8]);
+
+impl<'a> WasmReader<'a> {
+    fn byte(&mut self) -> Option<u8> {
+        let (byte, rest) = self.0.split_first()?;
+        self.0 = rest;
+        Some(*byte)
+    }
+
+    fn leb(&mut self) -> Option<u32> {
+        let mut result = 0u32;
+        for shift in (0..35).step_by(7) {
+            let byte = self.byte()?;
+            result |= u32::from(byte & 0x7f).checked_shl(shift)?;
+            if byte & 0x80 == 0 {
+                return Some(result);
+            }
+        }
+        None
+    }
+
+    /// A length-prefixed byte vector.
+    fn bytes(&mut self) -> Option<&'a [u8]> {
+        let len = self.leb()? as usize;
+        let bytes = self.0.get(..len)?;
+        self.0 = &self.0[len..];
+        Some(bytes)
+    }
+
+    fn limits(&mut self) -> Option<()> {
+        let flags = self.byte()?;
+        self.leb()?;
+        if flags & 1 != 0 {
+            self.leb()?;
+        }
+        Some(())
+    }
+}
+
+struct ProfiledFrame {
+    label: String,
+    /// Remaining fuel at the last sample, `None` for frames that don't meter fuel.
+    fuel_mark: Option<u64>,
+    functions: Arc<FunctionMap>,
+}
+
+#[derive(Default)]
+struct FuelProfiler {
+    stack: Vec<ProfiledFrame>,
+    profile: FuelProfile,
+    /// Function maps of the modules seen so far, by code hash.
+    functions: BTreeMap<B256, Arc<FunctionMap>>,
+}
+
+impl FuelProfiler {
+    fn sample(&mut self, leaf: Option<SysFuncIdx>, remaining_fuel: Option<u64>, pc: Option<u32>) {
+        let Some(frame) = self.stack.last_mut() else {
+            return;
+        };
+        let fuel = frame
+            .fuel_mark
+            .zip(remaining_fuel)
+            .map(|(mark, remaining)| mark.saturating_sub(remaining))
+            .unwrap_or_default();
+        frame.fuel_mark = remaining_fuel;
+        if fuel == 0 && leaf.is_none() {
+            return;
+        }
+        let function = pc.and_then(|pc| frame.functions.function_at(pc));
+        let mut stack = self
+            .stack
+            .iter()
+            .map(|frame| frame.label.clone())
+            .collect::<Vec<_>>();
+        stack.extend(function);
+        if let Some(leaf) = leaf {
+            stack.push(leaf.to_string());
+        }
+        let entry = self.profile.stacks.entry(stack).or_default();
+        entry.0 += 1;
+        entry.1 += fuel;
+    }
+}
+
+thread_local! {
+    static PROFILER: RefCell<Option<FuelProfiler>> = const { RefCell::new(None) };
+}
+
+fn with_profiler(f: impl FnOnce(&mut FuelProfiler)) {
+    PROFILER.with_borrow_mut(|profiler| {
+        if let Some(profiler) = profiler {
+            f(profiler)
+        }
+    });
+}
+
+/// Disarms the collector when dropped, so a panicking closure doesn't leave profiling enabled.
+struct ProfilingGuard;
+
+impl Drop for ProfilingGuard {
+    fn drop(&mut self) {
+        PROFILER.with_borrow_mut(|profiler| *profiler = None);
+    }
+}
+
+/// Runs `f` with fuel profiling enabled on the current thread and returns the collected profile.
+///
+/// Nested calls are not supported: the inner call collects everything and the outer one returns
+/// an empty profile.
+pub fn profile_fuel<R>(f: impl FnOnce() -> R) -> (R, FuelProfile) {
+    PROFILER.with_borrow_mut(|profiler| *profiler = Some(FuelProfiler::default()));
+    let _guard = ProfilingGuard;
+    let result = f();
+    let profile = PROFILER
+        .with_borrow_mut(Option::take)
+        .map(|profiler| profiler.profile)
+        .unwrap_or_default();
+    (result, profile)
+}
+
+/// Label of a contract frame: the executed address or code hash, and its entrypoint.
+pub(crate) fn frame_label(address: Option<&Address>, code_hash: &B256, state: u32) -> String {
+    let entrypoint = if state == STATE_DEPLOY {
+        "deploy"
+    } else {
+        "main"
+    };
+    match address {
+        Some(address) => format!("{address}::{entrypoint}"),
+        None => format!("{code_hash}::{entrypoint}"),
+    }
+}
+
+/// A frame of the module with `code_hash` starts executing with `remaining_fuel` left.
+pub(crate) fn enter_frame(
+    label: impl FnOnce() -> String,
+    code_hash: B256,
+    module: &RwasmModule,
+    remaining_fuel: Option<u64>,
+) {
+    with_profiler(|profiler| {
+        let functions = profiler
+            .functions
+            .entry(code_hash)
+            .or_insert_with(|| Arc::new(FunctionMap::from_module(module)))
+            .clone();
+        profiler.stack.push(ProfiledFrame {
+            label: label(),
+            fuel_mark: remaining_fuel,
+            functions,
+        })
+    });
+}
+
+/// The top frame calls a host function from `pc`; fuel burnt since the last sample belongs to the
+/// Wasm function there. `pc` is `None` for engines that don't expose it.
+pub(crate) fn before_host_call(remaining_fuel: Option<u64>, pc: Option<u32>) {
+    with_profiler(|profiler| profiler.sample(None, remaining_fuel, pc));
+}
+
+/// A host function called from `pc` returned; fuel charged since [`before_host_call`] belongs to it.
+pub(crate) fn after_host_call(
+    sys_func_idx: SysFuncIdx,
+    remaining_fuel: Option<u64>,
+    pc: Option<u32>,
+) {
+    with_profiler(|profiler| profiler.sample(Some(sys_func_idx), remaining_fuel, pc));
+}
+
+/// The top frame stopped, either interrupted (`finished == false`) or for good.
+pub(crate) fn leave_frame(remaining_fuel: Option<u64>, finished: bool) {
+    with_profiler(|profiler| {
+        profiler.sample(None, remaining_fuel, None);
+        if finished {
+            profiler.stack.pop();
+        }
+    });
+}
+
+/// An interrupted top frame continues with `remaining_fuel` left.
+///
+/// Fuel charged to the frame for the work done during the interruption is excluded here, since
+/// that work was already sampled in the child frames.
+pub(crate) fn resume_frame(remaining_fuel: Option<u64>) {
+    with_profiler(|profiler| {
+        if let Some(frame) = profiler.stack.last_mut() {
+            frame.fuel_mark = remaining_fuel;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rwasm::{InstructionSet, RwasmModuleInner};
+
+    fn module() -> RwasmModule {
+        RwasmModuleInner {
+            code_section: InstructionSet::default(),
+            hint_section: vec![],
+            data_section: vec![],
+            elem_section: vec![],
+            source_pc: 0,
+        }
+        .into()
+    }
+
+    #[test]
+    fn fuel_is_attributed_to_frames_and_host_functions() {
+        let module = module();
+        let ((), profile) = profile_fuel(|| {
+            enter_frame(|| "parent::main".into(), B256::ZERO, &module, Some(1_000));
+            before_host_call(Some(900), None);
+            after_host_call(SysFuncIdx::KECCAK256, Some(850), None);
+            before_host_call(Some(800), None);
+            after_host_call(SysFuncIdx::EXEC, Some(790), None);
+            leave_frame(Some(790), false);
+
+            enter_frame(|| "child::main".into(), B256::ZERO, &module, Some(500));
+            leave_frame(Some(200), true);
+
+            // The parent is charged 300 fuel for the child before resuming.
+            resume_frame(Some(490));
+            leave_frame(Some(400), true);
+        });
+
+        let stack = |frames: &[&str]| frames.iter().map(|frame| frame.to_string()).collect();
+        assert_eq!(profile.stacks[&stack(&["parent::main"])], (3, 240));
+        assert_eq!(
+            profile.stacks[&stack(&["parent::main", "KECCAK256"])],
+            (1, 50)
+        );
+        assert_eq!(profile.stacks[&stack(&["parent::main", "EXEC"])], (1, 10));
+        assert_eq!(
+            profile.stacks[&stack(&["parent::main", "child::main"])],
+            (1, 300)
+        );
+        assert_eq!(profile.total_fuel(), 600);
+        assert_eq!(
+            profile.to_folded(),
+            "parent::main 240\n\
+             parent::main;EXEC 10\n\
+             parent::main;KECCAK256 50\n\
+             parent::main;child::main 300\n"
+        );
+    }
+
+    #[test]
+    fn fuel_is_attributed_to_the_calling_function() {
+        let module = module();
+        let ((), profile) = profile_fuel(|| {
+            with_profiler(|profiler| {
+                profiler.functions.insert(
+                    B256::ZERO,
+                    Arc::new(FunctionMap {
+                        entries: vec![10, 20],
+                        imported_functions: 1,
+                        names: BTreeMap::from([(1, "transfer".into())]),
+                    }),
+                );
+            });
+            enter_frame(|| "token::main".into(), B256::ZERO, &module, Some(1_000));
+            before_host_call(Some(900), Some(15));
+            after_host_call(SysFuncIdx::KECCAK256, Some(850), Some(15));
+            before_host_call(Some(800), Some(25));
+            after_host_call(SysFuncIdx::WRITE_OUTPUT, Some(790), Some(25));
+            leave_frame(Some(700), true);
+        });
+
+        let stack = |frames: &[&str]| frames.iter().map(|frame| frame.to_string()).collect();
+        assert_eq!(
+            profile.stacks[&stack(&["token::main", "transfer"])],
+            (1, 100)
+        );
+        assert_eq!(
+            profile.stacks[&stack(&["token::main", "transfer", "KECCAK256"])],
+            (1, 50)
+        );
+        assert_eq!(profile.stacks[&stack(&["token::main", "func[2]"])], (1, 50));
+        assert_eq!(
+            profile.stacks[&stack(&["token::main", "func[2]", "WRITE_OUTPUT"])],
+            (1, 10)
+        );
+        assert_eq!(profile.stacks[&stack(&["token::main"])], (1, 90));
+        assert_eq!(profile.total_fuel(), 300);
+    }
+
+    #[test]
+    fn code_before_the_first_function_has_no_function_frame() {
+        let functions = FunctionMap {
+            entries: vec![10],
+            ..Default::default()
+        };
+        assert_eq!(functions.function_at(9), None);
+        assert_eq!(functions.function_at(10), Some("func[0]".into()));
+    }
+
+    #[test]
+    fn function_names_come_from_the_name_section() {
+        let wasm = wat::parse_str(
+            r#"
+                (module
+                    (import "fluentbase_v1preview" "_exit" (func $exit (param i32)))
+                    (memory 1)
+                    (func $transfer)
+                    (func (export "main")))
+            "#,
+        )
+        .unwrap();
+        let (imported_functions, names) = parse_function_names(&wasm).unwrap();
+        assert_eq!(imported_functions, 1);
+        assert_eq!(names.get(&1).map(String::as_str), Some("transfer"));
+        assert_eq!(names.get(&2), None);
+
+        assert_eq!(parse_function_names(b"not wasm"), None);
+    }
+
+    #[test]
+    fn nothing_is_collected_outside_of_profile_fuel() {
+        enter_frame(
+            || unreachable!("labels must not be built while profiling is off"),
+            B256::ZERO,
+            &module(),
+            None,
+        );
+        let ((), profile) = profile_fuel(|| before_host_call(Some(1), None));
+        assert_eq!(profile, FuelProfile::default());
+    }
+
+    #[test]
+    fn a_panicking_closure_disarms_the_profiler() {
+        let panicked = std::panic::catch_unwind(|| profile_fuel(|| panic!("boom")));
+        assert!(panicked.is_err());
+        assert!(PROFILER.with_borrow(Option::is_none));
+    }
+
+    #[test]
+    fn pprof_output_starts_with_sample_types_and_ends_with_strings() {
+        let module = module();
+        let ((), profile) = profile_fuel(|| {
+            enter_frame(|| "contract::main".into(), B256::ZERO, &module, Some(100));
+            leave_frame(Some(40), true);
+        });
+        let pprof = profile.to_pprof();
+        // sample_type { type: 1 ("samples"), unit: 2 ("count") }
+        assert_eq!(&pprof[..6], &[0x0a, 0x04, 0x08, 0x01, 0x10, 0x02]);
+        assert!(pprof.ends_with(b"\x32\x0econtract::main"));
+    }
+
+    #[test]
+    fn varints_use_base_128_encoding() {
+        let mut buffer = Vec::new();
+        pb_varint(&mut buffer, 300);
+        assert_eq!(buffer, [0xac, 0x02]);
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "profiler")]
+use crate::profiler;
 use crate::RuntimeContext;
 use fluentbase_types::{ExitCode, SysFuncIdx};
 use rwasm::{StoreTr, TrapCode, TypedCaller, Value};
@@ -23,7 +25,15 @@ pub(crate) fn runtime_syscall_handler(
     result: &mut [Value],
 ) -> Result<(), TrapCode> {
     let sys_func_idx = SysFuncIdx::from_repr(func_idx).ok_or(TrapCode::UnknownExternalFunction)?;
-    invoke_runtime_handler(caller, sys_func_idx, params, result)
+    // `None` under Wasmtime, which doesn't expose the program counter.
+    #[cfg(feature = "profiler")]
+    let pc = caller.program_counter();
+    #[cfg(feature = "profiler")]
+    profiler::before_host_call(caller.remaining_fuel(), pc);
+    let host_result = invoke_runtime_handler(caller, sys_func_idx, params, result);
+    #[cfg(feature = "profiler")]
+    profiler::after_host_call(sys_func_idx, caller.remaining_fuel(), pc);
+    host_result
 }
 
 #[rustfmt::skip]
@@ -57,11 +67,15 @@ pub fn invoke_runtime_handler(
         // TODO(dmitry123): This syscall is disabled since it can cause panic, we should refine it
         //  by introducing new system contracts where the same functionality is achieved.
         SysFuncIdx::WRITE_FD => Err(TrapCode::UnreachableCodeReached),
+        // Only scheduled on development chains, traps like an unknown syscall everywhere else.
+        SysFuncIdx::DEBUG_EVENT if caller.data().forks.debug_events => syscall_debug_event_handler(caller, params, result),
+        SysFuncIdx::DEBUG_EVENT => Err(TrapCode::UnreachableCodeReached),
 
         // hashing functions (0x01)
         SysFuncIdx::KECCAK256 => syscall_hashing_keccak256_handler(caller, params, result),
         SysFuncIdx::KECCAK256_PERMUTE => syscall_hashing_keccak256_permute_handler(caller, params, result),
-        // TODO(dmitry123): This syscall is disabled since we don't support SVM yet.
+        // Traps like an unknown syscall until the Poseidon fork is active, see `RuntimeForkSchedule`.
+        SysFuncIdx::POSEIDON if caller.data().forks.poseidon => syscall_hashing_poseidon_handler(caller, params, result),
         SysFuncIdx::POSEIDON => Err(TrapCode::UnreachableCodeReached),
         SysFuncIdx::SHA256_EXTEND => syscall_hashing_sha256_extend_handler(caller, params, result),
         SysFuncIdx::SHA256_COMPRESS => syscall_hashing_sha256_compress_handler(caller, params, result),
@@ -4,8 +4,8 @@ mod keccak256;
 pub use keccak256::*;
 mod keccak256_permute;
 pub use keccak256_permute::*;
-// mod poseidon;
-// pub use poseidon::*;
+mod poseidon;
+pub use poseidon::*;
 mod sha256;
 pub use sha256::*;
 mod sha256_compress;
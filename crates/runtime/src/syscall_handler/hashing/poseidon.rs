@@ -1,67 +1,175 @@
-/// This function will be removed in the future.
-/// We keep it only for backward compatibility with testnet.
+//! Poseidon hash over the BN254 scalar field.
+//!
+//! Uses the circom parameter set with the x^5 S-box (`Bn254X5`), the one used by circomlib,
+//! Semaphore and most on-chain Merkle trees. Widths 2..=13, i.e. 1..=12 inputs of 32 bytes each,
+//! are supported. The syscall is only available once the Poseidon runtime fork is active, see
+//! `RuntimeForkSchedule`.
 use crate::{syscall_handler::syscall_process_exit_code, RuntimeContext};
-use fluentbase_types::{ExitCode, B256};
+use ark_bn254::Fr;
+use fluentbase_types::{
+    ExitCode, B256, POSEIDON_ENDIANNESS_BIG, POSEIDON_ENDIANNESS_LITTLE, POSEIDON_INPUT_SIZE,
+    POSEIDON_MAX_INPUTS, POSEIDON_PARAMETERS_BN254_X5,
+};
+use light_poseidon::{Poseidon, PoseidonBytesHasher};
 use rwasm::{StoreTr, TrapCode, Value};
-use solana_poseidon::HASH_BYTES;
 
+/// Hashes `data_len / 32` field elements and writes the 32-byte hash to `output_offset`.
+///
+/// Returns `0` on success and `1` if an input is not below the field modulus, which is the only
+/// failure that depends on the hashed data. Malformed parameters halt with
+/// `MalformedBuiltinParams`.
 pub fn syscall_hashing_poseidon_handler(
     caller: &mut impl StoreTr<RuntimeContext>,
     params: &[Value],
     result: &mut [Value],
 ) -> Result<(), TrapCode> {
     let (parameters, endianness, data_offset, data_len, output_offset) = (
-        params[0].i32().unwrap() as usize,
-        params[1].i32().unwrap() as usize,
+        params[0].i32().unwrap() as u32,
+        params[1].i32().unwrap() as u32,
         params[2].i32().unwrap() as usize,
         params[3].i32().unwrap() as usize,
         params[4].i32().unwrap() as usize,
     );
-    if data_len % HASH_BYTES != 0 {
-        result[0] = Value::I32(1i32);
-        syscall_process_exit_code(caller, ExitCode::MalformedBuiltinParams);
-        return Ok(());
+    if !is_valid_poseidon_input_len(data_len) {
+        return Err(syscall_process_exit_code(
+            caller,
+            ExitCode::MalformedBuiltinParams,
+        ));
     }
     let mut data = vec![0; data_len];
     caller.memory_read(data_offset, &mut data)?;
-    let hash = syscall_hashing_poseidon_impl(parameters as u64, endianness as u64, &data);
-    match hash {
-        Ok(v) => {
-            caller.memory_write(output_offset, v.as_slice())?;
+    match syscall_hashing_poseidon_impl(parameters, endianness, &data) {
+        Ok(Some(hash)) => {
+            caller.memory_write(output_offset, hash.as_slice())?;
+            result[0] = Value::I32(0);
         }
-        Err(e) => {
-            syscall_process_exit_code(caller, e);
-        }
-    };
-    result[0] = Value::I32(hash.is_err() as i32);
-
+        Ok(None) => result[0] = Value::I32(1),
+        Err(exit_code) => return Err(syscall_process_exit_code(caller, exit_code)),
+    }
     Ok(())
 }
 
+fn is_valid_poseidon_input_len(data_len: usize) -> bool {
+    data_len > 0
+        && data_len % POSEIDON_INPUT_SIZE == 0
+        && data_len / POSEIDON_INPUT_SIZE <= POSEIDON_MAX_INPUTS
+}
+
+/// Computes the Poseidon hash of `data` split into 32-byte field elements.
+///
+/// Returns `Ok(None)` if an input is not a canonical field element.
 pub fn syscall_hashing_poseidon_impl(
-    parameters: u64,
-    endianness: u64,
+    parameters: u32,
+    endianness: u32,
     data: &[u8],
-) -> Result<B256, ExitCode> {
-    let parameters: solana_poseidon::Parameters = parameters
-        .try_into()
-        .map_err(|_| ExitCode::MalformedBuiltinParams)?;
-    let endianness: solana_poseidon::Endianness = endianness
-        .try_into()
-        .map_err(|_| ExitCode::MalformedBuiltinParams)?;
-
-    if data.len() % HASH_BYTES != 0 {
+) -> Result<Option<B256>, ExitCode> {
+    if parameters != POSEIDON_PARAMETERS_BN254_X5 || !is_valid_poseidon_input_len(data.len()) {
         return Err(ExitCode::MalformedBuiltinParams);
+    }
+    let inputs = data.chunks(POSEIDON_INPUT_SIZE).collect::<Vec<_>>();
+    let mut hasher =
+        Poseidon::<Fr>::new_circom(inputs.len()).map_err(|_| ExitCode::MalformedBuiltinParams)?;
+    let hash = match endianness {
+        POSEIDON_ENDIANNESS_BIG => hasher.hash_bytes_be(&inputs),
+        POSEIDON_ENDIANNESS_LITTLE => hasher.hash_bytes_le(&inputs),
+        _ => return Err(ExitCode::MalformedBuiltinParams),
     };
-    let data = data.chunks(HASH_BYTES).map(|v| v).collect::<Vec<&[u8]>>();
+    Ok(hash.ok().map(B256::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluentbase_types::{b256, U256};
 
-    let hash = match solana_poseidon::hashv(parameters, endianness, &data) {
-        Ok(hash) => hash,
-        Err(_e) => {
-            return Err(ExitCode::MalformedBuiltinParams);
+    fn be_inputs(values: &[u64]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| U256::from(*value).to_be_bytes::<32>())
+            .collect()
+    }
+
+    #[test]
+    fn matches_circomlib_vectors() {
+        let hash = syscall_hashing_poseidon_impl(
+            POSEIDON_PARAMETERS_BN254_X5,
+            POSEIDON_ENDIANNESS_BIG,
+            &be_inputs(&[1]),
+        );
+        assert_eq!(
+            hash,
+            Ok(Some(b256!(
+                "29176100eaa962bdc1fe6c654d6a3c130e96a4d1168b33848b897dc502820133"
+            )))
+        );
+        let hash = syscall_hashing_poseidon_impl(
+            POSEIDON_PARAMETERS_BN254_X5,
+            POSEIDON_ENDIANNESS_BIG,
+            &be_inputs(&[1, 2]),
+        );
+        assert_eq!(
+            hash,
+            Ok(Some(b256!(
+                "115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"
+            )))
+        );
+    }
+
+    #[test]
+    fn little_endian_inputs_hash_to_reversed_output() {
+        let mut input = be_inputs(&[1, 2]);
+        input[..32].reverse();
+        input[32..].reverse();
+        let mut hash = syscall_hashing_poseidon_impl(
+            POSEIDON_PARAMETERS_BN254_X5,
+            POSEIDON_ENDIANNESS_LITTLE,
+            &input,
+        )
+        .unwrap()
+        .unwrap();
+        hash.reverse();
+        assert_eq!(
+            hash,
+            b256!("115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a")
+        );
+    }
+
+    #[test]
+    fn rejects_inputs_outside_of_the_width_set() {
+        for inputs in [0, POSEIDON_MAX_INPUTS + 1] {
+            let data = vec![0u8; inputs * POSEIDON_INPUT_SIZE];
+            assert_eq!(
+                syscall_hashing_poseidon_impl(
+                    POSEIDON_PARAMETERS_BN254_X5,
+                    POSEIDON_ENDIANNESS_BIG,
+                    &data
+                ),
+                Err(ExitCode::MalformedBuiltinParams)
+            );
         }
-    };
-    let mut hash_result = [0u8; 32];
-    hash_result.copy_from_slice(&hash.to_bytes());
-    Ok(hash_result.into())
+        assert_eq!(
+            syscall_hashing_poseidon_impl(1, POSEIDON_ENDIANNESS_BIG, &be_inputs(&[1])),
+            Err(ExitCode::MalformedBuiltinParams)
+        );
+        assert_eq!(
+            syscall_hashing_poseidon_impl(
+                POSEIDON_PARAMETERS_BN254_X5,
+                POSEIDON_ENDIANNESS_BIG,
+                &[0u8; 31]
+            ),
+            Err(ExitCode::MalformedBuiltinParams)
+        );
+    }
+
+    #[test]
+    fn inputs_above_the_modulus_are_not_hashed() {
+        assert_eq!(
+            syscall_hashing_poseidon_impl(
+                POSEIDON_PARAMETERS_BN254_X5,
+                POSEIDON_ENDIANNESS_BIG,
+                &[0xff; 32]
+            ),
+            Ok(None)
+        );
+    }
 }
@@ -1,5 +1,7 @@
 mod charge_fuel;
 pub use charge_fuel::*;
+mod debug_event;
+pub use debug_event::*;
 mod debug_log;
 pub use debug_log::*;
 mod exec;
@@ -0,0 +1,96 @@
+use crate::RuntimeContext;
+use fluentbase_types::{
+    DebugEvent, DebugEventFormat, DEBUG_EVENT_MAX_DATA_SIZE, DEBUG_EVENT_MAX_RECORDS,
+};
+use rwasm::{StoreTr, TrapCode, Value};
+
+/// Collects a structured debug record when the node has debug events enabled.
+///
+/// Only reachable on chains where the `debug_events` runtime fork is active. There it never traps
+/// and never fails: malformed records (unknown format, oversized payload or an out-of-bounds
+/// pointer) are dropped, so the call behaves the same on nodes that don't collect.
+pub fn syscall_debug_event_handler(
+    caller: &mut impl StoreTr<RuntimeContext>,
+    params: &[Value],
+    _result: &mut [Value],
+) -> Result<(), TrapCode> {
+    if !caller.data().debug_events {
+        return Ok(());
+    }
+    let (channel, format, data_ptr, data_len) = (
+        params[0].i32().unwrap() as u32,
+        params[1].i32().unwrap() as u32,
+        params[2].i32().unwrap() as usize,
+        params[3].i32().unwrap() as usize,
+    );
+    if data_len > DEBUG_EVENT_MAX_DATA_SIZE {
+        return Ok(());
+    }
+    let Ok(data) = caller.memory_read_into_vec(data_ptr, data_len) else {
+        return Ok(());
+    };
+    syscall_debug_event_impl(caller.data_mut(), channel, format, &data);
+    Ok(())
+}
+
+pub fn syscall_debug_event_impl(ctx: &mut RuntimeContext, channel: u32, format: u32, data: &[u8]) {
+    let debug_events = &mut ctx.execution_result.debug_events;
+    if !ctx.debug_events
+        || data.len() > DEBUG_EVENT_MAX_DATA_SIZE
+        || debug_events.len() >= DEBUG_EVENT_MAX_RECORDS
+    {
+        return;
+    }
+    let Some(format) = DebugEventFormat::from_u32(format) else {
+        return;
+    };
+    debug_events.push(DebugEvent {
+        channel,
+        format,
+        data: data.to_vec().into(),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_are_only_collected_when_enabled() {
+        let mut ctx = RuntimeContext::default();
+        syscall_debug_event_impl(&mut ctx, 7, DebugEventFormat::Bytes as u32, b"ignored");
+        assert!(ctx.execution_result.debug_events.is_empty());
+
+        let mut ctx = RuntimeContext::default().with_debug_events(true);
+        syscall_debug_event_impl(&mut ctx, 7, DebugEventFormat::Json as u32, br#"{"a":1}"#);
+        assert_eq!(
+            ctx.execution_result.debug_events,
+            vec![DebugEvent {
+                channel: 7,
+                format: DebugEventFormat::Json,
+                data: br#"{"a":1}"#.to_vec().into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn malformed_and_excess_records_are_dropped() {
+        let mut ctx = RuntimeContext::default().with_debug_events(true);
+        syscall_debug_event_impl(&mut ctx, 0, 2, b"unknown format");
+        syscall_debug_event_impl(
+            &mut ctx,
+            0,
+            DebugEventFormat::Bytes as u32,
+            &[0u8; DEBUG_EVENT_MAX_DATA_SIZE + 1],
+        );
+        assert!(ctx.execution_result.debug_events.is_empty());
+
+        for _ in 0..DEBUG_EVENT_MAX_RECORDS + 1 {
+            syscall_debug_event_impl(&mut ctx, 0, DebugEventFormat::Bytes as u32, b"");
+        }
+        assert_eq!(
+            ctx.execution_result.debug_events.len(),
+            DEBUG_EVENT_MAX_RECORDS
+        );
+    }
+}
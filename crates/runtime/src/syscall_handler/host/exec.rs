@@ -124,9 +124,14 @@ pub fn syscall_exec_impl<I: Into<BytecodeOrHash>>(
         .with_fuel_limit(fuel_limit)
         .with_input(input.into_owned())
         .with_state(state)
-        .with_call_depth(ctx.call_depth + 1);
+        .with_call_depth(ctx.call_depth + 1)
+        .with_forks(ctx.forks)
+        .with_debug_events(ctx.debug_events);
 
     let result = default_runtime_executor().execute(code_hash.into(), ctx2);
     ctx.execution_result.return_data = result.output;
+    ctx.execution_result
+        .debug_events
+        .extend(result.debug_events);
     (result.fuel_consumed, result.fuel_refunded, result.exit_code)
 }
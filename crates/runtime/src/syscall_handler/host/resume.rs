@@ -75,6 +75,9 @@ pub fn syscall_resume_impl(
     );
     // Move output into parent's return data
     ctx.execution_result.return_data = result.output;
+    ctx.execution_result
+        .debug_events
+        .extend(result.debug_events);
     Ok((
         result.fuel_consumed,
         result.fuel_refunded,
@@ -0,0 +1,312 @@
+//! SDK access and security components used by a contract
+//!
+//! `Ownable`, `Ownable2Step`, `AccessControl` and the reentrancy guard are declared in
+//! `fluentbase-sdk`, outside the contract crate, so walking the crate sources never finds their
+//! events and errors. A contract still emits and reverts with them, so they belong to its ABI
+//! whenever the router applies one of their modifiers or returns one of their errors, or the
+//! contract embeds the component as a field.
+//!
+//! Only the components are identified here. Their events and errors come from the SDK itself,
+//! which exposes the ABI entries `#[derive(Event)]` and `#[derive(SolidityError)]` generate for
+//! them through `fluentbase_sdk::ComponentAbi`. Types are matched by the path they resolve to
+//! through the `use` declarations of their module, so a contract's own `Ownable` is not mistaken
+//! for the SDK's.
+
+use crate::{
+    abi::{
+        error::ABIError,
+        structs::{has_derive, walk_crate},
+    },
+    attr::Modifier,
+};
+use std::{collections::HashMap, path::Path};
+use syn::{Item, ItemUse, Path as SynPath, Type, UseTree};
+
+/// Name of the SDK crate as contracts refer to it
+const SDK_CRATE: &str = "fluentbase_sdk";
+
+/// Public paths of the component types
+const COMPONENT_TYPES: [(&str, SdkComponent); 3] = [
+    ("fluentbase_sdk::access::Ownable", SdkComponent::Ownable),
+    (
+        "fluentbase_sdk::access::Ownable2Step",
+        SdkComponent::Ownable2Step,
+    ),
+    (
+        "fluentbase_sdk::access::AccessControl",
+        SdkComponent::AccessControl,
+    ),
+];
+
+/// Public paths of the error types of the components
+const ERROR_TYPES: [(&str, SdkComponent); 3] = [
+    (
+        "fluentbase_sdk::access::OwnableError",
+        SdkComponent::Ownable,
+    ),
+    (
+        "fluentbase_sdk::access::AccessControlError",
+        SdkComponent::AccessControl,
+    ),
+    (
+        "fluentbase_sdk::security::ReentrancyGuardError",
+        SdkComponent::ReentrancyGuard,
+    ),
+];
+
+/// An SDK component whose events and errors belong to the ABI of the contracts using it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SdkComponent {
+    Ownable,
+    Ownable2Step,
+    AccessControl,
+    ReentrancyGuard,
+}
+
+impl SdkComponent {
+    /// Component stored in a field of this type
+    pub fn from_field_type(ty: &Type, imports: &Imports) -> Option<Self> {
+        find_type(&COMPONENT_TYPES, ty, imports)
+    }
+
+    /// Component whose custom errors this `Result` error type is
+    pub fn from_error_type(ty: &Type, imports: &Imports) -> Option<Self> {
+        find_type(&ERROR_TYPES, ty, imports)
+    }
+
+    /// Component a router modifier checks
+    ///
+    /// `#[only_owner]` works with `Ownable2Step` as well, which adds an event but no error, so the
+    /// modifier only accounts for the `Ownable` part.
+    pub fn from_modifier(modifier: &Modifier) -> Self {
+        match modifier {
+            Modifier::OnlyOwner { .. } => Self::Ownable,
+            Modifier::OnlyRole { .. } => Self::AccessControl,
+            Modifier::NonReentrant => Self::ReentrancyGuard,
+        }
+    }
+
+    /// Components embedded as fields of the `#[derive(Contract)]` and `#[derive(Storage)]` structs
+    /// of a crate, in source order
+    ///
+    /// # Arguments
+    /// * `entry_file` - Path to the crate root (`src/lib.rs` or `src/main.rs`)
+    pub fn embedded_in_crate(entry_file: &Path) -> Result<Vec<Self>, ABIError> {
+        let mut imports = HashMap::<Vec<String>, Imports>::new();
+        let mut field_types = Vec::new();
+
+        walk_crate(entry_file, &mut |module, item| match item {
+            Item::Use(item_use) => imports.entry(module.to_vec()).or_default().add(item_use),
+            Item::Struct(item_struct)
+                if has_derive(&item_struct.attrs, "Contract")
+                    || has_derive(&item_struct.attrs, "Storage") =>
+            {
+                field_types.extend(
+                    item_struct
+                        .fields
+                        .iter()
+                        .map(|field| (module.to_vec(), field.ty.clone())),
+                );
+            }
+            _ => {}
+        })?;
+
+        let no_imports = Imports::default();
+        let mut components = Vec::new();
+        for (module, ty) in &field_types {
+            let imports = imports.get(module).unwrap_or(&no_imports);
+            if let Some(component) = Self::from_field_type(ty, imports) {
+                if !components.contains(&component) {
+                    components.push(component);
+                }
+            }
+        }
+        Ok(components)
+    }
+}
+
+/// Names the `use` declarations of a module bring into scope
+#[derive(Debug, Clone, Default)]
+pub struct Imports {
+    /// Imported name, or its `as` alias, mapped to the path it stands for
+    names: HashMap<String, Vec<String>>,
+    /// Paths imported with `::*`
+    globs: Vec<Vec<String>>,
+}
+
+impl Imports {
+    /// Imports of the given items, usually those of a whole file
+    pub fn from_items(items: &[Item]) -> Self {
+        let mut imports = Self::default();
+        for item in items {
+            if let Item::Use(item_use) = item {
+                imports.add(item_use);
+            }
+        }
+        imports
+    }
+
+    /// Adds the names a `use` declaration imports
+    pub fn add(&mut self, item_use: &ItemUse) {
+        self.add_tree(&mut Vec::new(), &item_use.tree);
+    }
+
+    fn add_tree(&mut self, prefix: &mut Vec<String>, tree: &UseTree) {
+        match tree {
+            UseTree::Path(path) => {
+                prefix.push(path.ident.to_string());
+                self.add_tree(prefix, &path.tree);
+                prefix.pop();
+            }
+            // `use fluentbase_sdk::{self, ...}` imports the prefix itself
+            UseTree::Name(name) if name.ident == "self" => {
+                if let Some(last) = prefix.last() {
+                    self.names.insert(last.clone(), prefix.clone());
+                }
+            }
+            UseTree::Name(name) => {
+                let mut path = prefix.clone();
+                path.push(name.ident.to_string());
+                self.names.insert(name.ident.to_string(), path);
+            }
+            UseTree::Rename(rename) => {
+                let mut path = prefix.clone();
+                if rename.ident != "self" {
+                    path.push(rename.ident.to_string());
+                }
+                self.names.insert(rename.rename.to_string(), path);
+            }
+            UseTree::Glob(_) => self.globs.push(prefix.clone()),
+            UseTree::Group(group) => {
+                for tree in &group.items {
+                    self.add_tree(prefix, tree);
+                }
+            }
+        }
+    }
+
+    /// Full paths a path written in the module may stand for
+    ///
+    /// A name imported by name resolves to a single path, one that isn't may come from any of the
+    /// glob imports.
+    fn candidates(&self, path: &SynPath) -> Vec<String> {
+        let segments: Vec<String> = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        let Some(first) = segments.first() else {
+            return Vec::new();
+        };
+
+        if path.leading_colon.is_some() || first == SDK_CRATE {
+            return vec![segments.join("::")];
+        }
+        if let Some(imported) = self.names.get(first) {
+            let mut full = imported.clone();
+            full.extend_from_slice(&segments[1..]);
+            return vec![full.join("::")];
+        }
+        self.globs
+            .iter()
+            .map(|glob| {
+                let mut full = glob.clone();
+                full.extend_from_slice(&segments);
+                full.join("::")
+            })
+            .collect()
+    }
+}
+
+fn find_type(known: &[(&str, SdkComponent)], ty: &Type, imports: &Imports) -> Option<SdkComponent> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    if type_path.qself.is_some() {
+        return None;
+    }
+    imports
+        .candidates(&type_path.path)
+        .iter()
+        .find_map(|candidate| {
+            known
+                .iter()
+                .find(|(path, _)| path == candidate)
+                .map(|(_, component)| *component)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn imports(items: &[Item]) -> Imports {
+        Imports::from_items(items)
+    }
+
+    #[test]
+    fn test_components_are_recognized_through_imports() {
+        let sdk_imports = imports(&[
+            parse_quote!(
+                use fluentbase_sdk::{
+                    access::{Ownable2Step, AccessControlError as Denied},
+                    security,
+                };
+            ),
+            parse_quote!(
+                use fluentbase_sdk::access::*;
+            ),
+        ]);
+        assert_eq!(
+            SdkComponent::from_field_type(&parse_quote!(Ownable2Step), &sdk_imports),
+            Some(SdkComponent::Ownable2Step)
+        );
+        assert_eq!(
+            SdkComponent::from_field_type(&parse_quote!(AccessControl), &sdk_imports),
+            Some(SdkComponent::AccessControl)
+        );
+        assert_eq!(
+            SdkComponent::from_error_type(&parse_quote!(Denied), &sdk_imports),
+            Some(SdkComponent::AccessControl)
+        );
+        assert_eq!(
+            SdkComponent::from_error_type(
+                &parse_quote!(security::ReentrancyGuardError),
+                &sdk_imports
+            ),
+            Some(SdkComponent::ReentrancyGuard)
+        );
+        assert_eq!(
+            SdkComponent::from_field_type(
+                &parse_quote!(fluentbase_sdk::access::Ownable),
+                &Imports::default()
+            ),
+            Some(SdkComponent::Ownable)
+        );
+        assert_eq!(
+            SdkComponent::from_field_type(&parse_quote!(StorageU256), &sdk_imports),
+            None
+        );
+    }
+
+    #[test]
+    fn test_contract_types_with_component_names_are_ignored() {
+        // A contract's own `Ownable` only shares the name of the SDK component
+        let local_imports = imports(&[parse_quote!(
+            use crate::auth::{Ownable, OwnableError};
+        )]);
+        assert_eq!(
+            SdkComponent::from_field_type(&parse_quote!(Ownable), &local_imports),
+            None
+        );
+        assert_eq!(
+            SdkComponent::from_error_type(&parse_quote!(OwnableError), &local_imports),
+            None
+        );
+        assert_eq!(
+            SdkComponent::from_field_type(&parse_quote!(Ownable), &Imports::default()),
+            None
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Events and custom errors declared by a contract crate
+//!
+//! Unlike functions, these never pass through the `#[router]` macro, so they are found by walking
+//! the crate sources: every struct with `#[derive(Event)]`, every enum with
+//! `#[derive(SolidityError)]` and every `derive_evm_error!("...")` invocation, in any module
+//! reachable from the crate root. The events and errors of the SDK components a contract uses are
+//! declared by the SDK instead, see [`SdkComponent`](crate::abi::components::SdkComponent).
+
+use crate::abi::{
+    error::ABIError,
+    event::EventABI,
+    sol_error::ErrorABI,
+    structs::{
+        has_derive, item_enum_to_derive_input, item_struct_to_derive_input, walk_crate,
+        StructResolver,
+    },
+};
+use std::path::Path;
+use syn::{visit::Visit, Item, LitStr, Macro};
+
+/// Events and errors of a contract crate, in source order and without duplicates
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContractDeclarations {
+    pub events: Vec<EventABI>,
+    pub errors: Vec<ErrorABI>,
+}
+
+impl ContractDeclarations {
+    /// Parse every event and custom error reachable from a crate entry file
+    ///
+    /// # Arguments
+    /// * `entry_file` - Path to the crate root (`src/lib.rs` or `src/main.rs`)
+    /// * `resolver` - Codec structs used to expand struct fields of events
+    pub fn parse_crate(entry_file: &Path, resolver: &StructResolver) -> Result<Self, ABIError> {
+        let mut event_structs = Vec::new();
+        let mut error_enums = Vec::new();
+        let mut error_signatures = Vec::new();
+
+        walk_crate(entry_file, &mut |_, item| {
+            match item {
+                Item::Struct(item_struct) if has_derive(&item_struct.attrs, "Event") => {
+                    event_structs.push(item_struct.clone());
+                }
+                Item::Enum(item_enum) if has_derive(&item_enum.attrs, "SolidityError") => {
+                    error_enums.push(item_enum.clone());
+                }
+                _ => {}
+            }
+            ErrorMacroFinder {
+                signatures: &mut error_signatures,
+            }
+            .visit_item(item);
+        })?;
+
+        let mut declarations = Self::default();
+        for item_struct in &event_structs {
+            let event = EventABI::from_derive_input_with(
+                &item_struct_to_derive_input(item_struct),
+                resolver,
+            )
+            .map_err(|error| ABIError::Syntax(format!("event `{}`: {error}", item_struct.ident)))?;
+            declarations.add_event(event);
+        }
+        for item_enum in &error_enums {
+            let errors =
+                ErrorABI::from_derive_input_with(&item_enum_to_derive_input(item_enum), resolver)
+                    .map_err(|error| {
+                    ABIError::Syntax(format!("solidity error `{}`: {error}", item_enum.ident))
+                })?;
+            for error in errors {
+                declarations.add_error(error);
+            }
+        }
+        for signature in error_signatures {
+            declarations.add_error(signature?);
+        }
+
+        Ok(declarations)
+    }
+
+    /// Adds an event unless it is already declared
+    pub fn add_event(&mut self, event: EventABI) {
+        if !self.events.contains(&event) {
+            self.events.push(event);
+        }
+    }
+
+    /// Adds a custom error unless it is already declared
+    pub fn add_error(&mut self, error: ErrorABI) {
+        if !self.errors.contains(&error) {
+            self.errors.push(error);
+        }
+    }
+
+    /// Adds the events and errors of `other` that are not declared yet
+    pub fn extend(&mut self, other: ContractDeclarations) {
+        for event in other.events {
+            self.add_event(event);
+        }
+        for error in other.errors {
+            self.add_error(error);
+        }
+    }
+}
+
+/// Collects the signatures passed to `derive_evm_error!`, wherever the invocation is nested
+struct ErrorMacroFinder<'a> {
+    signatures: &'a mut Vec<Result<ErrorABI, ABIError>>,
+}
+
+impl<'ast> Visit<'ast> for ErrorMacroFinder<'_> {
+    fn visit_macro(&mut self, node: &'ast Macro) {
+        let is_error_macro = node
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "derive_evm_error");
+
+        if is_error_macro {
+            let signature = node
+                .parse_body::<LitStr>()
+                .map_err(|error| ABIError::Syntax(format!("derive_evm_error!: {error}")))
+                .and_then(|literal| ErrorABI::from_signature(&literal.value()));
+            self.signatures.push(signature);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::structs::StructRegistry;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn parse(files: &[(&str, &str)]) -> ContractDeclarations {
+        let temp_dir = TempDir::new().unwrap();
+        for (name, content) in files {
+            fs::write(temp_dir.path().join(name), content).unwrap();
+        }
+        let entry_file = temp_dir.path().join("lib.rs");
+        let resolver = StructResolver::registry(StructRegistry::parse_crate(&entry_file).unwrap());
+        ContractDeclarations::parse_crate(&entry_file, &resolver).unwrap()
+    }
+
+    #[test]
+    fn test_events_and_errors_across_modules() {
+        let declarations = parse(&[
+            (
+                "lib.rs",
+                r#"
+mod events;
+
+pub const ERR_PAUSED: EvmExitCode = derive_evm_error!("EnforcedPause()");
+
+#[derive(Debug, SolidityError)]
+pub enum MoveError {
+    OutOfBounds { to: Point },
+    Frozen(Address),
+}
+
+#[derive(Codec)]
+pub struct Point { pub x: U256, pub y: U256 }
+
+#[derive(Event)]
+pub struct Moved {
+    #[indexed]
+    pub who: Address,
+    pub to: Point,
+}
+"#,
+            ),
+            (
+                "events.rs",
+                r#"
+#[derive(Event)]
+#[anonymous]
+pub struct Ping {
+    #[indexed]
+    pub from: Address,
+}
+
+fn check() -> Result<(), EvmExitCode> {
+    Err(fluentbase_sdk::derive::derive_evm_error!("Unauthorized(address)"))
+}
+
+pub const ERR_PAUSED_AGAIN: EvmExitCode = derive_evm_error!("EnforcedPause()");
+"#,
+            ),
+        ]);
+
+        let events = declarations
+            .events
+            .iter()
+            .map(|event| event.signature().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            ["Ping(address)", "Moved(address,(uint256,uint256))"]
+        );
+        assert!(declarations.events[0].anonymous);
+        assert!(declarations.events[1].inputs[0].indexed);
+
+        let errors = declarations
+            .errors
+            .iter()
+            .map(|error| error.signature().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            [
+                "OutOfBounds((uint256,uint256))",
+                "Frozen(address)",
+                "Unauthorized(address)",
+                "EnforcedPause()"
+            ]
+        );
+        assert_eq!(declarations.errors[0].inputs[0].name, "to");
+    }
+
+    #[test]
+    fn test_malformed_error_signature_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let entry_file = temp_dir.path().join("lib.rs");
+        fs::write(
+            &entry_file,
+            r#"pub const ERR: EvmExitCode = derive_evm_error!("Broken(uint7)");"#,
+        )
+        .unwrap();
+
+        let resolver = StructResolver::registry(StructRegistry::default());
+        assert!(ContractDeclarations::parse_crate(&entry_file, &resolver).is_err());
+    }
+}
@@ -0,0 +1,218 @@
+use super::types::rust_to_sol;
+use crate::abi::{error::ABIError, parameter::Parameter, structs::StructResolver};
+use crypto_hashes::{digest::Digest, sha3::Keccak256};
+use serde::{Deserialize, Serialize};
+use syn::{Attribute, Data, DeriveInput, Fields};
+
+pub const EVENT_ABI_TYPE: &str = "event";
+
+/// Represents an event in the Solidity ABI
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventABI {
+    /// Event name, exactly as the struct is named
+    pub name: String,
+
+    /// Event fields in declaration order
+    pub inputs: Vec<EventParameter>,
+
+    /// Anonymous events carry no signature topic
+    pub anonymous: bool,
+
+    /// Event type (always "event")
+    #[serde(rename = "type")]
+    pub abi_type: String,
+}
+
+/// Event field: a regular ABI parameter plus whether it is stored in a topic
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventParameter {
+    #[serde(flatten)]
+    pub parameter: Parameter,
+    pub indexed: bool,
+}
+
+impl EventABI {
+    /// Builds the ABI of a `#[derive(Event)]` struct, honoring `#[indexed]` and `#[anonymous]`
+    pub fn from_derive_input(input: &DeriveInput) -> Result<Self, ABIError> {
+        let fields = match &input.data {
+            Data::Struct(data) => match &data.fields {
+                Fields::Named(named) => &named.named,
+                _ => {
+                    return Err(ABIError::UnsupportedType(format!(
+                        "event `{}` must have named fields",
+                        input.ident
+                    )))
+                }
+            },
+            _ => {
+                return Err(ABIError::UnsupportedType(format!(
+                    "event `{}` must be a struct",
+                    input.ident
+                )))
+            }
+        };
+
+        let inputs = fields
+            .iter()
+            .map(|field| {
+                let name = field
+                    .ident
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                Ok(EventParameter {
+                    parameter: Parameter::new(rust_to_sol(&field.ty)?, name),
+                    indexed: has_attribute(&field.attrs, "indexed"),
+                })
+            })
+            .collect::<Result<Vec<_>, ABIError>>()?;
+
+        Ok(Self {
+            name: input.ident.to_string(),
+            inputs,
+            anonymous: has_attribute(&input.attrs, "anonymous"),
+            abi_type: EVENT_ABI_TYPE.to_string(),
+        })
+    }
+
+    /// Builds the ABI with every struct field expanded into its components
+    pub fn from_derive_input_with(
+        input: &DeriveInput,
+        resolver: &StructResolver,
+    ) -> Result<Self, ABIError> {
+        let mut abi = Self::from_derive_input(input)?;
+        abi.resolve_structs(resolver)?;
+        Ok(abi)
+    }
+
+    /// Expands the components of every struct field, if any field needs it
+    pub fn resolve_structs(&mut self, resolver: &StructResolver) -> Result<(), ABIError> {
+        if !self
+            .inputs
+            .iter()
+            .any(|input| input.parameter.has_unresolved_struct())
+        {
+            return Ok(());
+        }
+
+        let structs = resolver.structs()?;
+        for input in &mut self.inputs {
+            // Events are looked up from the crate root, the same as contract signatures
+            input.parameter.resolve_structs(structs, "")?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns canonical event signature
+    /// Format: EventName(type1,type2,...)
+    ///
+    /// Indexed fields are part of the signature like any other field; only the log layout differs.
+    pub fn signature(&self) -> Result<String, ABIError> {
+        let params = self
+            .inputs
+            .iter()
+            .map(|input| input.parameter.get_canonical_type())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(format!("{}({})", self.name, params.join(",")))
+    }
+
+    /// Calculates the event selector (keccak256 of the signature), emitted as `topics[0]`
+    pub fn selector(&self) -> Result<[u8; 32], ABIError> {
+        let signature = self.signature()?;
+        let mut hasher = Keccak256::new();
+        hasher.update(signature.as_bytes());
+
+        let mut selector = [0u8; 32];
+        selector.copy_from_slice(&hasher.finalize());
+        Ok(selector)
+    }
+
+    pub fn to_json_value(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+
+    pub fn from_json_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+}
+
+/// Returns true if the event derive attributes contain `#[name]`
+pub(crate) fn has_attribute(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().any(|attr| attr.path().is_ident(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_event_abi_transfer() {
+        let input: DeriveInput = parse_quote! {
+            struct Transfer {
+                #[indexed]
+                from: Address,
+                #[indexed]
+                to: Address,
+                value: U256,
+            }
+        };
+
+        let abi = EventABI::from_derive_input(&input).unwrap();
+        assert_eq!(
+            abi.signature().unwrap(),
+            "Transfer(address,address,uint256)"
+        );
+        assert_eq!(
+            hex::encode(abi.selector().unwrap()),
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+        assert!(!abi.anonymous);
+
+        assert_eq!(
+            abi.to_json_value().unwrap(),
+            serde_json::json!({
+                "name": "Transfer",
+                "type": "event",
+                "anonymous": false,
+                "inputs": [
+                    {"name": "from", "type": "address", "internalType": "address", "indexed": true},
+                    {"name": "to", "type": "address", "internalType": "address", "indexed": true},
+                    {"name": "value", "type": "uint256", "internalType": "uint256", "indexed": false}
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_event_abi_anonymous() {
+        let input: DeriveInput = parse_quote! {
+            #[anonymous]
+            struct Ping {
+                #[indexed]
+                who: Address,
+            }
+        };
+
+        let abi = EventABI::from_derive_input(&input).unwrap();
+        assert!(abi.anonymous);
+        assert!(abi.inputs[0].indexed);
+    }
+
+    #[test]
+    fn test_event_abi_json_round_trip() {
+        let input: DeriveInput = parse_quote! {
+            struct Message {
+                #[indexed]
+                sender: Address,
+                text: String,
+            }
+        };
+
+        let abi = EventABI::from_derive_input(&input).unwrap();
+        let value = abi.to_json_value().unwrap();
+        assert_eq!(EventABI::from_json_value(value).unwrap(), abi);
+    }
+}
@@ -1,4 +1,4 @@
-use super::types::{rust_to_sol, ConversionError};
+use super::types::{result_type_args, rust_to_sol, ConversionError};
 use crate::abi::{error::ABIError, parameter::Parameter, structs::StructResolver};
 use convert_case::{Case, Casing};
 use crypto_hashes::{digest::Digest, sha3::Keccak256};
@@ -115,7 +115,8 @@ impl FunctionABI {
     fn convert_outputs(output: &ReturnType) -> Result<Vec<Parameter>, ABIError> {
         match output {
             ReturnType::Default => Ok(vec![]),
-            ReturnType::Type(_, ty) => match &**ty {
+            // The error type of a `Result` is a revert, so only the success type is returned
+            ReturnType::Type(_, ty) => match result_type_args(ty).map_or(&**ty, |(ok, _)| ok) {
                 Type::Tuple(tuple) => tuple
                     .elems
                     .iter()
@@ -126,7 +127,7 @@ impl FunctionABI {
                             .map_err(ABIError::from)
                     })
                     .collect(),
-                _ => Ok(vec![Parameter::new(
+                ty => Ok(vec![Parameter::new(
                     rust_to_sol(ty).map_err(ABIError::from)?,
                     "_0".to_string(),
                 )]),
@@ -243,6 +244,27 @@ mod tests {
         assert_eq!(abi.inputs[0].ty, "address");
     }
 
+    #[test]
+    fn test_function_with_result_return() {
+        let sig: Signature = parse_quote! {
+            fn withdraw(amount: U256) -> Result<(U256, bool), VaultError>
+        };
+
+        let abi = FunctionABI::from_signature(&sig).unwrap();
+
+        assert_eq!(abi.outputs.len(), 2);
+        assert_eq!(abi.outputs[0].ty, "uint256");
+        assert_eq!(abi.outputs[1].ty, "bool");
+
+        let sig: Signature = parse_quote! {
+            fn pause(&mut self) -> Result<(), VaultError>
+        };
+        assert!(FunctionABI::from_signature(&sig)
+            .unwrap()
+            .outputs
+            .is_empty());
+    }
+
     #[test]
     fn test_function_json_serialization() {
         let sig: Signature = parse_quote! {
@@ -5,6 +5,8 @@
 //!
 //! * `SolType` - represents Solidity types, parses Rust types into their Solidity equivalents
 //! * `FunctionABI` - represents Solidity function definitions
+//! * `EventABI` / `ErrorABI` - represent `#[derive(Event)]` events and `derive_evm_error!` errors
+//! * `SdkComponent` - SDK components a contract uses, whose events and errors the SDK publishes
 //! * `StructRegistry` - the crate's `#[derive(Codec)]` structs, used to expand struct parameters
 //!
 //! # Function ID Generation
@@ -41,10 +43,14 @@
 //! - Generic types are not supported
 //! - Module path is used for type identification, so a bare name matching several modules is
 //!   rejected rather than resolved arbitrarily
+pub mod components;
 pub mod constructor;
 pub mod contract;
+pub mod declarations;
 pub mod error;
+pub mod event;
 pub mod function;
 pub mod parameter;
+pub mod sol_error;
 pub mod structs;
 pub mod types;
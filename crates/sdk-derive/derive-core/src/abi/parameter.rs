@@ -1,4 +1,4 @@
-use super::types::{rust_to_sol, ConversionError, SolType};
+use super::types::{enum_to_sol, rust_to_sol, ConversionError, SolType};
 use serde::{Deserialize, Serialize};
 use syn::{DeriveInput, Type, TypePath};
 
@@ -20,13 +20,22 @@ impl Parameter {
     }
 
     /// Creates a parameter from derive input
+    ///
+    /// A C-like enum yields a `uint8` parameter without components; see [`enum_to_sol`] for how
+    /// tagged unions are described.
     pub fn from_derive_input(input: &DeriveInput) -> Result<Self, ConversionError> {
         // Get struct fields
         let fields = match &input.data {
             syn::Data::Struct(data) => &data.fields,
-            _ => {
+            syn::Data::Enum(_) => {
+                return Ok(Self::from_sol_type(
+                    enum_to_sol(input)?,
+                    input.ident.to_string(),
+                ))
+            }
+            syn::Data::Union(_) => {
                 return Err(ConversionError::UnsupportedType(
-                    "Only structs are supported".to_string(),
+                    "Only structs and enums are supported".to_string(),
                 ))
             }
         };
@@ -232,7 +241,7 @@ fn struct_components(fields: &[(String, SolType)]) -> Option<Vec<Parameter>> {
 /// Split an ABI type into its base type and the array suffixes attached to it
 ///
 /// `tuple[3][]` -> `("tuple", "[3][]")`, `uint256` -> `("uint256", "")`
-fn split_array_suffix(ty: &str) -> (&str, &str) {
+pub(crate) fn split_array_suffix(ty: &str) -> (&str, &str) {
     match ty.find('[') {
         Some(index) => ty.split_at(index),
         None => (ty, ""),
@@ -0,0 +1,367 @@
+//! Solidity custom errors declared with `derive_evm_error!` or `#[derive(SolidityError)]`
+//!
+//! `derive_evm_error!` only receives the canonical signature (`ERC20InvalidSender(address)`), so
+//! that string is the whole definition: parameters carry no names and tuples have no struct name.
+//! A `#[derive(SolidityError)]` enum declares one error per variant, with the variant fields as
+//! its parameters.
+
+use crate::abi::{
+    error::ABIError, parameter::Parameter, structs::StructResolver, types::rust_to_sol,
+};
+use crypto_hashes::{digest::Digest, sha3::Keccak256};
+use serde::{Deserialize, Serialize};
+use syn::{Data, DeriveInput};
+
+pub const ERROR_ABI_TYPE: &str = "error";
+
+/// Represents a custom error in the Solidity ABI
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ErrorABI {
+    /// Error name
+    pub name: String,
+
+    /// Error parameters, unnamed
+    pub inputs: Vec<Parameter>,
+
+    /// Error type (always "error")
+    #[serde(rename = "type")]
+    pub abi_type: String,
+}
+
+impl ErrorABI {
+    /// Parses a canonical error signature such as `ERC20InsufficientBalance(address,uint256,uint256)`
+    pub fn from_signature(signature: &str) -> Result<Self, ABIError> {
+        let signature = signature.trim();
+        let invalid = || ABIError::Syntax(format!("invalid error signature `{signature}`"));
+
+        let open = signature.find('(').ok_or_else(invalid)?;
+        let (name, params) = signature.split_at(open);
+        let params = params
+            .strip_prefix('(')
+            .and_then(|params| params.strip_suffix(')'))
+            .ok_or_else(invalid)?;
+
+        if !is_identifier(name) {
+            return Err(invalid());
+        }
+
+        let inputs = split_top_level(params)
+            .ok_or_else(invalid)?
+            .into_iter()
+            .map(|ty| parse_parameter(ty, String::new()).ok_or_else(invalid))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            name: name.to_string(),
+            inputs,
+            abi_type: ERROR_ABI_TYPE.to_string(),
+        })
+    }
+
+    /// Builds one error per variant of a `#[derive(SolidityError)]` enum, in declaration order
+    ///
+    /// Named fields keep their names; tuple variant fields are unnamed, like in `derive_evm_error!`.
+    pub fn from_derive_input(input: &DeriveInput) -> Result<Vec<Self>, ABIError> {
+        let Data::Enum(data) = &input.data else {
+            return Err(ABIError::UnsupportedType(format!(
+                "solidity error `{}` must be an enum",
+                input.ident
+            )));
+        };
+
+        data.variants
+            .iter()
+            .map(|variant| {
+                let inputs = variant
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let name = field
+                            .ident
+                            .as_ref()
+                            .map(ToString::to_string)
+                            .unwrap_or_default();
+                        Ok(Parameter::new(rust_to_sol(&field.ty)?, name))
+                    })
+                    .collect::<Result<Vec<_>, ABIError>>()?;
+
+                Ok(Self {
+                    name: variant.ident.to_string(),
+                    inputs,
+                    abi_type: ERROR_ABI_TYPE.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Builds the errors of an enum with every struct field expanded into its components
+    pub fn from_derive_input_with(
+        input: &DeriveInput,
+        resolver: &StructResolver,
+    ) -> Result<Vec<Self>, ABIError> {
+        let mut errors = Self::from_derive_input(input)?;
+        if errors
+            .iter()
+            .flat_map(|error| &error.inputs)
+            .any(Parameter::has_unresolved_struct)
+        {
+            let structs = resolver.structs()?;
+            for input in errors.iter_mut().flat_map(|error| &mut error.inputs) {
+                // Errors are looked up from the crate root, the same as contract signatures
+                input.resolve_structs(structs, "")?;
+            }
+        }
+        Ok(errors)
+    }
+
+    /// Returns canonical error signature
+    pub fn signature(&self) -> Result<String, ABIError> {
+        let params = self
+            .inputs
+            .iter()
+            .map(Parameter::get_canonical_type)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(format!("{}({})", self.name, params.join(",")))
+    }
+
+    /// Calculates error selector (first 4 bytes of keccak256 hash), the prefix of the revert data
+    pub fn selector(&self) -> Result<[u8; 4], ABIError> {
+        let signature = self.signature()?;
+        let mut hasher = Keccak256::new();
+        hasher.update(signature.as_bytes());
+        let result = hasher.finalize();
+
+        let mut selector = [0u8; 4];
+        selector.copy_from_slice(&result[..4]);
+        Ok(selector)
+    }
+
+    pub fn to_json_value(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+
+    pub fn from_json_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+}
+
+/// Parses one canonical ABI type (`uint256`, `bytes32[]`, `(address,uint256)[2]`, ...)
+fn parse_parameter(ty: &str, name: String) -> Option<Parameter> {
+    let ty = ty.trim();
+
+    if ty.starts_with('(') {
+        let close = matching_paren(ty)?;
+        let suffix = &ty[close + 1..];
+        if !is_array_suffix(suffix) {
+            return None;
+        }
+
+        let components = split_top_level(&ty[1..close])?
+            .into_iter()
+            .enumerate()
+            .map(|(i, component)| parse_parameter(component, format!("_{i}")))
+            .collect::<Option<Vec<_>>>()?;
+
+        return Some(Parameter {
+            internal_type: format!("tuple{suffix}"),
+            ty: format!("tuple{suffix}"),
+            name,
+            components: Some(components),
+        });
+    }
+
+    let base_end = ty.find('[').unwrap_or(ty.len());
+    let (base, suffix) = ty.split_at(base_end);
+    if !is_elementary(base) || !is_array_suffix(suffix) {
+        return None;
+    }
+
+    Some(Parameter {
+        internal_type: ty.to_string(),
+        ty: ty.to_string(),
+        name,
+        components: None,
+    })
+}
+
+/// Splits a comma separated type list, ignoring commas nested in tuples
+///
+/// Returns an empty list for an empty string and `None` for unbalanced parentheses or empty items.
+fn split_top_level(list: &str) -> Option<Vec<&str>> {
+    if list.trim().is_empty() {
+        return Some(Vec::new());
+    }
+
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, char) in list.char_indices() {
+        match char {
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => {
+                items.push(&list[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return None;
+    }
+    items.push(&list[start..]);
+
+    items
+        .iter()
+        .all(|item| !item.trim().is_empty())
+        .then_some(items)
+}
+
+/// Position of the parenthesis closing the one `ty` starts with
+fn matching_paren(ty: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, char) in ty.char_indices() {
+        match char {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Whether `suffix` is a (possibly empty) sequence of `[]` and `[N]`
+fn is_array_suffix(mut suffix: &str) -> bool {
+    while !suffix.is_empty() {
+        let Some(rest) = suffix.strip_prefix('[') else {
+            return false;
+        };
+        let Some(close) = rest.find(']') else {
+            return false;
+        };
+        if !rest[..close].chars().all(|char| char.is_ascii_digit()) {
+            return false;
+        }
+        suffix = &rest[close + 1..];
+    }
+    true
+}
+
+/// Whether `ty` is a canonical elementary Solidity type
+fn is_elementary(ty: &str) -> bool {
+    let sized = |prefix: &str, valid: fn(usize) -> bool| {
+        ty.strip_prefix(prefix)
+            .and_then(|bits| bits.parse::<usize>().ok())
+            .is_some_and(valid)
+    };
+
+    matches!(ty, "address" | "bool" | "string" | "bytes" | "function")
+        || sized("uint", |bits| bits % 8 == 0 && (8..=256).contains(&bits))
+        || sized("int", |bits| bits % 8 == 0 && (8..=256).contains(&bits))
+        || sized("bytes", |size| (1..=32).contains(&size))
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_' || first == '$')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_' || char == '$')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_abi_from_signature() {
+        let abi =
+            ErrorABI::from_signature("ERC20InsufficientBalance(address,uint256,uint256)").unwrap();
+
+        assert_eq!(abi.name, "ERC20InsufficientBalance");
+        assert_eq!(abi.inputs.len(), 3);
+        assert_eq!(abi.inputs[0].ty, "address");
+        assert_eq!(abi.inputs[1].ty, "uint256");
+        assert_eq!(
+            abi.signature().unwrap(),
+            "ERC20InsufficientBalance(address,uint256,uint256)"
+        );
+        assert_eq!(abi.selector().unwrap(), [0xe4, 0x50, 0xd3, 0x8c]);
+    }
+
+    #[test]
+    fn test_error_abi_without_parameters() {
+        let abi = ErrorABI::from_signature("EnforcedPause()").unwrap();
+        assert!(abi.inputs.is_empty());
+        assert_eq!(abi.selector().unwrap(), [0xd9, 0x3c, 0x06, 0x65]);
+        assert_eq!(
+            abi.to_json_value().unwrap(),
+            serde_json::json!({"name": "EnforcedPause", "type": "error", "inputs": []})
+        );
+    }
+
+    #[test]
+    fn test_error_abi_tuples_and_arrays() {
+        let abi = ErrorABI::from_signature("Bad((address,uint256[])[2],bytes32[])").unwrap();
+
+        assert_eq!(abi.inputs[0].ty, "tuple[2]");
+        let components = abi.inputs[0].components.as_ref().unwrap();
+        assert_eq!(components[1].ty, "uint256[]");
+        assert_eq!(abi.inputs[1].ty, "bytes32[]");
+        assert_eq!(
+            abi.signature().unwrap(),
+            "Bad((address,uint256[])[2],bytes32[])"
+        );
+    }
+
+    #[test]
+    fn test_error_abi_from_enum() {
+        let input: DeriveInput = syn::parse_quote! {
+            enum VaultError {
+                Paused,
+                InsufficientBalance { available: U256, required: U256 },
+                Unauthorized(Address),
+            }
+        };
+
+        let errors = ErrorABI::from_derive_input(&input).unwrap();
+        let signatures = errors
+            .iter()
+            .map(|error| error.signature().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            signatures,
+            [
+                "Paused()",
+                "InsufficientBalance(uint256,uint256)",
+                "Unauthorized(address)"
+            ]
+        );
+        assert_eq!(errors[1].inputs[0].name, "available");
+        assert_eq!(errors[2].inputs[0].name, "");
+    }
+
+    #[test]
+    fn test_error_abi_rejects_malformed_signatures() {
+        for signature in [
+            "NoParens",
+            "Bad(uint256",
+            "Bad(uint7)",
+            "Bad(address,)",
+            "Bad((address)",
+            "Bad(Custom)",
+            "1Bad()",
+        ] {
+            assert!(
+                ErrorABI::from_signature(signature).is_err(),
+                "`{signature}` should be rejected"
+            );
+        }
+    }
+}
@@ -12,7 +12,10 @@
 //! Structs are keyed by their module-qualified path (`types::Config`) rather than the bare
 //! identifier, so two modules declaring the same struct name no longer overwrite each other.
 
-use crate::abi::{error::ABIError, parameter::Parameter};
+use crate::abi::{
+    error::ABIError,
+    parameter::{split_array_suffix, Parameter},
+};
 use quote::ToTokens;
 use std::{
     cell::OnceCell,
@@ -21,14 +24,14 @@ use std::{
     path::{Path, PathBuf},
 };
 use syn::{
-    parse::Parser, parse_file, punctuated::Punctuated, Attribute, DeriveInput, Item, ItemMod,
-    ItemStruct, Meta, Path as SynPath, Token,
+    parse::Parser, parse_file, punctuated::Punctuated, Attribute, DeriveInput, Item, ItemEnum,
+    ItemMod, ItemStruct, Meta, Path as SynPath, Token,
 };
 
 /// Crate root candidates, in the order cargo itself would pick them
 const CRATE_ROOT_CANDIDATES: [&str; 4] = ["src/lib.rs", "src/main.rs", "lib.rs", "main.rs"];
 
-/// Registry of `#[derive(Codec)]` structs found in a contract crate.
+/// Registry of `#[derive(Codec)]` structs and enums found in a contract crate.
 ///
 /// Keys are module-qualified paths relative to the crate root: `Config` for a struct in the root
 /// module, `types::Config` for one declared inside `mod types`.
@@ -49,19 +52,23 @@ impl fmt::Debug for StructRegistry {
 }
 
 impl StructRegistry {
-    /// Parse every `#[derive(Codec)]` struct reachable from a crate entry file
+    /// Parse every `#[derive(Codec)]` struct and enum reachable from a crate entry file
     ///
     /// # Arguments
     /// * `entry_file` - Path to the crate root (`src/lib.rs` or `src/main.rs`)
     pub fn parse_crate(entry_file: &Path) -> Result<Self, ABIError> {
-        let mod_dir = entry_file
-            .parent()
-            .unwrap_or_else(|| Path::new("."))
-            .to_path_buf();
-
-        let mut walker = CrateWalker::default();
-        walker.walk_file(entry_file, &mod_dir, &mut Vec::new())?;
-        Ok(walker.registry)
+        let mut registry = Self::default();
+        walk_crate(entry_file, &mut |module, item| match item {
+            Item::Struct(item_struct) if has_derive(&item_struct.attrs, "Codec") => {
+                registry.insert(module, item_struct_to_derive_input(item_struct));
+            }
+            // Enums resolve like structs, to `uint8` or to their tagged union description
+            Item::Enum(item_enum) if has_derive(&item_enum.attrs, "Codec") => {
+                registry.insert(module, item_enum_to_derive_input(item_enum));
+            }
+            _ => {}
+        })?;
+        Ok(registry)
     }
 
     /// Parse the crate rooted at a package directory, locating its entry file
@@ -130,9 +137,8 @@ impl StructRegistry {
         }
     }
 
-    fn insert(&mut self, module: &[String], item: &ItemStruct) {
-        let key = qualify(module, &item.ident.to_string());
-        let derive_input = item_struct_to_derive_input(item);
+    fn insert(&mut self, module: &[String], derive_input: DeriveInput) {
+        let key = qualify(module, &derive_input.ident.to_string());
         let encoded = derive_input.to_token_stream().to_string();
 
         let definitions = self.structs.entry(key).or_default();
@@ -251,7 +257,14 @@ impl Parameter {
         let mut nested_scope = scope.to_string();
         let mut expanded_path = None;
 
-        if let Some(name) = self.struct_path().map(str::to_string) {
+        // Components that are already known, such as the variant payloads of a tagged union, need
+        // no lookup; only their own members are resolved below
+        let unresolved_path = self
+            .struct_path()
+            .filter(|_| self.components.is_none())
+            .map(str::to_string);
+
+        if let Some(name) = unresolved_path {
             let (path, definition) = structs.resolve(&name, scope)?.ok_or_else(|| {
                 ABIError::StructResolution(format!(
                     "struct `{name}` appears in the contract ABI but has no `#[derive(Codec)]` \
@@ -270,6 +283,15 @@ impl Parameter {
             nested_scope = module_of(path).to_string();
             let path = path.to_string();
             let expanded = Parameter::from_derive_input(definition)?;
+
+            // A C-like enum is a `uint8` rather than a tuple, in whatever array it is wrapped
+            if expanded.components.is_none() {
+                let (_, array_suffix) = split_array_suffix(&self.ty);
+                self.ty = format!("{}{array_suffix}", expanded.ty);
+                self.internal_type = format!("enum {}", &self.internal_type["struct ".len()..]);
+                return Ok(());
+            }
+
             self.components = expanded.components;
             expanding.push(path.clone());
             expanded_path = Some(path);
@@ -287,14 +309,33 @@ impl Parameter {
     }
 }
 
-/// Walks a crate's module tree, collecting structs with `#[derive(Codec)]`
-#[derive(Default)]
-struct CrateWalker {
-    registry: StructRegistry,
+/// Walks the module tree of the crate rooted at `entry_file`, in source order
+///
+/// `on_item` receives every item other than a module declaration, together with the path of the
+/// module it was declared in. Modules are descended into instead.
+pub fn walk_crate(
+    entry_file: &Path,
+    on_item: &mut dyn FnMut(&[String], &Item),
+) -> Result<(), ABIError> {
+    let mod_dir = entry_file
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .to_path_buf();
+
+    let mut walker = CrateWalker {
+        visited: HashSet::new(),
+        on_item,
+    };
+    walker.walk_file(entry_file, &mod_dir, &mut Vec::new())
+}
+
+/// Walks a crate's module tree, handing every item to a callback
+struct CrateWalker<'a> {
     visited: HashSet<PathBuf>,
+    on_item: &'a mut dyn FnMut(&[String], &Item),
 }
 
-impl CrateWalker {
+impl CrateWalker<'_> {
     /// Parse one source file and descend into the modules it declares
     ///
     /// `mod_dir` is the directory holding the child modules of this file.
@@ -331,11 +372,8 @@ impl CrateWalker {
     ) -> Result<(), ABIError> {
         for item in items {
             match item {
-                Item::Struct(item_struct) if has_codec_derive(&item_struct.attrs) => {
-                    self.registry.insert(module, item_struct);
-                }
                 Item::Mod(item_mod) => self.walk_module(item_mod, mod_dir, module)?,
-                _ => {}
+                _ => (self.on_item)(module, item),
             }
         }
         Ok(())
@@ -418,8 +456,8 @@ fn child_mod_dir(file: &Path) -> PathBuf {
     }
 }
 
-/// Returns true if attributes contain `#[derive(Codec)]`
-fn has_codec_derive(attrs: &[Attribute]) -> bool {
+/// Returns true if attributes contain `#[derive(name)]`
+pub fn has_derive(attrs: &[Attribute], name: &str) -> bool {
     attrs.iter().any(|attr| match &attr.meta {
         Meta::List(list) if list.path.is_ident("derive") => {
             let derives = Punctuated::<SynPath, Token![,]>::parse_terminated
@@ -427,7 +465,7 @@ fn has_codec_derive(attrs: &[Attribute]) -> bool {
                 .ok();
 
             derives
-                .map(|d| d.iter().any(|p| p.is_ident("Codec")))
+                .map(|d| d.iter().any(|p| p.is_ident(name)))
                 .unwrap_or(false)
         }
         _ => false,
@@ -435,7 +473,7 @@ fn has_codec_derive(attrs: &[Attribute]) -> bool {
 }
 
 /// Convert `ItemStruct` to `DeriveInput`
-fn item_struct_to_derive_input(item: &ItemStruct) -> DeriveInput {
+pub(crate) fn item_struct_to_derive_input(item: &ItemStruct) -> DeriveInput {
     DeriveInput {
         attrs: item.attrs.clone(),
         vis: item.vis.clone(),
@@ -449,6 +487,21 @@ fn item_struct_to_derive_input(item: &ItemStruct) -> DeriveInput {
     }
 }
 
+/// Convert `ItemEnum` to `DeriveInput`
+pub(crate) fn item_enum_to_derive_input(item: &ItemEnum) -> DeriveInput {
+    DeriveInput {
+        attrs: item.attrs.clone(),
+        vis: item.vis.clone(),
+        ident: item.ident.clone(),
+        generics: item.generics.clone(),
+        data: syn::Data::Enum(syn::DataEnum {
+            enum_token: item.enum_token,
+            brace_token: item.brace_token,
+            variants: item.variants.clone(),
+        }),
+    }
+}
+
 /// Split a type path into segments, dropping the path qualifiers a signature may carry
 fn split_path(path: &str) -> Vec<&str> {
     path.split("::")
@@ -1031,6 +1084,60 @@ pub struct Item {
             assert_eq!(fixed.get_canonical_type().unwrap(), "(uint256,address)[3]");
         }
 
+        #[test]
+        fn test_resolve_enum_parameters() {
+            let (_temp, structs) = registry(
+                r#"
+#[derive(Codec)]
+pub enum Status {
+    Pending,
+    Active,
+}
+
+#[derive(Codec)]
+pub struct Point {
+    pub x: U256,
+    pub y: U256,
+}
+
+#[derive(Codec)]
+pub enum Shape {
+    Empty,
+    Circle { center: Point, radius: U256 },
+    Marker(Status),
+}
+"#,
+            );
+
+            let mut status = struct_parameter("status", "struct Status", "tuple");
+            let mut history = struct_parameter("history", "struct Status[]", "tuple[]");
+            status.resolve_structs(&structs, "").unwrap();
+            history.resolve_structs(&structs, "").unwrap();
+
+            assert_eq!(
+                (status.ty.as_str(), status.internal_type.as_str()),
+                ("uint8", "enum Status")
+            );
+            assert_eq!(
+                (history.ty.as_str(), history.internal_type.as_str()),
+                ("uint8[]", "enum Status[]")
+            );
+            assert!(status.components.is_none());
+
+            let mut shape = struct_parameter("shape", "struct Shape", "tuple");
+            shape.resolve_structs(&structs, "").unwrap();
+
+            assert!(!shape.has_unresolved_struct());
+            assert_eq!(component_names(&shape), vec!["tag", "Circle", "Marker"]);
+            let circle = &shape.components.as_ref().unwrap()[1];
+            assert_eq!(circle.internal_type, "struct ShapeCircle");
+            assert_eq!(component_names(circle), vec!["center", "radius"]);
+            assert_eq!(
+                shape.get_canonical_type().unwrap(),
+                "(uint8,((uint256,uint256),uint256),uint8)"
+            );
+        }
+
         #[test]
         fn test_recursive_struct_is_rejected() {
             let (_temp, structs) = registry(
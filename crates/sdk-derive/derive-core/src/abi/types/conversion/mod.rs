@@ -2,6 +2,6 @@ mod rust_to_sol;
 mod sol_to_rust;
 mod syn_sol_to_internal;
 
-pub use rust_to_sol::{rust_to_sol, ConversionError};
+pub use rust_to_sol::{enum_to_sol, result_type_args, rust_to_sol, ConversionError};
 pub use sol_to_rust::sol_to_rust;
 pub use syn_sol_to_internal::convert_solidity_type;
@@ -1,5 +1,5 @@
 use crate::abi::types::SolType;
-use syn::{self, GenericArgument, PathArguments, Type};
+use syn::{self, DeriveInput, Fields, GenericArgument, PathArguments, Type};
 
 #[derive(Debug, thiserror::Error, Clone, PartialEq)]
 pub enum ConversionError {
@@ -33,6 +33,84 @@ pub fn rust_to_sol(ty: &Type) -> Result<SolType, ConversionError> {
     }
 }
 
+/// Splits a `Result<T, E>` return type into its success and error types
+///
+/// A router method returning `Result` publishes `T` as its outputs, while `E` is a
+/// `#[derive(SolidityError)]` enum whose variants become the custom errors the method reverts with.
+pub fn result_type_args(ty: &Type) -> Option<(&Type, &Type)> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Result" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    let mut types = args.args.iter().filter_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+    match (types.next(), types.next(), types.next()) {
+        (Some(ok), Some(err), None) => Some((ok, err)),
+        _ => None,
+    }
+}
+
+/// Convert a `#[derive(Codec)]` enum to its Solidity type
+///
+/// A C-like enum is a Solidity `enum`, encoded as `uint8`. A tagged union has no Solidity
+/// counterpart, so it is described as a struct holding the `tag` followed by one member per
+/// data-carrying variant: the variant's only unnamed field, or a `{Enum}{Variant}` struct of its
+/// fields.
+pub fn enum_to_sol(input: &DeriveInput) -> Result<SolType, ConversionError> {
+    let syn::Data::Enum(data) = &input.data else {
+        return Err(ConversionError::UnsupportedType(format!(
+            "`{}` is not an enum",
+            input.ident
+        )));
+    };
+
+    let name = input.ident.to_string();
+    if data
+        .variants
+        .iter()
+        .all(|variant| variant.fields.is_empty())
+    {
+        return Ok(SolType::Enum { name });
+    }
+
+    let mut fields = vec![("tag".to_string(), SolType::Uint(8))];
+    for variant in &data.variants {
+        let payload = match &variant.fields {
+            Fields::Unit => continue,
+            Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+                rust_to_sol(&unnamed.unnamed[0].ty)?
+            }
+            variant_fields if variant_fields.is_empty() => continue,
+            variant_fields => SolType::Struct {
+                name: format!("{name}{}", variant.ident),
+                fields: variant_fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| {
+                        let field_name = field
+                            .ident
+                            .as_ref()
+                            .map_or_else(|| format!("_{i}"), ToString::to_string);
+                        Ok((field_name, rust_to_sol(&field.ty)?))
+                    })
+                    .collect::<Result<Vec<_>, ConversionError>>()?,
+            },
+        };
+        fields.push((variant.ident.to_string(), payload));
+    }
+
+    Ok(SolType::Struct { name, fields })
+}
+
 fn get_full_path(type_path: &syn::TypePath) -> Result<String, ConversionError> {
     let mut path = String::new();
     for segment in &type_path.path.segments {
@@ -236,6 +314,22 @@ mod tests {
         error_check(&result.unwrap_err());
     }
 
+    #[test]
+    fn test_result_type_args() {
+        let ty: Type = parse_str("Result<(Address, U256), TokenError>").unwrap();
+        let (ok, err) = result_type_args(&ty).unwrap();
+        assert_eq!(
+            rust_to_sol(ok).unwrap(),
+            SolType::Tuple(vec![SolType::Address, SolType::Uint(256)])
+        );
+        assert_eq!(quote::quote!(#err).to_string(), "TokenError");
+
+        for ty in ["U256", "Result<U256>", "Option<U256>"] {
+            let ty: Type = parse_str(ty).unwrap();
+            assert!(result_type_args(&ty).is_none());
+        }
+    }
+
     #[test]
     fn test_primitive_types() {
         assert_type("bool", SolType::Bool);
@@ -521,6 +615,79 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_enum_types() {
+        let c_like: DeriveInput = syn::parse_quote! {
+            enum Status { Pending, Active = 3, Closed }
+        };
+        assert_eq!(
+            enum_to_sol(&c_like).unwrap(),
+            SolType::Enum {
+                name: "Status".to_string()
+            }
+        );
+
+        let tagged_union: DeriveInput = syn::parse_quote! {
+            enum Action {
+                Noop,
+                Transfer { to: Address, amount: U256 },
+                Call(Address, Bytes),
+                Config(Settings),
+            }
+        };
+        assert_eq!(
+            enum_to_sol(&tagged_union).unwrap(),
+            SolType::Struct {
+                name: "Action".to_string(),
+                fields: vec![
+                    ("tag".to_string(), SolType::Uint(8)),
+                    (
+                        "Transfer".to_string(),
+                        SolType::Struct {
+                            name: "ActionTransfer".to_string(),
+                            fields: vec![
+                                ("to".to_string(), SolType::Address),
+                                ("amount".to_string(), SolType::Uint(256)),
+                            ],
+                        }
+                    ),
+                    (
+                        "Call".to_string(),
+                        SolType::Struct {
+                            name: "ActionCall".to_string(),
+                            fields: vec![
+                                ("_0".to_string(), SolType::Address),
+                                ("_1".to_string(), SolType::Bytes),
+                            ],
+                        }
+                    ),
+                    (
+                        "Config".to_string(),
+                        SolType::Struct {
+                            name: "Settings".to_string(),
+                            fields: vec![],
+                        }
+                    ),
+                ],
+            }
+        );
+
+        assert_eq!(
+            SolType::Enum {
+                name: "Status".to_string()
+            }
+            .to_string(),
+            "uint8"
+        );
+        assert_eq!(
+            SolType::Array(Box::new(SolType::Enum {
+                name: "Status".to_string()
+            }))
+            .abi_type_internal(),
+            "enum Status[]"
+        );
+    }
+
     mod b_type_tests {
         use super::*;
         use syn::parse_str;
@@ -30,7 +30,7 @@ pub fn sol_to_rust(sol_type: &SolType) -> Result<Type, ABIError> {
         SolType::Tuple(types) => to_rust_tuple(types),
 
         // User-defined types
-        SolType::Struct { name, .. } => {
+        SolType::Struct { name, .. } | SolType::Enum { name } => {
             let ident = format_ident!("{}", name);
             Ok(parse_quote!(#ident))
         }
@@ -22,6 +22,10 @@ pub enum SolType {
         name: String,
         fields: Vec<(String, SolType)>,
     },
+    /// C-like enum, encoded as its `uint8` tag
+    Enum {
+        name: String,
+    },
 }
 
 impl SolType {
@@ -34,12 +38,15 @@ impl SolType {
     pub fn abi_type_internal(&self) -> String {
         match self {
             Self::Struct { name, .. } => format!("struct {name}"),
+            Self::Enum { name } => format!("enum {name}"),
             Self::Array(inner) => match &**inner {
                 Self::Struct { name, .. } => format!("struct {name}[]"),
+                Self::Enum { name } => format!("enum {name}[]"),
                 _ => self.to_string(),
             },
             Self::FixedArray(inner, size) => match &**inner {
                 Self::Struct { name, .. } => format!("struct {name}[{size}]"),
+                Self::Enum { name } => format!("enum {name}[{size}]"),
                 _ => self.to_string(),
             },
             _ => self.to_string(),
@@ -70,6 +77,7 @@ impl Display for SolType {
                 write!(f, ")")
             }
             Self::Struct { .. } => write!(f, "tuple"),
+            Self::Enum { .. } => write!(f, "uint8"),
         }
     }
 }
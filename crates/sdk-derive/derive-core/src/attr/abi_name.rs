@@ -0,0 +1,75 @@
+use syn::{spanned::Spanned, Attribute, LitStr};
+
+/// Name of the attribute overriding the Solidity name a method is published under.
+pub const ABI_NAME_ATTR: &str = "abi_name";
+
+/// Parses the `#[abi_name("...")]` attribute of a method.
+///
+/// The ABI name is the Rust name in camelCase by default, which can't spell every Solidity name,
+/// e.g. `proxiableUUID`. The override renames the published function, so it also changes the
+/// signature the selector is derived from unless `#[function_id]` pins it.
+///
+/// # Arguments
+///
+/// * `attrs` - The attributes of the method
+///
+/// # Returns
+///
+/// The declared name, `None` without the attribute, or an error if it isn't a Solidity identifier
+pub fn resolve_abi_name(attrs: &[Attribute]) -> syn::Result<Option<String>> {
+    let Some(attr) = attrs.iter().find(|attr| attr.path().is_ident(ABI_NAME_ATTR)) else {
+        return Ok(None);
+    };
+
+    let literal = attr.parse_args::<LitStr>().map_err(|_| {
+        syn::Error::new(
+            attr.span(),
+            format!("Expected #[{ABI_NAME_ATTR}(\"...\")] with a Solidity function name"),
+        )
+    })?;
+
+    let name = literal.value();
+    if !is_solidity_identifier(&name) {
+        return Err(syn::Error::new(
+            literal.span(),
+            format!("Invalid Solidity function name '{name}'"),
+        ));
+    }
+
+    Ok(Some(name))
+}
+
+fn is_solidity_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_' || first == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_abi_name_is_parsed() {
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[abi_name("proxiableUUID")])];
+        assert_eq!(
+            resolve_abi_name(&attrs).unwrap().as_deref(),
+            Some("proxiableUUID")
+        );
+        assert_eq!(resolve_abi_name(&[]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_invalid_abi_name_is_rejected() {
+        for attr in [
+            parse_quote!(#[abi_name("1st")]),
+            parse_quote!(#[abi_name("name()")]),
+            parse_quote!(#[abi_name(name)]),
+        ] {
+            assert!(resolve_abi_name(&[attr]).is_err());
+        }
+    }
+}
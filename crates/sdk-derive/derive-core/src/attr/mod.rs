@@ -1,9 +1,16 @@
+pub(crate) mod abi_name;
 pub(crate) mod artifacts_dir;
 pub(crate) mod function_id;
 pub(crate) mod mode;
+pub(crate) mod modifier;
 pub(crate) mod state_mutability;
 
+pub use abi_name::ABI_NAME_ATTR;
 pub use artifacts_dir::Artifacts;
 pub use function_id::FunctionIDAttribute;
 pub use mode::Mode;
-pub use state_mutability::{StateMutabilityExt, STATE_MUTABILITY_ATTR};
+pub use modifier::{
+    is_modifier_attr, modifier_outside_router, Modifier, MODIFIER_ATTRS, NON_REENTRANT_ATTR,
+    ONLY_OWNER_ATTR, ONLY_ROLE_ATTR,
+};
+pub use state_mutability::{mutable_receiver_lint, StateMutabilityExt, STATE_MUTABILITY_ATTR};
@@ -0,0 +1,276 @@
+use crate::{abi::function::StateMutability, attr::state_mutability::StateMutabilityExt};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Attribute, Expr, Ident, Meta, Result, Token,
+};
+
+/// Restricts a method to the owner of the contract's `Ownable` or `Ownable2Step` field.
+pub const ONLY_OWNER_ATTR: &str = "only_owner";
+/// Restricts a method to the members of a role of the contract's `AccessControl` field.
+pub const ONLY_ROLE_ATTR: &str = "only_role";
+/// Rejects calls that re-enter any guarded method of the contract.
+pub const NON_REENTRANT_ATTR: &str = "non_reentrant";
+
+/// Every attribute the router consumes as a modifier
+pub const MODIFIER_ATTRS: &[&str] = &[ONLY_OWNER_ATTR, ONLY_ROLE_ATTR, NON_REENTRANT_ATTR];
+
+/// Default name of the field holding the `Ownable` component
+const DEFAULT_OWNABLE_FIELD: &str = "ownable";
+/// Default name of the field holding the `AccessControl` component
+const DEFAULT_ACCESS_CONTROL_FIELD: &str = "access_control";
+
+/// A Solidity-style modifier wrapped around a routed method.
+///
+/// Modifiers are applied by the dispatcher rather than by rewriting the method, so they also work
+/// on `&self` methods. They guard calls coming into the contract only; calling the method directly
+/// from Rust skips them.
+///
+/// # Example
+/// ```rust, ignore
+/// #[only_owner]
+/// pub fn pause(&mut self) { /* ... */ }
+///
+/// #[only_role(MINTER_ROLE)]
+/// #[non_reentrant]
+/// pub fn mint(&mut self, to: Address, amount: U256) { /* ... */ }
+///
+/// // Components stored under other field names
+/// #[only_owner(field = admin)]
+/// #[only_role(MINTER_ROLE, field = roles)]
+/// pub fn burn(&mut self, amount: U256) { /* ... */ }
+/// ```
+#[derive(Debug, Clone)]
+pub enum Modifier {
+    /// `#[only_owner]` or `#[only_owner(field = name)]`
+    OnlyOwner { field: Ident },
+    /// `#[only_role(ROLE)]` or `#[only_role(ROLE, field = name)]`
+    OnlyRole { role: Expr, field: Ident },
+    /// `#[non_reentrant]`
+    NonReentrant,
+}
+
+/// Checks whether an attribute is one of the router modifiers
+pub fn is_modifier_attr(attr: &Attribute) -> bool {
+    MODIFIER_ATTRS.iter().any(|name| attr.path().is_ident(name))
+}
+
+/// The optional `field = name` argument
+struct FieldArg(Ident);
+
+impl Parse for FieldArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let key: Ident = input.parse()?;
+        if key != "field" {
+            return Err(syn::Error::new(
+                key.span(),
+                format!("unknown argument `{key}`, expected `field = name`"),
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        Ok(Self(input.parse()?))
+    }
+}
+
+/// Arguments of `#[only_role(...)]`
+struct OnlyRoleArgs {
+    role: Expr,
+    field: Option<Ident>,
+}
+
+impl Parse for OnlyRoleArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let role = input.parse()?;
+        let field = if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            Some(input.parse::<FieldArg>()?.0)
+        } else {
+            None
+        };
+        if !input.is_empty() {
+            return Err(
+                input.error("expected `#[only_role(ROLE)]` or `#[only_role(ROLE, field = name)]`")
+            );
+        }
+        Ok(Self { role, field })
+    }
+}
+
+impl Modifier {
+    /// Collects the modifiers of a method in declaration order
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Vec<Self>> {
+        attrs
+            .iter()
+            .filter(|attr| is_modifier_attr(attr))
+            .map(Self::from_attr)
+            .collect()
+    }
+
+    fn from_attr(attr: &Attribute) -> Result<Self> {
+        let default_field = |name: &str| Ident::new(name, attr.path().span());
+
+        if attr.path().is_ident(ONLY_OWNER_ATTR) {
+            let field = match &attr.meta {
+                Meta::Path(_) => default_field(DEFAULT_OWNABLE_FIELD),
+                _ => attr.parse_args::<FieldArg>()?.0,
+            };
+            Ok(Self::OnlyOwner { field })
+        } else if attr.path().is_ident(ONLY_ROLE_ATTR) {
+            let Meta::List(_) = &attr.meta else {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "#[only_role] requires the role, e.g. #[only_role(MINTER_ROLE)]",
+                ));
+            };
+            let args = attr.parse_args::<OnlyRoleArgs>()?;
+            Ok(Self::OnlyRole {
+                role: args.role,
+                field: args
+                    .field
+                    .unwrap_or_else(|| default_field(DEFAULT_ACCESS_CONTROL_FIELD)),
+            })
+        } else {
+            attr.meta.require_path_only()?;
+            Ok(Self::NonReentrant)
+        }
+    }
+
+    /// Code the dispatcher runs before calling the method
+    pub fn before(&self, mutability: StateMutability) -> TokenStream2 {
+        match self {
+            Self::OnlyOwner { field } => quote! {
+                self.#field.only_owner(&mut self.sdk);
+            },
+            Self::OnlyRole { role, field } => quote! {
+                self.#field.only_role(&mut self.sdk, #role);
+            },
+            // A `view` method cannot take the lock, but it must not observe a half-done update
+            Self::NonReentrant if mutability.is_static() => quote! {
+                fluentbase_sdk::security::ReentrancyGuard::check(&mut self.sdk);
+            },
+            Self::NonReentrant => quote! {
+                fluentbase_sdk::security::ReentrancyGuard::enter(&mut self.sdk);
+            },
+        }
+    }
+
+    /// Code the dispatcher runs once the method has returned
+    pub fn after(&self, mutability: StateMutability) -> TokenStream2 {
+        match self {
+            Self::NonReentrant if !mutability.is_static() => quote! {
+                fluentbase_sdk::security::ReentrancyGuard::exit(&mut self.sdk);
+            },
+            _ => quote! {},
+        }
+    }
+}
+
+/// Expansion of a modifier attribute found outside a `#[router]` impl.
+///
+/// The router strips the modifiers it applies, so the attribute only expands by itself when nothing
+/// would enforce it. That is reported as an error instead of silently leaving the method open.
+pub fn modifier_outside_router(name: &str, item: TokenStream2) -> TokenStream2 {
+    let message = format!(
+        "#[{name}] only takes effect on public methods of a #[router] impl; call the component's \
+         check from the method body instead"
+    );
+    quote! {
+        ::core::compile_error!(#message);
+        #item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::{parse_quote, ImplItemFn};
+
+    fn modifiers(method: ImplItemFn) -> Result<Vec<Modifier>> {
+        Modifier::from_attrs(&method.attrs)
+    }
+
+    fn expand(modifier: &Modifier, mutability: StateMutability) -> (String, String) {
+        (
+            modifier.before(mutability).to_string(),
+            modifier.after(mutability).to_string(),
+        )
+    }
+
+    #[test]
+    fn test_modifiers_parse_in_declaration_order() {
+        let parsed = modifiers(parse_quote! {
+            #[only_role(Self::MINTER_ROLE, field = roles)]
+            #[function_id("mint(address,uint256)")]
+            #[non_reentrant]
+            #[only_owner]
+            pub fn mint(&mut self, to: Address, amount: U256) {}
+        })
+        .unwrap();
+
+        assert_eq!(parsed.len(), 3);
+        assert!(matches!(
+            &parsed[0],
+            Modifier::OnlyRole { role, field }
+                if quote!(#role).to_string() == "Self :: MINTER_ROLE" && field == "roles"
+        ));
+        assert!(matches!(parsed[1], Modifier::NonReentrant));
+        assert!(matches!(&parsed[2], Modifier::OnlyOwner { field } if field == "ownable"));
+    }
+
+    #[test]
+    fn test_modifier_expansion() {
+        let parsed = modifiers(parse_quote! {
+            #[only_owner(field = admin)]
+            #[only_role(MINTER_ROLE)]
+            pub fn mint(&mut self) {}
+        })
+        .unwrap();
+
+        let (before, after) = expand(&parsed[0], StateMutability::Payable);
+        assert_eq!(before, "self . admin . only_owner (& mut self . sdk) ;");
+        assert!(after.is_empty());
+
+        let (before, _) = expand(&parsed[1], StateMutability::Payable);
+        assert_eq!(
+            before,
+            "self . access_control . only_role (& mut self . sdk , MINTER_ROLE) ;"
+        );
+    }
+
+    #[test]
+    fn test_non_reentrant_only_checks_static_methods() {
+        let guard = Modifier::NonReentrant;
+
+        let (before, after) = expand(&guard, StateMutability::NonPayable);
+        assert!(before.contains("ReentrancyGuard :: enter"));
+        assert!(after.contains("ReentrancyGuard :: exit"));
+
+        let (before, after) = expand(&guard, StateMutability::View);
+        assert!(before.contains("ReentrancyGuard :: check"));
+        assert!(after.is_empty());
+    }
+
+    #[test]
+    fn test_malformed_modifiers_are_rejected() {
+        let err = modifiers(parse_quote! {
+            #[only_role]
+            pub fn mint(&mut self) {}
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("requires the role"));
+
+        let err = modifiers(parse_quote! {
+            #[only_owner(owner = admin)]
+            pub fn pause(&mut self) {}
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown argument `owner`"));
+
+        assert!(modifiers(parse_quote! {
+            #[non_reentrant(true)]
+            pub fn withdraw(&mut self) {}
+        })
+        .is_err());
+    }
+}
@@ -1,4 +1,6 @@
 use crate::abi::function::StateMutability;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote_spanned};
 use syn::{spanned::Spanned, Attribute, FnArg, LitStr, Signature};
 
 /// Name of the attribute carrying the Solidity state mutability of a method.
@@ -69,8 +71,7 @@ impl StateMutabilityExt for StateMutability {
 ///
 /// # Returns
 ///
-/// The resolved state mutability, or an error if the attribute is malformed or
-/// contradicts the receiver
+/// The resolved state mutability, or an error if the attribute is malformed
 pub fn resolve_state_mutability(
     attrs: &[Attribute],
     sig: &Signature,
@@ -106,19 +107,42 @@ pub fn resolve_state_mutability(
         )
     })?;
 
-    // A `&mut self` receiver promises state access, so it cannot be reconciled
-    // with a read-only mutability: the two would disagree on the host operation.
-    if declared.is_static() && has_mutable_receiver(sig) {
-        return Err(syn::Error::new(
-            literal.span(),
-            format!(
-                "Method declared as '{}' must take '&self', not '&mut self'",
-                literal.value()
-            ),
-        ));
+    Ok(declared)
+}
+
+/// Warns about a `view` or `pure` method taking `&mut self`.
+///
+/// The receiver promises state access the mutability forbids. The method still compiles, and the
+/// router runs it against a read-only SDK, but every mutation it attempts fails at runtime with
+/// `StateChangeDuringStaticCall`, so the contradiction is surfaced as a warning at the receiver.
+///
+/// Proc macros cannot emit warnings on stable, so this expands to a use of a deprecated constant.
+pub fn mutable_receiver_lint(sig: &Signature, mutability: StateMutability) -> Option<TokenStream2> {
+    if !mutability.is_static() {
+        return None;
     }
+    let receiver = sig.inputs.iter().find_map(|arg| match arg {
+        FnArg::Receiver(receiver) if receiver.mutability.is_some() => Some(receiver),
+        _ => None,
+    })?;
 
-    Ok(declared)
+    let note = format!(
+        "method `{}` is declared `{}` but takes `&mut self`; its state changes fail with \
+         `StateChangeDuringStaticCall`, take `&self` instead",
+        sig.ident,
+        mutability.as_str()
+    );
+    let lint = format_ident!("{}_takes_mut_self", sig.ident);
+
+    Some(quote_spanned! {receiver.span()=>
+        const _: () = {
+            #[deprecated(note = #note)]
+            #[allow(non_upper_case_globals)]
+            const #lint: () = ();
+            #[allow(clippy::let_unit_value)]
+            let _ = #lint;
+        };
+    })
 }
 
 /// Returns the mutability implied by a method receiver
@@ -176,16 +200,33 @@ mod tests {
     }
 
     #[test]
-    fn test_read_only_mutability_rejects_mutable_receiver() {
+    fn test_read_only_mutability_with_mutable_receiver_is_linted() {
         let contradiction: TraitItemFn = parse_quote! {
             #[state_mutability("view")]
             fn balance_of(&mut self, owner: Address) -> U256;
         };
-        let err = resolve(&contradiction).unwrap_err();
+        let mutability = resolve(&contradiction).unwrap();
+        assert_eq!(mutability, StateMutability::View);
+
+        let lint = mutable_receiver_lint(&contradiction.sig, mutability)
+            .expect("a view method taking &mut self should be linted")
+            .to_string();
+        assert!(lint.contains("deprecated"), "unexpected lint: {lint}");
         assert!(
-            err.to_string().contains("must take '&self'"),
-            "unexpected error: {err}"
+            lint.contains("method `balance_of` is declared `view` but takes `&mut self`"),
+            "unexpected lint: {lint}"
         );
+
+        let immutable: TraitItemFn = parse_quote! {
+            #[state_mutability("view")]
+            fn balance_of(&self, owner: Address) -> U256;
+        };
+        assert!(mutable_receiver_lint(&immutable.sig, StateMutability::View).is_none());
+
+        let mutable: TraitItemFn = parse_quote! {
+            fn transfer(&mut self, to: Address) -> bool;
+        };
+        assert!(mutable_receiver_lint(&mutable.sig, StateMutability::Payable).is_none());
     }
 
     #[test]
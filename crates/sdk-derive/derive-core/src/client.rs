@@ -223,14 +223,14 @@ impl<T: MethodLike> Client<T> {
         };
 
         // Generate decode output
-        let decode_output = match &sig.output {
-            syn::ReturnType::Default => {
+        let decode_output = match sig.success_type() {
+            None => {
                 quote! {
                     ()
                 }
             }
-            syn::ReturnType::Type(_, ty) => {
-                if let syn::Type::Tuple(_) = &**ty {
+            Some(ty) => {
+                if let syn::Type::Tuple(_) = ty {
                     quote! {
                         #fn_return::decode(&result.data)
                             .expect("failed to decode result")
@@ -294,6 +294,29 @@ impl<T: MethodLike> Client<T> {
             }
         };
 
+        // A `Result` method gets its custom error back; any other revert still panics
+        let (status_check, decode_output) = match sig.error_type() {
+            Some(error_type) => (
+                quote! {
+                    if !fluentbase_sdk::SyscallResult::is_ok(result.status) {
+                        return match <#error_type as fluentbase_sdk::SolidityError>::decode(&result.data) {
+                            Some(err) => Err(err),
+                            None => ::core::panic!("Contract call failed"),
+                        };
+                    }
+                },
+                quote! { Ok(#decode_output) },
+            ),
+            None => (
+                quote! {
+                    if !fluentbase_sdk::SyscallResult::is_ok(result.status) {
+                        ::core::panic!("Contract call failed");
+                    }
+                },
+                decode_output,
+            ),
+        };
+
         Ok(quote! {
             pub fn #fn_name(
                 &mut self,
@@ -319,9 +342,7 @@ impl<T: MethodLike> Client<T> {
                     .expect("gas limit is too large to convert to fuel");
                 let result = #host_call;
 
-                if !fluentbase_sdk::SyscallResult::is_ok(result.status) {
-                    ::core::panic!("Contract call failed");
-                }
+                #status_check
 
                 #decode_output
             }
@@ -415,6 +436,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_result_methods_decode_custom_errors() {
+        let generated = generated_method(parse_quote! {
+            fn withdraw(&mut self, amount: U256) -> Result<U256, VaultError>;
+        });
+
+        assert!(generated.contains(")->Result<U256,VaultError>{"));
+        assert!(
+            generated.contains("<VaultErrorasfluentbase_sdk::SolidityError>::decode(&result.data)")
+        );
+        assert!(generated.contains("Ok(WithdrawReturn::decode(&result.data)"));
+    }
+
     #[test]
     fn test_nonpayable_methods_call_with_zero_value() {
         let generated = generated_method(parse_quote! {
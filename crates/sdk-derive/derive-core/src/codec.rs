@@ -5,7 +5,7 @@ use crate::{
 use convert_case::{Case, Casing};
 use proc_macro2::{Ident, TokenStream as TokenStream2};
 use quote::{format_ident, quote};
-use syn::{FnArg, Result, ReturnType, Type};
+use syn::{FnArg, Result, Type};
 
 /// Handles generation of encoding/decoding code for contract function parameters and return values
 ///
@@ -176,13 +176,13 @@ impl<'a, T: MethodLike> CodecGenerator<'a, T> {
     }
 
     /// Extracts output types from the function signature
+    ///
+    /// A `Result<T, E>` returns `T`; the error is encoded as revert data by `E` itself.
     fn extract_output_types(&self) -> Vec<&Type> {
-        match &self.route.parsed_signature().output {
-            ReturnType::Default => Vec::new(),
-            ReturnType::Type(_, ty) => match &**ty {
-                Type::Tuple(tuple) => tuple.elems.iter().collect(),
-                ty => vec![ty],
-            },
+        match self.route.parsed_signature().success_type() {
+            None => Vec::new(),
+            Some(Type::Tuple(tuple)) => tuple.elems.iter().collect(),
+            Some(ty) => vec![ty],
         }
     }
 
@@ -21,10 +21,14 @@
 //! Transfer { from, to, value }.emit(&mut sdk);
 //! ```
 
-use crate::abi::types::rust_to_sol;
+use crate::abi::{
+    event::{has_attribute, EventABI},
+    structs::StructResolver,
+    types::rust_to_sol,
+};
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
-use syn::{Attribute, Data, DeriveInput, Error, Fields, Ident, Result, Type};
+use syn::{Data, DeriveInput, Error, Fields, Ident, Result};
 
 /// EVM allows maximum 4 topics per log.
 /// Regular events use topic[0] for signature, leaving 3 for indexed fields.
@@ -35,7 +39,6 @@ const MAX_INDEXED_FIELDS_ANONYMOUS: usize = 4;
 
 struct EventField {
     name: Ident,
-    ty: Type,
     /// Indexed fields go to topics (filterable via bloom filter).
     /// Non-indexed fields go to data (cheaper, but requires full scan to filter).
     indexed: bool,
@@ -50,9 +53,30 @@ struct ParsedEvent {
 
 /// Main entry point for the Event derive macro.
 pub fn process_event(input: DeriveInput) -> Result<TokenStream2> {
-    let event = parse_event(input)?;
+    process_event_with_structs(input, &StructResolver::crate_sources())
+}
+
+/// Processes the event with an explicit struct resolver.
+///
+/// The signature is taken from the same [`EventABI`] the build tooling publishes, so struct fields
+/// hash with their components rather than as an empty `tuple`. The entry itself is exposed as
+/// `ABI`, which is how the SDK publishes the events of its components.
+pub fn process_event_with_structs(
+    input: DeriveInput,
+    resolver: &StructResolver,
+) -> Result<TokenStream2> {
+    let event = parse_event(input.clone())?;
     validate_event(&event)?;
-    generate_event_impl(&event)
+    let abi = EventABI::from_derive_input_with(&input, resolver)
+        .map_err(|e| Error::new_spanned(&input.ident, e.to_string()))?;
+    let signature = abi
+        .signature()
+        .map_err(|e| Error::new_spanned(&input.ident, e.to_string()))?;
+    let abi_json = abi
+        .to_json_value()
+        .map_err(|e| Error::new_spanned(&input.ident, e.to_string()))?
+        .to_string();
+    generate_event_impl(&event, &signature, &abi_json)
 }
 
 fn parse_event(input: DeriveInput) -> Result<ParsedEvent> {
@@ -72,7 +96,6 @@ fn parse_event(input: DeriveInput) -> Result<ParsedEvent> {
 
                     Ok(EventField {
                         name: f.ident.expect("Named field"),
-                        ty: f.ty,
                         indexed: has_attribute(&f.attrs, "indexed"),
                     })
                 })
@@ -89,10 +112,6 @@ fn parse_event(input: DeriveInput) -> Result<ParsedEvent> {
     })
 }
 
-fn has_attribute(attrs: &[Attribute], name: &str) -> bool {
-    attrs.iter().any(|attr| attr.path().is_ident(name))
-}
-
 fn validate_event(event: &ParsedEvent) -> Result<()> {
     let indexed_count = event.fields.iter().filter(|f| f.indexed).count();
     let max = if event.anonymous {
@@ -129,21 +148,9 @@ fn keccak256(input: &[u8]) -> [u8; 32] {
     output
 }
 
-fn generate_event_impl(event: &ParsedEvent) -> Result<TokenStream2> {
+fn generate_event_impl(event: &ParsedEvent, signature: &str, abi: &str) -> Result<TokenStream2> {
     let name = &event.name;
 
-    // Build Solidity signature: "EventName(type1,type2,...)"
-    let sol_types: Vec<String> = event
-        .fields
-        .iter()
-        .map(|f| {
-            rust_to_sol(&f.ty)
-                .map(|t| t.abi_type())
-                .unwrap_or_else(|_| "unknown".to_string())
-        })
-        .collect();
-    let signature = format!("{}({})", name, sol_types.join(","));
-
     // Computed at compile-time by proc-macro
     let selector = keccak256(signature.as_bytes());
 
@@ -162,6 +169,9 @@ fn generate_event_impl(event: &ParsedEvent) -> Result<TokenStream2> {
             /// Keccak256 hash of signature, computed at compile-time.
             pub const SELECTOR: [u8; 32] = [#(#selector),*];
 
+            /// Entry of the event in the contract ABI, as JSON.
+            pub const ABI: &'static str = #abi;
+
             /// Emits this event as an EVM log.
             pub fn emit<SDK: fluentbase_sdk::SharedAPI>(&self, sdk: &mut SDK) -> Result<(), fluentbase_sdk::ExitCode> {
                 let topics: [fluentbase_sdk::B256; #topic_count] = #topics_code;
@@ -11,10 +11,12 @@ pub mod method;
 pub mod router;
 pub mod signature;
 pub mod sol_input;
+pub mod solidity_error;
 pub mod storage;
 // #[deprecated(
 //     note = "Use `fluentbase_sdk_derive_core::storage` instead",
 //     since = "0.4.5-dev"
 // )]
 pub mod storage_legacy;
-mod utils;
+pub mod uups;
+pub mod utils;
@@ -5,6 +5,7 @@ use crate::{
         structs::StructResolver,
     },
     attr::{
+        abi_name::resolve_abi_name,
         function_id::FunctionID,
         state_mutability::{resolve_state_mutability, StateMutabilityExt},
         FunctionIDAttribute,
@@ -97,6 +98,11 @@ pub trait MethodLike: Sized {
     fn state_mutability(&self) -> syn::Result<StateMutability> {
         resolve_state_mutability(self.attrs(), self.sig())
     }
+
+    /// Extracts the Solidity name override if present
+    fn abi_name(&self) -> syn::Result<Option<String>> {
+        resolve_abi_name(self.attrs())
+    }
 }
 
 // Implement MethodLike for TraitItemFn
@@ -150,6 +156,7 @@ impl<T: MethodLike> ParsedMethod<T> {
     pub fn new(inner: T, resolver: &StructResolver) -> syn::Result<Self> {
         let sig = ParsedSignature::new(inner.sig().clone());
         let state_mutability = inner.state_mutability()?;
+        let abi_name = inner.abi_name()?;
         let attr = inner.function_id_attr()?;
 
         // A custom selector without validation is authoritative, and is also the escape hatch for
@@ -158,12 +165,15 @@ impl<T: MethodLike> ParsedMethod<T> {
         if let Some((attr, _)) = &attr {
             if !attr.is_validation_enabled() {
                 let function_id = attr.function_id_bytes()?;
+                let pinned_signature = attr.signature();
                 let abi = sig.function_abi_with(resolver).ok().map(|mut abi| {
                     abi.state_mutability = state_mutability;
+                    if let Some(name) = &abi_name {
+                        abi.name = name.clone();
+                    }
                     abi
                 });
-                let signature = attr
-                    .signature()
+                let signature = pinned_signature
                     .or_else(|| abi.as_ref().and_then(|abi| abi.signature().ok()))
                     .unwrap_or_else(|| format!("0x{}", hex::encode(function_id)));
 
@@ -187,6 +197,9 @@ impl<T: MethodLike> ParsedMethod<T> {
             syn::Error::new(sig.span(), format!("{error}{}", help.unwrap_or_default()))
         })?;
         abi.state_mutability = state_mutability;
+        if let Some(name) = abi_name {
+            abi.name = name;
+        }
         let signature = abi.signature()?;
         let function_id = abi.function_id()?;
 
@@ -1,6 +1,12 @@
 use crate::{
-    abi::structs::StructResolver,
-    attr::{mode::Mode, state_mutability::StateMutabilityExt, STATE_MUTABILITY_ATTR},
+    abi::{
+        components::{Imports, SdkComponent},
+        structs::StructResolver,
+    },
+    attr::{
+        is_modifier_attr, mode::Mode, mutable_receiver_lint, state_mutability::StateMutabilityExt,
+        Modifier, ABI_NAME_ATTR, STATE_MUTABILITY_ATTR,
+    },
     codec::CodecGenerator,
     method::{combine_errors, MethodCollector, ParsedMethod},
 };
@@ -8,7 +14,12 @@ use convert_case::{Case, Casing};
 use darling::{ast::NestedMeta, FromMeta};
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{format_ident, quote, ToTokens};
-use syn::{spanned::Spanned, visit, Error, Ident, ImplItemFn, ItemImpl, Result};
+use syn::{
+    spanned::Spanned,
+    visit,
+    visit_mut::{self, VisitMut},
+    Error, FnArg, GenericParam, Ident, ImplItemFn, ItemImpl, Result, Type, TypeParamBound,
+};
 /// Attributes for the router configuration.
 #[derive(Debug, FromMeta, Default, Clone)]
 pub struct RouterAttributes {
@@ -146,6 +157,37 @@ impl Router {
             .collect()
     }
 
+    /// Checks if a method of the impl block gets a dispatch arm.
+    fn is_routed(&self, ident: &Ident) -> bool {
+        self.available_methods()
+            .iter()
+            .any(|route| &route.sig().ident == ident)
+    }
+
+    /// SDK components whose events and errors the routed methods expose, in first-use order.
+    ///
+    /// A component counts when one of its modifiers guards a method or a method returns its error
+    /// type, resolved through the `imports` of the module the router is declared in, see
+    /// [`SdkComponent`].
+    pub fn sdk_components(&self, imports: &Imports) -> Result<Vec<SdkComponent>> {
+        let mut components = Vec::new();
+        for route in self.available_methods() {
+            let modifiers = Modifier::from_attrs(&route.inner().attrs)?;
+            let used = modifiers.iter().map(SdkComponent::from_modifier).chain(
+                route
+                    .parsed_signature()
+                    .error_type()
+                    .and_then(|ty| SdkComponent::from_error_type(ty, imports)),
+            );
+            for component in used {
+                if !components.contains(&component) {
+                    components.push(component);
+                }
+            }
+        }
+        Ok(components)
+    }
+
     /// Checks if the router is based on a trait implementation.
     pub fn is_trait_impl(&self) -> bool {
         self.is_trait_impl
@@ -223,9 +265,22 @@ impl Router {
 
         for item in &mut clean_impl_block.items {
             if let syn::ImplItem::Fn(method) = item {
+                // Modifiers are applied by the dispatcher, which only covers routed methods
+                if let Some(attr) = method.attrs.iter().find(|attr| is_modifier_attr(attr)) {
+                    if !self.is_routed(&method.sig.ident) {
+                        return Err(Error::new(
+                            attr.span(),
+                            "modifiers only apply to routed methods, i.e. public methods other \
+                             than the constructor and the fallback",
+                        ));
+                    }
+                }
+
                 method.attrs.retain(|attr| {
                     !attr.path().is_ident("function_id")
                         && !attr.path().is_ident(STATE_MUTABILITY_ATTR)
+                        && !attr.path().is_ident(ABI_NAME_ATTR)
+                        && !is_modifier_attr(attr)
                 });
             }
         }
@@ -243,10 +298,18 @@ impl Router {
             quote! {}
         };
 
+        // `view`/`pure` methods taking `&mut self` compile, but with a warning
+        let receiver_lints = self
+            .routes
+            .iter()
+            .filter_map(|route| mutable_receiver_lint(route.sig(), route.state_mutability()));
+
         // Build the base output
         let output = quote! {
             #clean_impl_block
 
+            #(#receiver_lints)*
+
             #(#method_codecs)*
 
             #dispatch_method
@@ -306,6 +369,65 @@ impl Router {
         })
     }
 
+    /// Returns the type parameter the contract is generic over its SDK with.
+    ///
+    /// This is the parameter bounded by `SharedAPI`, or else the one named `SDK`.
+    fn sdk_param(&self) -> Option<&Ident> {
+        let type_params = || {
+            self.impl_block
+                .generics
+                .params
+                .iter()
+                .filter_map(|param| match param {
+                    GenericParam::Type(param) => Some(param),
+                    _ => None,
+                })
+        };
+
+        type_params()
+            .find(|param| {
+                param.bounds.iter().any(|bound| match bound {
+                    TypeParamBound::Trait(bound) => bound
+                        .path
+                        .segments
+                        .last()
+                        .is_some_and(|segment| segment.ident == "SharedAPI"),
+                    _ => false,
+                })
+            })
+            .or_else(|| type_params().find(|param| param.ident == "SDK"))
+            .map(|param| &param.ident)
+    }
+
+    /// Returns the contract type with its SDK replaced by `ReadOnlySdk`, the type `view` and `pure`
+    /// methods run on.
+    ///
+    /// `None` when the contract is not generic over its SDK, in which case the method is called on
+    /// the contract itself.
+    fn read_only_self_ty(&self) -> Option<Type> {
+        struct ReplaceSdk<'a>(&'a Ident);
+
+        impl VisitMut for ReplaceSdk<'_> {
+            fn visit_type_mut(&mut self, ty: &mut Type) {
+                if let Type::Path(path) = ty {
+                    if path.qself.is_none() && path.path.is_ident(self.0) {
+                        let sdk = self.0;
+                        *ty = syn::parse_quote! {
+                            fluentbase_sdk::read_only::ReadOnlySdk<'_, #sdk>
+                        };
+                        return;
+                    }
+                }
+                visit_mut::visit_type_mut(self, ty);
+            }
+        }
+
+        let sdk = self.sdk_param()?;
+        let mut self_ty = (*self.impl_block.self_ty).clone();
+        ReplaceSdk(sdk).visit_type_mut(&mut self_ty);
+        Some(self_ty)
+    }
+
     /// Generates the deploy method implementation for constructor.
     fn generate_deploy_method(&self) -> Result<TokenStream2> {
         let Some(constructor) = &self.constructor else {
@@ -374,6 +496,17 @@ impl Router {
         let param_count = params.len();
         let return_type_count = route.parsed_signature().return_type().len();
 
+        // Modifiers run in declaration order, and unwind in reverse once the method returns
+        let modifiers = Modifier::from_attrs(&route.inner().attrs)?;
+        let modifiers_before = modifiers
+            .iter()
+            .map(|modifier| modifier.before(route.state_mutability()));
+        let modifiers_after = modifiers
+            .iter()
+            .rev()
+            .map(|modifier| modifier.after(route.state_mutability()))
+            .collect::<Vec<_>>();
+
         let value_guard = if route.state_mutability().allows_value() {
             quote! {}
         } else {
@@ -416,33 +549,74 @@ impl Router {
             }
         };
 
-        // Generate function call based on parameter count
-        let fn_call = match param_count {
-            0 => quote! { self.#fn_name() },
-            1 => quote! { self.#fn_name(param0) },
-            _ => {
-                let param_names = (0..param_count)
-                    .map(|i| format_ident!("param{}", i))
-                    .collect::<Vec<_>>();
+        let param_names = (0..param_count)
+            .map(|i| format_ident!("param{}", i))
+            .collect::<Vec<_>>();
+
+        // `view` and `pure` methods run on a copy of the contract built over a read-only SDK, so a
+        // state change exits with `StateChangeDuringStaticCall` even when called without
+        // STATICCALL. The copy comes from the same `new(sdk)` the entrypoint uses.
+        let read_only_self_ty = route
+            .state_mutability()
+            .is_static()
+            .then(|| self.read_only_self_ty())
+            .flatten();
+
+        let fn_call = match read_only_self_ty {
+            Some(read_only_self_ty) => {
+                let has_mutable_receiver = route.sig().inputs.iter().any(
+                    |arg| matches!(arg, FnArg::Receiver(receiver) if receiver.mutability.is_some()),
+                );
+                let binding = if has_mutable_receiver {
+                    quote! { mut contract }
+                } else {
+                    quote! { contract }
+                };
+                quote! {
+                    {
+                        let sdk = fluentbase_sdk::read_only::ReadOnlySdk::new(&mut self.sdk);
+                        let #binding = <#read_only_self_ty>::new(sdk);
+                        contract.#fn_name(#(#param_names),*)
+                    }
+                }
+            }
+            None => quote! { self.#fn_name(#(#param_names),*) },
+        };
 
-                quote! { self.#fn_name(#(#param_names),*) }
+        // A `Result` method reverts with the error's selector and arguments, which keeps the
+        // revert data decodable as a Solidity custom error instead of an `Error(string)` panic
+        let fn_call = if route.parsed_signature().error_type().is_some() {
+            quote! {
+                match #fn_call {
+                    Ok(output) => output,
+                    Err(err) => {
+                        let revert_data = fluentbase_sdk::SolidityError::encode(&err);
+                        self.sdk.write(&revert_data);
+                        self.sdk.native_exit(fluentbase_sdk::ExitCode::Panic);
+                    }
+                }
             }
+        } else {
+            fn_call
         };
 
         // Generate result handling based on return type count
         let result_handling = match return_type_count {
             0 => quote! {
                 let _ = #fn_call;
+                #(#modifiers_after)*
                 let encoded_output = [0u8; 0];
                 self.sdk.write(&encoded_output);
             },
             1 => quote! {
                 let output = #fn_call;
+                #(#modifiers_after)*
                 let encoded_output = #return_struct::new((output,)).encode();
                 self.sdk.write(&encoded_output);
             },
             _ => quote! {
                 let output = #fn_call;
+                #(#modifiers_after)*
                 let encoded_output = #return_struct::new(output).encode();
                 self.sdk.write(&encoded_output);
             },
@@ -451,6 +625,7 @@ impl Router {
         Ok(quote! {
             [#(#function_id),*] => {
                 #value_guard
+                #(#modifiers_before)*
                 #param_handling
                 #result_handling
             }
@@ -732,6 +907,244 @@ mod b {
         assert_snapshot!("constructor_two_params", formatted);
     }
 
+    #[test]
+    fn test_result_route_reverts_with_custom_error() {
+        let impl_block: syn::ItemImpl = parse_quote! {
+            impl<SDK: SharedAPI> App<SDK> {
+                pub fn withdraw(&mut self, amount: U256) -> Result<U256, VaultError> {
+                    Ok(amount)
+                }
+
+                pub fn pause(&mut self) -> Result<(), VaultError> {
+                    Ok(())
+                }
+            }
+        };
+
+        let router = process_router(quote! { mode = "solidity" }, impl_block.into_token_stream())
+            .expect("Failed to process router");
+
+        // The error type is not part of the selector or the outputs
+        let withdraw = router.available_methods()[0];
+        assert_eq!(withdraw.signature(), "withdraw(uint256)");
+        assert_eq!(withdraw.function_abi().unwrap().outputs.len(), 1);
+        assert!(router.available_methods()[1]
+            .function_abi()
+            .unwrap()
+            .outputs
+            .is_empty());
+
+        let generated = router
+            .generate()
+            .expect("Failed to generate router code")
+            .to_string()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+
+        assert!(generated.contains("pubtypeWithdrawReturnArgs=(U256,);"));
+        assert!(generated.contains("pubtypePauseReturnArgs=();"));
+        assert_eq!(
+            generated
+                .matches("fluentbase_sdk::SolidityError::encode(&err)")
+                .count(),
+            2
+        );
+    }
+
+    /// `view` and `pure` methods run on a read-only SDK, and `&mut self` on them is linted
+    #[test]
+    fn test_static_routes_run_on_read_only_sdk() {
+        let impl_block: syn::ItemImpl = parse_quote! {
+            impl<S: fluentbase_sdk::SharedAPI> Token<S> {
+                pub fn total_supply(&self) -> U256 {
+                    U256::ZERO
+                }
+
+                #[state_mutability("view")]
+                pub fn balance_of(&mut self, owner: Address) -> U256 {
+                    U256::ZERO
+                }
+
+                #[state_mutability("nonpayable")]
+                pub fn burn(&mut self, amount: U256) {}
+            }
+        };
+
+        let router = process_router(quote! { mode = "solidity" }, impl_block.into_token_stream())
+            .expect("a view method taking &mut self should only be linted");
+
+        let generated = router
+            .generate()
+            .expect("Failed to generate router code")
+            .to_string()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+
+        assert_eq!(
+            generated
+                .matches("letsdk=fluentbase_sdk::read_only::ReadOnlySdk::new(&mutself.sdk);")
+                .count(),
+            2
+        );
+        assert_eq!(
+            generated
+                .matches("<Token<fluentbase_sdk::read_only::ReadOnlySdk<'_,S>>>::new(sdk)")
+                .count(),
+            2
+        );
+        assert!(
+            generated.contains("letcontract=") && generated.contains("contract.total_supply()")
+        );
+        assert!(
+            generated.contains("letmutcontract=")
+                && generated.contains("contract.balance_of(param0)")
+        );
+        assert!(generated.contains("self.burn(param0)"));
+
+        assert!(generated.contains("constbalance_of_takes_mut_self:()=();"));
+        assert!(!generated.contains("total_supply_takes_mut_self"));
+        assert!(!generated.contains("burn_takes_mut_self"));
+    }
+
+    /// A contract that is not generic over its SDK has no read-only copy to run on
+    #[test]
+    fn test_static_routes_of_concrete_contract_are_called_directly() {
+        let impl_block: syn::ItemImpl = parse_quote! {
+            impl Token<TestingContextImpl> {
+                pub fn total_supply(&self) -> U256 {
+                    U256::ZERO
+                }
+            }
+        };
+
+        let router = process_router(quote! { mode = "solidity" }, impl_block.into_token_stream())
+            .expect("Failed to process router");
+
+        let generated = router
+            .generate()
+            .expect("Failed to generate router code")
+            .to_string()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+
+        assert!(!generated.contains("ReadOnlySdk"));
+        assert!(generated.contains("self.total_supply()"));
+    }
+
+    #[test]
+    fn test_modifiers_wrap_the_dispatched_call() {
+        let impl_block: syn::ItemImpl = parse_quote! {
+            impl<SDK: SharedAPI> Vault<SDK> {
+                #[only_owner]
+                #[non_reentrant]
+                pub fn withdraw(&mut self, amount: U256) -> U256 {
+                    amount
+                }
+
+                #[non_reentrant]
+                #[only_role(AUDITOR_ROLE)]
+                pub fn total(&self) -> U256 {
+                    U256::ZERO
+                }
+            }
+        };
+
+        let router = process_router(quote! { mode = "solidity" }, impl_block.into_token_stream())
+            .expect("Failed to process router");
+        let generated = router
+            .generate()
+            .expect("Failed to generate router code")
+            .to_string()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+
+        // The impl block is emitted without the modifier attributes
+        assert!(!generated.contains("#[only_owner]"));
+        assert!(!generated.contains("#[non_reentrant]"));
+        assert!(!generated.contains("#[only_role"));
+
+        let owner_check = generated
+            .find("self.ownable.only_owner(&mutself.sdk);")
+            .unwrap();
+        let enter = generated
+            .find("fluentbase_sdk::security::ReentrancyGuard::enter(&mutself.sdk);")
+            .unwrap();
+        let call = generated.find("self.withdraw(param0)").unwrap();
+        let exit = generated
+            .find("fluentbase_sdk::security::ReentrancyGuard::exit(&mutself.sdk);")
+            .unwrap();
+        assert!(owner_check < enter && enter < call && call < exit);
+
+        // A view method only checks the lock, then its role
+        let check = generated
+            .find("fluentbase_sdk::security::ReentrancyGuard::check(&mutself.sdk);")
+            .unwrap();
+        let role_check = generated
+            .find("self.access_control.only_role(&mutself.sdk,AUDITOR_ROLE);")
+            .unwrap();
+        assert!(check < role_check);
+        assert_eq!(generated.matches("ReentrancyGuard::exit").count(), 1);
+    }
+
+    #[test]
+    fn test_modifiers_and_errors_register_sdk_components() {
+        let impl_block: syn::ItemImpl = parse_quote! {
+            impl<SDK: SharedAPI> Vault<SDK> {
+                #[non_reentrant]
+                pub fn deposit(&mut self) {}
+
+                pub fn grant(
+                    &mut self,
+                    role: B256,
+                    account: Address,
+                ) -> Result<(), AccessControlError> {
+                    Ok(())
+                }
+
+                #[only_owner]
+                #[non_reentrant]
+                pub fn sweep(&mut self) {}
+            }
+        };
+
+        let router = process_router(quote! { mode = "solidity" }, impl_block.into_token_stream())
+            .expect("Failed to process router");
+        let imports = Imports::from_items(&[parse_quote!(
+            use fluentbase_sdk::access::AccessControlError;
+        )]);
+        assert_eq!(
+            router.sdk_components(&imports).unwrap(),
+            [
+                SdkComponent::ReentrancyGuard,
+                SdkComponent::AccessControl,
+                SdkComponent::Ownable
+            ]
+        );
+    }
+
+    #[test]
+    fn test_modifiers_on_unrouted_methods_are_rejected() {
+        let impl_block: syn::ItemImpl = parse_quote! {
+            impl<SDK: SharedAPI> Vault<SDK> {
+                pub fn total(&self) -> U256 {
+                    U256::ZERO
+                }
+
+                #[only_owner]
+                fn sweep(&mut self) {}
+            }
+        };
+
+        let router = process_router(quote! { mode = "solidity" }, impl_block.into_token_stream())
+            .expect("Failed to process router");
+        let err = router.generate().unwrap_err();
+        assert!(err.to_string().contains("only apply to routed methods"));
+    }
+
     #[test]
     fn test_nonpayable_routes_and_constructor_reject_value() {
         let impl_block: syn::ItemImpl = parse_quote! {
@@ -1,14 +1,14 @@
 use crate::{
     abi::{
         constructor::ConstructorABI, error::ABIError, function::FunctionABI,
-        structs::StructResolver,
+        structs::StructResolver, types::result_type_args,
     },
     method::CONSTRUCTOR_METHOD,
 };
 use convert_case::{Case, Casing};
 use quote::ToTokens;
 use std::ops::Deref;
-use syn::{spanned::Spanned, FnArg, ReturnType, Signature};
+use syn::{spanned::Spanned, FnArg, ReturnType, Signature, Type};
 
 /// Wrapper around `syn::Signature`
 #[derive(Debug, Clone)]
@@ -68,12 +68,32 @@ impl ParsedSignature {
         self.inputs_without_receiver()
     }
 
+    /// Returns the type the function returns on success
+    ///
+    /// For `Result<T, E>` this is `T`; `None` when the function has no return type at all.
+    pub fn success_type(&self) -> Option<&Type> {
+        match &self.0.output {
+            ReturnType::Default => None,
+            ReturnType::Type(_, ty) => Some(result_type_args(ty).map_or(&**ty, |(ok, _)| ok)),
+        }
+    }
+
+    /// Returns `E` when the function returns `Result<T, E>`
+    ///
+    /// The error is a `SolidityError`, reverting with the selector and arguments of its variant.
+    pub fn error_type(&self) -> Option<&Type> {
+        match &self.0.output {
+            ReturnType::Default => None,
+            ReturnType::Type(_, ty) => result_type_args(ty).map(|(_, err)| err),
+        }
+    }
+
     /// Returns information about function return type
     /// Used for codec generation to determine if function has a return value
     pub fn return_type(&self) -> Vec<String> {
-        match &self.0.output {
-            ReturnType::Default => Vec::new(),
-            ReturnType::Type(_, ty) => match &**ty {
+        match self.success_type() {
+            None => Vec::new(),
+            Some(ty) => match ty {
                 syn::Type::Tuple(tuple) => {
                     if tuple.elems.is_empty() {
                         Vec::new()
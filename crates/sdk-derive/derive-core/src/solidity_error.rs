@@ -0,0 +1,268 @@
+//! SolidityError derive macro for Solidity custom errors.
+//!
+//! Every variant of the enum is one custom error: its name is the variant name and its parameters
+//! are the variant fields. Returning the enum from a `#[router]` method as `Result<T, E>` reverts
+//! with the selector and ABI-encoded arguments of the variant, exactly like Solidity's `revert`.
+//!
+//! # Example
+//! ```ignore
+//! #[derive(SolidityError)]
+//! enum VaultError {
+//!     Paused,
+//!     InsufficientBalance { available: U256, required: U256 },
+//!     Unauthorized(Address),
+//! }
+//!
+//! // Revert data:
+//! // keccak256("InsufficientBalance(uint256,uint256)")[..4] ++ abi.encode(available, required)
+//! let data = VaultError::InsufficientBalance { available, required }.encode();
+//! ```
+
+use crate::abi::{sol_error::ErrorABI, structs::StructResolver};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use std::collections::HashMap;
+use syn::{Data, DeriveInput, Error, Fields, Ident, Result, Type};
+
+struct ErrorVariant {
+    ident: Ident,
+    /// Binding names used to destructure the variant
+    bindings: Vec<Ident>,
+    types: Vec<Type>,
+    /// Field names for named variants, `None` for tuple and unit variants
+    field_names: Option<Vec<Ident>>,
+    signature: String,
+    selector: [u8; 4],
+    /// ABI entry of the error, as JSON
+    abi: String,
+}
+
+/// Main entry point for the SolidityError derive macro.
+pub fn process_solidity_error(input: DeriveInput) -> Result<TokenStream2> {
+    process_solidity_error_with_structs(input, &StructResolver::crate_sources())
+}
+
+/// Processes the error enum with an explicit struct resolver.
+///
+/// Signatures come from the same [`ErrorABI`] entries the build tooling publishes, so struct fields
+/// hash with their components.
+pub fn process_solidity_error_with_structs(
+    input: DeriveInput,
+    resolver: &StructResolver,
+) -> Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "SolidityError does not support generic enums",
+        ));
+    }
+
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "SolidityError can only be derived for enums",
+        ));
+    };
+
+    let abis = ErrorABI::from_derive_input_with(&input, resolver)
+        .map_err(|e| Error::new_spanned(&input.ident, e.to_string()))?;
+
+    let mut selectors = HashMap::new();
+    let mut variants = Vec::new();
+    for (variant, abi) in data.variants.iter().zip(abis) {
+        let to_error = |e: crate::abi::error::ABIError| Error::new_spanned(variant, e.to_string());
+        let signature = abi.signature().map_err(to_error)?;
+        let selector = abi.selector().map_err(to_error)?;
+        let abi = abi
+            .to_json_value()
+            .map_err(|e| Error::new_spanned(variant, e.to_string()))?
+            .to_string();
+
+        if let Some(other) = selectors.insert(selector, signature.clone()) {
+            return Err(Error::new_spanned(
+                variant,
+                format!(
+                    "error selector 0x{} of `{signature}` collides with `{other}`",
+                    hex::encode(selector)
+                ),
+            ));
+        }
+
+        let types = variant.fields.iter().map(|f| f.ty.clone()).collect();
+        let (bindings, field_names) = match &variant.fields {
+            Fields::Named(named) => {
+                let names = named
+                    .named
+                    .iter()
+                    .map(|f| f.ident.clone().expect("Named field"))
+                    .collect::<Vec<_>>();
+                (names.clone(), Some(names))
+            }
+            fields => (
+                (0..fields.len())
+                    .map(|i| format_ident!("field{}", i))
+                    .collect(),
+                None,
+            ),
+        };
+
+        variants.push(ErrorVariant {
+            ident: variant.ident.clone(),
+            bindings,
+            types,
+            field_names,
+            signature,
+            selector,
+            abi,
+        });
+    }
+
+    Ok(generate_error_impl(&input.ident, &variants))
+}
+
+/// Pattern matching a variant and binding all of its fields
+fn variant_pattern(variant: &ErrorVariant) -> TokenStream2 {
+    let ident = &variant.ident;
+    let bindings = &variant.bindings;
+    match &variant.field_names {
+        Some(_) => quote! { Self::#ident { #(#bindings),* } },
+        None if bindings.is_empty() => quote! { Self::#ident },
+        None => quote! { Self::#ident(#(#bindings),*) },
+    }
+}
+
+fn generate_error_impl(name: &Ident, variants: &[ErrorVariant]) -> TokenStream2 {
+    let signatures = variants.iter().map(|v| &v.signature);
+    let abis = variants.iter().map(|v| &v.abi);
+
+    let selector_arms = variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let selector = variant.selector;
+        quote! { Self::#ident { .. } => [#(#selector),*] }
+    });
+
+    let encode_arms = variants.iter().map(|variant| {
+        let pattern = variant_pattern(variant);
+        let bindings = &variant.bindings;
+        if bindings.is_empty() {
+            return quote! { #pattern => {} };
+        }
+        quote! {
+            #pattern => {
+                let values = (#(#bindings.clone(),)*);
+                fluentbase_sdk::codec::SolidityABI::encode_function_args(&values, &mut args)
+                    .expect("encode error arguments");
+            }
+        }
+    });
+
+    let decode_arms = variants.iter().map(|variant| {
+        let selector = variant.selector;
+        let pattern = variant_pattern(variant);
+        let bindings = &variant.bindings;
+        let types = &variant.types;
+        if bindings.is_empty() {
+            return quote! { [#(#selector),*] => Some(#pattern) };
+        }
+        quote! {
+            [#(#selector),*] => {
+                let (#(#bindings,)*) =
+                    fluentbase_sdk::codec::SolidityABI::<(#(#types,)*)>::decode_function_args(&args)
+                        .ok()?;
+                Some(#pattern)
+            }
+        }
+    });
+
+    quote! {
+        impl fluentbase_sdk::SolidityError for #name {
+            const SIGNATURES: &'static [&'static str] = &[#(#signatures),*];
+
+            const ABI: &'static [&'static str] = &[#(#abis),*];
+
+            fn selector(&self) -> [u8; 4] {
+                match self {
+                    #(#selector_arms,)*
+                }
+            }
+
+            // An enum of unit variants only never touches the argument buffers
+            #[allow(unused_mut, unused_variables)]
+            fn encode(&self) -> fluentbase_sdk::Bytes {
+                let mut args = fluentbase_sdk::codec::bytes::BytesMut::new();
+                match self {
+                    #(#encode_arms)*
+                }
+
+                let mut buf = fluentbase_sdk::codec::bytes::BytesMut::new();
+                buf.extend_from_slice(&self.selector());
+                buf.extend_from_slice(&args);
+                buf.freeze().into()
+            }
+
+            #[allow(unused_variables)]
+            fn decode(data: &[u8]) -> Option<Self> {
+                if data.len() < 4 {
+                    return None;
+                }
+                let (selector, args) = data.split_at(4);
+                match [selector[0], selector[1], selector[2], selector[3]] {
+                    #(#decode_arms,)*
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn generate(input: DeriveInput) -> String {
+        let tokens =
+            process_solidity_error_with_structs(input, &StructResolver::default()).unwrap();
+        tokens
+            .to_string()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect()
+    }
+
+    #[test]
+    fn test_variants_become_custom_errors() {
+        let generated = generate(parse_quote! {
+            enum VaultError {
+                Paused,
+                InsufficientBalance { available: U256, required: U256 },
+                Unauthorized(Address),
+            }
+        });
+
+        assert!(generated.contains(
+            r#"&["Paused()","InsufficientBalance(uint256,uint256)","Unauthorized(address)"]"#
+        ));
+        // The ABI entries are published in the same order
+        assert!(generated.contains(
+            r#"constABI:&'static[&'staticstr]=&["{\"inputs\":[],\"name\":\"Paused\",\"type\":\"error\"}","#
+        ));
+        // keccak256("Paused()")[..4] = 0x9e87fac8
+        assert!(generated.contains("Self::Paused{..}=>[158u8,135u8,250u8,200u8]"));
+        assert!(generated.contains("SolidityABI::<(U256,U256,)>::decode_function_args(&args)"));
+        assert!(generated.contains("Some(Self::InsufficientBalance{available,required})"));
+        assert!(generated.contains("Some(Self::Unauthorized(field0))"));
+    }
+
+    #[test]
+    fn test_structs_are_rejected() {
+        let input: DeriveInput = parse_quote! {
+            struct NotAnEnum {
+                code: U256,
+            }
+        };
+        let err =
+            process_solidity_error_with_structs(input, &StructResolver::default()).unwrap_err();
+        assert!(err.to_string().contains("only be derived for enums"));
+    }
+}
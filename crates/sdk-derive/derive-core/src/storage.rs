@@ -22,6 +22,7 @@ pub fn process_storage_layout(input: DeriveInput) -> Result<TokenStream2, syn::E
     let bytes_calculation = generate_const_bytes_calculation(&fields)?;
     let constructor = generate_constructor_body(&fields, has_sdk)?;
     let accessors = generate_accessor_methods(&fields);
+    let storage_layout = generate_storage_layout_fn(&fields)?;
 
     if has_sdk {
         // Contract with SDK - generate impl block only
@@ -43,6 +44,7 @@ pub fn process_storage_layout(input: DeriveInput) -> Result<TokenStream2, syn::E
                     #constructor
                 }
 
+                #storage_layout
                 #slots_calculation
                 #accessors
             }
@@ -64,6 +66,7 @@ pub fn process_storage_layout(input: DeriveInput) -> Result<TokenStream2, syn::E
                     #constructor
                 }
 
+                #storage_layout
                 #slots_calculation
                 #bytes_calculation
                 #accessors
@@ -101,6 +104,10 @@ pub fn process_storage_layout(input: DeriveInput) -> Result<TokenStream2, syn::E
                 fn access(descriptor: Self::Descriptor) -> Self::Accessor {
                     descriptor
                 }
+
+                fn storage_fields() -> Option<fluentbase_sdk::storage::StorageFields> {
+                    Some(Self::storage_layout())
+                }
             }
         })
     }
@@ -172,8 +179,7 @@ fn generate_constructor_body(
     };
 
     Ok(quote! {
-        let mut current_slot = slot;
-        let mut current_offset: u8 = offset;
+        let mut cursor = fluentbase_sdk::storage::StorageCursor::new(offset);
 
         #(#layout_calculations)*
 
@@ -216,41 +222,61 @@ fn generate_layout_calculation(
         });
     }
 
-    // Auto-layout with packing
+    // Auto-layout with packing, following the shared `StorageCursor` rules
     Ok(quote! {
         let #layout_var = {
-            let bytes = <#field_type as fluentbase_sdk::storage::StorageLayout>::BYTES as u8;
-            let slots = <#field_type as fluentbase_sdk::storage::StorageLayout>::SLOTS;
-
-            if slots == 0 {
-                // Packable type (fits within a slot)
-                if current_offset + bytes <= 32 {
-                    // Fits in current slot - right-align
-                    let actual_offset = 32 - current_offset - bytes;
-                    let result = (current_slot, actual_offset);
-                    current_offset += bytes;
-                    result
-                } else {
-                    // Move to next slot
-                    current_slot += fluentbase_sdk::U256::from(1);
-                    let actual_offset = 32 - bytes;
-                    current_offset = bytes;
-                    (current_slot, actual_offset)
-                }
-            } else {
-                // Full-slot type (maps, arrays, large structs)
-                if current_offset > 0 {
-                    current_slot += fluentbase_sdk::U256::from(1);
-                    current_offset = 0;
-                }
-                let result = (current_slot, 0);
-                current_slot += fluentbase_sdk::U256::from(slots);
-                result
-            }
+            let (field_slot, field_offset) = cursor.place(
+                <#field_type as fluentbase_sdk::storage::StorageLayout>::BYTES,
+                <#field_type as fluentbase_sdk::storage::StorageLayout>::SLOTS,
+            );
+            (slot + fluentbase_sdk::U256::from(field_slot), field_offset)
         };
     })
 }
 
+/// Generates `storage_layout()`, which lists where every field lives when the struct starts at
+/// slot 0, in declaration order.
+fn generate_storage_layout_fn(
+    fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>,
+) -> Result<TokenStream2, syn::Error> {
+    let mut layout_calculations = Vec::new();
+    let mut entries = Vec::new();
+
+    for field in fields {
+        let field_name = field.ident.as_ref().expect("Named fields required");
+        let field_type = &field.ty;
+        let layout_var = quote::format_ident!("{}_layout", field_name);
+        let label = field_name.to_string();
+        let explicit = has_explicit_slot(field);
+
+        layout_calculations.push(generate_layout_calculation(field, &layout_var, field_type)?);
+        entries.push(quote! {
+            fluentbase_sdk::storage::StorageField::append::<#field_type>(
+                &mut fields,
+                #label,
+                #layout_var.0,
+                #layout_var.1,
+                #explicit,
+            );
+        });
+    }
+
+    Ok(quote! {
+        /// Placement of every storage field, in declaration order, nested structs member by member.
+        /// Compare the layouts of two versions with `check_storage_upgrade` before upgrading.
+        pub fn storage_layout() -> fluentbase_sdk::storage::StorageFields {
+            let slot = fluentbase_sdk::U256::from(0);
+            let mut cursor = fluentbase_sdk::storage::StorageCursor::new(0);
+
+            #(#layout_calculations)*
+
+            let mut fields = fluentbase_sdk::storage::StorageFields::new();
+            #(#entries)*
+            fields
+        }
+    })
+}
+
 /// Generates field initialization from calculated layout.
 fn generate_field_init(
     field_name: &syn::Ident,
@@ -296,42 +322,21 @@ fn generate_const_slots_calculation(
         .map(|field| {
             let field_type = &field.ty;
             quote! {
-                {
-                    let bytes = <#field_type as fluentbase_sdk::storage::StorageLayout>::BYTES;
-                    let slots = <#field_type as fluentbase_sdk::storage::StorageLayout>::SLOTS;
-
-                    if slots == 0 {
-                        if current_offset + bytes <= 32 {
-                            current_offset += bytes;
-                        } else {
-                            current_slot += 1;
-                            current_offset = bytes;
-                        }
-                    } else {
-                        if current_offset > 0 {
-                            current_slot += 1;
-                            current_offset = 0;
-                        }
-                        current_slot += slots;
-                        current_offset = 0;
-                    }
-                }
+                cursor.place(
+                    <#field_type as fluentbase_sdk::storage::StorageLayout>::BYTES,
+                    <#field_type as fluentbase_sdk::storage::StorageLayout>::SLOTS,
+                );
             }
         })
         .collect();
 
     Ok(quote! {
         const fn calculate_slots() -> usize {
-            let mut current_slot: usize = 0;
-            let mut current_offset: usize = 0;
+            let mut cursor = fluentbase_sdk::storage::StorageCursor::new(0);
 
             #(#field_calculations)*
 
-            if current_offset > 0 {
-                current_slot + 1
-            } else {
-                current_slot
-            }
+            cursor.slots()
         }
     })
 }
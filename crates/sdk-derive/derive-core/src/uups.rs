@@ -0,0 +1,220 @@
+//! `#[uups]`: turns a `#[router]` impl into a UUPS implementation.
+//!
+//! The attribute appends OpenZeppelin's `UUPSUpgradeable` entry points to the impl before the
+//! router sees it, so they are dispatched, listed in the ABI and guarded like any other method:
+//!
+//! - `proxiableUUID()`, answered only when the implementation is called directly;
+//! - `upgradeToAndCall(address,bytes)`, callable only through a proxy pointing at this
+//!   implementation, after the contract's own `authorize_upgrade(&mut self, Address)` method has
+//!   accepted the upgrade.
+//!
+//! `authorize_upgrade` plays the role of OpenZeppelin's `_authorizeUpgrade`: it must revert for
+//! any caller not allowed to upgrade. It is a regular method of the contract, so forgetting it is a
+//! compile error rather than an upgrade open to everyone.
+use proc_macro2::TokenStream as TokenStream2;
+use quote::ToTokens;
+use syn::{parse_quote, spanned::Spanned, Attribute, Error, ItemImpl, Result};
+
+/// Name of the attribute
+pub const UUPS_ATTR: &str = "uups";
+
+/// Checks whether an attribute is `#[uups]`
+pub fn is_uups_attr(attr: &Attribute) -> bool {
+    attr.path().is_ident(UUPS_ATTR)
+}
+
+/// Checks whether an attribute is `#[router]`, imported or referred to by path
+fn is_router_attr(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "router")
+}
+
+/// Expands `#[uups]` on an impl block.
+pub fn process_uups(attr: TokenStream2, input: TokenStream2) -> Result<TokenStream2> {
+    if !attr.is_empty() {
+        return Err(Error::new(attr.span(), "#[uups] takes no arguments"));
+    }
+    let mut impl_block = syn::parse2::<ItemImpl>(input)?;
+    if impl_block.trait_.is_some() {
+        return Err(Error::new(
+            impl_block.span(),
+            "#[uups] requires an inherent #[router] impl, not a trait impl",
+        ));
+    }
+    if !impl_block.attrs.iter().any(is_router_attr) {
+        return Err(Error::new(
+            impl_block.span(),
+            "#[uups] must be placed above the #[router] attribute of the impl",
+        ));
+    }
+    add_upgrade_methods(&mut impl_block);
+    Ok(impl_block.into_token_stream())
+}
+
+/// Appends the UUPS entry points to a router impl.
+///
+/// Build tooling calls this too, so the generated artifacts list the methods the macro adds.
+pub fn add_upgrade_methods(impl_block: &mut ItemImpl) {
+    impl_block.items.push(parse_quote! {
+        /// ERC-1822 `proxiableUUID()`: the ERC-1967 implementation slot, reverting with
+        /// `UUPSUnauthorizedCallContext()` when called through a proxy.
+        #[function_id("proxiableUUID()")]
+        #[abi_name("proxiableUUID")]
+        pub fn proxiable_uuid(
+            &self,
+        ) -> Result<fluentbase_sdk::B256, fluentbase_sdk::proxy::ProxyError> {
+            fluentbase_sdk::proxy::UupsUpgradeable::proxiable_uuid(&self.sdk)
+        }
+    });
+    impl_block.items.push(parse_quote! {
+        /// Points the calling proxy at `new_implementation` once `authorize_upgrade` accepts it,
+        /// then delegates `data` to the new implementation unless it is empty.
+        #[function_id("upgradeToAndCall(address,bytes)")]
+        #[state_mutability("payable")]
+        pub fn upgrade_to_and_call(
+            &mut self,
+            new_implementation: fluentbase_sdk::Address,
+            data: fluentbase_sdk::Bytes,
+        ) -> Result<(), fluentbase_sdk::proxy::ProxyError> {
+            fluentbase_sdk::proxy::UupsUpgradeable::check_proxy(&self.sdk)?;
+            self.authorize_upgrade(new_implementation);
+            fluentbase_sdk::proxy::UupsUpgradeable::upgrade_to_and_call(
+                &mut self.sdk,
+                new_implementation,
+                &data,
+            )
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::function::StateMutability;
+    use quote::quote;
+    use syn::ImplItem;
+
+    fn method_names(tokens: TokenStream2) -> Vec<String> {
+        let impl_block = syn::parse2::<ItemImpl>(tokens).unwrap();
+        impl_block
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                ImplItem::Fn(method) => Some(method.sig.ident.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_upgrade_methods_are_appended() {
+        let expanded = process_uups(
+            quote! {},
+            quote! {
+                #[router(mode = "solidity")]
+                impl<SDK: SharedAPI> Counter<SDK> {
+                    pub fn increment(&mut self) {}
+                    fn authorize_upgrade(&mut self, _new_implementation: Address) {}
+                }
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            method_names(expanded.clone()),
+            [
+                "increment",
+                "authorize_upgrade",
+                "proxiable_uuid",
+                "upgrade_to_and_call"
+            ]
+        );
+        let expanded = expanded.to_string();
+        assert!(expanded.contains("# [router (mode = \"solidity\")]"));
+        assert!(expanded.contains("# [function_id (\"proxiableUUID()\")]"));
+        assert!(expanded.contains("# [function_id (\"upgradeToAndCall(address,bytes)\")]"));
+        assert!(expanded.contains("# [state_mutability (\"payable\")]"));
+        assert!(expanded.contains("self . authorize_upgrade (new_implementation) ;"));
+    }
+
+    #[test]
+    fn test_upgrade_methods_are_routed() {
+        let mut impl_block: ItemImpl = parse_quote! {
+            impl<SDK: fluentbase_sdk::SharedAPI> Counter<SDK> {
+                pub fn increment(&mut self) {}
+                fn authorize_upgrade(&mut self, _new_implementation: Address) {}
+            }
+        };
+        add_upgrade_methods(&mut impl_block);
+        let router = crate::router::process_router(
+            quote! { mode = "solidity" },
+            impl_block.into_token_stream(),
+        )
+        .unwrap();
+
+        let methods = router.available_methods();
+        let proxiable_uuid = methods
+            .iter()
+            .find(|method| method.signature() == "proxiableUUID()")
+            .unwrap();
+        assert_eq!(proxiable_uuid.function_id(), [0x52, 0xd1, 0x90, 0x2d]);
+        let upgrade = methods
+            .iter()
+            .find(|method| method.signature() == "upgradeToAndCall(address,bytes)")
+            .unwrap();
+        assert_eq!(upgrade.function_id(), [0x4f, 0x1e, 0xf2, 0x86]);
+        assert_eq!(upgrade.state_mutability(), StateMutability::Payable);
+
+        // Both methods are published under their Solidity names
+        for method in [proxiable_uuid, upgrade] {
+            let abi = method.function_abi().unwrap();
+            assert_eq!(abi.name, method.signature().split('(').next().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_router_path_is_recognized() {
+        let expanded = process_uups(
+            quote! {},
+            quote! {
+                #[fluentbase_sdk::derive::router(mode = "solidity")]
+                impl<SDK: SharedAPI> Counter<SDK> {}
+            },
+        );
+        assert!(expanded.is_ok());
+    }
+
+    #[test]
+    fn test_misplaced_uups_is_rejected() {
+        let err = process_uups(
+            quote! { owner },
+            quote! {
+                #[router(mode = "solidity")]
+                impl<SDK: SharedAPI> Counter<SDK> {}
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("takes no arguments"));
+
+        let err = process_uups(
+            quote! {},
+            quote! {
+                impl<SDK: SharedAPI> Counter<SDK> {}
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("placed above the #[router]"));
+
+        let err = process_uups(
+            quote! {},
+            quote! {
+                #[router(mode = "solidity")]
+                impl<SDK: SharedAPI> CounterApi for Counter<SDK> {}
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("inherent #[router] impl"));
+    }
+}
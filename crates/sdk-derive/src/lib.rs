@@ -1,6 +1,7 @@
 //! Procedural macros for routers, clients, events, and storage layouts in Fluentbase contracts.
 use fluentbase_sdk_derive_core::{
-    client, event, router, storage::process_storage_layout, storage_legacy,
+    attr, client, event, router, solidity_error, storage::process_storage_layout, storage_legacy,
+    uups,
 };
 use proc_macro::TokenStream;
 use proc_macro_error::proc_macro_error;
@@ -105,6 +106,41 @@ pub fn function_id(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// or can use custom selectors with `#[function_id(...)]`.
 ///
 /// Selector collisions are detected at compile time.
+///
+/// # State Mutability
+///
+/// `view` and `pure` methods run on a copy of the contract built with `new(sdk)` over a
+/// `ReadOnlySdk`, so any state change they attempt exits the call with
+/// `ExitCode::StateChangeDuringStaticCall`, whether or not the caller used `STATICCALL`. Declaring
+/// such a method with `&mut self` compiles, but with a warning.
+///
+/// # Custom Errors
+///
+/// A method may return `Result<T, E>` where `E` derives `SolidityError`. `T` is encoded as the
+/// method output, while `Err(e)` reverts with the selector and ABI-encoded arguments of the
+/// variant, so callers see a Solidity custom error:
+///
+/// ```rust,ignore
+/// pub fn withdraw(&mut self, amount: U256) -> Result<U256, VaultError> {
+///     let available = self.balance.get();
+///     if available < amount {
+///         return Err(VaultError::InsufficientBalance { available, required: amount });
+///     }
+///     Ok(available - amount)
+/// }
+/// ```
+///
+/// # Modifiers
+///
+/// Routed methods can be guarded with [`macro@only_owner`], [`macro@only_role`] and
+/// [`macro@non_reentrant`]. The dispatcher applies them around the call in declaration order, so
+/// they work for `&self` methods too, but they do not run when the method is called directly from
+/// Rust.
+///
+/// # Upgradeable Contracts
+///
+/// Placing [`macro@uups`] above `#[router]` adds the `proxiableUUID()` and
+/// `upgradeToAndCall(address,bytes)` methods of a UUPS implementation.
 #[proc_macro_attribute]
 #[proc_macro_error]
 pub fn router(attr: TokenStream, input: TokenStream) -> TokenStream {
@@ -113,6 +149,105 @@ pub fn router(attr: TokenStream, input: TokenStream) -> TokenStream {
         Err(err) => err.to_compile_error().into(),
     }
 }
+
+/// Restricts a `#[router]` method to the contract owner.
+///
+/// The dispatcher calls `only_owner` on the contract's `ownable` field, an
+/// `fluentbase_sdk::access::Ownable` or `Ownable2Step`, which reverts with
+/// `OwnableUnauthorizedAccount(address)` for any other caller. Use `#[only_owner(field = name)]`
+/// when the component is stored under another name.
+///
+/// # Example
+/// ```rust,ignore
+/// #[router(mode = "solidity")]
+/// impl<SDK: SharedAPI> Vault<SDK> {
+///     #[only_owner]
+///     pub fn pause(&mut self) {
+///         self.paused_accessor().set(&mut self.sdk, true);
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn only_owner(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr::modifier_outside_router(attr::ONLY_OWNER_ATTR, item.into()).into()
+}
+
+/// Restricts a `#[router]` method to the members of a role.
+///
+/// The dispatcher calls `only_role` on the contract's `access_control` field, an
+/// `fluentbase_sdk::access::AccessControl`, which reverts with
+/// `AccessControlUnauthorizedAccount(address,bytes32)` unless the caller has the role. The role is
+/// any `B256` expression; use `#[only_role(ROLE, field = name)]` when the component is stored under
+/// another name.
+///
+/// # Example
+/// ```rust,ignore
+/// pub const MINTER_ROLE: B256 = B256::new(derive_keccak256!("MINTER_ROLE"));
+///
+/// #[router(mode = "solidity")]
+/// impl<SDK: SharedAPI> Token<SDK> {
+///     #[only_role(MINTER_ROLE)]
+///     pub fn mint(&mut self, to: Address, amount: U256) {
+///         // ...
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn only_role(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr::modifier_outside_router(attr::ONLY_ROLE_ATTR, item.into()).into()
+}
+
+/// Rejects calls into a `#[router]` method while any guarded method of the contract is running.
+///
+/// The lock is `fluentbase_sdk::security::ReentrancyGuard`, a flag in transient storage, and a
+/// re-entrant call reverts with `ReentrancyGuardReentrantCall()`. `view` and `pure` methods cannot
+/// take the lock, so for them the attribute only checks that it is free.
+///
+/// # Example
+/// ```rust,ignore
+/// #[router(mode = "solidity")]
+/// impl<SDK: SharedAPI> Vault<SDK> {
+///     #[non_reentrant]
+///     pub fn withdraw(&mut self, amount: U256) {
+///         // The callee cannot re-enter `withdraw`
+///         let caller = self.sdk.context().contract_caller();
+///         self.sdk.call(caller, amount, &[], None);
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn non_reentrant(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    attr::modifier_outside_router(attr::NON_REENTRANT_ATTR, item.into()).into()
+}
+
+/// Makes a `#[router]` impl a UUPS implementation that ERC-1967 proxies can upgrade.
+///
+/// Adds the `proxiableUUID()` and `upgradeToAndCall(address,bytes)` methods of OpenZeppelin's
+/// `UUPSUpgradeable`, built on `fluentbase_sdk::proxy::UupsUpgradeable`. Before upgrading, they
+/// call the contract's `authorize_upgrade(&mut self, new_implementation: Address)` method, which
+/// must revert unless the caller may upgrade. The attribute goes above `#[router]` and the impl
+/// must not be a trait impl.
+///
+/// # Example
+/// ```rust,ignore
+/// #[uups]
+/// #[router(mode = "solidity")]
+/// impl<SDK: SharedAPI> Counter<SDK> {
+///     pub fn increment(&mut self) { /* ... */ }
+///
+///     fn authorize_upgrade(&mut self, _new_implementation: Address) {
+///         self.ownable.only_owner(&mut self.sdk);
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn uups(attr: TokenStream, input: TokenStream) -> TokenStream {
+    match uups::process_uups(attr.into(), input.into()) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 /// Client macro for type-safe interaction with Fluentbase contracts.
 ///
 /// Generates a client struct and methods from a trait definition, handling contract
@@ -157,6 +292,8 @@ pub fn router(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// - **Runtime safety checks** for insufficient funds or gas
 /// - **Compatible with router** when using the same encoding mode
 /// - **Preserves method signatures** from the trait definition
+/// - **Decodes custom errors**: a method returning `Result<T, E>` yields `Err(e)` when the callee
+///   reverts with one of the errors of `E`, and panics on any other revert
 /// - **Preserves state mutability**: `&self` (or `#[state_mutability("pure"/"view")]`) issues a
 ///   `STATICCALL` and takes no value, `#[state_mutability("nonpayable")]` calls with a zero value,
 ///   and only payable methods forward a caller-supplied value
@@ -447,6 +584,11 @@ fn derive_storage_layout(input: TokenStream) -> TokenStream {
 /// - Are excluded from `SLOTS` constant
 /// - Must use `U256` type (compile error otherwise)
 ///
+/// # Layout
+///
+/// The generated `storage_layout()` lists the slot, offset and type of every field, which
+/// `fluentbase_sdk::storage::check_storage_upgrade` compares between two versions of a contract.
+///
 /// # Example
 /// ```rust,ignore
 /// #[derive(Storage)]
@@ -498,6 +640,20 @@ pub fn derive_storage(input: TokenStream) -> TokenStream {
 /// - [`eip1967_slot!`] - Computes EIP-1967 slot: `keccak256(id) - 1`
 /// - [`erc7201_slot!`] - Computes ERC-7201 slot: `keccak256(keccak256(id) - 1) & ~0xff`
 ///
+/// # Upgrades
+///
+/// Like [`macro@Storage`], the derive generates `storage_layout()`. Comparing it with the layout of
+/// the next version through `fluentbase_sdk::storage::check_storage_upgrade` catches fields that
+/// would read another version's data once a proxy is upgraded:
+///
+/// ```rust,ignore
+/// check_storage_upgrade(
+///     &CounterV1::<TestingContextImpl>::storage_layout(),
+///     &CounterV2::<TestingContextImpl>::storage_layout(),
+/// )
+/// .unwrap();
+/// ```
+///
 /// # Example
 /// ```rust,ignore
 /// #[derive(Contract)]
@@ -656,3 +812,33 @@ pub fn derive_event(input: TokenStream) -> TokenStream {
         Err(err) => err.to_compile_error().into(),
     }
 }
+
+/// Derives Solidity custom errors for an enum.
+///
+/// Each variant is one error named after the variant, with the variant fields as its parameters.
+/// The generated `SolidityError` implementation encodes the revert payload (4-byte selector followed
+/// by the ABI-encoded fields) and decodes it back, which is what `#[router]` and `#[client]` use
+/// for methods returning `Result<T, E>`.
+///
+/// # Example
+/// ```rust,ignore
+/// #[derive(Debug, PartialEq, SolidityError)]
+/// enum VaultError {
+///     Paused,
+///     InsufficientBalance { available: U256, required: U256 },
+///     Unauthorized(Address),
+/// }
+///
+/// // 0x9e87fac8
+/// let data = VaultError::Paused.encode();
+/// assert_eq!(VaultError::decode(&data), Some(VaultError::Paused));
+/// ```
+#[proc_macro_derive(SolidityError)]
+#[proc_macro_error]
+pub fn derive_solidity_error(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    match solidity_error::process_solidity_error(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,75 @@
+//! Access control building blocks compatible with OpenZeppelin's `access` contracts.
+//!
+//! Each component is a `#[derive(Storage)]` struct pinned to the same ERC-7201 namespace as its
+//! OpenZeppelin v5 counterpart, so it can be embedded into any contract without a `#[slot]`
+//! attribute and without shifting the slots of the other fields. Events and custom errors use the
+//! OpenZeppelin signatures, so existing tooling and clients decode them unchanged.
+//!
+//! # Example
+//! ```rust,ignore
+//! #[derive(Contract)]
+//! struct Vault<SDK> {
+//!     sdk: SDK,
+//!     ownable: Ownable,
+//!     balance: StorageU256,
+//! }
+//!
+//! #[router(mode = "solidity")]
+//! impl<SDK: SharedAPI> Vault<SDK> {
+//!     #[only_owner]
+//!     pub fn set_balance(&mut self, balance: U256) {
+//!         self.balance_accessor().set(&mut self.sdk, balance);
+//!     }
+//! }
+//! ```
+
+mod access_control;
+mod ownable;
+mod ownable_2step;
+
+pub use access_control::*;
+pub use ownable::*;
+pub use ownable_2step::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::U256;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_storage_slots_match_openzeppelin() {
+        assert_eq!(
+            OWNABLE_STORAGE_SLOT,
+            U256::from_be_bytes(hex!(
+                "9016d09d72d40fdae2fd8ceac6b6234c7706214fd39c1cd1e609a0528c199300"
+            ))
+        );
+        assert_eq!(
+            OWNABLE_2STEP_STORAGE_SLOT,
+            U256::from_be_bytes(hex!(
+                "237e158222e3e6968b72b9db0d8043aacf074ad9f650f0d1606b4d82ee432c00"
+            ))
+        );
+        assert_eq!(
+            ACCESS_CONTROL_STORAGE_SLOT,
+            U256::from_be_bytes(hex!(
+                "02dd7bc7dec4dceedda775e58dd541e08a116c6c53815c0bd028192f7b626800"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_errors_match_openzeppelin() {
+        use crate::{Address, SolidityError};
+
+        let unauthorized = OwnableError::OwnableUnauthorizedAccount {
+            account: Address::ZERO,
+        };
+        assert_eq!(unauthorized.selector(), hex!("118cdaa7"));
+        assert_eq!(
+            AccessControlError::AccessControlBadConfirmation.selector(),
+            hex!("6697b232")
+        );
+    }
+}
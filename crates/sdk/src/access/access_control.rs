@@ -0,0 +1,220 @@
+use crate::{
+    storage::{StorageBool, StorageBytes32, StorageMap},
+    Address, ComponentAbi, ContextReader, SharedAPI, SolidityError, StorageAPI, B256, U256,
+};
+use fluentbase_sdk_derive::{erc7201_slot, Event, SolidityError, Storage};
+
+/// `erc7201:openzeppelin.storage.AccessControl`
+pub const ACCESS_CONTROL_STORAGE_SLOT: U256 = erc7201_slot!("openzeppelin.storage.AccessControl");
+
+/// Role every other role is administered by unless [`AccessControl::set_role_admin`] says
+/// otherwise
+pub const DEFAULT_ADMIN_ROLE: B256 = B256::ZERO;
+
+/// Members and admin role of a single role, laid out like OpenZeppelin's `RoleData`
+#[derive(Storage)]
+pub struct RoleData {
+    has_role: StorageMap<Address, StorageBool>,
+    admin_role: StorageBytes32,
+}
+
+/// Role-based access control, the storage-compatible counterpart of OpenZeppelin's
+/// `AccessControl`
+///
+/// Roles are arbitrary `bytes32` identifiers, conventionally `keccak256` of the role name. Each
+/// role has an admin role whose members may grant and revoke it; all roles start out administered
+/// by [`DEFAULT_ADMIN_ROLE`]. Methods restricted to a role use `#[only_role(ROLE)]` or call
+/// [`AccessControl::only_role`].
+#[derive(Storage)]
+pub struct AccessControl {
+    #[slot(ACCESS_CONTROL_STORAGE_SLOT)]
+    roles: StorageMap<B256, RoleData>,
+}
+
+/// Emitted when the admin role of `role` changes
+#[derive(Event)]
+pub struct RoleAdminChanged {
+    #[indexed]
+    pub role: B256,
+    #[indexed]
+    pub previous_admin_role: B256,
+    #[indexed]
+    pub new_admin_role: B256,
+}
+
+/// Emitted when `account` is granted `role` by `sender`
+#[derive(Event)]
+pub struct RoleGranted {
+    #[indexed]
+    pub role: B256,
+    #[indexed]
+    pub account: Address,
+    #[indexed]
+    pub sender: Address,
+}
+
+/// Emitted when `account` loses `role`, either revoked by `sender` or renounced
+#[derive(Event)]
+pub struct RoleRevoked {
+    #[indexed]
+    pub role: B256,
+    #[indexed]
+    pub account: Address,
+    #[indexed]
+    pub sender: Address,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, SolidityError)]
+pub enum AccessControlError {
+    /// `account` lacks `needed_role`
+    AccessControlUnauthorizedAccount { account: Address, needed_role: B256 },
+    /// `renounce_role` was called with an account other than the caller
+    AccessControlBadConfirmation,
+}
+
+impl ComponentAbi for AccessControl {
+    const EVENTS: &'static [&'static str] =
+        &[RoleAdminChanged::ABI, RoleGranted::ABI, RoleRevoked::ABI];
+    const ERRORS: &'static [&'static [&'static str]] = &[AccessControlError::ABI];
+}
+
+impl AccessControl {
+    /// Whether `account` has been granted `role`
+    pub fn has_role<SDK: StorageAPI>(&self, sdk: &SDK, role: B256, account: Address) -> bool {
+        self.roles.entry(role).has_role.entry(account).get(sdk)
+    }
+
+    /// Role whose members may grant and revoke `role`
+    pub fn get_role_admin<SDK: StorageAPI>(&self, sdk: &SDK, role: B256) -> B256 {
+        self.roles.entry(role).admin_role.get(sdk)
+    }
+
+    /// Fails unless `account` has `role`
+    pub fn check_role<SDK: StorageAPI>(
+        &self,
+        sdk: &SDK,
+        role: B256,
+        account: Address,
+    ) -> Result<(), AccessControlError> {
+        if !self.has_role(sdk, role, account) {
+            return Err(AccessControlError::AccessControlUnauthorizedAccount {
+                account,
+                needed_role: role,
+            });
+        }
+        Ok(())
+    }
+
+    /// Reverts with `AccessControlUnauthorizedAccount` unless the caller has `role`
+    ///
+    /// This is the check `#[only_role(ROLE)]` inserts at the start of a method.
+    pub fn only_role<SDK: SharedAPI>(&self, sdk: &mut SDK, role: B256) {
+        let caller = sdk.context().contract_caller();
+        if let Err(err) = self.check_role(sdk, role, caller) {
+            err.revert(sdk);
+        }
+    }
+
+    /// Grants `role` to `account`; the caller must have the admin role of `role`
+    pub fn grant_role<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        role: B256,
+        account: Address,
+    ) -> Result<(), AccessControlError> {
+        let caller = sdk.context().contract_caller();
+        self.check_role(sdk, self.get_role_admin(sdk, role), caller)?;
+        self.set_role(sdk, role, account);
+        Ok(())
+    }
+
+    /// Revokes `role` from `account`; the caller must have the admin role of `role`
+    pub fn revoke_role<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        role: B256,
+        account: Address,
+    ) -> Result<(), AccessControlError> {
+        let caller = sdk.context().contract_caller();
+        self.check_role(sdk, self.get_role_admin(sdk, role), caller)?;
+        self.unset_role(sdk, role, account);
+        Ok(())
+    }
+
+    /// Gives up `role` held by the caller
+    ///
+    /// `caller_confirmation` must be the caller itself, which guards against renouncing a role by
+    /// mistake.
+    pub fn renounce_role<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        role: B256,
+        caller_confirmation: Address,
+    ) -> Result<(), AccessControlError> {
+        if caller_confirmation != sdk.context().contract_caller() {
+            return Err(AccessControlError::AccessControlBadConfirmation);
+        }
+        self.unset_role(sdk, role, caller_confirmation);
+        Ok(())
+    }
+
+    /// Makes `admin_role` the admin of `role` without any check and emits `RoleAdminChanged`
+    pub fn set_role_admin<SDK: SharedAPI>(&self, sdk: &mut SDK, role: B256, admin_role: B256) {
+        let previous_admin_role = self.get_role_admin(sdk, role);
+        self.roles.entry(role).admin_role.set(sdk, admin_role);
+        let event = RoleAdminChanged {
+            role,
+            previous_admin_role,
+            new_admin_role: admin_role,
+        };
+        if let Err(exit_code) = event.emit(sdk) {
+            sdk.native_exit(exit_code);
+        }
+    }
+
+    /// Grants `role` to `account` without any check
+    ///
+    /// Emits `RoleGranted` and returns `true` only if the account did not have the role yet.
+    pub fn set_role<SDK: SharedAPI>(&self, sdk: &mut SDK, role: B256, account: Address) -> bool {
+        if self.has_role(sdk, role, account) {
+            return false;
+        }
+        self.roles
+            .entry(role)
+            .has_role
+            .entry(account)
+            .set(sdk, true);
+        let event = RoleGranted {
+            role,
+            account,
+            sender: sdk.context().contract_caller(),
+        };
+        if let Err(exit_code) = event.emit(sdk) {
+            sdk.native_exit(exit_code);
+        }
+        true
+    }
+
+    /// Revokes `role` from `account` without any check
+    ///
+    /// Emits `RoleRevoked` and returns `true` only if the account had the role.
+    pub fn unset_role<SDK: SharedAPI>(&self, sdk: &mut SDK, role: B256, account: Address) -> bool {
+        if !self.has_role(sdk, role, account) {
+            return false;
+        }
+        self.roles
+            .entry(role)
+            .has_role
+            .entry(account)
+            .set(sdk, false);
+        let event = RoleRevoked {
+            role,
+            account,
+            sender: sdk.context().contract_caller(),
+        };
+        if let Err(exit_code) = event.emit(sdk) {
+            sdk.native_exit(exit_code);
+        }
+        true
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{
+    storage::StorageAddress, Address, ComponentAbi, ContextReader, ExitCode, SharedAPI,
+    SolidityError, StorageAPI, U256,
+};
+use fluentbase_sdk_derive::{erc7201_slot, Event, SolidityError, Storage};
+
+/// `erc7201:openzeppelin.storage.Ownable`
+pub const OWNABLE_STORAGE_SLOT: U256 = erc7201_slot!("openzeppelin.storage.Ownable");
+
+/// Single-owner access control, the storage-compatible counterpart of OpenZeppelin's `Ownable`
+///
+/// The owner lives at [`OWNABLE_STORAGE_SLOT`] no matter where the component is declared in the
+/// contract. Methods restricted to the owner use `#[only_owner]` or call [`Ownable::only_owner`].
+#[derive(Storage)]
+pub struct Ownable {
+    #[slot(OWNABLE_STORAGE_SLOT)]
+    owner: StorageAddress,
+}
+
+/// Emitted whenever the owner changes, including the initial assignment and renouncement
+#[derive(Event)]
+pub struct OwnershipTransferred {
+    #[indexed]
+    pub previous_owner: Address,
+    #[indexed]
+    pub new_owner: Address,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, SolidityError)]
+pub enum OwnableError {
+    /// The caller is not the owner
+    OwnableUnauthorizedAccount { account: Address },
+    /// The zero address cannot become the owner
+    OwnableInvalidOwner { owner: Address },
+}
+
+impl ComponentAbi for Ownable {
+    const EVENTS: &'static [&'static str] = &[OwnershipTransferred::ABI];
+    const ERRORS: &'static [&'static [&'static str]] = &[OwnableError::ABI];
+}
+
+impl Ownable {
+    /// Current owner, or the zero address when there is none
+    pub fn owner<SDK: StorageAPI>(&self, sdk: &SDK) -> Address {
+        self.owner.get(sdk)
+    }
+
+    /// Sets the initial owner, usually from the constructor
+    pub fn initialize<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        initial_owner: Address,
+    ) -> Result<(), OwnableError> {
+        if initial_owner == Address::ZERO {
+            return Err(OwnableError::OwnableInvalidOwner {
+                owner: Address::ZERO,
+            });
+        }
+        if let Err(exit_code) = self.set_owner(sdk, initial_owner) {
+            sdk.native_exit(exit_code);
+        }
+        Ok(())
+    }
+
+    /// Fails unless the caller is the owner
+    pub fn check_owner<SDK: SharedAPI>(&self, sdk: &SDK) -> Result<(), OwnableError> {
+        let caller = sdk.context().contract_caller();
+        if self.owner(sdk) != caller {
+            return Err(OwnableError::OwnableUnauthorizedAccount { account: caller });
+        }
+        Ok(())
+    }
+
+    /// Reverts with `OwnableUnauthorizedAccount` unless the caller is the owner
+    ///
+    /// This is the check `#[only_owner]` inserts at the start of a method.
+    pub fn only_owner<SDK: SharedAPI>(&self, sdk: &mut SDK) {
+        if let Err(err) = self.check_owner(sdk) {
+            err.revert(sdk);
+        }
+    }
+
+    /// Hands ownership over to `new_owner`; only the current owner may call it
+    pub fn transfer_ownership<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        new_owner: Address,
+    ) -> Result<(), OwnableError> {
+        self.check_owner(sdk)?;
+        if new_owner == Address::ZERO {
+            return Err(OwnableError::OwnableInvalidOwner {
+                owner: Address::ZERO,
+            });
+        }
+        if let Err(exit_code) = self.set_owner(sdk, new_owner) {
+            sdk.native_exit(exit_code);
+        }
+        Ok(())
+    }
+
+    /// Leaves the contract without an owner, disabling every owner-only method for good
+    pub fn renounce_ownership<SDK: SharedAPI>(&self, sdk: &mut SDK) -> Result<(), OwnableError> {
+        self.check_owner(sdk)?;
+        if let Err(exit_code) = self.set_owner(sdk, Address::ZERO) {
+            sdk.native_exit(exit_code);
+        }
+        Ok(())
+    }
+
+    /// Replaces the owner without any check and emits `OwnershipTransferred`
+    ///
+    /// Fails with the exit code of the log syscall when the event cannot be emitted, e.g. during a
+    /// static call.
+    pub fn set_owner<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        new_owner: Address,
+    ) -> Result<(), ExitCode> {
+        let previous_owner = self.owner(sdk);
+        self.owner.set(sdk, new_owner);
+        OwnershipTransferred {
+            previous_owner,
+            new_owner,
+        }
+        .emit(sdk)
+    }
+}
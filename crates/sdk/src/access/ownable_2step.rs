@@ -0,0 +1,120 @@
+use super::{Ownable, OwnableError, OwnershipTransferred};
+use crate::{
+    storage::StorageAddress, Address, ComponentAbi, ContextReader, ExitCode, SharedAPI,
+    SolidityError, StorageAPI, U256,
+};
+use fluentbase_sdk_derive::{erc7201_slot, Event, Storage};
+
+/// `erc7201:openzeppelin.storage.Ownable2Step`
+pub const OWNABLE_2STEP_STORAGE_SLOT: U256 = erc7201_slot!("openzeppelin.storage.Ownable2Step");
+
+/// Ownership that changes hands only once the new owner accepts it, as in OpenZeppelin's
+/// `Ownable2Step`
+///
+/// The owner is the [`Ownable`] slot, so a contract can move from `Ownable` to `Ownable2Step`
+/// without migrating storage. The pending owner lives at [`OWNABLE_2STEP_STORAGE_SLOT`].
+#[derive(Storage)]
+pub struct Ownable2Step {
+    ownable: Ownable,
+    #[slot(OWNABLE_2STEP_STORAGE_SLOT)]
+    pending_owner: StorageAddress,
+}
+
+/// Emitted when the owner nominates a new owner, who still has to accept
+#[derive(Event)]
+pub struct OwnershipTransferStarted {
+    #[indexed]
+    pub previous_owner: Address,
+    #[indexed]
+    pub new_owner: Address,
+}
+
+impl ComponentAbi for Ownable2Step {
+    const EVENTS: &'static [&'static str] =
+        &[OwnershipTransferred::ABI, OwnershipTransferStarted::ABI];
+    const ERRORS: &'static [&'static [&'static str]] = &[OwnableError::ABI];
+}
+
+impl Ownable2Step {
+    /// Current owner, or the zero address when there is none
+    pub fn owner<SDK: StorageAPI>(&self, sdk: &SDK) -> Address {
+        self.ownable.owner(sdk)
+    }
+
+    /// Nominated owner waiting to accept, or the zero address when there is none
+    pub fn pending_owner<SDK: StorageAPI>(&self, sdk: &SDK) -> Address {
+        self.pending_owner.get(sdk)
+    }
+
+    /// Sets the initial owner, usually from the constructor
+    pub fn initialize<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        initial_owner: Address,
+    ) -> Result<(), OwnableError> {
+        self.ownable.initialize(sdk, initial_owner)
+    }
+
+    /// Fails unless the caller is the owner
+    pub fn check_owner<SDK: SharedAPI>(&self, sdk: &SDK) -> Result<(), OwnableError> {
+        self.ownable.check_owner(sdk)
+    }
+
+    /// Reverts with `OwnableUnauthorizedAccount` unless the caller is the owner
+    pub fn only_owner<SDK: SharedAPI>(&self, sdk: &mut SDK) {
+        self.ownable.only_owner(sdk)
+    }
+
+    /// Nominates `new_owner`; only the current owner may call it
+    ///
+    /// The zero address cancels a pending transfer.
+    pub fn transfer_ownership<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        new_owner: Address,
+    ) -> Result<(), OwnableError> {
+        self.check_owner(sdk)?;
+        self.pending_owner.set(sdk, new_owner);
+        let event = OwnershipTransferStarted {
+            previous_owner: self.owner(sdk),
+            new_owner,
+        };
+        if let Err(exit_code) = event.emit(sdk) {
+            sdk.native_exit(exit_code);
+        }
+        Ok(())
+    }
+
+    /// Completes a transfer; only the pending owner may call it
+    pub fn accept_ownership<SDK: SharedAPI>(&self, sdk: &mut SDK) -> Result<(), OwnableError> {
+        let caller = sdk.context().contract_caller();
+        if self.pending_owner(sdk) != caller {
+            return Err(OwnableError::OwnableUnauthorizedAccount { account: caller });
+        }
+        if let Err(exit_code) = self.set_owner(sdk, caller) {
+            sdk.native_exit(exit_code);
+        }
+        Ok(())
+    }
+
+    /// Leaves the contract without an owner and drops any pending transfer
+    pub fn renounce_ownership<SDK: SharedAPI>(&self, sdk: &mut SDK) -> Result<(), OwnableError> {
+        self.check_owner(sdk)?;
+        if let Err(exit_code) = self.set_owner(sdk, Address::ZERO) {
+            sdk.native_exit(exit_code);
+        }
+        Ok(())
+    }
+
+    /// Replaces the owner without any check, clearing the pending owner
+    ///
+    /// Fails like [`Ownable::set_owner`] when `OwnershipTransferred` cannot be emitted.
+    pub fn set_owner<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        new_owner: Address,
+    ) -> Result<(), ExitCode> {
+        self.pending_owner.set(sdk, Address::ZERO);
+        self.ownable.set_owner(sdk, new_owner)
+    }
+}
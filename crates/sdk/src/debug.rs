@@ -257,6 +257,41 @@ pub fn debug_log_write_with_loc(
     Ok(())
 }
 
+/// Streams a structured record on `channel` to the node's debug event sink.
+///
+/// Unlike [`debug_log!`], records are kept in release builds, but the syscall only exists on
+/// development chains and traps on public networks, so don't ship contracts calling it there.
+/// Payloads above [`fluentbase_types::DEBUG_EVENT_MAX_DATA_SIZE`] bytes are dropped.
+#[inline]
+pub fn debug_event(channel: u32, data: &[u8]) {
+    write_debug_event(channel, fluentbase_types::DebugEventFormat::Bytes, data);
+}
+
+/// Same as [`debug_event`], for a UTF-8 JSON document that tracers render as JSON.
+#[inline]
+pub fn debug_event_json(channel: u32, json: &str) {
+    write_debug_event(
+        channel,
+        fluentbase_types::DebugEventFormat::Json,
+        json.as_bytes(),
+    );
+}
+
+#[inline]
+fn write_debug_event(channel: u32, format: fluentbase_types::DebugEventFormat, data: &[u8]) {
+    #[cfg(target_arch = "wasm32")]
+    unsafe {
+        fluentbase_types::bindings::_debug_event(
+            channel,
+            format as u32,
+            data.as_ptr(),
+            data.len() as u32,
+        );
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = (channel, format, data);
+}
+
 #[cfg(all(test, feature = "std"))]
 mod filter_tests {
     use super::debug_log_enabled_for_filter;
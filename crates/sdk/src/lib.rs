@@ -3,9 +3,12 @@
 
 extern crate alloc;
 extern crate core;
+// The storage, event and error derives refer to `fluentbase_sdk::`, including the ones used here
+extern crate self as fluentbase_sdk;
 
 pub extern crate rwasm as rwasm_core;
 
+pub mod access;
 mod address;
 mod allocator;
 pub mod constructor;
@@ -13,8 +16,12 @@ pub mod debug;
 pub mod entrypoint;
 pub mod leb128;
 mod macros;
+pub mod oauth2;
 #[cfg(target_arch = "wasm32")]
 pub mod panic;
+pub mod proxy;
+pub mod read_only;
+pub mod security;
 pub mod shared;
 pub mod storage;
 // #[deprecated(note = "Use `fluentbase_sdk::storage` instead", since = "0.4.5-dev")]
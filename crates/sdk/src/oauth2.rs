@@ -0,0 +1,32 @@
+//! OAuth2 / OpenID Connect verifier SDK
+//!
+//! This module holds everything the OAuth2 verifier precompile and its callers have to agree on:
+//! the call selectors and their ABI-encoded commands, the strict compact-JWS/JSON profile, and the
+//! storage layout of the JWKS (the issuer signing keys) kept in the precompile's own storage.
+//!
+//! # How It Works
+//!
+//! 1. Governance registers issuer signing keys with `setRsaKey`/`setEcKey`, addressed by the issuer
+//!    (`iss`) and key id (`kid`) exactly as they appear in the token.
+//! 2. A caller sends `verify(token, issuer, audience, nonce)` to `PRECOMPILE_OAUTH2_VERIFIER`.
+//! 3. Before execution the host reads the token header, derives the storage slots of the
+//!    `(issuer, kid)` key record (see [`oauth2_compute_storage_keys`]) and preloads them.
+//! 4. The verifier checks the signature and the `iss`/`aud`/`exp`/`nbf`/`nonce` claims against the
+//!    block timestamp and returns the ABI-encoded [`VerifiedClaims`].
+//!
+//! The host and the verifier share [`parse_compact_jws`], so they always agree on which key a token
+//! refers to. A token the host can't parse gets no preloaded key and fails verification.
+
+/// Re-export the precompile address for convenience
+pub use fluentbase_types::PRECOMPILE_OAUTH2_VERIFIER;
+
+mod command;
+pub use command::*;
+mod consts;
+pub use consts::*;
+mod json;
+pub use json::*;
+mod jws;
+pub use jws::*;
+mod storage;
+pub use storage::*;
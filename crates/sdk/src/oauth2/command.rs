@@ -0,0 +1,215 @@
+use crate::oauth2::consts::{
+    SIG_OAUTH2_CHANGE_OWNER, SIG_OAUTH2_REMOVE_KEY, SIG_OAUTH2_SET_EC_KEY, SIG_OAUTH2_SET_RSA_KEY,
+    SIG_OAUTH2_VERIFY,
+};
+use alloc::{string::String, vec::Vec};
+use fluentbase_codec::{Codec, Encoder, SolidityABI};
+use fluentbase_types::{byteorder::BE, bytes::BytesMut, Address, Bytes, ExitCode, U256};
+
+pub trait OAuth2Command
+where
+    Self: Encoder<BE, 32, true, false>,
+{
+    const SIGNATURE: u32;
+
+    /// Encodes the command as calldata: the selector followed by the ABI-encoded arguments.
+    ///
+    /// The codec encodes a dynamic struct as a tuple behind an offset word, while calldata carries
+    /// the arguments without it, so the word is dropped here and restored in [`Self::try_decode`].
+    fn encode_for_send(&self, buffer: &mut Vec<u8>) {
+        let mut bytes = BytesMut::new();
+        SolidityABI::<Self>::encode(self, &mut bytes, 0).unwrap();
+        let bytes = bytes.freeze();
+        let signature_be = Self::SIGNATURE.to_be_bytes();
+        buffer.extend_from_slice(&signature_be);
+        let offset = if Self::IS_DYNAMIC { U256::BYTES } else { 0 };
+        buffer.extend_from_slice(&bytes[offset..]);
+    }
+
+    fn try_decode(buf: &[u8]) -> Result<Self, ExitCode> {
+        if !Self::IS_DYNAMIC {
+            return SolidityABI::<Self>::decode(&buf, 0)
+                .map_err(|_| ExitCode::MalformedBuiltinParams);
+        }
+        let mut tuple = Vec::with_capacity(U256::BYTES + buf.len());
+        tuple.extend_from_slice(&U256::from(U256::BYTES).to_be_bytes::<{ U256::BYTES }>());
+        tuple.extend_from_slice(buf);
+        SolidityABI::<Self>::decode(&tuple.as_slice(), 0)
+            .map_err(|_| ExitCode::MalformedBuiltinParams)
+    }
+}
+
+/// Verify a compact JWS (an OIDC ID token) issued by `issuer` for `audience`.
+///
+/// An empty `nonce` skips the nonce check; otherwise the token must carry exactly this nonce.
+#[derive(Default, Debug, Clone, Codec)]
+pub struct VerifyCommand {
+    pub token: String,
+    pub issuer: String,
+    pub audience: String,
+    pub nonce: String,
+}
+impl OAuth2Command for VerifyCommand {
+    const SIGNATURE: u32 = SIG_OAUTH2_VERIFY;
+}
+
+/// Register (or replace) an `RS256` key for `(issuer, kid)`.
+///
+/// `modulus` is the big-endian JWK `n` parameter, `exponent` is the JWK `e` parameter.
+#[derive(Default, Debug, Clone, Codec)]
+pub struct SetRsaKeyCommand {
+    pub issuer: String,
+    pub kid: String,
+    pub modulus: Bytes,
+    pub exponent: U256,
+}
+impl OAuth2Command for SetRsaKeyCommand {
+    const SIGNATURE: u32 = SIG_OAUTH2_SET_RSA_KEY;
+}
+
+/// Register (or replace) an `ES256` key for `(issuer, kid)`, given by its P-256 affine coordinates.
+#[derive(Default, Debug, Clone, Codec)]
+pub struct SetEcKeyCommand {
+    pub issuer: String,
+    pub kid: String,
+    pub x: U256,
+    pub y: U256,
+}
+impl OAuth2Command for SetEcKeyCommand {
+    const SIGNATURE: u32 = SIG_OAUTH2_SET_EC_KEY;
+}
+
+/// Remove the key registered for `(issuer, kid)`.
+#[derive(Default, Debug, Clone, Codec)]
+pub struct RemoveKeyCommand {
+    pub issuer: String,
+    pub kid: String,
+}
+impl OAuth2Command for RemoveKeyCommand {
+    const SIGNATURE: u32 = SIG_OAUTH2_REMOVE_KEY;
+}
+
+#[derive(Default, Debug, Clone, Codec)]
+pub struct ChangeOwnerCommand {
+    pub new_owner: Address,
+}
+impl OAuth2Command for ChangeOwnerCommand {
+    const SIGNATURE: u32 = SIG_OAUTH2_CHANGE_OWNER;
+}
+
+/// Claims returned by a successful `verify` call.
+///
+/// `audience` is the audience the caller asked for (one of the token's `aud` values), and `email`
+/// is empty when the token carries no `email` claim.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Codec)]
+pub struct VerifiedClaims {
+    pub issuer: String,
+    pub subject: String,
+    pub audience: String,
+    pub nonce: String,
+    pub email: String,
+    pub issued_at: u64,
+    pub expires_at: u64,
+}
+
+impl VerifiedClaims {
+    /// Encodes the claims as the ABI return value of `verify`: an offset word followed by the
+    /// claims tuple, the same bytes Solidity's `abi.encode(claims)` produces.
+    pub fn encode_output(&self) -> Bytes {
+        let mut bytes = BytesMut::new();
+        SolidityABI::<VerifiedClaims>::encode(self, &mut bytes, 0).unwrap();
+        bytes.freeze().into()
+    }
+
+    pub fn decode_output(buf: &[u8]) -> Result<Self, ExitCode> {
+        SolidityABI::<VerifiedClaims>::decode(&buf, 0).map_err(|_| ExitCode::MalformedBuiltinParams)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::ToString, vec};
+    use alloy_sol_types::{sol, SolCall, SolValue};
+
+    sol! {
+        struct SolVerifiedClaims {
+            string issuer;
+            string subject;
+            string audience;
+            string nonce;
+            string email;
+            uint64 issued_at;
+            uint64 expires_at;
+        }
+
+        function verify(string token, string issuer, string audience, string nonce);
+        function setRsaKey(string issuer, string kid, bytes modulus, uint256 exponent);
+    }
+
+    #[test]
+    fn test_command_calldata_matches_solidity_abi() {
+        let command = VerifyCommand {
+            token: "header.payload.signature".to_string(),
+            issuer: "https://issuer.example".to_string(),
+            audience: "client".to_string(),
+            nonce: "".to_string(),
+        };
+        let mut input = vec![];
+        command.encode_for_send(&mut input);
+        let expected = verifyCall {
+            token: command.token.clone(),
+            issuer: command.issuer.clone(),
+            audience: command.audience.clone(),
+            nonce: command.nonce.clone(),
+        }
+        .abi_encode();
+        assert_eq!(input, expected);
+        let decoded = VerifyCommand::try_decode(&input[4..]).unwrap();
+        assert_eq!(decoded.token, command.token);
+        assert_eq!(decoded.issuer, command.issuer);
+
+        let command = SetRsaKeyCommand {
+            issuer: "https://issuer.example".to_string(),
+            kid: "key-1".to_string(),
+            modulus: Bytes::from(vec![0xc5; 256]),
+            exponent: U256::from(65537),
+        };
+        let mut input = vec![];
+        command.encode_for_send(&mut input);
+        let expected = setRsaKeyCall {
+            issuer: command.issuer.clone(),
+            kid: command.kid.clone(),
+            modulus: command.modulus.clone(),
+            exponent: command.exponent,
+        }
+        .abi_encode();
+        assert_eq!(input, expected);
+    }
+
+    #[test]
+    fn test_claims_output_matches_solidity_abi() {
+        let claims = VerifiedClaims {
+            issuer: "https://issuer.example".to_string(),
+            subject: "user-1".to_string(),
+            audience: "client".to_string(),
+            nonce: "nonce".to_string(),
+            email: "".to_string(),
+            issued_at: 1_700_000_000,
+            expires_at: 1_700_003_600,
+        };
+        let expected = SolVerifiedClaims {
+            issuer: claims.issuer.clone(),
+            subject: claims.subject.clone(),
+            audience: claims.audience.clone(),
+            nonce: claims.nonce.clone(),
+            email: claims.email.clone(),
+            issued_at: claims.issued_at,
+            expires_at: claims.expires_at,
+        }
+        .abi_encode();
+        let output = claims.encode_output();
+        assert_eq!(output.as_ref(), expected.as_slice());
+        assert_eq!(VerifiedClaims::decode_output(&output), Ok(claims));
+    }
+}
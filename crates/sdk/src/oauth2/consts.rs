@@ -0,0 +1,67 @@
+use fluentbase_sdk_derive::{derive_keccak256_id, erc7201_slot};
+use fluentbase_types::U256;
+
+// These signatures are compliant with the verifier's `abi.json`
+pub const SIG_OAUTH2_VERIFY: u32 = derive_keccak256_id!("verify(string,string,string,string)");
+pub const SIG_OAUTH2_SET_RSA_KEY: u32 =
+    derive_keccak256_id!("setRsaKey(string,string,bytes,uint256)");
+pub const SIG_OAUTH2_SET_EC_KEY: u32 =
+    derive_keccak256_id!("setEcKey(string,string,uint256,uint256)");
+pub const SIG_OAUTH2_REMOVE_KEY: u32 = derive_keccak256_id!("removeKey(string,string)");
+pub const SIG_OAUTH2_CHANGE_OWNER: u32 = derive_keccak256_id!("changeOwner(address)");
+pub const SIG_OAUTH2_OWNER: u32 = derive_keccak256_id!("owner()");
+
+// Storage slots (all ERC7201 complaint)
+pub const OWNER_STORAGE_SLOT: U256 = erc7201_slot!("oauth2-verifier.owner");
+pub const JWKS_STORAGE_SLOT: U256 = erc7201_slot!("oauth2-verifier.jwks");
+
+/// Identifier of an RSASSA-PKCS1-v1_5 with SHA-256 key (`RS256`).
+pub const JWK_ALG_RS256: u8 = 1;
+/// Identifier of an ECDSA P-256 with SHA-256 key (`ES256`).
+pub const JWK_ALG_ES256: u8 = 2;
+
+/// Smallest accepted RSA modulus, in bytes (2048 bits).
+pub const JWK_RSA_MIN_MODULUS_LEN: usize = 256;
+/// Largest accepted RSA modulus, in bytes (4096 bits).
+pub const JWK_RSA_MAX_MODULUS_LEN: usize = 512;
+
+/// Number of storage words reserved for one key record.
+///
+/// Word 0 is the header (algorithm and modulus length), words `1..=16` hold an RSA modulus of up
+/// to 4096 bits (or the P-256 `x`/`y` coordinates in words 1 and 2), and word 17 holds the RSA
+/// public exponent. The size is fixed so the host can preload a record without reading it first.
+pub const JWK_RECORD_SLOTS: usize = 2 + JWK_RSA_MAX_MODULUS_LEN / 32;
+
+/// Largest accepted compact JWS, in bytes.
+///
+/// ID tokens from the major providers are 1-2 KiB; the limit leaves room for large claim sets and
+/// 4096-bit signatures while bounding decoding and parsing work.
+pub const OAUTH2_MAX_TOKEN_LEN: usize = 8 * 1024;
+
+/// Largest accepted JSON nesting depth for the JWS header and claims. The top-level object counts
+/// as depth 1.
+pub const OAUTH2_MAX_JSON_DEPTH: usize = 8;
+
+#[allow(unused)]
+const fn assert_unique_u32<const N: usize>(values: [u32; N]) {
+    let mut i = 0;
+    while i < N {
+        let mut j = i + 1;
+        while j < N {
+            if values[i] == values[j] {
+                panic!("duplicate u32 constant detected");
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+const _: () = assert_unique_u32([
+    SIG_OAUTH2_VERIFY,
+    SIG_OAUTH2_SET_RSA_KEY,
+    SIG_OAUTH2_SET_EC_KEY,
+    SIG_OAUTH2_REMOVE_KEY,
+    SIG_OAUTH2_CHANGE_OWNER,
+    SIG_OAUTH2_OWNER,
+]);
@@ -0,0 +1,434 @@
+//! Strict, deterministic JSON object parser for JWS headers and JWT claim sets.
+//!
+//! Verification must decide on JSON *semantics*: a signed object with duplicate `iss`, `aud` or
+//! `kid` members would otherwise mean different things to different parsers. This parser accepts a
+//! single well-formed JSON object, rejects duplicate member names at every level, decodes string
+//! escapes, and keeps only the top-level members with the value shapes a verifier compares. Nested
+//! values are validated against the depth limit and then dropped.
+
+use crate::oauth2::consts::OAUTH2_MAX_JSON_DEPTH;
+use alloc::{string::String, vec::Vec};
+
+/// Reason a JSON object was rejected by the strict profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonError {
+    /// Input is not valid UTF-8.
+    NotUtf8,
+    /// Input is not exactly one well-formed JSON object.
+    Malformed,
+    /// Nesting is deeper than [`OAUTH2_MAX_JSON_DEPTH`].
+    TooDeep,
+    /// The same member name appears more than once in one object.
+    DuplicateMember,
+}
+
+/// A top-level member value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonValue {
+    Str(String),
+    Bool(bool),
+    Null,
+    /// A non-negative integer that fits into `u64`, e.g. a JWT `NumericDate`.
+    Integer(u64),
+    /// Any other number (negative, fractional, exponent or out of range).
+    Number,
+    /// An array whose elements are all strings, e.g. a multi-valued `aud`.
+    StrArray(Vec<String>),
+    /// Any other array.
+    Array,
+    Object,
+}
+
+/// The top-level members of a parsed JSON object, in input order and without duplicates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonObject {
+    members: Vec<(String, JsonValue)>,
+}
+
+impl JsonObject {
+    pub fn get(&self, name: &str) -> Option<&JsonValue> {
+        self.members
+            .iter()
+            .find(|(member, _)| member == name)
+            .map(|(_, value)| value)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    pub fn members(&self) -> &[(String, JsonValue)] {
+        &self.members
+    }
+}
+
+/// Parses `input` as exactly one JSON object under the strict profile.
+pub fn parse_json_object(input: &[u8]) -> Result<JsonObject, JsonError> {
+    core::str::from_utf8(input).map_err(|_| JsonError::NotUtf8)?;
+
+    let mut parser = Parser { input, pos: 0 };
+    parser.skip_whitespace();
+    let members = parser.parse_object(1)?;
+    parser.skip_whitespace();
+    if parser.pos != input.len() {
+        return Err(JsonError::Malformed);
+    }
+    Ok(JsonObject { members })
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.pos += 1;
+        Some(byte)
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), JsonError> {
+        if self.bump() == Some(byte) {
+            Ok(())
+        } else {
+            Err(JsonError::Malformed)
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_object(&mut self, depth: usize) -> Result<Vec<(String, JsonValue)>, JsonError> {
+        if depth > OAUTH2_MAX_JSON_DEPTH {
+            return Err(JsonError::TooDeep);
+        }
+        self.expect(b'{')?;
+
+        let mut members: Vec<(String, JsonValue)> = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(members);
+        }
+
+        loop {
+            self.skip_whitespace();
+            let name = self.parse_string()?;
+            if members.iter().any(|(existing, _)| *existing == name) {
+                return Err(JsonError::DuplicateMember);
+            }
+            self.skip_whitespace();
+            self.expect(b':')?;
+            self.skip_whitespace();
+            let value = self.parse_value(depth)?;
+            members.push((name, value));
+
+            self.skip_whitespace();
+            match self.bump() {
+                Some(b',') => continue,
+                Some(b'}') => return Ok(members),
+                _ => return Err(JsonError::Malformed),
+            }
+        }
+    }
+
+    fn parse_array(&mut self, depth: usize) -> Result<JsonValue, JsonError> {
+        if depth > OAUTH2_MAX_JSON_DEPTH {
+            return Err(JsonError::TooDeep);
+        }
+        self.expect(b'[')?;
+
+        let mut strings: Option<Vec<String>> = Some(Vec::new());
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(JsonValue::StrArray(Vec::new()));
+        }
+
+        loop {
+            self.skip_whitespace();
+            match self.parse_value(depth)? {
+                JsonValue::Str(string) => {
+                    if let Some(strings) = strings.as_mut() {
+                        strings.push(string);
+                    }
+                }
+                _ => strings = None,
+            }
+            self.skip_whitespace();
+            match self.bump() {
+                Some(b',') => continue,
+                Some(b']') => return Ok(strings.map_or(JsonValue::Array, JsonValue::StrArray)),
+                _ => return Err(JsonError::Malformed),
+            }
+        }
+    }
+
+    fn parse_value(&mut self, depth: usize) -> Result<JsonValue, JsonError> {
+        match self.peek().ok_or(JsonError::Malformed)? {
+            b'"' => Ok(JsonValue::Str(self.parse_string()?)),
+            b'{' => {
+                self.parse_object(depth + 1)?;
+                Ok(JsonValue::Object)
+            }
+            b'[' => self.parse_array(depth + 1),
+            b't' => {
+                self.expect_literal(b"true")?;
+                Ok(JsonValue::Bool(true))
+            }
+            b'f' => {
+                self.expect_literal(b"false")?;
+                Ok(JsonValue::Bool(false))
+            }
+            b'n' => {
+                self.expect_literal(b"null")?;
+                Ok(JsonValue::Null)
+            }
+            b'-' | b'0'..=b'9' => self.parse_number(),
+            _ => Err(JsonError::Malformed),
+        }
+    }
+
+    fn expect_literal(&mut self, literal: &[u8]) -> Result<(), JsonError> {
+        for byte in literal {
+            self.expect(*byte)?;
+        }
+        Ok(())
+    }
+
+    fn parse_string(&mut self) -> Result<String, JsonError> {
+        self.expect(b'"')?;
+
+        let mut decoded: Vec<u8> = Vec::new();
+        loop {
+            match self.bump().ok_or(JsonError::Malformed)? {
+                b'"' => {
+                    // The whole input was checked as UTF-8 and strings are only split at ASCII
+                    // delimiters, so the decoded bytes are valid UTF-8.
+                    return String::from_utf8(decoded).map_err(|_| JsonError::NotUtf8);
+                }
+                b'\\' => {
+                    let escape = self.bump().ok_or(JsonError::Malformed)?;
+                    let unescaped = match escape {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.parse_unicode_escape()?,
+                        _ => return Err(JsonError::Malformed),
+                    };
+                    let mut buffer = [0u8; 4];
+                    decoded.extend_from_slice(unescaped.encode_utf8(&mut buffer).as_bytes());
+                }
+                byte if byte < 0x20 => return Err(JsonError::Malformed),
+                byte => decoded.push(byte),
+            }
+        }
+    }
+
+    fn parse_unicode_escape(&mut self) -> Result<char, JsonError> {
+        let first = self.parse_hex4()?;
+        match first {
+            // A high surrogate is only valid when a low surrogate escape follows.
+            0xD800..=0xDBFF => {
+                self.expect(b'\\')?;
+                self.expect(b'u')?;
+                let second = self.parse_hex4()?;
+                if !(0xDC00..=0xDFFF).contains(&second) {
+                    return Err(JsonError::Malformed);
+                }
+                let code =
+                    0x10000 + ((u32::from(first - 0xD800) << 10) | u32::from(second - 0xDC00));
+                char::from_u32(code).ok_or(JsonError::Malformed)
+            }
+            0xDC00..=0xDFFF => Err(JsonError::Malformed),
+            _ => char::from_u32(u32::from(first)).ok_or(JsonError::Malformed),
+        }
+    }
+
+    fn parse_hex4(&mut self) -> Result<u16, JsonError> {
+        let mut value = 0u16;
+        for _ in 0..4 {
+            let byte = self.bump().ok_or(JsonError::Malformed)?;
+            let digit = match byte {
+                b'0'..=b'9' => byte - b'0',
+                b'a'..=b'f' => byte - b'a' + 10,
+                b'A'..=b'F' => byte - b'A' + 10,
+                _ => return Err(JsonError::Malformed),
+            };
+            value = (value << 4) | u16::from(digit);
+        }
+        Ok(value)
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, JsonError> {
+        let negative = self.peek() == Some(b'-');
+        if negative {
+            self.pos += 1;
+        }
+
+        // A leading zero may not be followed by more digits, so `01` is rejected.
+        let digits_start = self.pos;
+        match self.bump().ok_or(JsonError::Malformed)? {
+            b'0' => {}
+            b'1'..=b'9' => self.skip_digits(),
+            _ => return Err(JsonError::Malformed),
+        }
+        let digits_end = self.pos;
+
+        let mut integral = true;
+        if self.peek() == Some(b'.') {
+            integral = false;
+            self.pos += 1;
+            self.expect_digit()?;
+            self.skip_digits();
+        }
+
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            integral = false;
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            self.expect_digit()?;
+            self.skip_digits();
+        }
+
+        if negative || !integral {
+            return Ok(JsonValue::Number);
+        }
+        let mut value = 0u64;
+        for byte in &self.input[digits_start..digits_end] {
+            value = match value
+                .checked_mul(10)
+                .and_then(|value| value.checked_add(u64::from(byte - b'0')))
+            {
+                Some(value) => value,
+                None => return Ok(JsonValue::Number),
+            };
+        }
+        Ok(JsonValue::Integer(value))
+    }
+
+    fn expect_digit(&mut self) -> Result<(), JsonError> {
+        match self.peek() {
+            Some(b'0'..=b'9') => {
+                self.pos += 1;
+                Ok(())
+            }
+            _ => Err(JsonError::Malformed),
+        }
+    }
+
+    fn skip_digits(&mut self) {
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, string::ToString, vec};
+
+    fn parse(json: &str) -> Result<JsonObject, JsonError> {
+        parse_json_object(json.as_bytes())
+    }
+
+    #[test]
+    fn claim_shapes_are_decoded() {
+        let object = parse(
+            "{\"iss\":\"https://accounts.example\",\"aud\":[\"a\",\"b\"],\"exp\":1700000000,\
+             \"iat\":1.5,\"neg\":-1,\"big\":18446744073709551616,\"ok\":true,\"none\":null,\
+             \"mixed\":[1,\"a\"],\"nested\":{\"iss\":\"decoy\"}}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            object.get("iss"),
+            Some(&JsonValue::Str("https://accounts.example".to_string()))
+        );
+        assert_eq!(
+            object.get("aud"),
+            Some(&JsonValue::StrArray(vec!["a".to_string(), "b".to_string()]))
+        );
+        assert_eq!(object.get("exp"), Some(&JsonValue::Integer(1_700_000_000)));
+        assert_eq!(object.get("iat"), Some(&JsonValue::Number));
+        assert_eq!(object.get("neg"), Some(&JsonValue::Number));
+        assert_eq!(object.get("big"), Some(&JsonValue::Number));
+        assert_eq!(object.get("ok"), Some(&JsonValue::Bool(true)));
+        assert_eq!(object.get("none"), Some(&JsonValue::Null));
+        assert_eq!(object.get("mixed"), Some(&JsonValue::Array));
+        assert_eq!(object.get("nested"), Some(&JsonValue::Object));
+        assert_eq!(object.members().len(), 10);
+    }
+
+    #[test]
+    fn duplicate_members_are_rejected() {
+        for json in [
+            "{\"iss\":\"a\",\"iss\":\"b\"}",
+            // An escaped member name decodes to the same name, so it is a duplicate too.
+            "{\"iss\":\"a\",\"\\u0069ss\":\"b\"}",
+            "{\"nested\":{\"kid\":\"a\",\"kid\":\"b\"}}",
+        ] {
+            assert_eq!(parse(json), Err(JsonError::DuplicateMember), "{json}");
+        }
+    }
+
+    #[test]
+    fn malformed_objects_are_rejected() {
+        for json in [
+            "",
+            "[]",
+            "\"iss\"",
+            "{\"iss\":\"a\"}{}",
+            "{\"iss\":\"a\",}",
+            "{iss:\"a\"}",
+            "{\"exp\":01}",
+            "{\"exp\":1.}",
+            "{\"exp\":+1}",
+            "{\"iss\":\"\\x\"}",
+            "{\"iss\":\"\\ud83d\"}",
+            "{\"iss\":\"a\nb\"}",
+        ] {
+            assert_eq!(parse(json), Err(JsonError::Malformed), "{json}");
+        }
+    }
+
+    #[test]
+    fn depth_limit_is_enforced() {
+        let nesting = |levels: usize| {
+            format!(
+                "{{\"nested\":{}{}}}",
+                "[".repeat(levels),
+                "]".repeat(levels)
+            )
+        };
+        assert!(parse(&nesting(OAUTH2_MAX_JSON_DEPTH - 1)).is_ok());
+        assert_eq!(
+            parse(&nesting(OAUTH2_MAX_JSON_DEPTH)),
+            Err(JsonError::TooDeep)
+        );
+    }
+
+    #[test]
+    fn invalid_utf8_is_rejected() {
+        assert_eq!(
+            parse_json_object(b"{\"iss\":\"\xff\"}"),
+            Err(JsonError::NotUtf8)
+        );
+    }
+}
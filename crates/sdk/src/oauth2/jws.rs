@@ -0,0 +1,296 @@
+//! Compact JWS (RFC 7515) parsing under the verifier's strict profile.
+//!
+//! Only the compact serialization with a JSON header and a JSON payload is accepted. The header
+//! must name a supported `alg` and a `kid`, may declare `typ: "JWT"`, and may not carry `crit`
+//! (there are no extensions the verifier understands).
+
+use crate::oauth2::{
+    consts::OAUTH2_MAX_TOKEN_LEN,
+    json::{parse_json_object, JsonError, JsonObject, JsonValue},
+};
+use alloc::{string::String, vec::Vec};
+
+/// Signature algorithm named by the JWS `alg` header parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JwsAlgorithm {
+    Rs256,
+    Es256,
+}
+
+/// Reason a compact JWS was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JwsError {
+    /// The token is longer than [`OAUTH2_MAX_TOKEN_LEN`].
+    TooLong,
+    /// The token is not exactly three dot-separated segments.
+    MalformedSegments,
+    /// A segment is not unpadded, canonical base64url.
+    MalformedBase64,
+    /// The header or payload is not a JSON object under the strict profile.
+    MalformedJson(JsonError),
+    /// `alg` is missing, not a string, or not `RS256`/`ES256`.
+    UnsupportedAlgorithm,
+    /// `kid` is missing, empty or not a string.
+    MissingKeyId,
+    /// `typ` is present but not `JWT`.
+    UnsupportedType,
+    /// The header carries `crit`.
+    UnsupportedCritical,
+}
+
+/// JWS protected header fields the verifier acts on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JwsHeader {
+    pub alg: JwsAlgorithm,
+    pub kid: String,
+}
+
+/// A parsed compact JWS.
+#[derive(Debug, Clone)]
+pub struct CompactJws<'a> {
+    pub header: JwsHeader,
+    pub claims: JsonObject,
+    pub signature: Vec<u8>,
+    /// `BASE64URL(header) || '.' || BASE64URL(payload)`, the bytes covered by the signature.
+    pub signing_input: &'a [u8],
+}
+
+/// Splits, decodes and parses a compact JWS. The signature itself is not checked.
+pub fn parse_compact_jws(token: &[u8]) -> Result<CompactJws<'_>, JwsError> {
+    if token.len() > OAUTH2_MAX_TOKEN_LEN {
+        return Err(JwsError::TooLong);
+    }
+
+    let mut segments = token.split(|byte| *byte == b'.');
+    let (Some(header_b64), Some(payload_b64), Some(signature_b64), None) = (
+        segments.next(),
+        segments.next(),
+        segments.next(),
+        segments.next(),
+    ) else {
+        return Err(JwsError::MalformedSegments);
+    };
+    if header_b64.is_empty() || payload_b64.is_empty() || signature_b64.is_empty() {
+        return Err(JwsError::MalformedSegments);
+    }
+
+    let header = base64url_decode(header_b64).ok_or(JwsError::MalformedBase64)?;
+    let header = parse_json_object(&header).map_err(JwsError::MalformedJson)?;
+    let header = parse_header(&header)?;
+
+    let payload = base64url_decode(payload_b64).ok_or(JwsError::MalformedBase64)?;
+    let claims = parse_json_object(&payload).map_err(JwsError::MalformedJson)?;
+
+    let signature = base64url_decode(signature_b64).ok_or(JwsError::MalformedBase64)?;
+    let signing_input = &token[..header_b64.len() + 1 + payload_b64.len()];
+
+    Ok(CompactJws {
+        header,
+        claims,
+        signature,
+        signing_input,
+    })
+}
+
+fn parse_header(header: &JsonObject) -> Result<JwsHeader, JwsError> {
+    let alg = match header.get("alg") {
+        Some(JsonValue::Str(alg)) if alg == "RS256" => JwsAlgorithm::Rs256,
+        Some(JsonValue::Str(alg)) if alg == "ES256" => JwsAlgorithm::Es256,
+        _ => return Err(JwsError::UnsupportedAlgorithm),
+    };
+    let kid = match header.get("kid") {
+        Some(JsonValue::Str(kid)) if !kid.is_empty() => kid.clone(),
+        _ => return Err(JwsError::MissingKeyId),
+    };
+    match header.get("typ") {
+        None => {}
+        Some(JsonValue::Str(typ)) if typ == "JWT" => {}
+        Some(_) => return Err(JwsError::UnsupportedType),
+    }
+    if header.contains("crit") {
+        return Err(JwsError::UnsupportedCritical);
+    }
+    Ok(JwsHeader { alg, kid })
+}
+
+const BASE64URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+fn base64url_value(byte: u8) -> Option<u32> {
+    let value = match byte {
+        b'A'..=b'Z' => byte - b'A',
+        b'a'..=b'z' => byte - b'a' + 26,
+        b'0'..=b'9' => byte - b'0' + 52,
+        b'-' => 62,
+        b'_' => 63,
+        _ => return None,
+    };
+    Some(u32::from(value))
+}
+
+/// Decodes unpadded base64url (RFC 7515 section 2).
+///
+/// Padding, whitespace and non-canonical trailing bits are rejected, so every byte string has
+/// exactly one accepted encoding.
+pub fn base64url_decode(input: &[u8]) -> Option<Vec<u8>> {
+    if input.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut output = Vec::with_capacity(input.len() * 3 / 4);
+    for chunk in input.chunks(4) {
+        let mut word = 0u32;
+        for byte in chunk {
+            word = (word << 6) | base64url_value(*byte)?;
+        }
+        match chunk.len() {
+            4 => output.extend_from_slice(&word.to_be_bytes()[1..]),
+            3 => {
+                if word & 0b11 != 0 {
+                    return None;
+                }
+                output.extend_from_slice(&(word >> 2).to_be_bytes()[2..]);
+            }
+            2 => {
+                if word & 0b1111 != 0 {
+                    return None;
+                }
+                output.push((word >> 4) as u8);
+            }
+            _ => unreachable!("chunk of length 1 is rejected above"),
+        }
+    }
+    Some(output)
+}
+
+/// Encodes `input` as unpadded base64url.
+pub fn base64url_encode(input: &[u8]) -> String {
+    let mut output = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let mut buffer = [0u8; 3];
+        buffer[..chunk.len()].copy_from_slice(chunk);
+        let word = u32::from_be_bytes([0, buffer[0], buffer[1], buffer[2]]);
+        for i in 0..=chunk.len() {
+            let index = (word >> (18 - 6 * i)) & 0x3f;
+            output.push(BASE64URL_ALPHABET[index as usize] as char);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, string::ToString, vec};
+
+    fn token(header: &str, payload: &str, signature: &[u8]) -> String {
+        format!(
+            "{}.{}.{}",
+            base64url_encode(header.as_bytes()),
+            base64url_encode(payload.as_bytes()),
+            base64url_encode(signature)
+        )
+    }
+
+    #[test]
+    fn base64url_round_trips() {
+        for len in 0..=16 {
+            let input: Vec<u8> = (0..len).map(|i| (i * 37 + 250) as u8).collect();
+            let encoded = base64url_encode(&input);
+            assert!(!encoded.contains('='));
+            assert_eq!(base64url_decode(encoded.as_bytes()), Some(input));
+        }
+        assert_eq!(base64url_encode(b"\xfb\xff"), "-_8");
+    }
+
+    #[test]
+    fn base64url_rejects_non_canonical_input() {
+        for input in ["QQ==", "QR", "QUF", "Q", "QU+/", "QU I"] {
+            assert_eq!(base64url_decode(input.as_bytes()), None, "{input}");
+        }
+        assert_eq!(base64url_decode(b"QQ"), Some(b"A".to_vec()));
+        assert_eq!(base64url_decode(b"QUE"), Some(b"AA".to_vec()));
+    }
+
+    #[test]
+    fn compact_jws_is_parsed() {
+        let token = token(
+            "{\"alg\":\"ES256\",\"kid\":\"key-1\",\"typ\":\"JWT\"}",
+            "{\"iss\":\"https://issuer.example\"}",
+            &[1, 2, 3],
+        );
+        let jws = parse_compact_jws(token.as_bytes()).unwrap();
+        assert_eq!(
+            jws.header,
+            JwsHeader {
+                alg: JwsAlgorithm::Es256,
+                kid: "key-1".to_string(),
+            }
+        );
+        assert_eq!(
+            jws.claims.get("iss"),
+            Some(&JsonValue::Str("https://issuer.example".to_string()))
+        );
+        assert_eq!(jws.signature, vec![1, 2, 3]);
+        assert_eq!(
+            jws.signing_input,
+            &token.as_bytes()[..token.rfind('.').unwrap()]
+        );
+    }
+
+    #[test]
+    fn unsupported_headers_are_rejected() {
+        let cases = [
+            (
+                "{\"alg\":\"none\",\"kid\":\"k\"}",
+                JwsError::UnsupportedAlgorithm,
+            ),
+            (
+                "{\"alg\":\"HS256\",\"kid\":\"k\"}",
+                JwsError::UnsupportedAlgorithm,
+            ),
+            ("{\"kid\":\"k\"}", JwsError::UnsupportedAlgorithm),
+            ("{\"alg\":\"RS256\"}", JwsError::MissingKeyId),
+            ("{\"alg\":\"RS256\",\"kid\":\"\"}", JwsError::MissingKeyId),
+            ("{\"alg\":\"RS256\",\"kid\":1}", JwsError::MissingKeyId),
+            (
+                "{\"alg\":\"RS256\",\"kid\":\"k\",\"typ\":\"at+jwt\"}",
+                JwsError::UnsupportedType,
+            ),
+            (
+                "{\"alg\":\"RS256\",\"kid\":\"k\",\"crit\":[\"exp\"]}",
+                JwsError::UnsupportedCritical,
+            ),
+            (
+                "{\"alg\":\"RS256\",\"kid\":\"a\",\"kid\":\"b\"}",
+                JwsError::MalformedJson(JsonError::DuplicateMember),
+            ),
+        ];
+        for (header, expected) in cases {
+            let token = token(header, "{}", &[0]);
+            assert_eq!(
+                parse_compact_jws(token.as_bytes()).unwrap_err(),
+                expected,
+                "{header}"
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_tokens_are_rejected() {
+        let valid = token("{\"alg\":\"RS256\",\"kid\":\"k\"}", "{}", &[0]);
+        let cases = [
+            ("a.b".to_string(), JwsError::MalformedSegments),
+            (format!("{valid}.x"), JwsError::MalformedSegments),
+            (
+                format!("{}.", &valid[..valid.rfind('.').unwrap()]),
+                JwsError::MalformedSegments,
+            ),
+            (valid.replacen('.', "=.", 1), JwsError::MalformedBase64),
+            ("a".repeat(OAUTH2_MAX_TOKEN_LEN + 1), JwsError::TooLong),
+        ];
+        for (token, expected) in cases {
+            assert_eq!(parse_compact_jws(token.as_bytes()).unwrap_err(), expected);
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{
+    oauth2::{
+        command::{
+            OAuth2Command, RemoveKeyCommand, SetEcKeyCommand, SetRsaKeyCommand, VerifyCommand,
+        },
+        consts::{
+            JWKS_STORAGE_SLOT, JWK_ALG_ES256, JWK_ALG_RS256, JWK_RECORD_SLOTS,
+            JWK_RSA_MAX_MODULUS_LEN, OWNER_STORAGE_SLOT, SIG_OAUTH2_CHANGE_OWNER, SIG_OAUTH2_OWNER,
+            SIG_OAUTH2_REMOVE_KEY, SIG_OAUTH2_SET_EC_KEY, SIG_OAUTH2_SET_RSA_KEY,
+            SIG_OAUTH2_VERIFY,
+        },
+        jws::parse_compact_jws,
+    },
+    storage::MapKey,
+};
+use alloc::vec::Vec;
+use fluentbase_types::{Address, Bytes, U256};
+
+pub const SIG_LEN_BYTES: usize = 4;
+
+/// A registered issuer signing key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JwkRecord {
+    /// RSA public key: big-endian modulus without leading zeros, and public exponent.
+    Rs256 { modulus: Bytes, exponent: U256 },
+    /// P-256 public key given by its affine coordinates.
+    Es256 { x: U256, y: U256 },
+}
+
+impl JwkRecord {
+    /// Encodes the record into its fixed-size storage representation.
+    ///
+    /// An RSA modulus longer than [`JWK_RSA_MAX_MODULUS_LEN`] can't be represented; callers must
+    /// validate the key first.
+    pub fn to_words(&self) -> [U256; JWK_RECORD_SLOTS] {
+        let mut words = [U256::ZERO; JWK_RECORD_SLOTS];
+        match self {
+            JwkRecord::Rs256 { modulus, exponent } => {
+                assert!(
+                    modulus.len() <= JWK_RSA_MAX_MODULUS_LEN,
+                    "oauth2: rsa modulus is too long"
+                );
+                words[0] = U256::from(JWK_ALG_RS256) | (U256::from(modulus.len()) << 8);
+                let mut buffer = [0u8; JWK_RSA_MAX_MODULUS_LEN];
+                buffer[..modulus.len()].copy_from_slice(modulus);
+                for (word, chunk) in words[1..JWK_RECORD_SLOTS - 1]
+                    .iter_mut()
+                    .zip(buffer.chunks(32))
+                {
+                    *word = U256::from_be_slice(chunk);
+                }
+                words[JWK_RECORD_SLOTS - 1] = *exponent;
+            }
+            JwkRecord::Es256 { x, y } => {
+                words[0] = U256::from(JWK_ALG_ES256);
+                words[1] = *x;
+                words[2] = *y;
+            }
+        }
+        words
+    }
+
+    /// Decodes a record from storage, returning `None` for an empty (removed or never set) record.
+    pub fn from_words(words: &[U256; JWK_RECORD_SLOTS]) -> Option<Self> {
+        let header = words[0];
+        let alg = header.byte(0);
+        let modulus_len = (header >> 8).saturating_to::<usize>();
+        match alg {
+            JWK_ALG_RS256 if modulus_len <= JWK_RSA_MAX_MODULUS_LEN => {
+                let mut buffer = Vec::with_capacity(JWK_RSA_MAX_MODULUS_LEN);
+                for word in &words[1..JWK_RECORD_SLOTS - 1] {
+                    buffer.extend_from_slice(&word.to_be_bytes::<32>());
+                }
+                buffer.truncate(modulus_len);
+                Some(JwkRecord::Rs256 {
+                    modulus: buffer.into(),
+                    exponent: words[JWK_RECORD_SLOTS - 1],
+                })
+            }
+            JWK_ALG_ES256 => Some(JwkRecord::Es256 {
+                x: words[1],
+                y: words[2],
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the first storage slot of the key record for `(issuer, kid)`.
+///
+/// The JWKS is laid out like `mapping(string issuer => mapping(string kid => JwkRecord))`, and a
+/// record occupies [`JWK_RECORD_SLOTS`] consecutive slots starting at the returned one.
+pub fn jwk_storage_slot(issuer: &str, kid: &str) -> U256 {
+    kid.compute_slot(issuer.compute_slot(JWKS_STORAGE_SLOT))
+}
+
+/// Returns every storage slot of the key record for `(issuer, kid)`.
+pub fn jwk_record_slots(issuer: &str, kid: &str) -> impl Iterator<Item = U256> {
+    let base_slot = jwk_storage_slot(issuer, kid);
+    (0..JWK_RECORD_SLOTS).map(move |i| base_slot.wrapping_add(U256::from(i)))
+}
+
+/// Computes the storage slots the verifier touches for the given calldata, so the host can preload
+/// them before execution.
+///
+/// For `verify` this reads the key id from the token header; a token that doesn't parse gets no
+/// key record and fails verification inside the precompile.
+pub fn oauth2_compute_storage_keys(input: &[u8], _caller: &Address) -> Option<Vec<U256>> {
+    if input.len() < SIG_LEN_BYTES {
+        return None;
+    }
+    let mut result = Vec::with_capacity(JWK_RECORD_SLOTS + 1);
+    let (sig, input) = input.split_at(SIG_LEN_BYTES);
+    let sig = u32::from_be_bytes(sig.try_into().unwrap());
+    match sig {
+        SIG_OAUTH2_VERIFY => {
+            let VerifyCommand { token, issuer, .. } = VerifyCommand::try_decode(input).ok()?;
+            let jws = parse_compact_jws(token.as_bytes()).ok()?;
+            result.extend(jwk_record_slots(&issuer, &jws.header.kid));
+        }
+        SIG_OAUTH2_SET_RSA_KEY => {
+            result.push(OWNER_STORAGE_SLOT);
+            let SetRsaKeyCommand { issuer, kid, .. } = SetRsaKeyCommand::try_decode(input).ok()?;
+            result.extend(jwk_record_slots(&issuer, &kid));
+        }
+        SIG_OAUTH2_SET_EC_KEY => {
+            result.push(OWNER_STORAGE_SLOT);
+            let SetEcKeyCommand { issuer, kid, .. } = SetEcKeyCommand::try_decode(input).ok()?;
+            result.extend(jwk_record_slots(&issuer, &kid));
+        }
+        SIG_OAUTH2_REMOVE_KEY => {
+            result.push(OWNER_STORAGE_SLOT);
+            let RemoveKeyCommand { issuer, kid } = RemoveKeyCommand::try_decode(input).ok()?;
+            result.extend(jwk_record_slots(&issuer, &kid));
+        }
+        SIG_OAUTH2_CHANGE_OWNER | SIG_OAUTH2_OWNER => result.push(OWNER_STORAGE_SLOT),
+        _ => {}
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oauth2::{base64url_encode, ChangeOwnerCommand};
+    use alloc::{format, string::ToString, vec};
+
+    #[test]
+    fn test_jwk_record_round_trip() {
+        let rsa = JwkRecord::Rs256 {
+            modulus: (0..=255u8).chain(0..=99u8).collect::<Vec<_>>().into(),
+            exponent: U256::from(65537),
+        };
+        assert_eq!(JwkRecord::from_words(&rsa.to_words()), Some(rsa));
+
+        let ec = JwkRecord::Es256 {
+            x: U256::from(1),
+            y: U256::MAX,
+        };
+        assert_eq!(JwkRecord::from_words(&ec.to_words()), Some(ec));
+
+        assert_eq!(JwkRecord::from_words(&[U256::ZERO; JWK_RECORD_SLOTS]), None);
+    }
+
+    #[test]
+    fn test_jwk_storage_slot_is_nested_string_mapping() {
+        let issuer_slot = "https://issuer.example".compute_slot(JWKS_STORAGE_SLOT);
+        assert_eq!(
+            jwk_storage_slot("https://issuer.example", "key-1"),
+            "key-1".compute_slot(issuer_slot)
+        );
+        assert_ne!(
+            jwk_storage_slot("https://issuer.example", "key-1"),
+            jwk_storage_slot("https://issuer.example", "key-2")
+        );
+    }
+
+    #[test]
+    fn test_compute_storage_keys() {
+        let header = base64url_encode(b"{\"alg\":\"RS256\",\"kid\":\"key-1\"}");
+        let payload = base64url_encode(b"{}");
+        let command = VerifyCommand {
+            token: format!("{header}.{payload}.AA"),
+            issuer: "https://issuer.example".to_string(),
+            audience: "client".to_string(),
+            nonce: "".to_string(),
+        };
+        let mut input = vec![];
+        command.encode_for_send(&mut input);
+        let keys = oauth2_compute_storage_keys(&input, &Address::ZERO).unwrap();
+        assert_eq!(
+            keys,
+            jwk_record_slots("https://issuer.example", "key-1").collect::<Vec<_>>()
+        );
+
+        let command = VerifyCommand {
+            token: "not a token".to_string(),
+            ..command
+        };
+        let mut input = vec![];
+        command.encode_for_send(&mut input);
+        assert_eq!(oauth2_compute_storage_keys(&input, &Address::ZERO), None);
+
+        let mut input = vec![];
+        ChangeOwnerCommand {
+            new_owner: Address::ZERO,
+        }
+        .encode_for_send(&mut input);
+        assert_eq!(
+            oauth2_compute_storage_keys(&input, &Address::ZERO),
+            Some(vec![OWNER_STORAGE_SLOT])
+        );
+    }
+}
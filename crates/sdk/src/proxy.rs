@@ -0,0 +1,103 @@
+//! Upgradeable contracts compatible with OpenZeppelin's ERC-1967 proxies and `UUPSUpgradeable`.
+//!
+//! A proxy keeps the address of its implementation at [`IMPLEMENTATION_SLOT`] and forwards every
+//! call to it with `delegate_call`. The implementation code, EVM or rWasm, then runs on the
+//! proxy: it reads and writes the proxy's storage and balance, sees the original caller and value,
+//! and `contract_address()` is the proxy. An EVM implementation runs with its own account metadata,
+//! so the proxy's account is never consulted and both kinds of proxy can point at both kinds of
+//! implementation.
+//!
+//! Inside a delegated call `contract_bytecode_address()` is the implementation, which is how
+//! [`UupsUpgradeable`] tells a call through a proxy from a direct one, where OpenZeppelin relies on
+//! an immutable `__self`. Constructors of an implementation only touch the implementation's own
+//! storage, so state the proxy needs is set up by an [`Initializable`] method called through the
+//! proxy instead.
+//!
+//! # Example
+//! ```rust,ignore
+//! #[derive(Contract)]
+//! struct Counter<SDK> {
+//!     sdk: SDK,
+//!     initializable: Initializable,
+//!     ownable: Ownable,
+//!     count: StorageU256,
+//! }
+//!
+//! #[uups]
+//! #[router(mode = "solidity")]
+//! impl<SDK: SharedAPI> Counter<SDK> {
+//!     pub fn initialize(&mut self, owner: Address) -> Result<(), InitializableError> {
+//!         self.initializable.initialize(&mut self.sdk, 1)?;
+//!         self.ownable.initialize(&mut self.sdk, owner).unwrap();
+//!         Ok(())
+//!     }
+//!
+//!     fn authorize_upgrade(&mut self, _new_implementation: Address) {
+//!         self.ownable.only_owner(&mut self.sdk);
+//!     }
+//! }
+//! ```
+
+mod erc1967;
+mod initializable;
+mod uups;
+
+pub use erc1967::*;
+pub use initializable::*;
+pub use uups::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Address, SolidityError, B256, U256};
+    use hex_literal::hex;
+
+    #[test]
+    fn test_storage_slots_match_openzeppelin() {
+        assert_eq!(
+            IMPLEMENTATION_SLOT,
+            U256::from_be_bytes(hex!(
+                "360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc"
+            ))
+        );
+        assert_eq!(
+            ADMIN_SLOT,
+            U256::from_be_bytes(hex!(
+                "b53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103"
+            ))
+        );
+        assert_eq!(
+            BEACON_SLOT,
+            U256::from_be_bytes(hex!(
+                "a3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50"
+            ))
+        );
+        assert_eq!(
+            INITIALIZABLE_STORAGE_SLOT,
+            U256::from_be_bytes(hex!(
+                "f0c57e16840df040f15088dc2f81fe391c3923bec73e23a9662efc9c229c6a00"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_errors_match_openzeppelin() {
+        let invalid = ProxyError::ERC1967InvalidImplementation {
+            implementation: Address::ZERO,
+        };
+        assert_eq!(invalid.selector(), hex!("4c9c8ce3"));
+        assert_eq!(ProxyError::ERC1967NonPayable.selector(), hex!("b398979f"));
+        assert_eq!(ProxyError::FailedCall.selector(), hex!("d6bda275"));
+        assert_eq!(
+            ProxyError::UUPSUnauthorizedCallContext.selector(),
+            hex!("e07c8dba")
+        );
+        let unsupported = ProxyError::UUPSUnsupportedProxiableUUID { slot: B256::ZERO };
+        assert_eq!(unsupported.selector(), hex!("aa1d49a4"));
+        assert_eq!(
+            InitializableError::InvalidInitialization.selector(),
+            hex!("f92ee8a9")
+        );
+        assert_eq!(PROXIABLE_UUID_SELECTOR, hex!("52d1902d"));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    Address, Bytes, ContextReader, ExitCode, SharedAPI, SolidityError, StorageAPI, B256, U256,
+};
+use fluentbase_sdk_derive::{eip1967_slot, Event, SolidityError};
+
+/// `eip1967.proxy.implementation`, the address calls are delegated to
+pub const IMPLEMENTATION_SLOT: U256 = eip1967_slot!("eip1967.proxy.implementation");
+/// `eip1967.proxy.admin`, the admin of a transparent proxy
+pub const ADMIN_SLOT: U256 = eip1967_slot!("eip1967.proxy.admin");
+/// `eip1967.proxy.beacon`, the beacon of a beacon proxy
+pub const BEACON_SLOT: U256 = eip1967_slot!("eip1967.proxy.beacon");
+
+/// Emitted whenever the implementation of a proxy changes
+#[derive(Event)]
+pub struct Upgraded {
+    #[indexed]
+    pub implementation: Address,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, SolidityError)]
+pub enum ProxyError {
+    /// `implementation` has no code or is not a UUPS implementation
+    ERC1967InvalidImplementation { implementation: Address },
+    /// Value was sent to an upgrade that does not call the new implementation
+    ERC1967NonPayable,
+    /// The initialization call reverted without any data
+    FailedCall,
+    /// A UUPS method was called on the implementation instead of through a proxy, or the other
+    /// way round
+    UUPSUnauthorizedCallContext,
+    /// The new implementation reports another storage slot from `proxiableUUID()`
+    UUPSUnsupportedProxiableUUID { slot: B256 },
+}
+
+/// Reads and writes the ERC-1967 slots, the counterpart of OpenZeppelin's `ERC1967Utils`
+///
+/// The slots are read from the storage of the running contract, so when called from a delegated
+/// implementation they refer to the proxy.
+pub struct Erc1967;
+
+impl Erc1967 {
+    /// Implementation the proxy currently delegates to
+    pub fn implementation<SDK: StorageAPI>(sdk: &SDK) -> Address {
+        let value = sdk.storage(&IMPLEMENTATION_SLOT).unwrap();
+        Address::from_word(B256::from(value))
+    }
+
+    /// Points the proxy at `new_implementation`, emits `Upgraded` and, if `data` is not empty,
+    /// delegates `data` to the new implementation to initialize it
+    ///
+    /// A revert of the initialization call is bubbled up unchanged. Without `data` the upgrade must
+    /// not carry any value, since nothing would ever account for it.
+    pub fn upgrade_to_and_call<SDK: SharedAPI>(
+        sdk: &mut SDK,
+        new_implementation: Address,
+        data: &[u8],
+    ) -> Result<(), ProxyError> {
+        if sdk.code_size(&new_implementation).unwrap() == 0 {
+            return Err(ProxyError::ERC1967InvalidImplementation {
+                implementation: new_implementation,
+            });
+        }
+        sdk.write_storage(
+            IMPLEMENTATION_SLOT,
+            U256::from_be_slice(new_implementation.as_slice()),
+        )
+        .unwrap();
+        Upgraded {
+            implementation: new_implementation,
+        }
+        .emit(sdk)
+        .unwrap();
+        if !data.is_empty() {
+            Self::delegate_call(sdk, new_implementation, data);
+        } else if !sdk.context().contract_value().is_zero() {
+            return Err(ProxyError::ERC1967NonPayable);
+        }
+        Ok(())
+    }
+
+    /// Delegates `input` to `implementation`, bubbling up its revert data if it fails
+    pub fn delegate_call<SDK: SharedAPI>(
+        sdk: &mut SDK,
+        implementation: Address,
+        input: &[u8],
+    ) -> Bytes {
+        let result = sdk.delegate_call(implementation, input, None);
+        if !result.status.is_ok() {
+            if result.data.is_empty() {
+                ProxyError::FailedCall.revert(sdk);
+            }
+            sdk.write(&result.data);
+            sdk.native_exit(ExitCode::Panic);
+        }
+        result.data
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{storage::StorageU64, SharedAPI, StorageAPI, U256};
+use fluentbase_sdk_derive::{erc7201_slot, Event, SolidityError, Storage};
+
+/// `erc7201:openzeppelin.storage.Initializable`
+pub const INITIALIZABLE_STORAGE_SLOT: U256 = erc7201_slot!("openzeppelin.storage.Initializable");
+
+/// Initialization guard for contracts running behind a proxy, the storage-compatible counterpart
+/// of OpenZeppelin's `Initializable`
+///
+/// An implementation's constructor never runs on the proxy, so proxied contracts set their state
+/// up from a regular method guarded by [`Initializable::initialize`] instead. The version occupies
+/// the low 8 bytes of [`INITIALIZABLE_STORAGE_SLOT`], where OpenZeppelin keeps `_initialized`.
+#[derive(Storage)]
+pub struct Initializable {
+    #[slot(INITIALIZABLE_STORAGE_SLOT)]
+    initialized: StorageU64,
+}
+
+/// Emitted once the contract has been initialized to `version`
+#[derive(Event)]
+pub struct Initialized {
+    pub version: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, SolidityError)]
+pub enum InitializableError {
+    /// The contract was already initialized to this version or a later one
+    InvalidInitialization,
+}
+
+impl Initializable {
+    /// Highest version the contract has been initialized to, `u64::MAX` once initializers are
+    /// disabled
+    pub fn initialized_version<SDK: StorageAPI>(&self, sdk: &SDK) -> u64 {
+        self.initialized.get(sdk)
+    }
+
+    /// Marks the contract as initialized to `version`, failing if it already reached it
+    ///
+    /// Version 1 plays the role of OpenZeppelin's `initializer`, later versions the role of
+    /// `reinitializer(version)` run by an upgrade.
+    pub fn initialize<SDK: SharedAPI>(
+        &self,
+        sdk: &mut SDK,
+        version: u64,
+    ) -> Result<(), InitializableError> {
+        if self.initialized_version(sdk) >= version {
+            return Err(InitializableError::InvalidInitialization);
+        }
+        self.initialized.set(sdk, version);
+        let event = Initialized { version };
+        if let Err(exit_code) = event.emit(sdk) {
+            sdk.native_exit(exit_code);
+        }
+        Ok(())
+    }
+
+    /// Locks every future initialization
+    ///
+    /// Called from the constructor of an implementation, so nobody can initialize and take over
+    /// the implementation contract itself.
+    pub fn disable_initializers<SDK: SharedAPI>(&self, sdk: &mut SDK) {
+        if self.initialized_version(sdk) != u64::MAX {
+            self.initialized.set(sdk, u64::MAX);
+            let event = Initialized { version: u64::MAX };
+            if let Err(exit_code) = event.emit(sdk) {
+                sdk.native_exit(exit_code);
+            }
+        }
+    }
+}
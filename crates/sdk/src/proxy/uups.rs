@@ -0,0 +1,76 @@
+use crate::{
+    proxy::{Erc1967, ProxyError, IMPLEMENTATION_SLOT},
+    Address, ContextReader, SharedAPI, B256,
+};
+use fluentbase_sdk_derive::derive_keccak256_id;
+
+/// Selector of `proxiableUUID()`
+pub const PROXIABLE_UUID_SELECTOR: [u8; 4] = derive_keccak256_id!("proxiableUUID()").to_be_bytes();
+
+/// Upgrade logic living in the implementation, the counterpart of OpenZeppelin's
+/// `UUPSUpgradeable`
+///
+/// `#[uups]` adds `proxiableUUID()` and `upgradeToAndCall(address,bytes)` to a router on top of
+/// these checks, calling the contract's `authorize_upgrade` method in between.
+pub struct UupsUpgradeable;
+
+impl UupsUpgradeable {
+    /// Whether the running code was reached through a proxy that delegates to it
+    fn is_active_proxy<SDK: SharedAPI>(sdk: &SDK) -> bool {
+        let context = sdk.context();
+        let this = context.contract_bytecode_address();
+        context.contract_address() != this && Erc1967::implementation(sdk) == this
+    }
+
+    /// Fails unless the call goes through an ERC-1967 proxy pointing at this implementation
+    pub fn check_proxy<SDK: SharedAPI>(sdk: &SDK) -> Result<(), ProxyError> {
+        if !Self::is_active_proxy(sdk) {
+            return Err(ProxyError::UUPSUnauthorizedCallContext);
+        }
+        Ok(())
+    }
+
+    /// Fails if the call was delegated, so a proxy pointing at another proxy cannot be upgraded
+    /// by mistake
+    pub fn check_not_delegated<SDK: SharedAPI>(sdk: &SDK) -> Result<(), ProxyError> {
+        let context = sdk.context();
+        if context.contract_address() != context.contract_bytecode_address() {
+            return Err(ProxyError::UUPSUnauthorizedCallContext);
+        }
+        Ok(())
+    }
+
+    /// ERC-1822 `proxiableUUID()`: the slot the implementation expects its address to be stored
+    /// at, only answered when called directly on the implementation
+    pub fn proxiable_uuid<SDK: SharedAPI>(sdk: &SDK) -> Result<B256, ProxyError> {
+        Self::check_not_delegated(sdk)?;
+        Ok(B256::from(IMPLEMENTATION_SLOT))
+    }
+
+    /// Upgrades the proxy to `new_implementation` after checking that it is a UUPS implementation
+    /// using the same slot, then delegates `data` to it unless empty
+    ///
+    /// The caller is expected to have authorized the upgrade already.
+    pub fn upgrade_to_and_call<SDK: SharedAPI>(
+        sdk: &mut SDK,
+        new_implementation: Address,
+        data: &[u8],
+    ) -> Result<(), ProxyError> {
+        Self::check_proxy(sdk)?;
+        let slot = match sdk
+            .static_call(new_implementation, &PROXIABLE_UUID_SELECTOR, None)
+            .ok()
+        {
+            Ok(output) if output.len() == 32 => B256::from_slice(&output),
+            _ => {
+                return Err(ProxyError::ERC1967InvalidImplementation {
+                    implementation: new_implementation,
+                })
+            }
+        };
+        if slot != B256::from(IMPLEMENTATION_SLOT) {
+            return Err(ProxyError::UUPSUnsupportedProxiableUUID { slot });
+        }
+        Erc1967::upgrade_to_and_call(sdk, new_implementation, data)
+    }
+}
@@ -0,0 +1,186 @@
+//! Read-only view over an SDK, used by `#[router]` to run `view` and `pure` methods.
+//!
+//! Every read is forwarded to the wrapped SDK. Every state mutation exits the frame with
+//! [`ExitCode::StateChangeDuringStaticCall`] instead of reaching the host, so a `view` method
+//! behaves the same whether the caller used `CALL` or `STATICCALL`.
+use crate::{
+    Address, Bytes, ContextReader, ExitCode, SharedAPI, StorageAPI, SyscallResult, B256, U256,
+};
+use alloc::borrow::Cow;
+
+/// SDK adapter exiting on storage writes, logs, account creation and value transfers
+///
+/// Calls without value are still allowed, but they are issued as `STATICCALL` so the callee is
+/// bound by the same restriction.
+pub struct ReadOnlySdk<'a, SDK> {
+    sdk: &'a mut SDK,
+}
+
+impl<'a, SDK> ReadOnlySdk<'a, SDK> {
+    pub fn new(sdk: &'a mut SDK) -> Self {
+        Self { sdk }
+    }
+}
+
+impl<SDK: SharedAPI> ReadOnlySdk<'_, SDK> {
+    /// Ends the frame the way the host ends a static call attempting a state change
+    fn state_change(&self) -> ! {
+        self.sdk.native_exit(ExitCode::StateChangeDuringStaticCall)
+    }
+}
+
+impl<SDK: SharedAPI> StorageAPI for ReadOnlySdk<'_, SDK> {
+    fn write_storage(&mut self, _slot: U256, _value: U256) -> SyscallResult<()> {
+        self.state_change()
+    }
+
+    fn storage(&self, slot: &U256) -> SyscallResult<U256> {
+        self.sdk.storage(slot)
+    }
+}
+
+impl<SDK: SharedAPI> SharedAPI for ReadOnlySdk<'_, SDK> {
+    fn context(&self) -> impl ContextReader {
+        self.sdk.context()
+    }
+
+    fn read(&self, target: &mut [u8], offset: u32) {
+        self.sdk.read(target, offset)
+    }
+
+    fn input_size(&self) -> u32 {
+        self.sdk.input_size()
+    }
+
+    fn read_context(&self, target: &mut [u8], offset: u32) {
+        self.sdk.read_context(target, offset)
+    }
+
+    fn charge_fuel(&self, fuel_consumed: u64) {
+        self.sdk.charge_fuel(fuel_consumed)
+    }
+
+    fn fuel(&self) -> u64 {
+        self.sdk.fuel()
+    }
+
+    fn write<T: AsRef<[u8]>>(&mut self, output: T) {
+        // The return data of the method is not state
+        self.sdk.write(output)
+    }
+
+    fn native_exit(&self, exit_code: ExitCode) -> ! {
+        self.sdk.native_exit(exit_code)
+    }
+
+    fn native_exec(
+        &self,
+        _code_hash: B256,
+        _input: Cow<'_, [u8]>,
+        _fuel_limit: Option<u64>,
+        _state: u32,
+    ) -> (u64, i64, i32) {
+        // A raw syscall may write anything, so it cannot be proven read-only
+        self.state_change()
+    }
+
+    fn return_data(&self) -> Bytes {
+        self.sdk.return_data()
+    }
+
+    fn write_transient_storage(&mut self, _slot: U256, _value: U256) -> SyscallResult<()> {
+        self.state_change()
+    }
+
+    fn transient_storage(&self, slot: &U256) -> SyscallResult<U256> {
+        self.sdk.transient_storage(slot)
+    }
+
+    fn emit_log<D: AsRef<[u8]>>(&mut self, _topics: &[B256], _data: D) -> SyscallResult<()> {
+        self.state_change()
+    }
+
+    fn self_balance(&self) -> SyscallResult<U256> {
+        self.sdk.self_balance()
+    }
+
+    fn balance(&self, address: &Address) -> SyscallResult<U256> {
+        self.sdk.balance(address)
+    }
+
+    fn block_hash(&self, block_number: u64) -> SyscallResult<B256> {
+        self.sdk.block_hash(block_number)
+    }
+
+    fn code_size(&self, address: &Address) -> SyscallResult<u32> {
+        self.sdk.code_size(address)
+    }
+
+    fn code_hash(&self, address: &Address) -> SyscallResult<B256> {
+        self.sdk.code_hash(address)
+    }
+
+    fn code_copy(
+        &self,
+        address: &Address,
+        code_offset: u64,
+        code_length: u64,
+    ) -> SyscallResult<Bytes> {
+        self.sdk.code_copy(address, code_offset, code_length)
+    }
+
+    fn create(
+        &mut self,
+        _salt: Option<U256>,
+        _value: &U256,
+        _init_code: &[u8],
+    ) -> SyscallResult<Bytes> {
+        self.state_change()
+    }
+
+    fn call(
+        &mut self,
+        address: Address,
+        value: U256,
+        input: &[u8],
+        fuel_limit: Option<u64>,
+    ) -> SyscallResult<Bytes> {
+        if !value.is_zero() {
+            self.state_change();
+        }
+        self.sdk.static_call(address, input, fuel_limit)
+    }
+
+    fn call_code(
+        &mut self,
+        _address: Address,
+        _value: U256,
+        _input: &[u8],
+        _fuel_limit: Option<u64>,
+    ) -> SyscallResult<Bytes> {
+        // Foreign code running against our own storage
+        self.state_change()
+    }
+
+    fn delegate_call(
+        &mut self,
+        _address: Address,
+        _input: &[u8],
+        _fuel_limit: Option<u64>,
+    ) -> SyscallResult<Bytes> {
+        self.state_change()
+    }
+
+    fn static_call(
+        &mut self,
+        address: Address,
+        input: &[u8],
+        fuel_limit: Option<u64>,
+    ) -> SyscallResult<Bytes> {
+        self.sdk.static_call(address, input, fuel_limit)
+    }
+
+    fn destroy_account(&mut self, _address: Address) -> SyscallResult<()> {
+        self.state_change()
+    }
+}
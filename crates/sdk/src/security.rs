@@ -0,0 +1,77 @@
+//! Reentrancy protection compatible with OpenZeppelin's `ReentrancyGuardTransient`.
+//!
+//! The lock is a flag in transient storage at [`REENTRANCY_GUARD_STORAGE_SLOT`], so it costs no
+//! persistent storage, is cleared at the end of the transaction even if a call reverts halfway,
+//! and never collides with the contract's own fields.
+use crate::{ComponentAbi, SharedAPI, SolidityError, U256};
+use fluentbase_sdk_derive::{erc7201_slot, SolidityError};
+
+/// `erc7201:openzeppelin.storage.ReentrancyGuard`
+pub const REENTRANCY_GUARD_STORAGE_SLOT: U256 =
+    erc7201_slot!("openzeppelin.storage.ReentrancyGuard");
+
+#[derive(Debug, Clone, PartialEq, Eq, SolidityError)]
+pub enum ReentrancyGuardError {
+    /// A guarded method was entered again before it returned
+    ReentrancyGuardReentrantCall,
+}
+
+/// Transient lock shared by every guarded method of a contract
+///
+/// `#[non_reentrant]` wraps a method in [`ReentrancyGuard::enter`] and [`ReentrancyGuard::exit`],
+/// and only runs [`ReentrancyGuard::check`] for `view` methods, which cannot take the lock.
+pub struct ReentrancyGuard;
+
+impl ComponentAbi for ReentrancyGuard {
+    const EVENTS: &'static [&'static str] = &[];
+    const ERRORS: &'static [&'static [&'static str]] = &[ReentrancyGuardError::ABI];
+}
+
+impl ReentrancyGuard {
+    /// Whether a guarded method is currently running
+    pub fn entered<SDK: SharedAPI>(sdk: &SDK) -> bool {
+        !sdk.transient_storage(&REENTRANCY_GUARD_STORAGE_SLOT)
+            .unwrap()
+            .is_zero()
+    }
+
+    /// Reverts with `ReentrancyGuardReentrantCall` if a guarded method is running
+    pub fn check<SDK: SharedAPI>(sdk: &mut SDK) {
+        if Self::entered(sdk) {
+            ReentrancyGuardError::ReentrancyGuardReentrantCall.revert(sdk);
+        }
+    }
+
+    /// Takes the lock, reverting with `ReentrancyGuardReentrantCall` if it is already taken
+    pub fn enter<SDK: SharedAPI>(sdk: &mut SDK) {
+        Self::check(sdk);
+        sdk.write_transient_storage(REENTRANCY_GUARD_STORAGE_SLOT, U256::from(1))
+            .unwrap();
+    }
+
+    /// Releases the lock
+    pub fn exit<SDK: SharedAPI>(sdk: &mut SDK) {
+        sdk.write_transient_storage(REENTRANCY_GUARD_STORAGE_SLOT, U256::ZERO)
+            .unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_guard_matches_openzeppelin() {
+        assert_eq!(
+            REENTRANCY_GUARD_STORAGE_SLOT,
+            U256::from_be_bytes(hex!(
+                "9b779b17422d0df92223018b32b4d1fa46e071723d6817e2486d003becc55f00"
+            ))
+        );
+        assert_eq!(
+            ReentrancyGuardError::ReentrancyGuardReentrantCall.selector(),
+            hex!("3ee5aeb5")
+        );
+    }
+}
@@ -0,0 +1,396 @@
+//! Storage layout rules and descriptions, used to place the fields of storage structs and to check
+//! that a new contract version keeps existing data in place.
+//!
+//! The storage derives and the build tooling both rely on this module, so the layout a contract
+//! uses, the `storage-layout.json` artifact exported for it and the upgrade checks always agree.
+use crate::{storage::StorageLayout, U256};
+use alloc::{borrow::Cow, format, vec::Vec};
+use core::{any::type_name, fmt};
+
+/// Auto-layout of the fields of a `#[derive(Storage)]` or `#[derive(Contract)]` struct
+///
+/// Fields are placed in declaration order with Solidity's packing: fields that fit in a slot
+/// (`StorageLayout::SLOTS == 0`) share it, right-aligned, as long as they fit, and every other
+/// field starts at the next free slot. Fields pinned with `#[slot(...)]` are not placed by the
+/// cursor and don't move it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StorageCursor {
+    /// Slot being filled, relative to the first slot of the struct
+    slot: usize,
+    /// Bytes of that slot already taken
+    used: usize,
+}
+
+impl StorageCursor {
+    /// Starts a layout whose first slot already has `offset` bytes taken
+    pub const fn new(offset: u8) -> Self {
+        Self {
+            slot: 0,
+            used: offset as usize,
+        }
+    }
+
+    /// Places a field of the given `StorageLayout::BYTES` and `StorageLayout::SLOTS`
+    ///
+    /// Returns the slot of the field, relative to the first slot of the struct, and its offset
+    /// counted as `StorageDescriptor::offset` does.
+    pub const fn place(&mut self, bytes: usize, slots: usize) -> (usize, u8) {
+        if slots == 0 {
+            if self.used + bytes > 32 {
+                self.slot += 1;
+                self.used = 0;
+            }
+            self.used += bytes;
+            (self.slot, (32 - self.used) as u8)
+        } else {
+            if self.used > 0 {
+                self.slot += 1;
+                self.used = 0;
+            }
+            let slot = self.slot;
+            self.slot += slots;
+            (slot, 0)
+        }
+    }
+
+    /// Slots taken by the fields placed so far, a partly filled slot included
+    pub const fn slots(&self) -> usize {
+        self.slot + (self.used > 0) as usize
+    }
+}
+
+/// Fields of a storage struct as listed by its generated `storage_layout()`
+pub type StorageFields = Vec<StorageField<'static>>;
+
+/// Placement of a single field of a `#[derive(Storage)]` or `#[derive(Contract)]` struct
+///
+/// The derives expose the fields of a struct, in declaration order, through its generated
+/// `storage_layout()` function. Build tooling reads them back from `storage-layout.json`
+/// artifacts, which is why the strings are borrowed rather than `'static`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageField<'a> {
+    /// Name of the field, `field.member` for the members of a nested struct
+    pub label: Cow<'a, str>,
+    /// Type of the field: the fully qualified Rust type, with aliases resolved, for layouts
+    /// generated by the derives, the Solidity type for layouts read from an artifact
+    pub ty: &'a str,
+    /// First slot the field occupies
+    pub slot: U256,
+    /// Byte offset of the field within its slot, counted as `StorageDescriptor::offset` does
+    pub offset: u8,
+    /// `StorageLayout::BYTES` of the field type
+    pub bytes: usize,
+    /// `StorageLayout::SLOTS` of the field type, zero for packed fields
+    pub slots: usize,
+    /// Whether the field is pinned with `#[slot(...)]` instead of being placed by the auto-layout
+    pub explicit: bool,
+}
+
+impl StorageField<'static> {
+    pub fn new<T: StorageLayout + 'static>(
+        label: &'static str,
+        slot: U256,
+        offset: u8,
+        explicit: bool,
+    ) -> Self {
+        Self {
+            label: Cow::Borrowed(label),
+            ty: type_name::<T>(),
+            slot,
+            offset,
+            bytes: T::BYTES,
+            slots: T::SLOTS,
+            explicit,
+        }
+    }
+
+    /// Appends a field of type `T` to `fields`
+    ///
+    /// A `#[derive(Storage)]` struct is appended member by member, labelled `field.member`, the
+    /// way `fluentbase-build` flattens struct members, so a member moving inside a nested struct
+    /// is caught like any other field. Members pinned with `#[slot(...)]` keep their slot.
+    pub fn append<T: StorageLayout + 'static>(
+        fields: &mut StorageFields,
+        label: &'static str,
+        slot: U256,
+        offset: u8,
+        explicit: bool,
+    ) {
+        let Some(members) = T::storage_fields() else {
+            fields.push(Self::new::<T>(label, slot, offset, explicit));
+            return;
+        };
+        fields.extend(members.into_iter().map(|member| Self {
+            label: Cow::Owned(format!("{label}.{}", member.label)),
+            slot: if member.explicit {
+                member.slot
+            } else {
+                slot.saturating_add(member.slot)
+            },
+            explicit: explicit || member.explicit,
+            ..member
+        }));
+    }
+}
+
+impl StorageField<'_> {
+    /// Whether the field occupies no storage of its own, like a component whose fields all have
+    /// explicit slots
+    pub fn is_empty(&self) -> bool {
+        self.bytes == 0 && self.slots == 0
+    }
+
+    /// Whether two fields share any byte of storage
+    pub fn overlaps(&self, other: &Self) -> bool {
+        if self.is_empty() || other.is_empty() {
+            return false;
+        }
+        if self.slots == 0 && other.slots == 0 {
+            let (start, end) = (self.offset as usize, self.offset as usize + self.bytes);
+            let (other_start, other_end) =
+                (other.offset as usize, other.offset as usize + other.bytes);
+            return self.slot == other.slot && start < other_end && other_start < end;
+        }
+        self.slot < other.end_slot() && other.slot < self.end_slot()
+    }
+
+    /// First slot after the field
+    fn end_slot(&self) -> U256 {
+        self.slot.saturating_add(U256::from(self.slots.max(1)))
+    }
+}
+
+/// First incompatibility found between the storage layouts of two contract versions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageLayoutError<'a> {
+    /// A field of the old version is missing from the new one
+    FieldRemoved { label: &'a str },
+    /// A field kept its name but changed its type
+    TypeChanged {
+        label: &'a str,
+        old_type: &'a str,
+        new_type: &'a str,
+    },
+    /// A field moved to another slot or offset, usually because a field was inserted before it
+    FieldMoved {
+        label: &'a str,
+        old_slot: U256,
+        old_offset: u8,
+        new_slot: U256,
+        new_offset: u8,
+    },
+    /// A new field overlaps the storage of a field of the old version
+    StorageCollision { label: &'a str, existing: &'a str },
+}
+
+impl fmt::Display for StorageLayoutError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FieldRemoved { label } => write!(f, "field `{label}` was removed"),
+            Self::TypeChanged {
+                label,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "field `{label}` changed type from `{old_type}` to `{new_type}`"
+            ),
+            Self::FieldMoved {
+                label,
+                old_slot,
+                old_offset,
+                new_slot,
+                new_offset,
+            } => write!(
+                f,
+                "field `{label}` moved from slot {old_slot} offset {old_offset} to slot {new_slot} \
+                 offset {new_offset}"
+            ),
+            Self::StorageCollision { label, existing } => write!(
+                f,
+                "new field `{label}` overlaps the storage of `{existing}`"
+            ),
+        }
+    }
+}
+
+/// Checks that a contract upgraded from the `old` layout to the `new` one still reads the data
+/// the old version wrote
+///
+/// Every old field must keep its name, type, slot and offset, and fields added by the new version
+/// must not overlap any of them. Appending fields and adding fields with explicit slots are the
+/// safe ways to grow a layout.
+///
+/// # Example
+/// ```rust,ignore
+/// check_storage_upgrade(
+///     &CounterV1::<TestingContextImpl>::storage_layout(),
+///     &CounterV2::<TestingContextImpl>::storage_layout(),
+/// )
+/// .unwrap();
+/// ```
+pub fn check_storage_upgrade<'a>(
+    old: &'a [StorageField<'_>],
+    new: &'a [StorageField<'_>],
+) -> Result<(), StorageLayoutError<'a>> {
+    for old_field in old {
+        let Some(new_field) = new.iter().find(|field| field.label == old_field.label) else {
+            return Err(StorageLayoutError::FieldRemoved {
+                label: &old_field.label,
+            });
+        };
+        if new_field.ty != old_field.ty {
+            return Err(StorageLayoutError::TypeChanged {
+                label: &old_field.label,
+                old_type: old_field.ty,
+                new_type: new_field.ty,
+            });
+        }
+        if (new_field.slot, new_field.offset) != (old_field.slot, old_field.offset) {
+            return Err(StorageLayoutError::FieldMoved {
+                label: &old_field.label,
+                old_slot: old_field.slot,
+                old_offset: old_field.offset,
+                new_slot: new_field.slot,
+                new_offset: new_field.offset,
+            });
+        }
+    }
+    for new_field in new
+        .iter()
+        .filter(|field| !old.iter().any(|old_field| old_field.label == field.label))
+    {
+        if let Some(existing) = old.iter().find(|old_field| old_field.overlaps(new_field)) {
+            return Err(StorageLayoutError::StorageCollision {
+                label: &new_field.label,
+                existing: &existing.label,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        storage::{StorageAddress, StorageBool, StorageMap, StorageU256, StorageU64},
+        Address,
+    };
+
+    fn field<T: StorageLayout + 'static>(
+        label: &'static str,
+        slot: u64,
+        offset: u8,
+    ) -> StorageField<'static> {
+        StorageField::new::<T>(label, U256::from(slot), offset, false)
+    }
+
+    #[test]
+    fn test_cursor_packs_like_solidity() {
+        let mut cursor = StorageCursor::new(0);
+        assert_eq!(cursor.place(StorageU64::BYTES, StorageU64::SLOTS), (0, 24));
+        assert_eq!(
+            cursor.place(StorageAddress::BYTES, StorageAddress::SLOTS),
+            (0, 4)
+        );
+        // 28 bytes are taken, a bool still fits but another u64 does not
+        assert_eq!(cursor.place(StorageBool::BYTES, StorageBool::SLOTS), (0, 3));
+        assert_eq!(cursor.place(StorageU64::BYTES, StorageU64::SLOTS), (1, 24));
+        assert_eq!(cursor.slots(), 2);
+        // Full-slot fields never share a slot
+        assert_eq!(cursor.place(StorageU256::BYTES, StorageU256::SLOTS), (2, 0));
+        assert_eq!(
+            cursor.place(StorageBool::BYTES, StorageBool::SLOTS),
+            (3, 31)
+        );
+        assert_eq!(cursor.slots(), 4);
+
+        // A struct placed mid-slot continues after the bytes already taken
+        let mut cursor = StorageCursor::new(24);
+        assert_eq!(cursor.place(StorageU64::BYTES, StorageU64::SLOTS), (0, 0));
+        assert_eq!(
+            cursor.place(StorageBool::BYTES, StorageBool::SLOTS),
+            (1, 31)
+        );
+    }
+
+    #[test]
+    fn test_appending_fields_is_compatible() {
+        let old = [
+            field::<StorageU256>("total_supply", 0, 0),
+            field::<StorageMap<Address, StorageU256>>("balances", 1, 0),
+        ];
+        let new = [
+            old[0].clone(),
+            old[1].clone(),
+            field::<StorageBool>("paused", 2, 31),
+            field::<StorageAddress>("owner", 2, 11),
+        ];
+        assert_eq!(check_storage_upgrade(&old, &new), Ok(()));
+    }
+
+    #[test]
+    fn test_incompatible_layouts_are_rejected() {
+        let old = [
+            field::<StorageU256>("total_supply", 0, 0),
+            field::<StorageU64>("nonce", 1, 24),
+        ];
+
+        // A field inserted in front shifts the old ones
+        let new = [
+            field::<StorageAddress>("owner", 0, 12),
+            field::<StorageU256>("total_supply", 1, 0),
+            field::<StorageU64>("nonce", 2, 24),
+        ];
+        assert!(matches!(
+            check_storage_upgrade(&old, &new),
+            Err(StorageLayoutError::FieldMoved {
+                label: "total_supply",
+                ..
+            })
+        ));
+
+        let new = [old[0].clone(), field::<StorageU256>("nonce", 1, 0)];
+        assert!(matches!(
+            check_storage_upgrade(&old, &new),
+            Err(StorageLayoutError::TypeChanged { label: "nonce", .. })
+        ));
+
+        let new = [old[1].clone()];
+        assert_eq!(
+            check_storage_upgrade(&old, &new),
+            Err(StorageLayoutError::FieldRemoved {
+                label: "total_supply"
+            })
+        );
+
+        // A renamed field takes over the bytes of the one it replaced
+        let new = [old[0].clone(), field::<StorageU64>("counter", 1, 24)];
+        assert_eq!(
+            check_storage_upgrade(&old, &new),
+            Err(StorageLayoutError::FieldRemoved { label: "nonce" })
+        );
+        let new = [
+            old[0].clone(),
+            old[1].clone(),
+            field::<StorageU64>("counter", 1, 24),
+        ];
+        assert_eq!(
+            check_storage_upgrade(&old, &new),
+            Err(StorageLayoutError::StorageCollision {
+                label: "counter",
+                existing: "nonce"
+            })
+        );
+    }
+
+    #[test]
+    fn test_packed_fields_overlap_by_bytes() {
+        let flag = field::<StorageBool>("flag", 3, 31);
+        assert!(!flag.overlaps(&field::<StorageU64>("nonce", 3, 23)));
+        assert!(flag.overlaps(&field::<StorageU64>("nonce", 3, 24)));
+        assert!(flag.overlaps(&field::<StorageMap<Address, StorageBool>>("map", 3, 0)));
+        assert!(!flag.overlaps(&field::<StorageU256>("value", 4, 0)));
+    }
+}
@@ -4,6 +4,7 @@ use crate::{StorageAPI, B256, U256};
 
 mod array;
 mod bytes;
+mod layout;
 mod map;
 mod primitive;
 mod vec;
@@ -11,6 +12,7 @@ mod vec;
 pub use array::*;
 pub use bytes::*;
 use fluentbase_types::ExitCode;
+pub use layout::*;
 pub use map::*;
 pub use primitive::*;
 pub use vec::*;
@@ -73,6 +75,13 @@ pub trait StorageLayout: Sized {
     /// This is the entry point for all storage operations.
     /// The descriptor tells WHERE, the accessor provides HOW.
     fn access(descriptor: Self::Descriptor) -> Self::Accessor;
+
+    /// Fields of a `#[derive(Storage)]` struct placed at slot 0, `None` for any other type.
+    ///
+    /// Lets the `storage_layout()` of an enclosing struct list nested structs member by member.
+    fn storage_fields() -> Option<StorageFields> {
+        None
+    }
 }
 
 /// Uniform interface for creating storage descriptors at specific locations.
@@ -8,6 +8,7 @@ mod sdk;
 mod storage;
 
 pub use context::*;
+pub use evm::{ComponentAbi, SolidityError};
 pub use preimage::*;
 pub use rwasm::*;
 pub use sdk::*;
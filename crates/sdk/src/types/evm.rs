@@ -1,4 +1,48 @@
-use crate::byteorder::{BigEndian, ByteOrder};
+use crate::{
+    byteorder::{BigEndian, ByteOrder},
+    Bytes, ExitCode, SharedAPI,
+};
+
+/// A Solidity custom error, usually derived for an enum with `#[derive(SolidityError)]`
+///
+/// Each variant is one error; its revert payload is the 4-byte selector of the error signature
+/// followed by the ABI-encoded variant fields.
+pub trait SolidityError: Sized {
+    /// Canonical signatures of every error, in variant order
+    const SIGNATURES: &'static [&'static str];
+
+    /// ABI entries of every error, as JSON, in variant order
+    const ABI: &'static [&'static str];
+
+    /// Selector of the error this value is
+    fn selector(&self) -> [u8; 4];
+
+    /// Encodes the revert payload: the selector followed by the ABI-encoded fields
+    fn encode(&self) -> Bytes;
+
+    /// Decodes a revert payload, or `None` if it is not one of these errors
+    fn decode(data: &[u8]) -> Option<Self>;
+
+    /// Reverts the current call with this error, like Solidity's `revert`
+    fn revert<SDK: SharedAPI>(&self, sdk: &mut SDK) -> ! {
+        sdk.write(self.encode());
+        sdk.native_exit(ExitCode::Panic)
+    }
+}
+
+/// An SDK component whose events and custom errors belong to the ABI of the contracts using it
+///
+/// `fluentbase-build` publishes these entries for a contract that embeds the component, guards a
+/// method with one of its modifiers or returns one of its errors. They are the `ABI` constants of
+/// `#[derive(Event)]` and `#[derive(SolidityError)]`, so the artifacts match what the component
+/// emits.
+pub trait ComponentAbi {
+    /// ABI entries of the events the component emits, as JSON
+    const EVENTS: &'static [&'static str];
+
+    /// ABI entries of the custom errors the component reverts with, one slice per error enum
+    const ERRORS: &'static [&'static [&'static str]];
+}
 
 #[inline(always)]
 pub fn write_evm_exit_message<R, F: FnOnce(&[u8]) -> R>(exit_code: u32, write_func: F) -> R {
@@ -5,25 +5,35 @@
     clippy::needless_borrows_for_generic_args
 )]
 
-use crate::TestingContextImpl;
+use crate::{
+    fork::ForkDB,
+    gas_report::{self, ContractAbi, GasRecord},
+    TestingContextImpl,
+};
 use core::{borrow::Borrow, mem::take, str::from_utf8};
 use fluentbase_evm::EthereumMetadata;
-use fluentbase_revm::{RwasmBuilder, RwasmContext, RwasmHaltReason};
+use fluentbase_revm::{
+    debug_events::{collect_debug_events, ContractDebugEvent},
+    RwasmBuilder, RwasmContext, RwasmHaltReason,
+};
+#[cfg(feature = "profiler")]
+use fluentbase_runtime::profiler::{profile_fuel, FuelProfile};
 use fluentbase_runtime::{default_runtime_executor, RuntimeContext, RuntimeExecutor};
 use fluentbase_sdk::{
-    bytes::BytesMut, calc_create_address, compile_wasm_to_rwasm, Address, BytecodeOrHash, Bytes,
-    ContextReader, ExitCode, GenesisContract, RwasmCompilationResult, SharedAPI,
-    SharedContextInputV1, PRECOMPILE_EVM_RUNTIME, STATE_MAIN, U256, WASM_SIG,
+    bytes::BytesMut, calc_create_address, compile_wasm_to_rwasm, ActiveRuntimeForks, Address,
+    BytecodeOrHash, Bytes, ContextReader, ExitCode, GenesisContract, RwasmCompilationResult,
+    SharedAPI, SharedContextInputV1, PRECOMPILE_EVM_RUNTIME, STATE_MAIN, U256, WASM_SIG,
 };
+use hashbrown::HashMap;
 use revm::{
     context::{
         result::{ExecutionResult, ExecutionResult::Success, Output},
         transaction::SignedAuthorization,
         BlockEnv, CfgEnv, TransactTo, TxEnv,
     },
-    database::{DbAccount, InMemoryDB},
+    database::{CacheDB, DbAccount},
     handler::MainnetContext,
-    primitives::{hardfork::PRAGUE, keccak256, AddressMap},
+    primitives::{hardfork::PRAGUE, keccak256, AddressMap, B256},
     state::{Account, AccountInfo, Bytecode},
     DatabaseCommit, ExecuteCommitEvm, MainBuilder,
 };
@@ -32,9 +42,14 @@ use rwasm::RwasmModule;
 #[allow(dead_code)]
 pub struct EvmTestingContext {
     pub sdk: TestingContextImpl,
-    pub db: InMemoryDB,
+    /// Empty unless the context was forked with [`Self::with_fork`].
+    pub db: CacheDB<ForkDB>,
     pub cfg: CfgEnv,
     pub disabled_rwasm: bool,
+    /// ABIs used to label calls in the gas report, see [`crate::gas_report`].
+    pub gas_report_contracts: HashMap<Address, ContractAbi>,
+    /// ABIs by init code hash, applied to every contract deployed with that code.
+    pub gas_report_artifacts: HashMap<B256, ContractAbi>,
 }
 
 impl Default for EvmTestingContext {
@@ -48,20 +63,27 @@ impl EvmTestingContext {
     pub fn new() -> Self {
         Self {
             sdk: TestingContextImpl::default(),
-            db: InMemoryDB::default(),
+            db: CacheDB::default(),
             cfg: CfgEnv::default(),
             disabled_rwasm: false,
+            gas_report_contracts: HashMap::default(),
+            gas_report_artifacts: HashMap::default(),
         }
     }
 
     pub fn with_block_number(self, number: u64) -> Self {
         let sdk = self.sdk.with_block_number(number);
-        Self {
-            sdk,
-            db: self.db,
-            cfg: self.cfg,
-            disabled_rwasm: self.disabled_rwasm,
-        }
+        Self { sdk, ..self }
+    }
+
+    /// Runs on top of the state of a live chain, see [`crate::fork`].
+    ///
+    /// Transactions execute in the block following the forked one, on the forked chain id.
+    pub fn with_fork(self, fork: ForkDB) -> Self {
+        let mut ctx = self.with_block_number(fork.block_number() + 1);
+        ctx.cfg.chain_id = fork.chain_id();
+        ctx.db = CacheDB::new(fork);
+        ctx
     }
 
     // Add smart contracts to the genesis
@@ -77,12 +99,7 @@ impl EvmTestingContext {
             };
             db.insert_account_info(contract.address, info);
         }
-        Self {
-            sdk: self.sdk,
-            db,
-            cfg: self.cfg,
-            disabled_rwasm: self.disabled_rwasm,
-        }
+        Self { db, ..self }
     }
 
     pub fn commit_sdk_to_db(&mut self) {
@@ -204,6 +221,29 @@ impl EvmTestingContext {
         info
     }
 
+    /// Reports calls to `address` as `name`, decoding selectors with its JSON ABI.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `abi_json` is not a valid Solidity JSON ABI.
+    pub fn register_gas_report_contract(&mut self, address: Address, name: &str, abi_json: &str) {
+        let abi = ContractAbi::from_json(name, abi_json)
+            .unwrap_or_else(|err| panic!("invalid ABI of `{name}`: {err}"));
+        self.gas_report_contracts.insert(address, abi);
+    }
+
+    /// Reports every contract deployed with `init_code` as `name`, decoding selectors with its JSON
+    /// ABI, such as the `abi.json` generated next to the contract's WASM.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `abi_json` is not a valid Solidity JSON ABI.
+    pub fn register_gas_report_artifact(&mut self, name: &str, init_code: &[u8], abi_json: &str) {
+        let abi = ContractAbi::from_json(name, abi_json)
+            .unwrap_or_else(|err| panic!("invalid ABI of `{name}`: {err}"));
+        self.gas_report_artifacts.insert(keccak256(init_code), abi);
+    }
+
     pub fn get_balance(&mut self, address: Address) -> U256 {
         let account = self.db.load_account(address).unwrap();
         account.info.balance
@@ -453,7 +493,7 @@ impl<'a> TxBuilder<'a> {
         self.tx.nonce = self.ctx.nonce(self.tx.caller);
         let db = take(&mut self.ctx.db);
         if self.ctx.disabled_rwasm {
-            let mut context: MainnetContext<InMemoryDB> = MainnetContext::new(db, PRAGUE);
+            let mut context: MainnetContext<CacheDB<ForkDB>> = MainnetContext::new(db, PRAGUE);
             context.cfg = self.ctx.cfg.clone();
             context.cfg.legacy_bytecode_enabled = true;
             context.block = self.block.clone();
@@ -464,7 +504,7 @@ impl<'a> TxBuilder<'a> {
             self.ctx.db = take(new_db);
             result
         } else {
-            let mut context: RwasmContext<InMemoryDB> = RwasmContext::new(db, PRAGUE);
+            let mut context: RwasmContext<CacheDB<ForkDB>> = RwasmContext::new(db, PRAGUE);
             context.cfg = self.ctx.cfg.clone();
             context.cfg.legacy_bytecode_enabled = false;
             context.block = self.block.clone();
@@ -493,12 +533,47 @@ impl<'a> TxBuilder<'a> {
             } => *address,
             _ => None,
         };
+        if let Some(address) = created_address {
+            if let Some(abi) = self.ctx.gas_report_artifacts.get(&keccak256(&self.tx.data)) {
+                self.ctx.gas_report_contracts.insert(address, abi.clone());
+            }
+        }
+        gas_report::record(|| {
+            let address = match self.tx.kind {
+                TransactTo::Create => created_address.or(expected_created_address),
+                TransactTo::Call(callee) => Some(callee),
+            };
+            GasRecord::new(
+                &self.ctx.gas_report_contracts,
+                address,
+                &self.tx.data,
+                self.tx.kind.is_create(),
+                result.tx_gas_used(),
+            )
+        });
         TxExecution {
             result,
             expected_created_address,
             created_address,
         }
     }
+
+    /// Executes the transaction with the fuel profiler enabled.
+    ///
+    /// Render the profile with [`FuelProfile::to_folded`] for flamegraphs or
+    /// [`FuelProfile::to_pprof`] for `go tool pprof`.
+    #[cfg(feature = "profiler")]
+    pub fn execute_profiled(self) -> (TxExecution, FuelProfile) {
+        profile_fuel(|| self.execute())
+    }
+
+    /// Executes the transaction and returns the `_debug_event` records emitted by its frames.
+    ///
+    /// The syscall is only scheduled on the local chain, so set `cfg.chain_id` to
+    /// [`fluentbase_sdk::FLUENT_LOCALNET_CHAIN_ID`] first, it traps on the default chain id.
+    pub fn execute_with_debug_events(self) -> (TxExecution, Vec<ContractDebugEvent>) {
+        collect_debug_events(|| self.execute())
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -831,7 +906,8 @@ pub fn run_with_default_context(wasm_binary: Vec<u8>, input_data: &[u8]) -> (Vec
     let ctx = RuntimeContext::default()
         .with_fuel_limit(100_000_000_000)
         .with_state(STATE_MAIN)
-        .with_input(context_input);
+        .with_input(context_input)
+        .with_forks(ActiveRuntimeForks::ALL);
     let result = default_runtime_executor().execute(bytecode_or_hash, ctx);
     println!(
         "exit_code: {} ({})",
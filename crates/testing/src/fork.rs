@@ -0,0 +1,707 @@
+//! Forked state for [`crate::EvmTestingContext`], to reproduce mainnet incidents in tests.
+//!
+//! A [`ForkDB`] answers database queries with the state of a live chain at a fixed block.
+//! Accounts, code and storage are fetched on first use from a JSON-RPC endpoint
+//! ([`ForkDB::rpc`]), from a reth datadir opened read-only ([`ForkDB::reth_datadir`], behind the
+//! `reth` feature), or from any other [`DatabaseRef`] ([`ForkDB::new`]). With
+//! [`ForkDB::with_disk_cache`] everything fetched is also written to disk, so reruns of a test
+//! don't hit the source again and keep working offline.
+//!
+//! Code is kept in its raw encoding and decoded with [`Bytecode::new_raw`], so EVM contracts and
+//! other ownable accounts keep their delegated runtime and run exactly as they do on chain. Over
+//! JSON-RPC it is read with Fluent's `eth_getRawCode`, `eth_getCode` returns the unwrapped view.
+//!
+//! ```rust,ignore
+//! let fork = ForkDB::rpc("http://localhost:8545", Some(1_234_567))?
+//!     .with_disk_cache("target/fork-cache")?;
+//! let mut ctx = EvmTestingContext::default().with_fork(fork);
+//! TxBuilder::call(&mut ctx, token).caller(holder).input(calldata).execute().expect_ok();
+//! ```
+use fluentbase_sdk::{keccak256, Address, Bytes, B256, KECCAK_EMPTY, U256};
+use revm::{
+    database::EmptyDB,
+    database_interface::DBErrorMarker,
+    state::{AccountInfo, Bytecode},
+    DatabaseRef,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    time::Duration,
+};
+
+/// Timeout of a single JSON-RPC request of [`ForkDB::rpc`].
+pub const FORK_RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Failure to load forked state from its source or the disk cache.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForkError(String);
+
+impl ForkError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+}
+
+impl fmt::Display for ForkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ForkError {}
+
+impl DBErrorMarker for ForkError {}
+
+type ForkSource = dyn DatabaseRef<Error = ForkError> + Send + Sync;
+
+/// State of a chain at a fixed block, loaded lazily, see the [module docs](self).
+///
+/// The default value has no source and behaves like an empty database, which is what
+/// [`crate::EvmTestingContext`] starts from.
+#[derive(Clone, Default)]
+pub struct ForkDB {
+    source: Option<Arc<ForkSource>>,
+    chain_id: u64,
+    block_number: u64,
+    disk_cache: Option<Arc<DiskCache>>,
+}
+
+impl fmt::Debug for ForkDB {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ForkDB")
+            .field("forked", &self.source.is_some())
+            .field("chain_id", &self.chain_id)
+            .field("block_number", &self.block_number)
+            .field(
+                "disk_cache",
+                &self.disk_cache.as_ref().map(|cache| &cache.path),
+            )
+            .finish()
+    }
+}
+
+impl ForkDB {
+    /// Forks the state served by `db`, which must be the state of `chain_id` at `block_number`.
+    pub fn new<DB>(db: DB, chain_id: u64, block_number: u64) -> Self
+    where
+        DB: DatabaseRef + Send + 'static,
+        DB::Error: fmt::Display,
+    {
+        Self::from_source(Arc::new(MapErr(Mutex::new(db))), chain_id, block_number)
+    }
+
+    fn from_source(source: Arc<ForkSource>, chain_id: u64, block_number: u64) -> Self {
+        Self {
+            source: Some(source),
+            chain_id,
+            block_number,
+            disk_cache: None,
+        }
+    }
+
+    /// Forks the chain behind a JSON-RPC endpoint at `block_number`, the latest block if `None`.
+    ///
+    /// Requests are blocking, so the returned database must not be used on an async runtime.
+    pub fn rpc(url: &str, block_number: Option<u64>) -> Result<Self, ForkError> {
+        let source = RpcSource::new(url)?;
+        let chain_id: U256 = source.request("eth_chainId", json!([]))?;
+        let block_number = match block_number {
+            Some(block_number) => block_number,
+            None => source
+                .request::<U256>("eth_blockNumber", json!([]))?
+                .saturating_to(),
+        };
+        let source = source.at_block(block_number);
+        Ok(Self::from_source(
+            Arc::new(source),
+            chain_id.saturating_to(),
+            block_number,
+        ))
+    }
+
+    /// Forks a reth datadir, opened read-only, at `block_number`, the latest block if `None`.
+    ///
+    /// The node may keep running while the test reads its database.
+    #[cfg(feature = "reth")]
+    pub fn reth_datadir(
+        datadir: impl AsRef<Path>,
+        chain_spec: Arc<reth_chainspec::ChainSpec>,
+        block_number: Option<u64>,
+    ) -> Result<Self, ForkError> {
+        use fluentbase_node::evm::FluentNode;
+        use reth_chainspec::EthChainSpec;
+        use reth_provider::{BlockNumReader, StateProviderFactory};
+        use reth_revm::database::StateProviderDatabase;
+
+        let chain_id = chain_spec.chain().id();
+        let factory = FluentNode::provider_factory_builder()
+            .open_read_only(chain_spec, datadir.as_ref())
+            .map_err(|err| ForkError::new(format!("open {}: {err}", datadir.as_ref().display())))?;
+        let block_number = match block_number {
+            Some(block_number) => block_number,
+            None => factory
+                .best_block_number()
+                .map_err(|err| ForkError::new(err.to_string()))?,
+        };
+        let state = factory
+            .history_by_block_number(block_number)
+            .map_err(|err| ForkError::new(format!("state at block {block_number}: {err}")))?;
+        Ok(Self::new(
+            StateProviderDatabase::new(state),
+            chain_id,
+            block_number,
+        ))
+    }
+
+    /// Keeps fetched state in `<dir>/<chain_id>-<block_number>.json`, reusing what's already
+    /// there.
+    ///
+    /// The file is written when the last clone of the database is dropped, or by
+    /// [`Self::flush_disk_cache`]. Test processes sharing a cache overwrite each other's
+    /// additions, which only costs refetching them.
+    pub fn with_disk_cache(mut self, dir: impl AsRef<Path>) -> Result<Self, ForkError> {
+        let path = dir
+            .as_ref()
+            .join(format!("{}-{}.json", self.chain_id, self.block_number));
+        self.disk_cache = Some(Arc::new(DiskCache::open(path)?));
+        Ok(self)
+    }
+
+    /// Writes fetched state to the disk cache, if any.
+    pub fn flush_disk_cache(&self) -> Result<(), ForkError> {
+        match &self.disk_cache {
+            Some(cache) => cache.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether the database has a source, i.e. isn't empty.
+    pub fn is_forked(&self) -> bool {
+        self.source.is_some()
+    }
+
+    /// Chain id of the forked chain, `0` when not forked.
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Block whose state is forked, `0` when not forked.
+    pub fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    /// Returns the value cached on disk, or fetches and caches it.
+    fn cached<T: Clone>(
+        &self,
+        get: impl FnOnce(&CachedState) -> Option<T>,
+        fetch: impl FnOnce() -> Result<T, ForkError>,
+        insert: impl FnOnce(&mut CachedState, T),
+    ) -> Result<T, ForkError> {
+        let Some(cache) = &self.disk_cache else {
+            return fetch();
+        };
+        if let Some(value) = get(&cache.state()) {
+            return Ok(value);
+        }
+        let value = fetch()?;
+        let mut state = cache.state();
+        insert(&mut state, value.clone());
+        state.dirty = true;
+        Ok(value)
+    }
+}
+
+impl DatabaseRef for ForkDB {
+    type Error = ForkError;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, ForkError> {
+        let Some(source) = &self.source else {
+            return Ok(None);
+        };
+        let account = self.cached(
+            |state| state.accounts.get(&address).cloned(),
+            || {
+                source
+                    .basic_ref(address)?
+                    .map(|info| CachedAccount::fetch(source.as_ref(), info))
+                    .transpose()
+            },
+            |state, account| {
+                state.accounts.insert(address, account);
+            },
+        )?;
+        Ok(account.map(CachedAccount::into_info))
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, ForkError> {
+        // Accounts are always loaded with their code, so this only serves code hashes that a
+        // test put into the database without code.
+        match &self.source {
+            Some(source) => source.code_by_hash_ref(code_hash),
+            None => Ok(Bytecode::default()),
+        }
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, ForkError> {
+        let Some(source) = &self.source else {
+            return Ok(U256::ZERO);
+        };
+        self.cached(
+            |state| state.storage.get(&address)?.get(&index).copied(),
+            || source.storage_ref(address, index),
+            |state, value| {
+                state
+                    .storage
+                    .entry(address)
+                    .or_default()
+                    .insert(index, value);
+            },
+        )
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, ForkError> {
+        let Some(source) = &self.source else {
+            return EmptyDB::default()
+                .block_hash_ref(number)
+                .map_err(|err| match err {});
+        };
+        self.cached(
+            |state| state.block_hashes.get(&number).copied(),
+            || source.block_hash_ref(number),
+            |state, hash| {
+                state.block_hashes.insert(number, hash);
+            },
+        )
+    }
+}
+
+/// Turns the errors of a [`DatabaseRef`] into [`ForkError`]s.
+struct MapErr<DB>(Mutex<DB>);
+
+impl<DB> MapErr<DB> {
+    fn db(&self) -> MutexGuard<'_, DB> {
+        self.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl<DB> DatabaseRef for MapErr<DB>
+where
+    DB: DatabaseRef,
+    DB::Error: fmt::Display,
+{
+    type Error = ForkError;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, ForkError> {
+        self.db()
+            .basic_ref(address)
+            .map_err(|err| ForkError::new(format!("account {address}: {err}")))
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, ForkError> {
+        self.db()
+            .code_by_hash_ref(code_hash)
+            .map_err(|err| ForkError::new(format!("code {code_hash}: {err}")))
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, ForkError> {
+        self.db()
+            .storage_ref(address, index)
+            .map_err(|err| ForkError::new(format!("storage {address}[{index}]: {err}")))
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, ForkError> {
+        self.db()
+            .block_hash_ref(number)
+            .map_err(|err| ForkError::new(format!("block hash {number}: {err}")))
+    }
+}
+
+/// Blocking JSON-RPC client reading state at a fixed block.
+struct RpcSource {
+    client: reqwest::blocking::Client,
+    url: String,
+    block: String,
+    next_id: AtomicU64,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct RpcBlock {
+    hash: B256,
+}
+
+impl RpcSource {
+    fn new(url: &str) -> Result<Self, ForkError> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(FORK_RPC_TIMEOUT)
+            .build()
+            .map_err(|err| ForkError::new(format!("failed to build HTTP client: {err}")))?;
+        Ok(Self {
+            client,
+            url: url.into(),
+            block: "latest".into(),
+            next_id: AtomicU64::new(0),
+        })
+    }
+
+    fn at_block(self, block_number: u64) -> Self {
+        Self {
+            block: format!("{block_number:#x}"),
+            ..self
+        }
+    }
+
+    fn request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T, ForkError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let request = json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params});
+        let response = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(request.to_string())
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.bytes())
+            .map_err(|err| ForkError::new(format!("{method}: {err}")))?;
+        let response: RpcResponse<T> = serde_json::from_slice(&response)
+            .map_err(|err| ForkError::new(format!("{method}: malformed response: {err}")))?;
+        match response {
+            RpcResponse {
+                error: Some(RpcError { code, message }),
+                ..
+            } => Err(ForkError::new(format!("{method}: error {code}: {message}"))),
+            RpcResponse {
+                result: Some(result),
+                ..
+            } => Ok(result),
+            _ => Err(ForkError::new(format!("{method}: empty result"))),
+        }
+    }
+}
+
+impl DatabaseRef for RpcSource {
+    type Error = ForkError;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, ForkError> {
+        let params = json!([address, self.block]);
+        let balance: U256 = self.request("eth_getBalance", params.clone())?;
+        let nonce: U256 = self.request("eth_getTransactionCount", params.clone())?;
+        // `eth_getCode` unwraps runtime-owned accounts into their EVM bytecode, the raw view keeps
+        // the ownable account and hashes the same way the state does.
+        let code: Bytes = self.request("eth_getRawCode", params)?;
+        if balance.is_zero() && nonce.is_zero() && code.is_empty() {
+            return Ok(None);
+        }
+        let code_hash = if code.is_empty() {
+            KECCAK_EMPTY
+        } else {
+            keccak256(&code)
+        };
+        Ok(Some(AccountInfo {
+            balance,
+            nonce: nonce.saturating_to(),
+            code_hash,
+            account_id: None,
+            code: Some(Bytecode::new_raw(code)),
+        }))
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, ForkError> {
+        Err(ForkError::new(format!(
+            "code {code_hash} can't be fetched by hash over JSON-RPC"
+        )))
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, ForkError> {
+        let value: B256 = self.request("eth_getStorageAt", json!([address, index, self.block]))?;
+        Ok(value.into())
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, ForkError> {
+        let block: RpcBlock = self.request(
+            "eth_getBlockByNumber",
+            json!([format!("{number:#x}"), false]),
+        )?;
+        Ok(block.hash)
+    }
+}
+
+/// An account as stored in the disk cache, with its code in raw encoding.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct CachedAccount {
+    balance: U256,
+    nonce: u64,
+    code_hash: B256,
+    code: Bytes,
+}
+
+impl CachedAccount {
+    fn fetch(source: &ForkSource, info: AccountInfo) -> Result<Self, ForkError> {
+        let code = match info.code {
+            Some(code) => code.original_bytes(),
+            None if info.code_hash == KECCAK_EMPTY => Bytes::new(),
+            None => source.code_by_hash_ref(info.code_hash)?.original_bytes(),
+        };
+        Ok(Self {
+            balance: info.balance,
+            nonce: info.nonce,
+            code_hash: info.code_hash,
+            code,
+        })
+    }
+
+    fn into_info(self) -> AccountInfo {
+        AccountInfo {
+            balance: self.balance,
+            nonce: self.nonce,
+            code_hash: self.code_hash,
+            account_id: None,
+            code: Some(Bytecode::new_raw(self.code)),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CachedState {
+    accounts: BTreeMap<Address, Option<CachedAccount>>,
+    storage: BTreeMap<Address, BTreeMap<U256, U256>>,
+    block_hashes: BTreeMap<u64, B256>,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+struct DiskCache {
+    path: PathBuf,
+    state: Mutex<CachedState>,
+}
+
+impl DiskCache {
+    fn open(path: PathBuf) -> Result<Self, ForkError> {
+        let state = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|err| {
+                ForkError::new(format!("corrupted fork cache {}: {err}", path.display()))
+            })?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => CachedState::default(),
+            Err(err) => {
+                return Err(ForkError::new(format!("read {}: {err}", path.display())));
+            }
+        };
+        Ok(Self {
+            path,
+            state: Mutex::new(state),
+        })
+    }
+
+    fn state(&self) -> MutexGuard<'_, CachedState> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn flush(&self) -> Result<(), ForkError> {
+        let mut state = self.state();
+        if !state.dirty {
+            return Ok(());
+        }
+        let write = || -> std::io::Result<()> {
+            if let Some(dir) = self.path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            // Written aside and renamed, so concurrent readers never see a partial file.
+            let tmp = self
+                .path
+                .with_extension(format!("json.{}", std::process::id()));
+            fs::write(&tmp, serde_json::to_vec(&*state)?)?;
+            fs::rename(&tmp, &self.path)
+        };
+        write().map_err(|err| ForkError::new(format!("write {}: {err}", self.path.display())))?;
+        state.dirty = false;
+        Ok(())
+    }
+}
+
+impl Drop for DiskCache {
+    fn drop(&mut self) {
+        if let Err(err) = self.flush() {
+            eprintln!("fork cache: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::{bytecode::ownable_account::OwnableAccountBytecode, database::CacheDB, Database};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::{TcpListener, TcpStream},
+        sync::atomic::AtomicUsize,
+    };
+
+    const ACCOUNT: Address = Address::repeat_byte(0x11);
+    /// Answers with an ownable account delegated to [`RUNTIME`].
+    const WRAPPED: Address = Address::repeat_byte(0x22);
+    const RUNTIME: Address = Address::repeat_byte(0x33);
+
+    fn wrapped_code() -> Bytes {
+        let ownable = OwnableAccountBytecode::new(RUNTIME, Bytes::from_static(b"meta"));
+        Bytecode::OwnableAccount(ownable.into()).original_bytes()
+    }
+
+    fn answer(method: &str, params: &serde_json::Value) -> serde_json::Value {
+        match method {
+            "eth_chainId" => json!("0x5202"),
+            "eth_blockNumber" => json!("0x10"),
+            "eth_getBalance" => json!("0x64"),
+            "eth_getTransactionCount" => json!("0x2"),
+            "eth_getRawCode" if params[0] == json!(WRAPPED) => json!(wrapped_code()),
+            "eth_getRawCode" => json!("0x"),
+            "eth_getStorageAt" => json!(B256::from(U256::from(42))),
+            "eth_getBlockByNumber" => json!({"hash": B256::repeat_byte(0xbb)}),
+            _ => panic!("unexpected method {method}"),
+        }
+    }
+
+    fn read_body(stream: &TcpStream) -> Vec<u8> {
+        let mut reader = BufReader::new(stream);
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        body
+    }
+
+    /// Serves [`answer`] over HTTP and counts the requests it receives.
+    fn mock_rpc() -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let request: serde_json::Value =
+                    serde_json::from_slice(&read_body(&stream)).unwrap();
+                counter.fetch_add(1, Ordering::SeqCst);
+                let body = json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "result": answer(request["method"].as_str().unwrap(), &request["params"]),
+                })
+                .to_string();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    #[test]
+    fn test_rpc_fork_loads_state_lazily() {
+        let (url, requests) = mock_rpc();
+        let fork = ForkDB::rpc(&url, None).unwrap();
+        assert_eq!((fork.chain_id(), fork.block_number()), (0x5202, 0x10));
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+        let mut db = CacheDB::new(fork);
+        let account = db.basic(ACCOUNT).unwrap().unwrap();
+        assert_eq!((account.balance, account.nonce), (U256::from(100), 2));
+        assert_eq!(account.code_hash, KECCAK_EMPTY);
+        assert_eq!(db.storage(ACCOUNT, U256::from(1)).unwrap(), U256::from(42));
+        assert_eq!(db.block_hash(3).unwrap(), B256::repeat_byte(0xbb));
+        // Loaded values stay in memory.
+        db.basic(ACCOUNT).unwrap();
+        db.storage(ACCOUNT, U256::from(1)).unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 2 + 3 + 1 + 1);
+    }
+
+    #[test]
+    fn test_rpc_fork_keeps_ownable_accounts_wrapped() {
+        let (url, _) = mock_rpc();
+        let mut db = CacheDB::new(ForkDB::rpc(&url, None).unwrap());
+        let account = db.basic(WRAPPED).unwrap().unwrap();
+        assert_eq!(account.code_hash, keccak256(wrapped_code()));
+        match account.code {
+            Some(Bytecode::OwnableAccount(ownable)) => {
+                assert_eq!(ownable.owner_address, RUNTIME);
+                assert_eq!(ownable.metadata[..], b"meta"[..]);
+            }
+            code => panic!("expected an ownable account, got {code:?}"),
+        }
+    }
+
+    #[test]
+    fn test_disk_cache_serves_reruns() {
+        let dir = tempfile::tempdir().unwrap();
+        let (url, requests) = mock_rpc();
+        {
+            let fork = ForkDB::rpc(&url, Some(7))
+                .unwrap()
+                .with_disk_cache(dir.path())
+                .unwrap();
+            let mut db = CacheDB::new(fork);
+            db.basic(ACCOUNT).unwrap();
+            db.storage(ACCOUNT, U256::from(1)).unwrap();
+        }
+        assert!(dir.path().join("20994-7.json").exists());
+
+        let fork = ForkDB::rpc(&url, Some(7))
+            .unwrap()
+            .with_disk_cache(dir.path())
+            .unwrap();
+        let before = requests.load(Ordering::SeqCst);
+        let mut db = CacheDB::new(fork);
+        let account = db.basic(ACCOUNT).unwrap().unwrap();
+        assert_eq!(account.balance, U256::from(100));
+        assert_eq!(db.storage(ACCOUNT, U256::from(1)).unwrap(), U256::from(42));
+        assert_eq!(requests.load(Ordering::SeqCst), before);
+    }
+
+    #[test]
+    fn test_default_is_empty() {
+        let mut db = CacheDB::new(ForkDB::default());
+        assert_eq!(db.basic(ACCOUNT).unwrap(), None);
+        assert_eq!(db.storage(ACCOUNT, U256::ZERO).unwrap(), U256::ZERO);
+        assert_eq!(
+            db.block_hash(0).unwrap(),
+            EmptyDB::default().block_hash_ref(0).unwrap()
+        );
+    }
+}
@@ -0,0 +1,382 @@
+//! Gas reporting for `EvmTestingContext` test suites, similar to `forge test --gas-report`.
+//!
+//! Reporting is enabled by pointing [`GAS_REPORT_DIR_ENV`] at a directory. Every transaction
+//! executed through [`crate::TxBuilder::execute`] then appends one [`GasRecord`] to a per-process
+//! JSON Lines file in that directory. Test runners such as nextest spawn a process per test, so
+//! records are aggregated afterward with [`GasReport::from_records_dir`] (see the `gas-report`
+//! binary), which also compares the result against a checked-in baseline.
+//!
+//! Contracts are reported by address and selector unless their ABI was registered with
+//! [`crate::EvmTestingContext::register_gas_report_contract`], or with
+//! [`crate::EvmTestingContext::register_gas_report_artifact`] for the code they were deployed with,
+//! in which case the contract name and function signatures are used instead. The e2e suite
+//! registers the generated ABI of every contract embedded in `fluentbase-contracts`.
+use fluentbase_sdk::Address;
+use fluentbase_sdk_derive_core::{
+    abi::function::FunctionABI, utils::selector::calculate_keccak256,
+};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    fs::{self, OpenOptions},
+    io::{self, BufRead, BufReader, Write as _},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// Directory that receives gas records; reporting is disabled when unset.
+pub const GAS_REPORT_DIR_ENV: &str = "FLUENT_GAS_REPORT_DIR";
+
+/// Function label of contract deployments.
+pub const CONSTRUCTOR: &str = "constructor";
+
+/// Contract name and function signatures by selector, decoded from a Solidity JSON ABI.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContractAbi {
+    pub name: String,
+    pub functions: HashMap<[u8; 4], String>,
+}
+
+impl ContractAbi {
+    /// Reads the `function` entries of a Solidity JSON ABI, such as the `abi.json` artifact.
+    pub fn from_json(name: &str, abi_json: &str) -> Result<Self, serde_json::Error> {
+        let entries: Vec<Value> = serde_json::from_str(abi_json)?;
+        let mut functions = HashMap::new();
+        for entry in entries {
+            if entry.get("type").and_then(Value::as_str) != Some("function") {
+                continue;
+            }
+            let signature = FunctionABI::from_json_value(entry)?
+                .signature()
+                .map_err(serde::de::Error::custom)?;
+            functions.insert(calculate_keccak256(&signature), signature);
+        }
+        Ok(Self {
+            name: name.into(),
+            functions,
+        })
+    }
+
+    /// Label of a call with the given calldata.
+    pub fn function_label(&self, input: &[u8]) -> String {
+        match input.get(..4) {
+            Some(selector) => self
+                .functions
+                .get(selector)
+                .cloned()
+                .unwrap_or_else(|| selector_label(input)),
+            None => selector_label(input),
+        }
+    }
+}
+
+/// Label of a call to a contract without a registered ABI.
+pub fn selector_label(input: &[u8]) -> String {
+    match input.get(..4) {
+        Some(selector) => format!("0x{}", hex::encode(selector)),
+        None if input.is_empty() => "receive".into(),
+        None => "fallback".into(),
+    }
+}
+
+/// Gas used by one transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasRecord {
+    pub contract: String,
+    pub function: String,
+    pub gas: u64,
+}
+
+impl GasRecord {
+    /// Builds the record of a call to (or deployment of) `address`.
+    pub(crate) fn new(
+        labels: &HashMap<Address, ContractAbi>,
+        address: Option<Address>,
+        input: &[u8],
+        is_create: bool,
+        gas: u64,
+    ) -> Self {
+        let abi = address.and_then(|address| labels.get(&address));
+        let contract = match (abi, address) {
+            (Some(abi), _) => abi.name.clone(),
+            (None, Some(address)) => address.to_string(),
+            (None, None) => "unknown".into(),
+        };
+        let function = match (is_create, abi) {
+            (true, _) => CONSTRUCTOR.into(),
+            (false, Some(abi)) => abi.function_label(input),
+            (false, None) => selector_label(input),
+        };
+        Self {
+            contract,
+            function,
+            gas,
+        }
+    }
+}
+
+static RECORDS_FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Appends `record` to this process's records file if reporting is enabled.
+///
+/// Failing to write a record panics, so that a misconfigured directory doesn't silently produce
+/// an empty report.
+pub(crate) fn record(record: impl FnOnce() -> GasRecord) {
+    let Some(dir) = std::env::var_os(GAS_REPORT_DIR_ENV) else {
+        return;
+    };
+    let mut records_file = RECORDS_FILE.lock().unwrap_or_else(|err| err.into_inner());
+    let path = records_file.get_or_insert_with(|| {
+        let dir = PathBuf::from(dir);
+        fs::create_dir_all(&dir).expect("gas report: failed to create the records directory");
+        dir.join(format!("gas-{}.jsonl", std::process::id()))
+    });
+    let mut line = serde_json::to_string(&record()).expect("gas report: record must serialize");
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .expect("gas report: failed to append a record");
+}
+
+/// Aggregated gas of one contract function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasStats {
+    pub calls: u64,
+    pub min: u64,
+    pub max: u64,
+    pub mean: u64,
+    /// Sum of all calls, kept to merge reports without losing precision.
+    pub total: u64,
+}
+
+impl GasStats {
+    fn add(&mut self, gas: u64) {
+        self.min = if self.calls == 0 {
+            gas
+        } else {
+            self.min.min(gas)
+        };
+        self.max = self.max.max(gas);
+        self.calls += 1;
+        self.total += gas;
+        self.mean = self.total / self.calls;
+    }
+}
+
+/// Gas statistics per contract and function.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct GasReport {
+    pub contracts: BTreeMap<String, BTreeMap<String, GasStats>>,
+}
+
+/// A function whose gas grew past the allowed tolerance.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GasRegression {
+    pub contract: String,
+    pub function: String,
+    pub baseline: GasStats,
+    pub current: GasStats,
+}
+
+impl GasReport {
+    pub fn add(&mut self, record: &GasRecord) {
+        self.contracts
+            .entry(record.contract.clone())
+            .or_default()
+            .entry(record.function.clone())
+            .or_default()
+            .add(record.gas);
+    }
+
+    /// Aggregates every `*.jsonl` records file in `dir`.
+    pub fn from_records_dir(dir: &Path) -> io::Result<Self> {
+        let mut paths = fs::read_dir(dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        paths.retain(|path| path.extension().is_some_and(|ext| ext == "jsonl"));
+        paths.sort();
+
+        let mut report = Self::default();
+        for path in paths {
+            for line in BufReader::new(fs::File::open(&path)?).lines() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let record: GasRecord = serde_json::from_str(&line).map_err(|err| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{}: {err}", path.display()),
+                    )
+                })?;
+                report.add(&record);
+            }
+        }
+        Ok(report)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("gas report must serialize")
+    }
+
+    /// Renders one table per contract, in the layout of `forge test --gas-report`.
+    pub fn to_table(&self) -> String {
+        let mut result = String::new();
+        for (contract, functions) in &self.contracts {
+            let width = functions
+                .keys()
+                .map(String::len)
+                .max()
+                .unwrap_or_default()
+                .max(contract.len())
+                .max("Function Name".len());
+            let _ = writeln!(
+                result,
+                "| {contract:<width$} | {:>10} | {:>10} | {:>10} | {:>8} |",
+                "", "", "", ""
+            );
+            let _ = writeln!(
+                result,
+                "| {:<width$} | {:>10} | {:>10} | {:>10} | {:>8} |",
+                "Function Name", "min", "mean", "max", "# calls"
+            );
+            for (function, stats) in functions {
+                let _ = writeln!(
+                    result,
+                    "| {function:<width$} | {:>10} | {:>10} | {:>10} | {:>8} |",
+                    stats.min, stats.mean, stats.max, stats.calls
+                );
+            }
+            result.push('\n');
+        }
+        result
+    }
+
+    /// Functions whose mean or max gas exceeds the baseline by more than `tolerance_percent`.
+    ///
+    /// Functions missing from the baseline are new and never count as regressions.
+    pub fn regressions(&self, baseline: &GasReport, tolerance_percent: u64) -> Vec<GasRegression> {
+        let exceeds = |current: u64, baseline: u64| {
+            current as u128 * 100 > baseline as u128 * (100 + tolerance_percent as u128)
+        };
+        let mut result = Vec::new();
+        for (contract, functions) in &self.contracts {
+            for (function, current) in functions {
+                let Some(baseline) = baseline
+                    .contracts
+                    .get(contract)
+                    .and_then(|functions| functions.get(function))
+                else {
+                    continue;
+                };
+                if exceeds(current.mean, baseline.mean) || exceeds(current.max, baseline.max) {
+                    result.push(GasRegression {
+                        contract: contract.clone(),
+                        function: function.clone(),
+                        baseline: *baseline,
+                        current: *current,
+                    });
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABI: &str = r#"[
+        {"type": "constructor", "inputs": [], "stateMutability": "nonpayable"},
+        {"type": "function", "name": "transfer", "inputs": [
+            {"name": "to", "type": "address", "internalType": "address"},
+            {"name": "amount", "type": "uint256", "internalType": "uint256"}
+        ], "outputs": [
+            {"name": "", "type": "bool", "internalType": "bool"}
+        ], "stateMutability": "nonpayable"},
+        {"type": "function", "name": "batch", "inputs": [
+            {"name": "items", "type": "tuple[]", "internalType": "struct Item[]", "components": [
+                {"name": "to", "type": "address", "internalType": "address"},
+                {"name": "amount", "type": "uint256", "internalType": "uint256"}
+            ]}
+        ], "outputs": [], "stateMutability": "nonpayable"},
+        {"type": "event", "name": "Transfer", "inputs": [], "anonymous": false}
+    ]"#;
+
+    #[test]
+    fn selectors_are_decoded_with_the_abi() {
+        let abi = ContractAbi::from_json("Token", ABI).unwrap();
+        assert_eq!(abi.functions.len(), 2);
+        // transfer(address,uint256)
+        assert_eq!(
+            abi.function_label(&[0xa9, 0x05, 0x9c, 0xbb, 0x00]),
+            "transfer(address,uint256)"
+        );
+        assert!(abi
+            .functions
+            .values()
+            .any(|signature| signature == "batch((address,uint256)[])"));
+        assert_eq!(abi.function_label(&[1, 2, 3, 4]), "0x01020304");
+        assert_eq!(abi.function_label(&[]), "receive");
+        assert_eq!(abi.function_label(&[1]), "fallback");
+    }
+
+    #[test]
+    fn records_are_aggregated_per_contract_and_function() {
+        let mut report = GasReport::default();
+        for gas in [100, 300, 200] {
+            report.add(&GasRecord {
+                contract: "Token".into(),
+                function: "transfer(address,uint256)".into(),
+                gas,
+            });
+        }
+        let stats = report.contracts["Token"]["transfer(address,uint256)"];
+        assert_eq!(
+            stats,
+            GasStats {
+                calls: 3,
+                min: 100,
+                max: 300,
+                mean: 200,
+                total: 600,
+            }
+        );
+        assert_eq!(GasReport::from_json(&report.to_json()).unwrap(), report);
+        assert!(report.to_table().contains(
+            "| transfer(address,uint256) |        100 |        200 |        300 |        3 |"
+        ));
+    }
+
+    #[test]
+    fn regressions_respect_the_tolerance() {
+        let report_with = |gas| {
+            let mut report = GasReport::default();
+            report.add(&GasRecord {
+                contract: "Token".into(),
+                function: CONSTRUCTOR.into(),
+                gas,
+            });
+            report
+        };
+        let baseline = report_with(1_000);
+        assert!(report_with(1_020).regressions(&baseline, 2).is_empty());
+        assert_eq!(report_with(1_021).regressions(&baseline, 2).len(), 1);
+        assert!(report_with(900).regressions(&baseline, 0).is_empty());
+        assert!(report_with(5_000)
+            .regressions(&GasReport::default(), 0)
+            .is_empty());
+    }
+}
@@ -1,5 +1,7 @@
 //! Testing harnesses and host utilities for exercising Fluentbase contracts and EVM flows.
 mod evm;
+pub mod fork;
+pub mod gas_report;
 mod host;
 mod store;
 
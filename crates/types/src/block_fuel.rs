@@ -45,6 +45,8 @@ pub const COPY_BASE_FUEL_COST: u32 = 20 * FUEL_DENOM_RATE as u32;
 pub const COPY_WORD_FUEL_COST: u32 = 3 * FUEL_DENOM_RATE as u32;
 pub const DEBUG_LOG_BASE_FUEL_COST: u32 = 50 * FUEL_DENOM_RATE as u32;
 pub const DEBUG_LOG_WORD_FUEL_COST: u32 = 16 * FUEL_DENOM_RATE as u32;
+pub const DEBUG_EVENT_BASE_FUEL_COST: u32 = 50 * FUEL_DENOM_RATE as u32;
+pub const DEBUG_EVENT_WORD_FUEL_COST: u32 = 16 * FUEL_DENOM_RATE as u32;
 pub const CHARGE_FUEL_BASE_COST: u32 = 20 * FUEL_DENOM_RATE as u32;
 pub const KECCAK_BASE_FUEL_COST: u32 = 30 * FUEL_DENOM_RATE as u32;
 pub const KECCAK_WORD_FUEL_COST: u32 = 6 * FUEL_DENOM_RATE as u32;
@@ -52,6 +54,11 @@ pub const SHA256_BASE_FUEL_COST: u32 = 60 * FUEL_DENOM_RATE as u32;
 pub const SHA256_WORD_FUEL_COST: u32 = 12 * FUEL_DENOM_RATE as u32;
 pub const BLAKE3_BASE_FUEL_COST: u32 = 60 * FUEL_DENOM_RATE as u32;
 pub const BLAKE3_WORD_FUEL_COST: u32 = 12 * FUEL_DENOM_RATE as u32;
+// Poseidon is charged per 32-byte input, one word per field element. The costs follow the
+// measured native hashing time of the circom BN254 parameters, roughly 1.5k gas for a two-input
+// hash, which is in line with the cheapest Solidity Poseidon implementations.
+pub const POSEIDON_BASE_FUEL_COST: u32 = 500 * FUEL_DENOM_RATE as u32;
+pub const POSEIDON_WORD_FUEL_COST: u32 = 500 * FUEL_DENOM_RATE as u32;
 
 // Ed25519
 pub const ED25519_DECOMPRESS_COST: u32 = 5_000 * FUEL_DENOM_RATE as u32;
@@ -143,11 +150,13 @@ pub(crate) fn calculate_syscall_fuel(sys_func_idx: SysFuncIdx) -> SyscallFuelPar
         EXIT_UNCONSTRAINED => no_fuel!(),
         // TODO: use correct fuel calculations here, once we implement `WRITE_FD`, no fuel for now
         WRITE_FD => no_fuel!(),
+        // Charged whether the node collects the records or not, so gas usage doesn't depend on it.
+        DEBUG_EVENT => linear_fuel!(3, DEBUG_EVENT_BASE_FUEL_COST, DEBUG_EVENT_WORD_FUEL_COST),
 
         // hashing functions (0x01)
         KECCAK256 => linear_fuel!(2, KECCAK_BASE_FUEL_COST, KECCAK_WORD_FUEL_COST),
         KECCAK256_PERMUTE => const_fuel!(KECCAK_BASE_FUEL_COST),
-        POSEIDON => linear_fuel!(2, 100, 20),
+        POSEIDON => linear_fuel!(3, POSEIDON_BASE_FUEL_COST, POSEIDON_WORD_FUEL_COST),
         SHA256_EXTEND => const_fuel!(SHA256_BASE_FUEL_COST),
         SHA256_COMPRESS => const_fuel!(SHA256_BASE_FUEL_COST),
         SHA256 => linear_fuel!(2, SHA256_BASE_FUEL_COST, SHA256_WORD_FUEL_COST),
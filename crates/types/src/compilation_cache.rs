@@ -4,7 +4,7 @@ use rwasm::CompilationConfig;
 
 /// Bump when a runtime, fork, linker ABI, or lowering policy change must invalidate caches.
 pub const COMPILATION_CONFIG_FINGERPRINT_VERSION: u32 = 1;
-pub const IMPORT_LINKER_V1_PREVIEW_VERSION: u16 = 1;
+pub const IMPORT_LINKER_V1_PREVIEW_VERSION: u16 = 4;
 pub const STATE_ROUTER_V1_VERSION: u16 = 1;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
@@ -86,11 +86,56 @@ impl CompilationConfigFingerprint {
         bytes
     }
 
+    /// Inverse of [`Self::stable_bytes`]; `None` if the bytes were not produced by it.
+    pub fn from_stable_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = StableBytesReader(bytes);
+        let fingerprint = Self {
+            version: u32::from_le_bytes(reader.take()?),
+            backend: match reader.take::<1>()? {
+                [1] => CompilationBackend::Rwasm,
+                [2] => CompilationBackend::Wasmtime,
+                _ => return None,
+            },
+            import_linker_version: u16::from_le_bytes(reader.take()?),
+            state_router_version: u16::from_le_bytes(reader.take()?),
+            config_version: u32::from_le_bytes(reader.take()?),
+            contract_address: Address::from(reader.take::<20>()?),
+            allow_malformed_entrypoint_func_type: reader.take_bool()?,
+            builtins_consume_fuel: reader.take_bool()?,
+            consume_fuel: reader.take_bool()?,
+            code_snippets: reader.take_bool()?,
+            consume_fuel_for_bulk_ops: reader.take_bool()?,
+            consume_fuel_for_params_and_locals: reader.take_bool()?,
+            allow_func_ref_function_types: reader.take_bool()?,
+            allow_start_section: reader.take_bool()?,
+            max_allowed_memory_pages: u32::from_le_bytes(reader.take()?),
+        };
+        reader.0.is_empty().then_some(fingerprint)
+    }
+
     pub fn identity_hash(&self) -> B256 {
         keccak256(self.stable_bytes())
     }
 }
 
+struct StableBytesReader<'a>(&'a [u8]);
+
+impl StableBytesReader<'_> {
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let (head, rest) = self.0.split_first_chunk::<N>()?;
+        self.0 = rest;
+        Some(*head)
+    }
+
+    fn take_bool(&mut self) -> Option<bool> {
+        match self.take::<1>()? {
+            [0] => Some(false),
+            [1] => Some(true),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub struct CompiledModuleCacheKey {
     pub code_hash: B256,
@@ -24,6 +24,15 @@ pub trait CryptoAPI {
     /// Output: `state` is updated in-place with the standard SHA-256 round function.
     fn sha256_compress(state: &mut [u32; 8], w: &[u32; 64]);
 
+    /// Poseidon hash with the circom BN254 parameters (x^5 S-box).
+    ///
+    /// Input: `data` is 1..=12 big-endian 32-byte field elements.
+    /// Output: big-endian 32-byte hash, `None` if an input is not below the field modulus.
+    ///
+    /// `_poseidon` isn't in the import linker until its fork is scheduled, so a contract calling
+    /// this doesn't compile to rWasm yet.
+    fn poseidon_bn254(data: &[u8]) -> Option<[u8; 32]>;
+
     /// Decompress an Ed25519 point from compressed y and a sign bit.
     ///
     /// Inputs: `y` is 32-byte compressed y-coordinate; `sign` selects the x parity.
@@ -29,3 +29,11 @@ pub const BLS12381_G1_RAW_AFFINE_SIZE: usize = 96;
 pub const BLS12381_G1_COMPRESSED_SIZE: usize = 48;
 pub const BLS12381_G2_RAW_AFFINE_SIZE: usize = 192;
 pub const BLS12381_G2_COMPRESSED_SIZE: usize = 96;
+
+/// POSEIDON
+pub const POSEIDON_INPUT_SIZE: usize = 32;
+pub const POSEIDON_MAX_INPUTS: usize = 12;
+/// Circom-compatible parameters over the BN254 scalar field with the x^5 S-box.
+pub const POSEIDON_PARAMETERS_BN254_X5: u32 = 0;
+pub const POSEIDON_ENDIANNESS_BIG: u32 = 0;
+pub const POSEIDON_ENDIANNESS_LITTLE: u32 = 1;
@@ -0,0 +1,44 @@
+//! Structured debug records streamed by contracts through the `_debug_event` syscall.
+//!
+//! This is the replacement for `_write_fd` diagnostics. The syscall only exists on development
+//! chains (the `debug_events` runtime fork is never scheduled on public networks, where the call
+//! traps). It has no result and charges the same fuel whether the node collects records or not,
+//! so collecting them can't change execution results.
+//! Collected records end up in the runtime `ExecutionResult` and in `fluentTracer` call frames.
+use crate::Bytes;
+
+/// Largest record payload that is collected, longer payloads are dropped.
+pub const DEBUG_EVENT_MAX_DATA_SIZE: usize = 4 * 1024;
+/// Largest number of records collected per execution, later records are dropped.
+pub const DEBUG_EVENT_MAX_RECORDS: usize = 1024;
+
+/// Encoding of a record payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[repr(u32)]
+pub enum DebugEventFormat {
+    /// Opaque bytes.
+    Bytes = 0,
+    /// A UTF-8 JSON document.
+    Json = 1,
+}
+
+impl DebugEventFormat {
+    pub fn from_u32(format: u32) -> Option<Self> {
+        match format {
+            0 => Some(Self::Bytes),
+            1 => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// A record emitted by a contract, tagged with a contract-chosen channel.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DebugEvent {
+    pub channel: u32,
+    pub format: DebugEventFormat,
+    pub data: Bytes,
+}
@@ -0,0 +1,145 @@
+//! Activation schedule of runtime features that change execution results.
+//!
+//! Enabling a syscall changes what a contract can observe, so a node replaying historical blocks
+//! must see it disabled before the activation point and enabled after, the same way EVM hardforks
+//! are scheduled. Every feature gated here is resolved per block into [`ActiveRuntimeForks`].
+//!
+//! The schedule is a property of the chain, so it is looked up by chain id with
+//! [`RuntimeForkSchedule::for_chain`] wherever a block is executed, rather than configured per node.
+
+pub const FLUENT_LOCALNET_CHAIN_ID: u64 = 1337;
+pub const FLUENT_DEVNET_CHAIN_ID: u64 = 0x5201;
+pub const FLUENT_TESTNET_CHAIN_ID: u64 = 0x5202;
+pub const FLUENT_MAINNET_CHAIN_ID: u64 = 25363;
+
+/// The point a runtime feature becomes active at.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ForkActivation {
+    /// Not scheduled on this chain.
+    #[default]
+    Never,
+    /// Active from the given block number onward.
+    Block(u64),
+    /// Active for blocks with a timestamp at or after the given one.
+    Timestamp(u64),
+}
+
+impl ForkActivation {
+    /// Returns whether the feature is active for a block with the given number and timestamp.
+    pub fn is_active_at(&self, block_number: u64, timestamp: u64) -> bool {
+        match *self {
+            Self::Never => false,
+            Self::Block(block) => block_number >= block,
+            Self::Timestamp(time) => timestamp >= time,
+        }
+    }
+}
+
+/// Per-chain activation points of the gated runtime features.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RuntimeForkSchedule {
+    /// The `_poseidon` syscall.
+    pub poseidon: ForkActivation,
+    /// The `_debug_event` syscall, never scheduled on public networks.
+    pub debug_events: ForkActivation,
+}
+
+impl RuntimeForkSchedule {
+    /// Every feature active from genesis, the schedule of the local development chain.
+    pub const ALL_ACTIVE: Self = Self {
+        poseidon: ForkActivation::Block(0),
+        debug_events: ForkActivation::Block(0),
+    };
+
+    /// Every feature disabled.
+    pub const NONE_ACTIVE: Self = Self {
+        poseidon: ForkActivation::Never,
+        debug_events: ForkActivation::Never,
+    };
+
+    /// Activation schedule of the given chain.
+    ///
+    /// Only the local development chain gets everything from genesis. Public networks list every
+    /// activation explicitly, a feature stays disabled there until its block or timestamp is
+    /// scheduled here. Any chain that isn't listed, including a misconfigured one, gets nothing.
+    pub const fn for_chain(chain_id: u64) -> Self {
+        match chain_id {
+            FLUENT_LOCALNET_CHAIN_ID => Self::ALL_ACTIVE,
+            FLUENT_DEVNET_CHAIN_ID | FLUENT_TESTNET_CHAIN_ID | FLUENT_MAINNET_CHAIN_ID => Self {
+                poseidon: ForkActivation::Never,
+                debug_events: ForkActivation::Never,
+            },
+            _ => Self::NONE_ACTIVE,
+        }
+    }
+
+    /// Resolves the schedule for a block with the given number and timestamp.
+    pub fn active_at(&self, block_number: u64, timestamp: u64) -> ActiveRuntimeForks {
+        ActiveRuntimeForks {
+            poseidon: self.poseidon.is_active_at(block_number, timestamp),
+            debug_events: self.debug_events.is_active_at(block_number, timestamp),
+        }
+    }
+}
+
+/// Every feature disabled: a schedule that wasn't resolved for a chain must not activate anything.
+impl Default for RuntimeForkSchedule {
+    fn default() -> Self {
+        Self::NONE_ACTIVE
+    }
+}
+
+/// Runtime features active for the block being executed.
+///
+/// The default has every feature disabled, so a context that never had the forks resolved can't
+/// use a gated syscall.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ActiveRuntimeForks {
+    /// Whether the `_poseidon` syscall is available.
+    pub poseidon: bool,
+    /// Whether the `_debug_event` syscall is available.
+    pub debug_events: bool,
+}
+
+impl ActiveRuntimeForks {
+    /// Every feature enabled.
+    pub const ALL: Self = Self {
+        poseidon: true,
+        debug_events: true,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn public_chains_keep_gated_features_disabled() {
+        for chain_id in [
+            FLUENT_DEVNET_CHAIN_ID,
+            FLUENT_TESTNET_CHAIN_ID,
+            FLUENT_MAINNET_CHAIN_ID,
+        ] {
+            let forks = RuntimeForkSchedule::for_chain(chain_id).active_at(u64::MAX, u64::MAX);
+            assert_eq!(forks, ActiveRuntimeForks::default());
+        }
+    }
+
+    #[test]
+    fn local_chains_activate_everything_from_genesis() {
+        let forks = RuntimeForkSchedule::for_chain(FLUENT_LOCALNET_CHAIN_ID).active_at(0, 0);
+        assert_eq!(forks, ActiveRuntimeForks::ALL);
+    }
+
+    #[test]
+    fn unknown_chains_keep_gated_features_disabled() {
+        let forks = RuntimeForkSchedule::for_chain(4242).active_at(u64::MAX, u64::MAX);
+        assert_eq!(forks, ActiveRuntimeForks::default());
+    }
+
+    #[test]
+    fn default_schedule_activates_nothing() {
+        let forks = RuntimeForkSchedule::default().active_at(u64::MAX, u64::MAX);
+        assert_eq!(forks, ActiveRuntimeForks::default());
+    }
+}
@@ -225,6 +225,10 @@ pub const DEFAULT_UPDATE_GENESIS_AUTH: Address =
 pub const DEFAULT_FEE_MANAGER_AUTH: Address =
     address!("0xa7bf6a9168fe8a111307b7c94b8883fe02b30934");
 
+/// Default OAuth2 verifier JWKS manager, should be changed after a chain is live
+pub const DEFAULT_OAUTH2_JWKS_AUTH: Address =
+    address!("0xa7bf6a9168fe8a111307b7c94b8883fe02b30934");
+
 /// Transaction calldata prefix for **genesis update**.
 /// Versioning allows introducing new update semantics without ambiguity.
 ///
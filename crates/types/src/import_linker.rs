@@ -36,10 +36,14 @@ pub fn import_linker_v1_preview() -> Arc<ImportLinker> {
     // TODO(dmitry123): This syscall is disabled since it can cause panic, we should refine it
     //  by introducing new system contracts where the same functionality is achieved.
     // import_function!("_write_fd", WRITE_FD, &[ValType::I32; 3], &[]);
+    import_function!("_debug_event", DEBUG_EVENT, &[ValType::I32; 4], &[]);
 
     // hashing functions (0x01)
     import_function!("_keccak256", KECCAK256, &[ValType::I32; 3], &[]);
     import_function!("_keccak256_permute", KECCAK256_PERMUTE, &[ValType::I32; 1], &[]);
+    // TODO: Link once the Poseidon fork is scheduled on public networks. The linker is shared by
+    //  every chain, including the on-chain compiler, so linking it earlier would let mainnet
+    //  contracts deploy against a syscall that always traps there, see `RuntimeForkSchedule`.
     // import_function!("_poseidon", POSEIDON, &[ValType::I32; 5], &[ValType::I32; 1]);
     import_function!("_sha256_extend", SHA256_EXTEND, &[ValType::I32; 1], &[]);
     import_function!("_sha256_compress", SHA256_COMPRESS, &[ValType::I32; 2], &[]);
@@ -9,9 +9,11 @@ mod bytecode;
 mod compilation_cache;
 mod crypto_api;
 mod curves;
+mod debug_event;
 mod eip2935;
 mod exit_code;
 pub mod fd;
+mod fork;
 mod genesis;
 mod import_linker;
 mod native_api;
@@ -28,8 +30,10 @@ pub use byteorder;
 pub use compilation_cache::*;
 pub use crypto_api::*;
 pub use curves::*;
+pub use debug_event::*;
 pub use eip2935::*;
 pub use exit_code::*;
+pub use fork::*;
 pub use genesis::*;
 pub use hashbrown::{self, hash_map, hash_set, HashMap, HashSet};
 pub use import_linker::*;
@@ -65,6 +69,10 @@ pub const CALL_DEPTH_ROOT: u32 = 0;
 /// The value can be changed in the future.
 pub const FUEL_DENOM_RATE: u64 = 20;
 
+/// An average overhead per each Wasm byte in fuel cost (~50 gas per byte), charged by the Wasm
+/// runtime before it compiles a deployed binary.
+pub const WASM_COMPILATION_OVERHEAD_FUEL_PER_BYTE: u32 = (50 * FUEL_DENOM_RATE) as u32;
+
 /// The maximum allowed value for the `x` parameter used in linear gas cost calculation
 /// of builtins.
 /// This limit ensures:
@@ -1,4 +1,4 @@
-use crate::{BytecodeOrHash, ExitCode};
+use crate::{BytecodeOrHash, DebugEventFormat, ExitCode};
 use alloc::borrow::Cow;
 
 /// A trait for providing shared API functionality.
@@ -39,4 +39,6 @@ pub trait NativeAPI {
     fn enter_unconstrained(&self);
     fn exit_unconstrained(&self);
     fn write_fd(&self, fd: u32, slice: &[u8]);
+    /// Streams a structured debug record, collected by development nodes only.
+    fn debug_event(&self, channel: u32, format: DebugEventFormat, data: &[u8]);
 }
@@ -1,7 +1,8 @@
 use crate::{
-    BytecodeOrHash, CryptoAPI, ExitCode, NativeAPI, BLS12381_FP_SIZE, BLS12381_G1_COMPRESSED_SIZE,
-    BLS12381_G1_RAW_AFFINE_SIZE, BN254_FP_SIZE, BN254_G1_RAW_AFFINE_SIZE,
-    ED25519_POINT_COMPRESSED_SIZE, ED25519_POINT_DECOMPRESSED_SIZE, SECP256K1_G1_COMPRESSED_SIZE,
+    BytecodeOrHash, CryptoAPI, DebugEventFormat, ExitCode, NativeAPI, BLS12381_FP_SIZE,
+    BLS12381_G1_COMPRESSED_SIZE, BLS12381_G1_RAW_AFFINE_SIZE, BN254_FP_SIZE,
+    BN254_G1_RAW_AFFINE_SIZE, ED25519_POINT_COMPRESSED_SIZE, ED25519_POINT_DECOMPRESSED_SIZE,
+    POSEIDON_ENDIANNESS_BIG, POSEIDON_PARAMETERS_BN254_X5, SECP256K1_G1_COMPRESSED_SIZE,
     SECP256K1_G1_RAW_AFFINE_SIZE, SECP256R1_G1_COMPRESSED_SIZE, SECP256R1_G1_RAW_AFFINE_SIZE,
 };
 use alloc::borrow::Cow;
@@ -127,6 +128,11 @@ impl NativeAPI for RwasmContext {
     fn write_fd(&self, fd: u32, slice: &[u8]) {
         unsafe { _write_fd(fd, slice.as_ptr(), slice.len() as u32) }
     }
+
+    #[inline(always)]
+    fn debug_event(&self, channel: u32, format: DebugEventFormat, data: &[u8]) {
+        unsafe { _debug_event(channel, format as u32, data.as_ptr(), data.len() as u32) }
+    }
 }
 
 #[rustfmt::skip]
@@ -143,6 +149,12 @@ impl CryptoAPI for RwasmContext {
     fn sha256_compress(state: &mut [u32; 8], w: &[u32; 64]) {
         unsafe { _sha256_compress(state.as_mut_ptr() as *mut [u32; 8], w.as_ptr() as *mut [u32; 64]) }
     }
+    #[inline(always)]
+    fn poseidon_bn254(data: &[u8]) -> Option<[u8; 32]> {
+        let mut result = [0u8; 32];
+        let exit_code = unsafe { _poseidon(POSEIDON_PARAMETERS_BN254_X5, POSEIDON_ENDIANNESS_BIG, data.as_ptr(), data.len() as u32, result.as_mut_ptr()) };
+        (exit_code == 0).then_some(result)
+    }
 
     #[inline(always)]
     fn ed25519_decompress(y: [u8; ED25519_POINT_COMPRESSED_SIZE], sign: u32) -> [u8; ED25519_POINT_DECOMPRESSED_SIZE] {
@@ -32,12 +32,12 @@ extern "C" {
     pub fn _enter_unconstrained();
     pub fn _exit_unconstrained();
     pub fn _write_fd(fd: u32, slice_ptr: *const u8, slice_len: u32);
+    pub fn _debug_event(channel: u32, format: u32, data_ptr: *const u8, data_len: u32);
 
     // hashing functions (0x01)
     #[deprecated(note = "will be removed in fluentbase_v1 schema version")]
     pub fn _keccak256(data_offset: *const u8, data_len: u32, output32_offset: *mut u8);
     pub fn _keccak256_permute(state_ptr: *mut [u64; 25]);
-    #[deprecated(note = "will be removed in fluentbase_v1 schema version")]
     pub fn _poseidon(
         parameters: u32,
         endianness: u32,
@@ -24,6 +24,7 @@ pub enum SysFuncIdx {
     ENTER_UNCONSTRAINED = 0x0012,
     EXIT_UNCONSTRAINED = 0x0013,
     WRITE_FD = 0x0014,
+    DEBUG_EVENT = 0x0015,
 
     // hashing functions (0x01)
     // #[deprecated(note = "use permute instead")]
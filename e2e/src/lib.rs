@@ -20,6 +20,7 @@ extern crate alloc;
 extern crate core;
 
 use crate::utils::download_and_cache_genesis;
+use fluentbase_contracts::BUILD_OUTPUTS;
 use fluentbase_genesis::GENESIS_CONTRACTS_BY_ADDRESS;
 use fluentbase_sdk::{GenesisContract, PRECOMPILE_WASM_RUNTIME, U256};
 use fluentbase_testing::EvmTestingContext;
@@ -77,6 +78,8 @@ mod universal_token;
 mod universal_token_solidity;
 #[cfg(test)]
 mod update_account;
+#[cfg(test)]
+mod upgradeable;
 mod utils;
 #[cfg(test)]
 mod wasm;
@@ -114,7 +117,7 @@ impl EvmTestingContextWithGenesis for EvmTestingContext {
             .iter()
             .map(|(_k, v)| v.clone())
             .collect();
-        self.with_contracts(&contracts)
+        with_build_output_abis(self.with_contracts(&contracts))
     }
 
     fn with_minimal_genesis(self) -> EvmTestingContext {
@@ -122,6 +125,18 @@ impl EvmTestingContextWithGenesis for EvmTestingContext {
             .get(&PRECOMPILE_WASM_RUNTIME)
             .unwrap()
             .clone();
-        self.with_contracts(&[wasm_runtime])
+        with_build_output_abis(self.with_contracts(&[wasm_runtime]))
+    }
+}
+
+/// Labels deployments of the embedded contracts in the gas report with their generated ABIs.
+fn with_build_output_abis(mut ctx: EvmTestingContext) -> EvmTestingContext {
+    for build_output in BUILD_OUTPUTS {
+        ctx.register_gas_report_artifact(
+            build_output.name,
+            build_output.wasm_bytecode,
+            build_output.abi,
+        );
     }
+    ctx
 }
@@ -1,8 +1,14 @@
 use crate::EvmTestingContextWithGenesis;
-use fluentbase_sdk::{Address, Bytes, PRECOMPILE_OAUTH2_VERIFIER};
-use fluentbase_testing::{EvmTestingContext, TxResultExt};
+use fluentbase_sdk::{
+    oauth2::{OAuth2Command, SetRsaKeyCommand, VerifiedClaims, VerifyCommand},
+    Address, Bytes, DEFAULT_OAUTH2_JWKS_AUTH, PRECOMPILE_OAUTH2_VERIFIER, U256,
+};
+use fluentbase_testing::{try_print_utf8_error, EvmTestingContext, TxBuilder, TxResultExt};
 use revm::context::result::HaltReason;
 
+const RS256_TOKEN: &str = include_str!("../../contracts/oauth2/testdata/rs256.jwt");
+const RS256_MODULUS: &str = include_str!("../../contracts/oauth2/testdata/rs256-modulus.hex");
+
 #[test]
 fn test_oauth2_should_not_panic() {
     let mut ctx = EvmTestingContext::default().with_full_genesis();
@@ -16,6 +22,70 @@ fn test_oauth2_should_not_panic() {
     );
     result
         .expect_halt()
-        .expect_reason(HaltReason::UnreachableCodeReached)
+        .expect_reason(HaltReason::MalformedBuiltinParams)
         .expect_gas_used(3_000_000);
 }
+
+#[test]
+fn test_oauth2_verify_rs256_token() {
+    let mut ctx = EvmTestingContext::default().with_full_genesis();
+    const CALLER: Address = Address::with_last_byte(81);
+    ctx.add_balance(DEFAULT_OAUTH2_JWKS_AUTH, U256::from(1e18));
+    ctx.add_balance(CALLER, U256::from(1e18));
+
+    let verify = VerifyCommand {
+        token: RS256_TOKEN.trim().into(),
+        issuer: "https://accounts.example.com".into(),
+        audience: "fluent-client".into(),
+        nonce: "n-0S6_WzA2Mj".into(),
+    };
+    let mut verify_input = Vec::new();
+    verify.encode_for_send(&mut verify_input);
+
+    // Nothing is registered for the token's `kid` yet.
+    let result = TxBuilder::call(&mut ctx, PRECOMPILE_OAUTH2_VERIFIER)
+        .caller(CALLER)
+        .input(verify_input.clone().into())
+        .timestamp(1_700_000_600)
+        .exec();
+    assert!(!result.is_success());
+
+    let mut input = Vec::new();
+    SetRsaKeyCommand {
+        issuer: verify.issuer.clone(),
+        kid: "rsa-key-1".into(),
+        modulus: hex::decode(RS256_MODULUS.trim()).unwrap().into(),
+        exponent: U256::from(65537),
+    }
+    .encode_for_send(&mut input);
+    let result = TxBuilder::call(&mut ctx, PRECOMPILE_OAUTH2_VERIFIER)
+        .caller(DEFAULT_OAUTH2_JWKS_AUTH)
+        .input(input.into())
+        .exec();
+    if !result.is_success() {
+        try_print_utf8_error(result.output().cloned().unwrap_or_default().as_ref());
+        panic!("setRsaKey call failed, result: {:?}", result);
+    }
+
+    // The host preloads the key record from the token header, so verification sees the key.
+    let result = TxBuilder::call(&mut ctx, PRECOMPILE_OAUTH2_VERIFIER)
+        .caller(CALLER)
+        .input(verify_input.clone().into())
+        .timestamp(1_700_000_600)
+        .exec();
+    if !result.is_success() {
+        try_print_utf8_error(result.output().cloned().unwrap_or_default().as_ref());
+        panic!("verify call failed, result: {:?}", result);
+    }
+    let claims = VerifiedClaims::decode_output(result.output().unwrap()).unwrap();
+    assert_eq!(claims.subject, "1234567890");
+    assert_eq!(claims.email, "alice@example.com");
+
+    // The same token is rejected once it has expired.
+    let result = TxBuilder::call(&mut ctx, PRECOMPILE_OAUTH2_VERIFIER)
+        .caller(CALLER)
+        .input(verify_input.into())
+        .timestamp(1_700_003_600)
+        .exec();
+    assert!(!result.is_success());
+}
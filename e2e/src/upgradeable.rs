@@ -0,0 +1,219 @@
+use crate::EvmTestingContextWithGenesis;
+use alloy_sol_types::{sol, SolCall, SolError, SolValue};
+use fluentbase_contracts::{FLUENTBASE_CONTRACTS_ERC1967_PROXY, FLUENTBASE_EXAMPLES_UUPS_COUNTER};
+use fluentbase_sdk::{
+    address, constructor::encode_constructor_params, proxy::IMPLEMENTATION_SLOT, Address, Bytes,
+    B256, U256,
+};
+use fluentbase_testing::EvmTestingContext;
+use hex_literal::hex;
+
+const DEPLOYER: Address = address!("1111111111111111111111111111111111111111");
+const OWNER: Address = address!("2222222222222222222222222222222222222222");
+const ALICE: Address = address!("3333333333333333333333333333333333333333");
+
+sol! {
+    function initialize(address owner) external;
+    function value() external view returns (uint256);
+    function increment() external;
+    function version() external view returns (uint64);
+    function proxiableUUID() external view returns (bytes32);
+    function upgradeToAndCall(address new_implementation, bytes data) external payable;
+
+    error InvalidInitialization();
+    error OwnableUnauthorizedAccount(address account);
+    error ERC1967InvalidImplementation(address implementation);
+    error UUPSUnauthorizedCallContext();
+}
+
+/// Init code of an EVM UUPS implementation sharing the storage layout of
+/// `examples/uups-counter`: `increment()` adds 2, `version()` returns 2 and `upgradeToAndCall`
+/// stores the new implementation when called by the `Ownable` owner, ignoring `data`.
+const EVM_COUNTER_V2: [u8; 235] = hex!(
+    "6100de8061000d6000396000f3"
+    "60003560e01c80633fa4f24514610041578063d09de08a1461004957806354fd4d5014610054"
+    "57806352d1902d1461005b5780634f1ef2861461008157600080fd5b6000546100d5565b6002"
+    "60005401600055005b60026100d5565b7f360894a13ba1a3210667c828492db98dca3e2076cc"
+    "3735a920a3ca505d382bbc6100d5565b7f9016d09d72d40fdae2fd8ceac6b6234c7706214fd3"
+    "9c1cd1e609a0528c1993005433146100ae57600080fd5b6004357f360894a13ba1a3210667c8"
+    "28492db98dca3e2076cc3735a920a3ca505d382bbc55005b60005260206000f3"
+);
+
+/// Init code of a minimal EVM ERC-1967 proxy, without the leading
+/// `PUSH20 <implementation> PUSH32 <IMPLEMENTATION_SLOT> SSTORE`
+const EVM_PROXY: [u8; 81] = hex!(
+    "610044806100446000396000f3"
+    "366000600037600060003660007f360894a13ba1a3210667c828492db98dca3e2076cc3735a9"
+    "20a3ca505d382bbc545af43d600060003e61003f573d6000fd5b3d6000f3"
+);
+
+fn deploy_wasm_counter(ctx: &mut EvmTestingContext) -> Address {
+    ctx.deploy_evm_tx(
+        DEPLOYER,
+        FLUENTBASE_EXAMPLES_UUPS_COUNTER.wasm_bytecode.into(),
+    )
+}
+
+/// Deploys `contracts/erc1967-proxy` initialized with `initialize(OWNER)`
+fn deploy_wasm_proxy(ctx: &mut EvmTestingContext, implementation: Address) -> Address {
+    let data = Bytes::from(initializeCall { owner: OWNER }.abi_encode());
+    let mut input: Vec<u8> = FLUENTBASE_CONTRACTS_ERC1967_PROXY.wasm_bytecode.into();
+    input.extend(encode_constructor_params(
+        &(implementation, data).abi_encode_params(),
+    ));
+    ctx.deploy_evm_tx(DEPLOYER, input.into())
+}
+
+fn deploy_evm_proxy(ctx: &mut EvmTestingContext, implementation: Address) -> Address {
+    let mut input = vec![0x73];
+    input.extend(implementation.as_slice());
+    input.push(0x7f);
+    input.extend(IMPLEMENTATION_SLOT.to_be_bytes::<32>());
+    input.push(0x55);
+    input.extend(EVM_PROXY);
+    ctx.deploy_evm_tx(DEPLOYER, input.into())
+}
+
+/// Calls `callee` as `caller`, returning the output or the revert data
+fn call(
+    ctx: &mut EvmTestingContext,
+    caller: Address,
+    callee: Address,
+    input: Vec<u8>,
+) -> Result<Bytes, Bytes> {
+    let result = ctx.call_evm_tx(caller, callee, input.into(), None, None);
+    let output = result.output().cloned().unwrap_or_default();
+    if result.is_success() {
+        Ok(output)
+    } else {
+        Err(output)
+    }
+}
+
+fn value(ctx: &mut EvmTestingContext, proxy: Address) -> U256 {
+    let output = call(ctx, ALICE, proxy, valueCall {}.abi_encode()).unwrap();
+    valueCall::abi_decode_returns(&output).unwrap()
+}
+
+fn version(ctx: &mut EvmTestingContext, proxy: Address) -> u64 {
+    let output = call(ctx, ALICE, proxy, versionCall {}.abi_encode()).unwrap();
+    versionCall::abi_decode_returns(&output).unwrap()
+}
+
+fn upgrade(new_implementation: Address) -> Vec<u8> {
+    upgradeToAndCallCall {
+        new_implementation,
+        data: Bytes::new(),
+    }
+    .abi_encode()
+}
+
+fn implementation(ctx: &mut EvmTestingContext, proxy: Address) -> Address {
+    let word = ctx.db.storage(proxy, IMPLEMENTATION_SLOT).unwrap();
+    Address::from_word(word.into())
+}
+
+#[test]
+fn test_wasm_proxy_upgrades_between_wasm_and_evm_implementations() {
+    let mut ctx = EvmTestingContext::default().with_full_genesis();
+    let counter_v1 = deploy_wasm_counter(&mut ctx);
+    let counter_v2 = ctx.deploy_evm_tx(DEPLOYER, EVM_COUNTER_V2.into());
+    let proxy = deploy_wasm_proxy(&mut ctx, counter_v1);
+    assert_eq!(implementation(&mut ctx, proxy), counter_v1);
+
+    call(&mut ctx, ALICE, proxy, incrementCall {}.abi_encode()).unwrap();
+    assert_eq!(value(&mut ctx, proxy), U256::from(1));
+    assert_eq!(version(&mut ctx, proxy), 1);
+
+    // The constructor data already initialized the proxy
+    let revert = call(
+        &mut ctx,
+        ALICE,
+        proxy,
+        initializeCall { owner: ALICE }.abi_encode(),
+    )
+    .unwrap_err();
+    assert_eq!(revert, InvalidInitialization {}.abi_encode());
+
+    let revert = call(&mut ctx, ALICE, proxy, upgrade(counter_v2)).unwrap_err();
+    assert_eq!(
+        revert,
+        OwnableUnauthorizedAccount { account: ALICE }.abi_encode()
+    );
+    let revert = call(&mut ctx, OWNER, proxy, upgrade(ALICE)).unwrap_err();
+    assert_eq!(
+        revert,
+        ERC1967InvalidImplementation {
+            implementation: ALICE
+        }
+        .abi_encode()
+    );
+
+    // The EVM implementation picks up the state written by the Wasm one
+    call(&mut ctx, OWNER, proxy, upgrade(counter_v2)).unwrap();
+    assert_eq!(implementation(&mut ctx, proxy), counter_v2);
+    assert_eq!(version(&mut ctx, proxy), 2);
+    assert_eq!(value(&mut ctx, proxy), U256::from(1));
+    call(&mut ctx, ALICE, proxy, incrementCall {}.abi_encode()).unwrap();
+    assert_eq!(value(&mut ctx, proxy), U256::from(3));
+
+    // And the owner stored by the Wasm implementation can move the proxy back
+    call(&mut ctx, ALICE, proxy, upgrade(counter_v1)).unwrap_err();
+    call(&mut ctx, OWNER, proxy, upgrade(counter_v1)).unwrap();
+    assert_eq!(version(&mut ctx, proxy), 1);
+    call(&mut ctx, ALICE, proxy, incrementCall {}.abi_encode()).unwrap();
+    assert_eq!(value(&mut ctx, proxy), U256::from(4));
+}
+
+#[test]
+fn test_evm_proxy_delegates_to_wasm_implementation() {
+    let mut ctx = EvmTestingContext::default().with_full_genesis();
+    let counter = deploy_wasm_counter(&mut ctx);
+    let proxy = deploy_evm_proxy(&mut ctx, counter);
+
+    call(
+        &mut ctx,
+        OWNER,
+        proxy,
+        initializeCall { owner: OWNER }.abi_encode(),
+    )
+    .unwrap();
+    call(&mut ctx, ALICE, proxy, incrementCall {}.abi_encode()).unwrap();
+    assert_eq!(value(&mut ctx, proxy), U256::from(1));
+    // The state lives in the proxy, the implementation keeps its own
+    assert_eq!(ctx.db.storage(proxy, U256::ZERO).unwrap(), U256::from(1));
+    assert_eq!(ctx.db.storage(counter, U256::ZERO).unwrap(), U256::ZERO);
+
+    // `proxiableUUID()` is only answered by the implementation itself
+    let revert = call(&mut ctx, ALICE, proxy, proxiableUUIDCall {}.abi_encode()).unwrap_err();
+    assert_eq!(revert, UUPSUnauthorizedCallContext {}.abi_encode());
+    let output = call(&mut ctx, ALICE, counter, proxiableUUIDCall {}.abi_encode()).unwrap();
+    assert_eq!(
+        proxiableUUIDCall::abi_decode_returns(&output).unwrap(),
+        B256::from(IMPLEMENTATION_SLOT)
+    );
+
+    let new_counter = deploy_wasm_counter(&mut ctx);
+    call(&mut ctx, OWNER, proxy, upgrade(new_counter)).unwrap();
+    assert_eq!(implementation(&mut ctx, proxy), new_counter);
+    assert_eq!(value(&mut ctx, proxy), U256::from(1));
+}
+
+#[test]
+fn test_implementation_cannot_be_used_directly() {
+    let mut ctx = EvmTestingContext::default().with_full_genesis();
+    let counter = deploy_wasm_counter(&mut ctx);
+
+    // The constructor disabled the initializers of the implementation
+    let revert = call(
+        &mut ctx,
+        ALICE,
+        counter,
+        initializeCall { owner: ALICE }.abi_encode(),
+    )
+    .unwrap_err();
+    assert_eq!(revert, InvalidInitialization {}.abi_encode());
+
+    let revert = call(&mut ctx, ALICE, counter, upgrade(counter)).unwrap_err();
+    assert_eq!(revert, UUPSUnauthorizedCallContext {}.abi_encode());
+}
@@ -0,0 +1,282 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+#![allow(dead_code)]
+
+extern crate alloc;
+extern crate fluentbase_sdk;
+
+use fluentbase_sdk::{
+    access::{AccessControl, AccessControlError, Ownable2Step, OwnableError, DEFAULT_ADMIN_ROLE},
+    basic_entrypoint,
+    derive::{constructor, derive_keccak256, router, Contract},
+    storage::{StorageBool, StorageMap, StorageU256},
+    Address, ContextReader, SharedAPI, SolidityError, B256, U256,
+};
+
+/// Accounts allowed to mint new tokens
+pub const MINTER_ROLE: B256 = B256::new(derive_keccak256!("MINTER_ROLE"));
+
+#[derive(Contract)]
+pub struct Token<SDK> {
+    sdk: SDK,
+    // Both components live in their ERC-7201 namespaces, so the token fields start at slot 0
+    ownable: Ownable2Step,
+    access_control: AccessControl,
+    paused: StorageBool,
+    total_supply: StorageU256,
+    balances: StorageMap<Address, StorageU256>,
+}
+
+#[constructor(mode = "solidity")]
+impl<SDK: SharedAPI> Token<SDK> {
+    pub fn constructor(&mut self) {
+        let deployer = self.sdk.context().contract_caller();
+        if let Err(err) = self.ownable.initialize(&mut self.sdk, deployer) {
+            err.revert(&mut self.sdk);
+        }
+        self.access_control
+            .set_role(&mut self.sdk, DEFAULT_ADMIN_ROLE, deployer);
+    }
+}
+
+#[router(mode = "solidity")]
+impl<SDK: SharedAPI> Token<SDK> {
+    pub fn owner(&self) -> Address {
+        self.ownable.owner(&self.sdk)
+    }
+
+    pub fn pending_owner(&self) -> Address {
+        self.ownable.pending_owner(&self.sdk)
+    }
+
+    pub fn transfer_ownership(&mut self, new_owner: Address) -> Result<(), OwnableError> {
+        self.ownable.transfer_ownership(&mut self.sdk, new_owner)
+    }
+
+    pub fn accept_ownership(&mut self) -> Result<(), OwnableError> {
+        self.ownable.accept_ownership(&mut self.sdk)
+    }
+
+    pub fn has_role(&self, role: B256, account: Address) -> bool {
+        self.access_control.has_role(&self.sdk, role, account)
+    }
+
+    pub fn grant_role(&mut self, role: B256, account: Address) -> Result<(), AccessControlError> {
+        self.access_control.grant_role(&mut self.sdk, role, account)
+    }
+
+    pub fn revoke_role(&mut self, role: B256, account: Address) -> Result<(), AccessControlError> {
+        self.access_control
+            .revoke_role(&mut self.sdk, role, account)
+    }
+
+    pub fn renounce_role(
+        &mut self,
+        role: B256,
+        caller_confirmation: Address,
+    ) -> Result<(), AccessControlError> {
+        self.access_control
+            .renounce_role(&mut self.sdk, role, caller_confirmation)
+    }
+
+    #[only_owner]
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused_accessor().set(&mut self.sdk, paused);
+    }
+
+    #[only_role(MINTER_ROLE)]
+    #[non_reentrant]
+    pub fn mint(&mut self, to: Address, amount: U256) {
+        if self.paused_accessor().get(&self.sdk) {
+            panic!("minting is paused");
+        }
+        let total_supply = self.total_supply_accessor().get(&self.sdk);
+        self.total_supply_accessor()
+            .set(&mut self.sdk, total_supply + amount);
+        let balance = self.balances_accessor().entry(to).get(&self.sdk);
+        self.balances_accessor()
+            .entry(to)
+            .set(&mut self.sdk, balance + amount);
+    }
+
+    #[non_reentrant]
+    pub fn balance_of(&self, account: Address) -> U256 {
+        self.balances_accessor().entry(account).get(&self.sdk)
+    }
+}
+
+basic_entrypoint!(Token);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluentbase_sdk::{
+        access::{Ownable, OwnershipTransferred, OWNABLE_STORAGE_SLOT},
+        address,
+        security::{ReentrancyGuardError, REENTRANCY_GUARD_STORAGE_SLOT},
+        storage::StorageDescriptor,
+        ContractContextV1, StorageAPI,
+    };
+    use fluentbase_testing::TestingContextImpl;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    const CONTRACT: Address = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    const DEPLOYER: Address = address!("1111111111111111111111111111111111111111");
+    const ALICE: Address = address!("2222222222222222222222222222222222222222");
+    const BOB: Address = address!("3333333333333333333333333333333333333333");
+
+    fn deploy() -> TestingContextImpl {
+        let sdk = TestingContextImpl::default().with_contract_context(ContractContextV1 {
+            address: CONTRACT,
+            caller: DEPLOYER,
+            ..Default::default()
+        });
+        Token::new(sdk.clone()).deploy();
+        sdk.take_logs();
+        sdk
+    }
+
+    /// Calls the contract as `caller`, returning the output or the revert data
+    fn call(sdk: &TestingContextImpl, caller: Address, input: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+        let sdk = sdk.clone().with_caller(caller).with_input(input);
+        let mut contract = Token::new(sdk.clone());
+        let result = catch_unwind(AssertUnwindSafe(|| contract.main()));
+        let output = sdk.take_output();
+        result.map(|_| output.clone()).map_err(|_| output)
+    }
+
+    #[test]
+    fn test_deployer_owns_the_token_and_administers_roles() {
+        let sdk = deploy();
+
+        let output = call(&sdk, ALICE, OwnerCall::new(()).encode().to_vec()).unwrap();
+        assert_eq!(OwnerReturn::decode(&&output[..]).unwrap().0 .0, DEPLOYER);
+
+        let input = HasRoleCall::new((DEFAULT_ADMIN_ROLE, DEPLOYER)).encode();
+        let output = call(&sdk, ALICE, input.to_vec()).unwrap();
+        assert!(HasRoleReturn::decode(&&output[..]).unwrap().0 .0);
+
+        // The token fields are not shifted by the components
+        assert_eq!(
+            Token::new(sdk.clone()).total_supply_accessor().slot(),
+            U256::ZERO
+        );
+        assert_eq!(
+            sdk.storage(&OWNABLE_STORAGE_SLOT).unwrap(),
+            U256::from_be_slice(DEPLOYER.as_slice())
+        );
+    }
+
+    #[test]
+    fn test_only_owner_rejects_other_callers() {
+        let sdk = deploy();
+
+        let input = SetPausedCall::new((true,)).encode().to_vec();
+        let revert = call(&sdk, ALICE, input.clone()).unwrap_err();
+        assert_eq!(
+            OwnableError::decode(&revert),
+            Some(OwnableError::OwnableUnauthorizedAccount { account: ALICE })
+        );
+
+        call(&sdk, DEPLOYER, input).unwrap();
+        assert!(Token::new(sdk.clone()).paused_accessor().get(&sdk));
+    }
+
+    #[test]
+    fn test_two_step_ownership_transfer() {
+        let sdk = deploy();
+
+        let input = TransferOwnershipCall::new((ALICE,)).encode().to_vec();
+        call(&sdk, DEPLOYER, input).unwrap();
+        assert_eq!(sdk.take_logs().len(), 1);
+
+        // Still the old owner until the transfer is accepted
+        let ownable = Ownable::new(U256::ZERO, 0);
+        assert_eq!(ownable.owner(&sdk), DEPLOYER);
+
+        let accept = AcceptOwnershipCall::new(()).encode().to_vec();
+        let revert = call(&sdk, BOB, accept.clone()).unwrap_err();
+        assert_eq!(
+            OwnableError::decode(&revert),
+            Some(OwnableError::OwnableUnauthorizedAccount { account: BOB })
+        );
+
+        call(&sdk, ALICE, accept).unwrap();
+        assert_eq!(ownable.owner(&sdk), ALICE);
+        let output = call(&sdk, ALICE, PendingOwnerCall::new(()).encode().to_vec()).unwrap();
+        assert_eq!(
+            PendingOwnerReturn::decode(&&output[..]).unwrap().0 .0,
+            Address::ZERO
+        );
+
+        let logs = sdk.take_logs();
+        let (_, topics) = logs.last().unwrap();
+        assert_eq!(topics[0], B256::new(OwnershipTransferred::SELECTOR));
+        assert_eq!(topics[2], ALICE.into_word());
+    }
+
+    #[test]
+    fn test_only_role_gates_minting() {
+        let sdk = deploy();
+
+        let mint = MintCall::new((BOB, U256::from(100))).encode().to_vec();
+        let revert = call(&sdk, ALICE, mint.clone()).unwrap_err();
+        assert_eq!(
+            AccessControlError::decode(&revert),
+            Some(AccessControlError::AccessControlUnauthorizedAccount {
+                account: ALICE,
+                needed_role: MINTER_ROLE,
+            })
+        );
+
+        // Only an admin of the role may grant it
+        let grant = GrantRoleCall::new((MINTER_ROLE, ALICE)).encode().to_vec();
+        assert!(call(&sdk, ALICE, grant.clone()).is_err());
+        call(&sdk, DEPLOYER, grant).unwrap();
+
+        call(&sdk, ALICE, mint.clone()).unwrap();
+        let output = call(&sdk, BOB, BalanceOfCall::new((BOB,)).encode().to_vec()).unwrap();
+        assert_eq!(
+            BalanceOfReturn::decode(&&output[..]).unwrap().0 .0,
+            U256::from(100)
+        );
+
+        // Renouncing on behalf of someone else is refused
+        let renounce = RenounceRoleCall::new((MINTER_ROLE, ALICE))
+            .encode()
+            .to_vec();
+        let revert = call(&sdk, BOB, renounce.clone()).unwrap_err();
+        assert_eq!(
+            AccessControlError::decode(&revert),
+            Some(AccessControlError::AccessControlBadConfirmation)
+        );
+        call(&sdk, ALICE, renounce).unwrap();
+        assert!(call(&sdk, ALICE, mint).is_err());
+    }
+
+    #[test]
+    fn test_non_reentrant_methods_reject_reentry() {
+        let sdk = deploy();
+        let grant = GrantRoleCall::new((MINTER_ROLE, ALICE)).encode().to_vec();
+        call(&sdk, DEPLOYER, grant).unwrap();
+
+        // The lock is released once a guarded call returns
+        let mint = MintCall::new((BOB, U256::from(1))).encode().to_vec();
+        call(&sdk, ALICE, mint.clone()).unwrap();
+        assert!(sdk
+            .transient_storage(&REENTRANCY_GUARD_STORAGE_SLOT)
+            .unwrap()
+            .is_zero());
+
+        // A call arriving while a guarded method runs is rejected, views included
+        sdk.clone()
+            .write_transient_storage(REENTRANCY_GUARD_STORAGE_SLOT, U256::from(1))
+            .unwrap();
+        let revert = call(&sdk, ALICE, mint).unwrap_err();
+        assert_eq!(
+            ReentrancyGuardError::decode(&revert),
+            Some(ReentrancyGuardError::ReentrancyGuardReentrantCall)
+        );
+        let balance_of = BalanceOfCall::new((BOB,)).encode().to_vec();
+        assert!(call(&sdk, BOB, balance_of).is_err());
+    }
+}
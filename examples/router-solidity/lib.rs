@@ -92,4 +92,48 @@ mod tests {
         println!("output: {:?}", &output.0);
         assert_eq!(output.0 .0, s);
     }
+
+    /// `view` methods run on a read-only SDK, so a state change ends the call with
+    /// `StateChangeDuringStaticCall` even though the router was reached with a plain `CALL`
+    #[allow(deprecated)] // `peek` takes `&mut self` on purpose
+    mod view_methods {
+        use fluentbase_sdk::{
+            derive::{router, Contract},
+            storage::StorageU256,
+            ExitCode, SharedAPI, StorageAPI, U256,
+        };
+        use fluentbase_testing::TestingContextImpl;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        #[derive(Contract)]
+        struct Vault<SDK> {
+            sdk: SDK,
+            balance: StorageU256,
+        }
+
+        #[router(mode = "solidity")]
+        impl<SDK: SharedAPI> Vault<SDK> {
+            #[state_mutability("view")]
+            pub fn peek(&mut self) -> U256 {
+                let balance = self.balance_accessor().get(&self.sdk);
+                self.balance_accessor().set(&mut self.sdk, U256::ZERO);
+                balance
+            }
+        }
+
+        #[test]
+        fn test_state_change_in_view_method_exits() {
+            let mut sdk = TestingContextImpl::default();
+            sdk.write_storage(U256::ZERO, U256::from(42)).unwrap();
+
+            let sdk = sdk.with_input(PeekCall::new(()).encode().to_vec());
+            let mut vault = Vault::new(sdk.clone());
+            assert!(catch_unwind(AssertUnwindSafe(|| vault.main())).is_err());
+            assert_eq!(
+                sdk.exit_code(),
+                ExitCode::StateChangeDuringStaticCall as i32
+            );
+            assert_eq!(sdk.storage(&U256::ZERO).unwrap(), U256::from(42));
+        }
+    }
 }
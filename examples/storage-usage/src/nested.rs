@@ -151,7 +151,10 @@ impl<SDK: SharedAPI> Game<SDK> {
 mod tests {
     use super::*;
     use crate::{assert_storage_layout, utils::storage_from_fixture};
-    use fluentbase_sdk::address;
+    use fluentbase_sdk::{
+        address,
+        storage::{check_storage_upgrade, StorageLayoutError},
+    };
     use fluentbase_testing::TestingContextImpl;
 
     #[test]
@@ -281,4 +284,54 @@ mod tests {
         let expected_storage = storage_from_fixture(EXPECTED_LAYOUT);
         assert_eq!(expected_storage, storage);
     }
+
+    /// `StorageItem` with `level` and `active` swapped inside their shared slot
+    #[derive(Storage)]
+    struct ReorderedItem {
+        owner: StorageAddress,
+        value: StorageU256,
+        active: StorageBool,
+        level: StorageU8,
+    }
+
+    #[derive(Contract)]
+    struct Shop<SDK> {
+        sdk: SDK,
+        featured: StorageItem,
+    }
+
+    #[derive(Contract)]
+    struct ReorderedShop<SDK> {
+        sdk: SDK,
+        featured: ReorderedItem,
+    }
+
+    #[test]
+    fn test_nested_struct_reorder_breaks_upgrade() {
+        let layout = Shop::<TestingContextImpl>::storage_layout();
+        let labels: Vec<_> = layout.iter().map(|field| field.label.as_ref()).collect();
+        assert_eq!(
+            labels,
+            [
+                "featured.owner",
+                "featured.value",
+                "featured.level",
+                "featured.active"
+            ]
+        );
+
+        assert_eq!(
+            check_storage_upgrade(
+                &layout,
+                &ReorderedShop::<TestingContextImpl>::storage_layout(),
+            ),
+            Err(StorageLayoutError::FieldMoved {
+                label: "featured.level",
+                old_slot: U256::from(2),
+                old_offset: 31,
+                new_slot: U256::from(2),
+                new_offset: 30,
+            })
+        );
+    }
 }
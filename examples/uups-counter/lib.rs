@@ -0,0 +1,299 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+#![allow(dead_code)]
+
+extern crate alloc;
+extern crate fluentbase_sdk;
+
+use fluentbase_sdk::{
+    access::Ownable,
+    basic_entrypoint,
+    derive::{constructor, router, uups, Contract},
+    proxy::{Initializable, InitializableError},
+    storage::StorageU256,
+    Address, SharedAPI, SolidityError, U256,
+};
+
+#[derive(Contract)]
+pub struct Counter<SDK> {
+    sdk: SDK,
+    // Both components live in their ERC-7201 namespaces, so `count` stays at slot 0
+    initializable: Initializable,
+    ownable: Ownable,
+    count: StorageU256,
+}
+
+#[constructor(mode = "solidity")]
+impl<SDK: SharedAPI> Counter<SDK> {
+    /// Runs on the implementation only, so nobody can initialize and take it over
+    pub fn constructor(&mut self) {
+        self.initializable.disable_initializers(&mut self.sdk);
+    }
+}
+
+#[uups]
+#[router(mode = "solidity")]
+impl<SDK: SharedAPI> Counter<SDK> {
+    /// Sets up the proxy state, in place of a constructor
+    pub fn initialize(&mut self, owner: Address) -> Result<(), InitializableError> {
+        self.initializable.initialize(&mut self.sdk, 1)?;
+        if let Err(err) = self.ownable.initialize(&mut self.sdk, owner) {
+            err.revert(&mut self.sdk);
+        }
+        Ok(())
+    }
+
+    pub fn owner(&self) -> Address {
+        self.ownable.owner(&self.sdk)
+    }
+
+    pub fn value(&self) -> U256 {
+        self.count_accessor().get(&self.sdk)
+    }
+
+    pub fn increment(&mut self) {
+        let count = self.count_accessor().get(&self.sdk);
+        self.count_accessor()
+            .set(&mut self.sdk, count + U256::from(1));
+    }
+
+    pub fn version(&self) -> u64 {
+        1
+    }
+
+    /// Only the owner may upgrade the proxy
+    fn authorize_upgrade(&mut self, _new_implementation: Address) {
+        self.ownable.only_owner(&mut self.sdk);
+    }
+}
+
+basic_entrypoint!(Counter);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluentbase_sdk::{
+        access::OwnableError,
+        address,
+        proxy::{Erc1967, ProxyError, IMPLEMENTATION_SLOT},
+        storage::{check_storage_upgrade, StorageLayoutError, StorageU64},
+        Bytes, ContractContextV1, ExitCode, StorageAPI, B256,
+    };
+    use fluentbase_testing::{TestingContextImpl, TestingContract};
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    const PROXY: Address = address!("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    const IMPLEMENTATION: Address = address!("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+    const NEW_IMPLEMENTATION: Address = address!("cccccccccccccccccccccccccccccccccccccccc");
+    const NOT_UUPS: Address = address!("dddddddddddddddddddddddddddddddddddddddd");
+    const OWNER: Address = address!("1111111111111111111111111111111111111111");
+    const ALICE: Address = address!("2222222222222222222222222222222222222222");
+
+    /// Minimal ERC-1967 proxy, delegating every call like `contracts/erc1967-proxy`
+    fn proxy(mut sdk: TestingContextImpl) {
+        let implementation = Erc1967::implementation(&sdk);
+        let input = sdk.bytes_input();
+        let result = sdk.delegate_call(implementation, &input, None);
+        sdk.write(&result.data);
+        if !result.status.is_ok() {
+            sdk.native_exit(ExitCode::Panic);
+        }
+    }
+
+    fn counter() -> TestingContract {
+        TestingContract::new(|sdk| Counter::new(sdk).main())
+    }
+
+    /// A proxy at `PROXY` pointing at a deployed counter, initialized with `OWNER`
+    fn deploy() -> TestingContextImpl {
+        let sdk = TestingContextImpl::default()
+            .with_contract(IMPLEMENTATION, counter())
+            .with_contract(NEW_IMPLEMENTATION, counter())
+            .with_contract(NOT_UUPS, TestingContract::native(|_| Ok(Bytes::new())))
+            .with_contract_context(ContractContextV1 {
+                address: IMPLEMENTATION,
+                bytecode_address: IMPLEMENTATION,
+                caller: OWNER,
+                ..Default::default()
+            });
+        Counter::new(sdk.clone()).deploy();
+
+        let sdk = sdk.with_contract_context(ContractContextV1 {
+            address: PROXY,
+            bytecode_address: PROXY,
+            caller: OWNER,
+            ..Default::default()
+        });
+        sdk.clone()
+            .write_storage(
+                IMPLEMENTATION_SLOT,
+                U256::from_be_slice(IMPLEMENTATION.as_slice()),
+            )
+            .unwrap();
+        call(&sdk, OWNER, InitializeCall::new((OWNER,)).encode().to_vec()).unwrap();
+        sdk.take_logs();
+        sdk
+    }
+
+    /// Calls the proxy as `caller`, returning the output or the revert data
+    fn call(sdk: &TestingContextImpl, caller: Address, input: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+        let sdk = sdk.clone().with_caller(caller).with_input(input);
+        let result = catch_unwind(AssertUnwindSafe(|| proxy(sdk.clone())));
+        let output = sdk.take_output();
+        result.map(|_| output.clone()).map_err(|_| output)
+    }
+
+    /// Calls the implementation directly, bypassing the proxy
+    fn call_implementation(sdk: &TestingContextImpl, input: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+        let sdk = sdk
+            .clone()
+            .with_contract_context(ContractContextV1 {
+                address: IMPLEMENTATION,
+                bytecode_address: IMPLEMENTATION,
+                caller: OWNER,
+                ..Default::default()
+            })
+            .with_input(input);
+        let result = catch_unwind(AssertUnwindSafe(|| Counter::new(sdk.clone()).main()));
+        let output = sdk.take_output();
+        result.map(|_| output.clone()).map_err(|_| output)
+    }
+
+    fn value(sdk: &TestingContextImpl) -> U256 {
+        let output = call(sdk, ALICE, ValueCall::new(()).encode().to_vec()).unwrap();
+        ValueReturn::decode(&&output[..]).unwrap().0 .0
+    }
+
+    #[test]
+    fn test_proxy_state_is_initialized_once() {
+        let sdk = deploy();
+
+        let output = call(&sdk, ALICE, OwnerCall::new(()).encode().to_vec()).unwrap();
+        assert_eq!(OwnerReturn::decode(&&output[..]).unwrap().0 .0, OWNER);
+
+        let initialize = InitializeCall::new((ALICE,)).encode().to_vec();
+        let revert = call(&sdk, ALICE, initialize.clone()).unwrap_err();
+        assert_eq!(
+            InitializableError::decode(&revert),
+            Some(InitializableError::InvalidInitialization)
+        );
+
+        // The implementation disabled its own initializers from the constructor
+        let revert = call_implementation(&sdk, initialize).unwrap_err();
+        assert_eq!(
+            InitializableError::decode(&revert),
+            Some(InitializableError::InvalidInitialization)
+        );
+    }
+
+    #[test]
+    fn test_upgrade_keeps_proxy_storage() {
+        let sdk = deploy();
+        call(&sdk, ALICE, IncrementCall::new(()).encode().to_vec()).unwrap();
+        call(&sdk, ALICE, IncrementCall::new(()).encode().to_vec()).unwrap();
+
+        let upgrade = UpgradeToAndCallCall::new((NEW_IMPLEMENTATION, Bytes::new()))
+            .encode()
+            .to_vec();
+        let revert = call(&sdk, ALICE, upgrade.clone()).unwrap_err();
+        assert_eq!(
+            OwnableError::decode(&revert),
+            Some(OwnableError::OwnableUnauthorizedAccount { account: ALICE })
+        );
+
+        call(&sdk, OWNER, upgrade).unwrap();
+        assert_eq!(Erc1967::implementation(&sdk), NEW_IMPLEMENTATION);
+        assert_eq!(value(&sdk), U256::from(2));
+
+        // Initialization data is delegated to the new implementation right away
+        let upgrade = UpgradeToAndCallCall::new((
+            IMPLEMENTATION,
+            IncrementCall::new(()).encode().to_vec().into(),
+        ))
+        .encode()
+        .to_vec();
+        call(&sdk, OWNER, upgrade).unwrap();
+        assert_eq!(Erc1967::implementation(&sdk), IMPLEMENTATION);
+        assert_eq!(value(&sdk), U256::from(3));
+    }
+
+    #[test]
+    fn test_upgrade_requires_a_uups_implementation_and_a_proxy() {
+        let sdk = deploy();
+
+        let upgrade = UpgradeToAndCallCall::new((NOT_UUPS, Bytes::new()))
+            .encode()
+            .to_vec();
+        let revert = call(&sdk, OWNER, upgrade).unwrap_err();
+        assert_eq!(
+            ProxyError::decode(&revert),
+            Some(ProxyError::ERC1967InvalidImplementation {
+                implementation: NOT_UUPS
+            })
+        );
+
+        // `proxiableUUID()` only answers on the implementation itself
+        let proxiable_uuid = ProxiableUuidCall::new(()).encode().to_vec();
+        let revert = call(&sdk, ALICE, proxiable_uuid.clone()).unwrap_err();
+        assert_eq!(
+            ProxyError::decode(&revert),
+            Some(ProxyError::UUPSUnauthorizedCallContext)
+        );
+        let output = call_implementation(&sdk, proxiable_uuid).unwrap();
+        assert_eq!(
+            ProxiableUuidReturn::decode(&&output[..]).unwrap().0 .0,
+            B256::from(IMPLEMENTATION_SLOT)
+        );
+
+        // The implementation cannot be upgraded as if it were a proxy
+        let upgrade = UpgradeToAndCallCall::new((NEW_IMPLEMENTATION, Bytes::new()))
+            .encode()
+            .to_vec();
+        let revert = call_implementation(&sdk, upgrade).unwrap_err();
+        assert_eq!(
+            ProxyError::decode(&revert),
+            Some(ProxyError::UUPSUnauthorizedCallContext)
+        );
+    }
+
+    /// Next version of the counter, appending a `step` after the existing fields
+    #[derive(Contract)]
+    struct CounterV2<SDK> {
+        sdk: SDK,
+        initializable: Initializable,
+        ownable: Ownable,
+        count: StorageU256,
+        step: StorageU64,
+    }
+
+    /// Layout inserting `step` in front of `count`, which would shift it to slot 1
+    #[derive(Contract)]
+    struct BrokenCounter<SDK> {
+        sdk: SDK,
+        initializable: Initializable,
+        ownable: Ownable,
+        step: StorageU64,
+        count: StorageU256,
+    }
+
+    #[test]
+    fn test_storage_layout_upgrades() {
+        let layout = Counter::<TestingContextImpl>::storage_layout();
+        assert_eq!(layout[2].label, "count");
+        assert_eq!(layout[2].slot, U256::ZERO);
+
+        check_storage_upgrade(&layout, &CounterV2::<TestingContextImpl>::storage_layout()).unwrap();
+        assert_eq!(
+            check_storage_upgrade(
+                &layout,
+                &BrokenCounter::<TestingContextImpl>::storage_layout(),
+            ),
+            Err(StorageLayoutError::FieldMoved {
+                label: "count",
+                old_slot: U256::ZERO,
+                old_offset: 0,
+                new_slot: U256::from(1),
+                new_offset: 0,
+            })
+        );
+    }
+}